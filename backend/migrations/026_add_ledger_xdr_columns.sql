@@ -0,0 +1,28 @@
+-- Columns populated from decoded LedgerCloseMeta XDR
+
+ALTER TABLE ledgers ADD COLUMN previous_hash TEXT;
+ALTER TABLE ledgers ADD COLUMN protocol_version INTEGER;
+ALTER TABLE ledgers ADD COLUMN base_fee INTEGER;
+ALTER TABLE ledgers ADD COLUMN base_reserve INTEGER;
+ALTER TABLE ledgers ADD COLUMN max_tx_set_size INTEGER;
+
+ALTER TABLE transactions ADD COLUMN application_order INTEGER;
+ALTER TABLE transactions ADD COLUMN fee_account TEXT;
+ALTER TABLE transactions ADD COLUMN max_fee INTEGER;
+ALTER TABLE transactions ADD COLUMN result_code TEXT;
+ALTER TABLE transactions ADD COLUMN inner_transaction_hash TEXT;
+
+CREATE TABLE IF NOT EXISTS ledger_operations (
+    transaction_hash TEXT NOT NULL REFERENCES transactions(hash),
+    application_order INTEGER NOT NULL,
+    ledger_sequence INTEGER NOT NULL REFERENCES ledgers(sequence),
+    operation_type TEXT NOT NULL,
+    source_account TEXT NOT NULL,
+    successful INTEGER NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (transaction_hash, application_order)
+);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_ledger_sequence ON transactions(ledger_sequence);
+CREATE INDEX IF NOT EXISTS idx_ledger_operations_ledger ON ledger_operations(ledger_sequence);
+CREATE INDEX IF NOT EXISTS idx_ledger_operations_type ON ledger_operations(operation_type);
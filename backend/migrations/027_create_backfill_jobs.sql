@@ -0,0 +1,37 @@
+-- Migration: Create Backfill Job Tables
+-- Description: Track historical ledger backfills split into concurrently processed shards
+
+CREATE TABLE IF NOT EXISTS backfill_jobs (
+    id TEXT PRIMARY KEY,
+    kind TEXT NOT NULL, -- 'range' or 'gaps'
+    start_ledger INTEGER NOT NULL,
+    end_ledger INTEGER NOT NULL,
+    shard_size INTEGER NOT NULL,
+    concurrency INTEGER NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending, running, completed, failed
+    ledgers_ingested INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    started_at TIMESTAMP,
+    completed_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_backfill_jobs_status ON backfill_jobs(status);
+CREATE INDEX IF NOT EXISTS idx_backfill_jobs_created ON backfill_jobs(created_at DESC);
+
+-- One row per shard; checkpoint_ledger is the last sequence fully processed
+CREATE TABLE IF NOT EXISTS backfill_shards (
+    job_id TEXT NOT NULL,
+    shard_index INTEGER NOT NULL,
+    start_ledger INTEGER NOT NULL,
+    end_ledger INTEGER NOT NULL,
+    checkpoint_ledger INTEGER,
+    status TEXT NOT NULL DEFAULT 'pending', -- pending, running, completed, failed
+    ledgers_ingested INTEGER NOT NULL DEFAULT 0,
+    error TEXT,
+    updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (job_id, shard_index),
+    FOREIGN KEY (job_id) REFERENCES backfill_jobs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_backfill_shards_status ON backfill_shards(job_id, status);
@@ -0,0 +1,16 @@
+-- Migration: Create Ledger Quarantine Table
+-- Description: Hold ledgers whose hashes do not link up with the stored chain
+
+CREATE TABLE IF NOT EXISTS quarantined_ledgers (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    sequence INTEGER NOT NULL,
+    hash TEXT NOT NULL,
+    previous_hash TEXT,
+    reason TEXT NOT NULL, -- previous_hash_mismatch, successor_mismatch, hash_conflict
+    expected_hash TEXT, -- hash the stored chain expects at the mismatched link
+    close_time TEXT,
+    detected_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (sequence, hash, reason)
+);
+
+CREATE INDEX IF NOT EXISTS idx_quarantined_ledgers_sequence ON quarantined_ledgers(sequence);
@@ -0,0 +1,15 @@
+-- Path payment legs: asset_code / asset_issuer / amount describe what the
+-- destination received, the source_* columns what the sender paid
+
+ALTER TABLE ledger_payments ADD COLUMN asset_type TEXT;
+ALTER TABLE ledger_payments ADD COLUMN source_asset_type TEXT;
+ALTER TABLE ledger_payments ADD COLUMN source_asset_code TEXT;
+ALTER TABLE ledger_payments ADD COLUMN source_asset_issuer TEXT;
+ALTER TABLE ledger_payments ADD COLUMN source_amount TEXT;
+ALTER TABLE ledger_payments ADD COLUMN exchange_rate REAL;
+-- JSON array of intermediate assets, e.g. [{"asset_type":"native"}]
+ALTER TABLE ledger_payments ADD COLUMN path TEXT;
+ALTER TABLE ledger_payments ADD COLUMN path_hops INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_ledger_payments_operation_type ON ledger_payments(operation_type);
+CREATE INDEX IF NOT EXISTS idx_ledger_payments_corridor ON ledger_payments(source_asset_code, source_asset_issuer, asset_code, asset_issuer);
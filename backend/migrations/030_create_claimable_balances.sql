@@ -0,0 +1,23 @@
+-- Claimable balance lifecycle (create -> claim / clawback) from ingested effects
+CREATE TABLE IF NOT EXISTS claimable_balances (
+    balance_id TEXT PRIMARY KEY,
+    -- NULL code and issuer for native XLM
+    asset_code TEXT,
+    asset_issuer TEXT,
+    amount REAL NOT NULL DEFAULT 0.0,
+    sponsor TEXT,
+    -- JSON array of claimant accounts
+    claimants TEXT NOT NULL DEFAULT '[]',
+    status TEXT NOT NULL DEFAULT 'unclaimed' CHECK (status IN ('unclaimed', 'claimed', 'clawed_back')),
+    -- NULL when the balance was created before ingestion started
+    created_ledger INTEGER,
+    created_at TEXT,
+    claimed_by TEXT,
+    resolved_ledger INTEGER,
+    resolved_at TEXT,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_claimable_balances_status ON claimable_balances(status);
+CREATE INDEX IF NOT EXISTS idx_claimable_balances_asset ON claimable_balances(asset_code, asset_issuer);
+CREATE INDEX IF NOT EXISTS idx_claimable_balances_created_at ON claimable_balances(created_at DESC);
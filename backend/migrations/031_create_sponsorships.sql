@@ -0,0 +1,33 @@
+-- Sponsored reserves derived from sponsorship operations and effects
+CREATE TABLE IF NOT EXISTS sponsorships (
+    id TEXT PRIMARY KEY,
+    sponsor TEXT NOT NULL,
+    sponsored_account TEXT NOT NULL,
+    -- Base reserves currently paid by the sponsor (an account entry counts as two)
+    sponsored_reserves INTEGER NOT NULL DEFAULT 0,
+    -- 'sponsoring' while a begin/end sandwich is open, 'inactive' once no reserves remain
+    status TEXT NOT NULL DEFAULT 'active' CHECK (status IN ('sponsoring', 'active', 'inactive')),
+    first_ledger INTEGER NOT NULL,
+    last_ledger INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE(sponsor, sponsored_account)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sponsorships_sponsor ON sponsorships(sponsor);
+CREATE INDEX IF NOT EXISTS idx_sponsorships_sponsored_account ON sponsorships(sponsored_account);
+
+CREATE TABLE IF NOT EXISTS sponsorship_history (
+    id TEXT PRIMARY KEY,
+    sponsorship_id TEXT NOT NULL REFERENCES sponsorships(id),
+    -- Operation or effect id; makes re-ingesting a ledger a no-op
+    source_id TEXT NOT NULL UNIQUE,
+    change_type TEXT NOT NULL,
+    -- account, trustline, data, signer or claimable_balance for entry changes
+    entry_type TEXT,
+    reserves_delta INTEGER NOT NULL DEFAULT 0,
+    ledger_sequence INTEGER NOT NULL,
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_sponsorship_history_sponsorship_id ON sponsorship_history(sponsorship_id);
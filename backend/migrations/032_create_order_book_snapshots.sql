@@ -0,0 +1,29 @@
+-- Periodic order book snapshots of tracked corridor pairs
+CREATE TABLE IF NOT EXISTS order_book_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    -- Corridor key, e.g. USDC:GA5Z...->XLM:native
+    pair_key TEXT NOT NULL,
+    best_bid REAL,
+    best_ask REAL,
+    mid_price REAL,
+    spread REAL,
+    spread_bps REAL,
+    bid_levels INTEGER NOT NULL DEFAULT 0,
+    ask_levels INTEGER NOT NULL DEFAULT 0,
+    -- JSON arrays of {price, amount}; amounts in selling (base) asset units
+    bids TEXT NOT NULL DEFAULT '[]',
+    asks TEXT NOT NULL DEFAULT '[]',
+    snapshot_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_obs_pair_time ON order_book_snapshots(pair_key, snapshot_at);
+CREATE INDEX IF NOT EXISTS idx_obs_snapshot_at ON order_book_snapshots(snapshot_at);
+
+-- Depth on each side within a slippage band around the mid price
+CREATE TABLE IF NOT EXISTS order_book_depth (
+    snapshot_id INTEGER NOT NULL REFERENCES order_book_snapshots(id) ON DELETE CASCADE,
+    slippage_pct REAL NOT NULL,
+    bid_depth REAL NOT NULL,
+    ask_depth REAL NOT NULL,
+    PRIMARY KEY (snapshot_id, slippage_pct)
+);
@@ -0,0 +1,43 @@
+-- Trades ingested from the Horizon trades stream
+CREATE TABLE IF NOT EXISTS trades (
+    id TEXT PRIMARY KEY,
+    paging_token TEXT NOT NULL,
+    -- e.g. XLM:native->USDC:GA5Z..., in Horizon's base/counter order
+    pair_key TEXT NOT NULL,
+    liquidity_pool_id TEXT,
+    trade_type TEXT NOT NULL,
+    base_amount REAL NOT NULL,
+    counter_amount REAL NOT NULL,
+    -- Counter units per base unit
+    price REAL NOT NULL,
+    ledger_close_time TEXT NOT NULL,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_trades_pair_time ON trades(pair_key, ledger_close_time);
+CREATE INDEX IF NOT EXISTS idx_trades_pool ON trades(liquidity_pool_id);
+
+-- OHLCV candles per asset pair and per liquidity pool
+CREATE TABLE IF NOT EXISTS trade_candles (
+    -- Pair key, or liquidity pool id for pool candles
+    market_key TEXT NOT NULL,
+    market_type TEXT NOT NULL CHECK (market_type IN ('pair', 'liquidity_pool')),
+    -- 1m, 5m, 1h, 1d, 1w
+    resolution TEXT NOT NULL,
+    bucket_start TEXT NOT NULL,
+    open REAL NOT NULL,
+    high REAL NOT NULL,
+    low REAL NOT NULL,
+    close REAL NOT NULL,
+    base_volume REAL NOT NULL DEFAULT 0,
+    counter_volume REAL NOT NULL DEFAULT 0,
+    trade_count INTEGER NOT NULL DEFAULT 0,
+    -- Close times of the trades that set open and close, so late trades
+    -- can be merged in any order
+    open_at TEXT NOT NULL,
+    close_at TEXT NOT NULL,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (market_key, resolution, bucket_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_trade_candles_type ON trade_candles(market_type, resolution);
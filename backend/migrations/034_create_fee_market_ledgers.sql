@@ -0,0 +1,26 @@
+-- Per-ledger fee market statistics derived from ingested transactions.
+-- Fees are per operation in stroops (fee bumps count the outer envelope as
+-- an extra operation).
+CREATE TABLE IF NOT EXISTS fee_market_ledgers (
+    ledger_sequence INTEGER PRIMARY KEY,
+    closed_at TEXT NOT NULL,
+    transaction_count INTEGER NOT NULL,
+    operation_count INTEGER NOT NULL,
+    -- operation_count / configured ledger capacity
+    capacity_usage REAL NOT NULL,
+    base_fee INTEGER NOT NULL,
+    min_fee INTEGER NOT NULL,
+    p10_fee INTEGER NOT NULL,
+    p50_fee INTEGER NOT NULL,
+    p90_fee INTEGER NOT NULL,
+    p99_fee INTEGER NOT NULL,
+    max_fee INTEGER NOT NULL,
+    -- Median max fee bid, to compare what users offer with what they pay
+    p50_max_fee_bid INTEGER NOT NULL,
+    total_fee_charged INTEGER NOT NULL,
+    -- Every transaction paid above the base fee, or the ledger was full
+    surge_pricing INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_fee_market_closed_at ON fee_market_ledgers(closed_at);
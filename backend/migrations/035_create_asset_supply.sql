@@ -0,0 +1,42 @@
+-- Circulating supply over time per issued asset, from Horizon's /assets.
+-- circulating_supply = account balances (all authorization states) plus
+-- amounts held in claimable balances, liquidity pools and contracts.
+CREATE TABLE IF NOT EXISTS asset_supply_snapshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    asset_code TEXT NOT NULL,
+    asset_issuer TEXT NOT NULL,
+    circulating_supply REAL NOT NULL,
+    authorized_supply REAL NOT NULL,
+    claimable_balances_amount REAL NOT NULL DEFAULT 0,
+    liquidity_pools_amount REAL NOT NULL DEFAULT 0,
+    contracts_amount REAL NOT NULL DEFAULT 0,
+    holders INTEGER NOT NULL DEFAULT 0,
+    -- Percent change against the previous snapshot of the same asset
+    change_pct REAL,
+    -- |change_pct| reached the configured jump threshold
+    supply_jump INTEGER NOT NULL DEFAULT 0,
+    snapshot_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_supply_asset_time
+    ON asset_supply_snapshots(asset_code, asset_issuer, snapshot_at);
+CREATE INDEX IF NOT EXISTS idx_asset_supply_jumps
+    ON asset_supply_snapshots(supply_jump, snapshot_at);
+
+-- Payments sent by an asset's issuer (mint) or received by it (burn)
+CREATE TABLE IF NOT EXISTS asset_issuance_events (
+    operation_id TEXT NOT NULL,
+    asset_code TEXT NOT NULL,
+    asset_issuer TEXT NOT NULL,
+    event_type TEXT NOT NULL CHECK (event_type IN ('mint', 'burn')),
+    amount REAL NOT NULL,
+    -- Recipient of a mint, sender of a burn
+    counterparty TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    ledger_sequence INTEGER NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (operation_id, asset_code, asset_issuer, event_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_issuance_asset_time
+    ON asset_issuance_events(asset_code, asset_issuer, created_at);
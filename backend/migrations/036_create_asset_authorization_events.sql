@@ -0,0 +1,35 @@
+-- Issuer control actions: trustline authorization changes, clawbacks and
+-- account flag changes (set_options) from ingested operations
+CREATE TABLE IF NOT EXISTS asset_authorization_events (
+    operation_id TEXT PRIMARY KEY,
+    event_type TEXT NOT NULL CHECK (event_type IN (
+        'trustline_authorized',
+        'trustline_maintain_liabilities',
+        'trustline_deauthorized',
+        'trustline_flags_updated',
+        'clawback',
+        'claimable_balance_clawback',
+        'issuer_flags_changed'
+    )),
+    -- NULL for issuer_flags_changed, which applies to every asset of the
+    -- issuer, and for clawed back claimable balances created before ingestion
+    asset_code TEXT,
+    asset_issuer TEXT NOT NULL,
+    -- Trustor, clawback source or claimable balance id
+    account TEXT,
+    -- Clawed back amount, or the trustor's balance when a trustline is frozen
+    amount REAL,
+    -- Comma separated flag names
+    set_flags TEXT NOT NULL DEFAULT '',
+    clear_flags TEXT NOT NULL DEFAULT '',
+    transaction_hash TEXT NOT NULL,
+    ledger_sequence INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_asset_auth_issuer_time
+    ON asset_authorization_events(asset_issuer, created_at);
+CREATE INDEX IF NOT EXISTS idx_asset_auth_asset_time
+    ON asset_authorization_events(asset_code, asset_issuer, created_at);
+CREATE INDEX IF NOT EXISTS idx_asset_auth_type
+    ON asset_authorization_events(event_type);
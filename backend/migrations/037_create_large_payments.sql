@@ -0,0 +1,45 @@
+-- Payments flagged as large for their asset or corridor
+CREATE TABLE IF NOT EXISTS large_payments (
+    payment_id TEXT PRIMARY KEY,
+    -- Directional `SOURCE_ASSET->DESTINATION_ASSET`, assets as `CODE:ISSUER`
+    corridor_key TEXT NOT NULL,
+    -- Destination asset; `amount` is denominated in it
+    asset TEXT NOT NULL,
+    amount REAL NOT NULL,
+    -- NULL when the asset has no price
+    amount_usd REAL,
+    source_account TEXT NOT NULL,
+    destination TEXT NOT NULL,
+    -- Deviation from the corridor's recent distribution, when it had enough samples
+    z_score REAL,
+    corridor_mean REAL,
+    corridor_stddev REAL,
+    -- Comma separated: absolute_threshold, deviation
+    reasons TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    ledger_sequence INTEGER NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_large_payments_time ON large_payments(created_at);
+CREATE INDEX IF NOT EXISTS idx_large_payments_corridor_time
+    ON large_payments(corridor_key, created_at);
+CREATE INDEX IF NOT EXISTS idx_large_payments_asset_time ON large_payments(asset, created_at);
+
+-- Running payment size totals per corridor and hour, from which the
+-- corridor's mean and standard deviation are derived
+CREATE TABLE IF NOT EXISTS corridor_payment_hourly_stats (
+    corridor_key TEXT NOT NULL,
+    hour TEXT NOT NULL,
+    payment_count INTEGER NOT NULL DEFAULT 0,
+    amount_sum REAL NOT NULL DEFAULT 0,
+    amount_sum_sq REAL NOT NULL DEFAULT 0,
+    PRIMARY KEY (corridor_key, hour)
+);
+
+-- Ledgers already folded into the hourly totals, so replays and backfill
+-- overlaps are not counted twice
+CREATE TABLE IF NOT EXISTS large_payment_ledgers (
+    ledger_sequence INTEGER PRIMARY KEY,
+    processed_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
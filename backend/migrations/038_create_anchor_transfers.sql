@@ -0,0 +1,47 @@
+-- SEP-24 and SEP-31 transactions initiated through the anchor proxies,
+-- polled on the anchor's transfer server until they reach a final status
+CREATE TABLE IF NOT EXISTS anchor_transfers (
+    id TEXT PRIMARY KEY,
+    protocol TEXT NOT NULL CHECK (protocol IN ('sep24', 'sep31')),
+    -- deposit, withdrawal (SEP-24) or send (SEP-31)
+    kind TEXT NOT NULL,
+    transfer_server TEXT NOT NULL,
+    transaction_id TEXT NOT NULL,
+    -- Matched on the transfer server host against anchors.home_domain
+    anchor_id TEXT REFERENCES anchors(id) ON DELETE SET NULL,
+    asset_code TEXT,
+    amount TEXT,
+    -- `CODE:ISSUER->CODE:ISSUER` in the transfer's direction, set when recorded;
+    -- the off-chain leg's issuer is `offchain`
+    corridor_key TEXT,
+    -- SEP-10 token used for polling, encrypted with ENCRYPTION_KEY
+    jwt TEXT,
+    status TEXT NOT NULL DEFAULT 'incomplete',
+    -- completed, failed or abandoned once polling stops
+    outcome TEXT,
+    -- The anchor's started_at to completed_at, else the first
+    -- pending_user_transfer_start / pending_sender to completed
+    settlement_time_ms INTEGER,
+    poll_count INTEGER NOT NULL DEFAULT 0,
+    poll_error_count INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_poll_at TEXT NOT NULL,
+    initiated_at TEXT NOT NULL,
+    finished_at TEXT,
+    UNIQUE (transfer_server, transaction_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_anchor_transfers_due
+    ON anchor_transfers(next_poll_at) WHERE outcome IS NULL;
+CREATE INDEX IF NOT EXISTS idx_anchor_transfers_anchor
+    ON anchor_transfers(anchor_id, finished_at);
+CREATE INDEX IF NOT EXISTS idx_anchor_transfers_corridor
+    ON anchor_transfers(corridor_key, finished_at);
+
+-- First time each status was observed for a transfer
+CREATE TABLE IF NOT EXISTS anchor_transfer_status_events (
+    transfer_id TEXT NOT NULL REFERENCES anchor_transfers(id) ON DELETE CASCADE,
+    status TEXT NOT NULL,
+    observed_at TEXT NOT NULL,
+    PRIMARY KEY (transfer_id, status)
+);
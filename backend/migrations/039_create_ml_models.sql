@@ -0,0 +1,22 @@
+-- Payment success models trained by MLService (logistic regression over
+-- hourly corridor features). Exactly one row is active at a time.
+CREATE TABLE IF NOT EXISTS ml_models (
+    version TEXT PRIMARY KEY,
+    -- JSON arrays in feature order, see ml::FEATURE_NAMES
+    weights TEXT NOT NULL,
+    feature_means TEXT NOT NULL,
+    feature_stds TEXT NOT NULL,
+    bias REAL NOT NULL,
+    -- Metrics on the most recent hours of the window, held out from training
+    auc REAL,
+    log_loss REAL,
+    training_samples INTEGER NOT NULL,
+    validation_samples INTEGER NOT NULL,
+    window_start TEXT NOT NULL,
+    window_end TEXT NOT NULL,
+    is_active INTEGER NOT NULL DEFAULT 0,
+    trained_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_ml_models_trained_at ON ml_models(trained_at DESC);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ml_models_active ON ml_models(is_active) WHERE is_active = 1;
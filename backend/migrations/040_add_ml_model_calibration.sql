@@ -0,0 +1,3 @@
+-- Held-out success rates per predicted-probability bin, used for the
+-- confidence interval around each prediction (JSON, see ml::CalibrationBin)
+ALTER TABLE ml_models ADD COLUMN calibration TEXT;
@@ -0,0 +1,36 @@
+-- Anomalies found by AnomalyDetector in hourly corridor and anchor metrics
+CREATE TABLE IF NOT EXISTS anomalies (
+    id TEXT PRIMARY KEY,
+    subject_type TEXT NOT NULL,   -- 'corridor' | 'anchor'
+    subject_id TEXT NOT NULL,     -- corridor key or anchor id
+    metric TEXT NOT NULL,         -- 'success_rate' | 'settlement_latency_ms' | 'volume_usd' | 'liquidity_depth_usd'
+    kind TEXT NOT NULL,           -- 'spike' | 'drift'
+    baseline TEXT NOT NULL,       -- 'seasonal' (same hour of week) | 'ewma'
+    observed_value REAL NOT NULL,
+    expected_value REAL NOT NULL,
+    expected_low REAL NOT NULL,
+    expected_high REAL NOT NULL,
+    score REAL NOT NULL,          -- standard deviations beyond the baseline
+    severity TEXT NOT NULL,       -- 'warning' | 'critical'
+    observed_at TEXT NOT NULL,    -- start of the hour, RFC 3339
+    detected_at TEXT NOT NULL,
+    UNIQUE(subject_type, subject_id, metric, kind, observed_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_anomalies_observed_at ON anomalies(observed_at DESC);
+CREATE INDEX IF NOT EXISTS idx_anomalies_subject ON anomalies(subject_type, subject_id, observed_at DESC);
+
+-- EWMA state of every metric series, so a run only reads the hours
+-- completed since the previous one
+CREATE TABLE IF NOT EXISTS anomaly_series_state (
+    subject_type TEXT NOT NULL,
+    subject_id TEXT NOT NULL,
+    metric TEXT NOT NULL,
+    ewma_mean REAL NOT NULL,
+    ewma_variance REAL NOT NULL,
+    drift REAL NOT NULL DEFAULT 0,
+    in_drift INTEGER NOT NULL DEFAULT 0,
+    observations INTEGER NOT NULL,
+    last_observed_at TEXT NOT NULL,
+    PRIMARY KEY (subject_type, subject_id, metric)
+);
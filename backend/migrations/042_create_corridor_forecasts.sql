@@ -0,0 +1,19 @@
+-- Hourly forecasts per corridor and metric from additive Holt-Winters models,
+-- refreshed by the corridor-forecast job
+CREATE TABLE IF NOT EXISTS corridor_forecasts (
+    corridor_key TEXT NOT NULL,
+    metric TEXT NOT NULL,           -- 'volume_usd' | 'transaction_count' | 'success_rate'
+    alpha REAL NOT NULL,            -- level smoothing
+    beta REAL NOT NULL,             -- trend smoothing
+    gamma REAL NOT NULL,            -- seasonal smoothing
+    season_hours INTEGER NOT NULL,
+    rmse REAL NOT NULL,             -- one-step-ahead error on the training hours
+    training_hours INTEGER NOT NULL,
+    -- JSON [{timestamp, value, lower, upper}], hourly from the hour after the
+    -- last observed one
+    points TEXT NOT NULL,
+    generated_at TEXT NOT NULL,
+    PRIMARY KEY (corridor_key, metric)
+);
+
+CREATE INDEX IF NOT EXISTS idx_corridor_forecasts_generated_at ON corridor_forecasts(generated_at);
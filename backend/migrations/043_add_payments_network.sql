@@ -0,0 +1,3 @@
+-- Network each payment was ingested from ('mainnet' | 'testnet'); NULL for
+-- payments stored before the column, which count under the configured network
+ALTER TABLE payments ADD COLUMN network TEXT;
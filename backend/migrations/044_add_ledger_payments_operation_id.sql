@@ -0,0 +1,5 @@
+-- Horizon operation id of each ingested payment, so reprocessing a ledger
+-- leaves existing rows alone; NULL for rows stored before the column
+ALTER TABLE ledger_payments ADD COLUMN operation_id TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_ledger_payments_operation_id ON ledger_payments(operation_id);
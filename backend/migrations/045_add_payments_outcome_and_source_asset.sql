@@ -0,0 +1,7 @@
+-- Outcome and sending leg of each payment, so the hourly corridor rollup
+-- sees failed payments and the asset path payments were sent in. Source
+-- legs use XLM / native for lumens; NULL for rows stored before the columns,
+-- which are read as having sent the asset they delivered
+ALTER TABLE payments ADD COLUMN successful INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE payments ADD COLUMN source_asset_code TEXT;
+ALTER TABLE payments ADD COLUMN source_asset_issuer TEXT;
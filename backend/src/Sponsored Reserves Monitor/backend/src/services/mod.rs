@@ -1,3 +0,0 @@
-pub mod sponsorship_tracker;
-
-pub use sponsorship_tracker::SponsorshipTrackerService;
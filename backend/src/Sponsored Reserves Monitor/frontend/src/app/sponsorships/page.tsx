@@ -24,8 +24,8 @@ export default function SponsorshipsPage() {
       const [sponsorshipsRes, leaderboardRes, analyticsRes] = await Promise.all(
         [
           axios.get("/api/sponsorships?limit=100"),
-          axios.get("/api/sponsors/leaderboard?limit=50"),
-          axios.get("/api/analytics/summary"),
+          axios.get("/api/sponsorships/leaderboard?limit=50"),
+          axios.get("/api/sponsorships/analytics"),
         ],
       );
 
@@ -2,12 +2,13 @@ import React from "react";
 
 interface SponsorshipAnalyticsType {
   total_sponsorships: number;
-  total_amount_sponsored: string;
+  active_sponsorships: number;
   unique_sponsors: number;
   unique_sponsored_accounts: number;
-  average_sponsorship: string;
-  largest_sponsorship: string;
-  smallest_sponsorship: string;
+  total_sponsored_reserves: number;
+  total_reserve_exposure_xlm: number;
+  average_sponsored_reserves: number;
+  largest_sponsored_reserves: number;
 }
 
 interface Props {
@@ -35,8 +36,8 @@ export default function SponsorshipAnalytics({ analytics }: Props) {
       color: "bg-purple-50 text-purple-700",
     },
     {
-      label: "Total Amount",
-      value: analytics.total_amount_sponsored,
+      label: "Reserve Exposure (XLM)",
+      value: analytics.total_reserve_exposure_xlm,
       icon: "💰",
       color: "bg-yellow-50 text-yellow-700",
     },
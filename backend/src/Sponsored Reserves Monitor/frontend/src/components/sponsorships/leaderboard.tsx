@@ -2,7 +2,8 @@ import React from "react";
 
 interface SponsorLeaderboard {
   sponsor: string;
-  total_sponsored_amount: string;
+  total_sponsored_reserves: number;
+  reserve_exposure_xlm: number;
   sponsored_accounts_count: number;
   rank: number;
 }
@@ -55,7 +56,7 @@ export default function SponsorshipLeaderboard({ data }: Props) {
                   {sponsor.sponsor}
                 </td>
                 <td className="px-6 py-4 font-semibold text-gray-900">
-                  {sponsor.total_sponsored_amount}
+                  {sponsor.reserve_exposure_xlm} XLM
                 </td>
                 <td className="px-6 py-4 text-gray-900 text-center">
                   {sponsor.sponsored_accounts_count}
@@ -5,7 +5,8 @@ interface Sponsorship {
   sponsor: string;
   sponsored_account: string;
   sponsored_reserves: number;
-  total_amount: string;
+  reserve_exposure_xlm: number;
+  status: string;
   created_at: string;
   updated_at: string;
 }
@@ -42,7 +43,7 @@ export default function SponsorshipList({ sponsorships }: Props) {
               Reserves
             </th>
             <th className="px-6 py-3 text-left font-semibold text-gray-900">
-              Amount
+              Reserve Exposure
             </th>
             <th className="px-6 py-3 text-left font-semibold text-gray-900">
               Created
@@ -62,7 +63,7 @@ export default function SponsorshipList({ sponsorships }: Props) {
                 {s.sponsored_reserves}
               </td>
               <td className="px-6 py-4 font-semibold text-gray-900">
-                {s.total_amount}
+                {s.reserve_exposure_xlm} XLM
               </td>
               <td className="px-6 py-4 text-gray-600">
                 {formatDate(s.created_at)}
@@ -121,7 +121,7 @@ mod tests {
         successful: bool,
     ) -> PaymentRecord {
         PaymentRecord {
-            id: Uuid::new_v4(),
+            id: Uuid::new_v4().to_string(),
             source_asset_code: source_code.to_string(),
             source_asset_issuer: source_issuer.to_string(),
             destination_asset_code: dest_code.to_string(),
@@ -0,0 +1,59 @@
+//! Account profile combining balances, payment activity and tracker history
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::account_profile::{
+    AccountProfile, AccountProfileError, AccountProfileService,
+};
+
+#[derive(Deserialize)]
+pub struct ProfileParams {
+    /// Most recent payments to analyze
+    #[serde(default = "default_max_payments")]
+    max_payments: u32,
+}
+
+fn default_max_payments() -> u32 {
+    500
+}
+
+pub fn routes(service: Arc<AccountProfileService>) -> Router {
+    Router::new()
+        .route("/:account_id", get(get_account_profile))
+        .with_state(service)
+}
+
+impl From<AccountProfileError> for ApiError {
+    fn from(e: AccountProfileError) -> Self {
+        match e {
+            AccountProfileError::InvalidAccount(account) => ApiError::bad_request(
+                "INVALID_ACCOUNT",
+                format!("{} is not a valid G- or M-address", account),
+            ),
+            AccountProfileError::NotFound(account) => {
+                ApiError::not_found("NOT_FOUND", format!("Account {} not found", account))
+            }
+            AccountProfileError::Upstream(e) => ApiError::internal("UPSTREAM_ERROR", e.to_string()),
+            AccountProfileError::Other(e) => ApiError::internal("INTERNAL_ERROR", e.to_string()),
+        }
+    }
+}
+
+/// GET /api/accounts/:account_id - Profile for a G- or M-address
+async fn get_account_profile(
+    State(service): State<Arc<AccountProfileService>>,
+    Path(account_id): Path<String>,
+    Query(params): Query<ProfileParams>,
+) -> ApiResult<Json<AccountProfile>> {
+    let profile = service
+        .get_profile(&account_id, params.max_payments.clamp(1, 5000))
+        .await?;
+    Ok(Json(profile))
+}
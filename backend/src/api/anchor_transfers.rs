@@ -0,0 +1,101 @@
+//! SEP-24 / SEP-31 transfer lifecycles and measured settlement times
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::anchor_transfer_tracker::{
+    AnchorTransfer, AnchorTransferDetail, AnchorTransferFilter, AnchorTransferTracker,
+    SettlementDistribution,
+};
+
+#[derive(Deserialize)]
+pub struct TransferParams {
+    anchor_id: Option<String>,
+    corridor_key: Option<String>,
+    status: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+#[derive(Deserialize)]
+pub struct SettlementParams {
+    #[serde(default = "default_days")]
+    days: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+fn default_days() -> i64 {
+    30
+}
+
+pub fn routes(tracker: Arc<AnchorTransferTracker>) -> Router {
+    Router::new()
+        .route("/transfers", get(get_transfers))
+        .route("/transfers/:id", get(get_transfer))
+        .route("/settlement/anchors", get(get_anchor_settlement))
+        .route("/settlement/corridors", get(get_corridor_settlement))
+        .with_state(tracker)
+}
+
+fn internal(e: anyhow::Error) -> ApiError {
+    ApiError::internal("INTERNAL_ERROR", e.to_string())
+}
+
+/// GET /api/anchor-transfers/transfers - Transfers created through the SEP-24/31 proxies
+async fn get_transfers(
+    State(tracker): State<Arc<AnchorTransferTracker>>,
+    Query(params): Query<TransferParams>,
+) -> ApiResult<Json<Vec<AnchorTransfer>>> {
+    let filter = AnchorTransferFilter {
+        anchor_id: params.anchor_id,
+        corridor_key: params.corridor_key,
+        status: params.status,
+        limit: params.limit.clamp(1, 1000),
+    };
+    let transfers = tracker.get_transfers(&filter).await.map_err(internal)?;
+    Ok(Json(transfers))
+}
+
+/// GET /api/anchor-transfers/transfers/:id - Transfer with its status timeline
+async fn get_transfer(
+    State(tracker): State<Arc<AnchorTransferTracker>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<AnchorTransferDetail>> {
+    let transfer = tracker.get_transfer(&id).await.map_err(internal)?;
+    transfer.map(Json).ok_or_else(|| {
+        ApiError::not_found("TRANSFER_NOT_FOUND", format!("Transfer {} not found", id))
+    })
+}
+
+/// GET /api/anchor-transfers/settlement/anchors - Settlement latency per anchor
+async fn get_anchor_settlement(
+    State(tracker): State<Arc<AnchorTransferTracker>>,
+    Query(params): Query<SettlementParams>,
+) -> ApiResult<Json<Vec<SettlementDistribution>>> {
+    let distributions = tracker
+        .get_anchor_settlement(params.days.clamp(1, 365))
+        .await
+        .map_err(internal)?;
+    Ok(Json(distributions))
+}
+
+/// GET /api/anchor-transfers/settlement/corridors - Settlement latency per corridor
+async fn get_corridor_settlement(
+    State(tracker): State<Arc<AnchorTransferTracker>>,
+    Query(params): Query<SettlementParams>,
+) -> ApiResult<Json<Vec<SettlementDistribution>>> {
+    let distributions = tracker
+        .get_corridor_settlement(params.days.clamp(1, 365))
+        .await
+        .map_err(internal)?;
+    Ok(Json(distributions))
+}
@@ -5,18 +5,14 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::{Arc, Mutex};
 use utoipa::{IntoParams, ToSchema};
 
 use crate::cache::helpers::cached_query;
 use crate::cache::{keys, CacheManager};
 use crate::database::Database;
 use crate::error::ApiResult;
-use crate::rpc::{
-    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
-    error::{with_retry, RetryConfig, RpcError},
-    StellarRpcClient,
-};
+use crate::rpc::StellarRpcClient;
 use crate::services::price_feed::PriceFeedClient;
 
 #[derive(Debug, Deserialize, IntoParams)]
@@ -36,18 +32,6 @@ fn default_limit() -> i64 {
     50
 }
 
-fn rpc_circuit_breaker() -> Arc<CircuitBreaker> {
-    static CIRCUIT_BREAKER: OnceLock<Arc<CircuitBreaker>> = OnceLock::new();
-    CIRCUIT_BREAKER
-        .get_or_init(|| {
-            Arc::new(CircuitBreaker::new(
-                CircuitBreakerConfig::default(),
-                "horizon",
-            ))
-        })
-        .clone()
-}
-
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
 pub struct AnchorMetricsResponse {
     /// Unique identifier for the anchor
@@ -147,7 +131,6 @@ pub async fn get_anchors(
                 .await
                 .unwrap_or_default();
 
-            let circuit_breaker = rpc_circuit_breaker();
             let mut anchor_responses = Vec::new();
 
             // Process anchors with pre-fetched data
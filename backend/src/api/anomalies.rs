@@ -0,0 +1,51 @@
+//! Anomalies detected in hourly corridor and anchor metrics
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::anomaly_detector::{Anomaly, AnomalyDetector, AnomalyFilter};
+
+#[derive(Deserialize)]
+pub struct AnomalyParams {
+    /// `corridor` or `anchor`
+    subject_type: Option<String>,
+    /// Corridor key or anchor id
+    subject_id: Option<String>,
+    metric: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+pub fn routes(detector: Arc<AnomalyDetector>) -> Router {
+    Router::new()
+        .route("/", get(get_anomalies))
+        .with_state(detector)
+}
+
+/// GET /api/anomalies - Recently detected anomalies, most recent hour first
+async fn get_anomalies(
+    State(detector): State<Arc<AnomalyDetector>>,
+    Query(params): Query<AnomalyParams>,
+) -> ApiResult<Json<Vec<Anomaly>>> {
+    let filter = AnomalyFilter {
+        subject_type: params.subject_type,
+        subject_id: params.subject_id,
+        metric: params.metric,
+        limit: params.limit.clamp(1, 1000),
+    };
+    let anomalies = detector
+        .get_anomalies(&filter)
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?;
+    Ok(Json(anomalies))
+}
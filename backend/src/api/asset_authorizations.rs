@@ -0,0 +1,97 @@
+//! Issuer control actions: trustline freezes, clawbacks and flag changes
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::asset_authorization::{
+    AssetAuthorizationTracker, AuthorizationEvent, AuthorizationEventFilter,
+    AuthorizationEventType, IssuerActivityDay, IssuerAssetSummary,
+};
+
+#[derive(Deserialize)]
+pub struct EventParams {
+    issuer: Option<String>,
+    asset_code: Option<String>,
+    event_type: Option<AuthorizationEventType>,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+#[derive(Deserialize)]
+pub struct SummaryParams {
+    issuer: Option<String>,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+#[derive(Deserialize)]
+pub struct ActivityParams {
+    #[serde(default = "default_days")]
+    days: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+fn default_days() -> i64 {
+    30
+}
+
+pub fn routes(tracker: Arc<AssetAuthorizationTracker>) -> Router {
+    Router::new()
+        .route("/events", get(get_events))
+        .route("/issuers", get(get_issuer_summaries))
+        .route("/issuers/:issuer/activity", get(get_issuer_activity))
+        .with_state(tracker)
+}
+
+fn internal(e: anyhow::Error) -> ApiError {
+    ApiError::internal("INTERNAL_ERROR", e.to_string())
+}
+
+/// GET /api/asset-authorizations/events - Authorization, clawback and flag events
+async fn get_events(
+    State(tracker): State<Arc<AssetAuthorizationTracker>>,
+    Query(params): Query<EventParams>,
+) -> ApiResult<Json<Vec<AuthorizationEvent>>> {
+    let filter = AuthorizationEventFilter {
+        issuer: params.issuer,
+        asset_code: params.asset_code,
+        event_type: params.event_type,
+        limit: params.limit.clamp(1, 1000),
+    };
+    let events = tracker.get_events(&filter).await.map_err(internal)?;
+    Ok(Json(events))
+}
+
+/// GET /api/asset-authorizations/issuers - Frozen and clawed back totals per asset
+async fn get_issuer_summaries(
+    State(tracker): State<Arc<AssetAuthorizationTracker>>,
+    Query(params): Query<SummaryParams>,
+) -> ApiResult<Json<Vec<IssuerAssetSummary>>> {
+    let summaries = tracker
+        .get_issuer_summaries(params.issuer.as_deref(), params.limit.clamp(1, 1000))
+        .await
+        .map_err(internal)?;
+    Ok(Json(summaries))
+}
+
+/// GET /api/asset-authorizations/issuers/:issuer/activity - Daily activity of an issuer
+async fn get_issuer_activity(
+    State(tracker): State<Arc<AssetAuthorizationTracker>>,
+    Path(issuer): Path<String>,
+    Query(params): Query<ActivityParams>,
+) -> ApiResult<Json<Vec<IssuerActivityDay>>> {
+    let activity = tracker
+        .get_issuer_activity(&issuer, params.days.clamp(1, 365))
+        .await
+        .map_err(internal)?;
+    Ok(Json(activity))
+}
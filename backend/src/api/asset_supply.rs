@@ -0,0 +1,120 @@
+//! Circulating supply time series and issuer mint/burn activity per asset
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::asset_supply::{
+    AssetSupplyTracker, IssuanceDay, IssuanceEvent, SupplySnapshot,
+};
+
+#[derive(Deserialize)]
+pub struct LimitParams {
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+#[derive(Deserialize)]
+pub struct TrendParams {
+    #[serde(default = "default_days")]
+    days: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+fn default_days() -> i64 {
+    30
+}
+
+pub fn routes(tracker: Arc<AssetSupplyTracker>) -> Router {
+    Router::new()
+        .route("/", get(get_latest_supplies))
+        .route("/jumps", get(get_supply_jumps))
+        .route(
+            "/:asset_code/:asset_issuer/history",
+            get(get_supply_history),
+        )
+        .route(
+            "/:asset_code/:asset_issuer/issuance",
+            get(get_issuance_events),
+        )
+        .route(
+            "/:asset_code/:asset_issuer/issuance/trend",
+            get(get_issuance_trend),
+        )
+        .with_state(tracker)
+}
+
+fn internal(e: anyhow::Error) -> ApiError {
+    ApiError::internal("INTERNAL_ERROR", e.to_string())
+}
+
+/// GET /api/asset-supply - Latest circulating supply per asset, largest first
+async fn get_latest_supplies(
+    State(tracker): State<Arc<AssetSupplyTracker>>,
+    Query(params): Query<LimitParams>,
+) -> ApiResult<Json<Vec<SupplySnapshot>>> {
+    let supplies = tracker
+        .get_latest_supplies(params.limit.clamp(1, 500))
+        .await
+        .map_err(internal)?;
+    Ok(Json(supplies))
+}
+
+/// GET /api/asset-supply/jumps - Snapshots where supply moved past the threshold
+async fn get_supply_jumps(
+    State(tracker): State<Arc<AssetSupplyTracker>>,
+    Query(params): Query<LimitParams>,
+) -> ApiResult<Json<Vec<SupplySnapshot>>> {
+    let jumps = tracker
+        .get_supply_jumps(params.limit.clamp(1, 500))
+        .await
+        .map_err(internal)?;
+    Ok(Json(jumps))
+}
+
+/// GET /api/asset-supply/:asset_code/:asset_issuer/history - Supply over time, oldest first
+async fn get_supply_history(
+    State(tracker): State<Arc<AssetSupplyTracker>>,
+    Path((asset_code, asset_issuer)): Path<(String, String)>,
+    Query(params): Query<LimitParams>,
+) -> ApiResult<Json<Vec<SupplySnapshot>>> {
+    let history = tracker
+        .get_supply_history(&asset_code, &asset_issuer, params.limit.clamp(1, 1000))
+        .await
+        .map_err(internal)?;
+    Ok(Json(history))
+}
+
+/// GET /api/asset-supply/:asset_code/:asset_issuer/issuance - Mint and burn events
+async fn get_issuance_events(
+    State(tracker): State<Arc<AssetSupplyTracker>>,
+    Path((asset_code, asset_issuer)): Path<(String, String)>,
+    Query(params): Query<LimitParams>,
+) -> ApiResult<Json<Vec<IssuanceEvent>>> {
+    let events = tracker
+        .get_issuance_events(&asset_code, &asset_issuer, params.limit.clamp(1, 1000))
+        .await
+        .map_err(internal)?;
+    Ok(Json(events))
+}
+
+/// GET /api/asset-supply/:asset_code/:asset_issuer/issuance/trend - Daily net issuance
+async fn get_issuance_trend(
+    State(tracker): State<Arc<AssetSupplyTracker>>,
+    Path((asset_code, asset_issuer)): Path<(String, String)>,
+    Query(params): Query<TrendParams>,
+) -> ApiResult<Json<Vec<IssuanceDay>>> {
+    let trend = tracker
+        .get_issuance_trend(&asset_code, &asset_issuer, params.days.clamp(1, 365))
+        .await
+        .map_err(internal)?;
+    Ok(Json(trend))
+}
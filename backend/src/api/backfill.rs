@@ -0,0 +1,141 @@
+//! Admin API for historical ledger backfill jobs
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::ingestion::backfill::{
+    BackfillError, BackfillJob, BackfillJobDetail, BackfillRequest, BackfillService,
+    GapFillRequest, LedgerGap,
+};
+
+#[derive(Deserialize)]
+pub struct ListJobsParams {
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Deserialize)]
+pub struct GapParams {
+    start_ledger: Option<u64>,
+    end_ledger: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct GapReport {
+    pub gaps: Vec<LedgerGap>,
+    pub missing_ledgers: u64,
+}
+
+#[derive(Serialize)]
+pub struct GapFillResponse {
+    /// `None` when no gaps were found
+    pub job: Option<BackfillJob>,
+}
+
+pub fn routes(service: Arc<BackfillService>) -> Router {
+    Router::new()
+        .route("/", get(list_jobs).post(start_backfill))
+        .route("/gaps", get(get_gaps).post(fill_gaps))
+        .route("/:id", get(get_job))
+        .route("/:id/resume", post(resume_job))
+        .with_state(service)
+}
+
+impl From<BackfillError> for ApiError {
+    fn from(e: BackfillError) -> Self {
+        match e {
+            BackfillError::InvalidRange(msg) => ApiError::bad_request("INVALID_RANGE", msg),
+            BackfillError::JobNotFound(id) => {
+                ApiError::not_found("NOT_FOUND", format!("Backfill job {} not found", id))
+            }
+            BackfillError::AlreadyRunning(id) => ApiError::bad_request(
+                "ALREADY_RUNNING",
+                format!("Backfill job {} is already running", id),
+            ),
+            BackfillError::Other(e) => ApiError::internal("INTERNAL_ERROR", e.to_string()),
+        }
+    }
+}
+
+/// POST /api/admin/backfill - Start a backfill of an explicit ledger range
+async fn start_backfill(
+    State(service): State<Arc<BackfillService>>,
+    Json(req): Json<BackfillRequest>,
+) -> ApiResult<(StatusCode, Json<BackfillJob>)> {
+    let job = service.create_range_job(req).await?;
+    service.spawn(job.id.clone());
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// GET /api/admin/backfill - List recent backfill jobs
+async fn list_jobs(
+    State(service): State<Arc<BackfillService>>,
+    Query(params): Query<ListJobsParams>,
+) -> ApiResult<Json<Vec<BackfillJob>>> {
+    let limit = params.limit.clamp(1, 200);
+    Ok(Json(service.list_jobs(limit).await?))
+}
+
+/// GET /api/admin/backfill/gaps - Report sequences missing from the ledgers table
+async fn get_gaps(
+    State(service): State<Arc<BackfillService>>,
+    Query(params): Query<GapParams>,
+) -> ApiResult<Json<GapReport>> {
+    let gaps = service
+        .find_gaps(params.start_ledger, params.end_ledger)
+        .await?;
+    let missing_ledgers = gaps.iter().map(LedgerGap::ledger_count).sum();
+    Ok(Json(GapReport {
+        gaps,
+        missing_ledgers,
+    }))
+}
+
+/// POST /api/admin/backfill/gaps - Start a job that re-ingests every gap
+async fn fill_gaps(
+    State(service): State<Arc<BackfillService>>,
+    body: Option<Json<GapFillRequest>>,
+) -> ApiResult<(StatusCode, Json<GapFillResponse>)> {
+    let req = body.map(|Json(req)| req).unwrap_or_default();
+    let job = service.create_gap_job(req).await?;
+    let status = match &job {
+        Some(job) => {
+            service.spawn(job.id.clone());
+            StatusCode::ACCEPTED
+        }
+        None => StatusCode::OK,
+    };
+    Ok((status, Json(GapFillResponse { job })))
+}
+
+/// GET /api/admin/backfill/:id - Job status with per-shard checkpoints
+async fn get_job(
+    State(service): State<Arc<BackfillService>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<BackfillJobDetail>> {
+    service
+        .get_job(&id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| BackfillError::JobNotFound(id).into())
+}
+
+/// POST /api/admin/backfill/:id/resume - Re-run unfinished shards from their checkpoints
+async fn resume_job(
+    State(service): State<Arc<BackfillService>>,
+    Path(id): Path<String>,
+) -> ApiResult<(StatusCode, Json<BackfillJob>)> {
+    let job = service.resume(&id).await?;
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
@@ -0,0 +1,111 @@
+//! OHLCV candles per asset pair and liquidity pool, built from ingested trades
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::trade_aggregator::{
+    Candle, CandleQuery, Market, MarketType, Resolution, TradeAggregator,
+};
+
+#[derive(Deserialize)]
+pub struct CandleParams {
+    #[serde(default = "default_resolution")]
+    resolution: Resolution,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+fn default_resolution() -> Resolution {
+    Resolution::OneHour
+}
+
+fn default_limit() -> i64 {
+    200
+}
+
+impl CandleParams {
+    fn to_query(&self) -> ApiResult<CandleQuery> {
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if start > end {
+                return Err(ApiError::bad_request(
+                    "INVALID_RANGE",
+                    "start must not be after end",
+                ));
+            }
+        }
+        Ok(CandleQuery {
+            start: self.start,
+            end: self.end,
+            limit: self.limit.clamp(1, 1000),
+        })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct MarketParams {
+    market_type: Option<MarketType>,
+}
+
+pub fn routes(aggregator: Arc<TradeAggregator>) -> Router {
+    Router::new()
+        .route("/markets", get(get_markets))
+        .route("/pairs/:pair_key", get(get_pair_candles))
+        .route("/pools/:pool_id", get(get_pool_candles))
+        .with_state(aggregator)
+}
+
+fn internal(e: anyhow::Error) -> ApiError {
+    ApiError::internal("INTERNAL_ERROR", e.to_string())
+}
+
+/// GET /api/candles/markets - Pairs and pools with candles, most traded first
+async fn get_markets(
+    State(aggregator): State<Arc<TradeAggregator>>,
+    Query(params): Query<MarketParams>,
+) -> ApiResult<Json<Vec<Market>>> {
+    let markets = aggregator
+        .get_markets(params.market_type)
+        .await
+        .map_err(internal)?;
+    Ok(Json(markets))
+}
+
+/// GET /api/candles/pairs/:pair_key - Candles for `BASE->COUNTER`, either direction
+async fn get_pair_candles(
+    State(aggregator): State<Arc<TradeAggregator>>,
+    Path(pair_key): Path<String>,
+    Query(params): Query<CandleParams>,
+) -> ApiResult<Json<Vec<Candle>>> {
+    let candles = aggregator
+        .get_pair_candles(&pair_key, params.resolution, &params.to_query()?)
+        .await
+        .map_err(internal)?;
+    Ok(Json(candles))
+}
+
+/// GET /api/candles/pools/:pool_id - Candles for trades against a liquidity pool
+async fn get_pool_candles(
+    State(aggregator): State<Arc<TradeAggregator>>,
+    Path(pool_id): Path<String>,
+    Query(params): Query<CandleParams>,
+) -> ApiResult<Json<Vec<Candle>>> {
+    let candles = aggregator
+        .get_candles(
+            MarketType::LiquidityPool,
+            &pool_id,
+            params.resolution,
+            &params.to_query()?,
+        )
+        .await
+        .map_err(internal)?;
+    Ok(Json(candles))
+}
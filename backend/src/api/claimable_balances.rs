@@ -0,0 +1,92 @@
+//! Claimable balance lifecycle API
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::claimable_balance_tracker::{
+    AnchorUnclaimedValue, ClaimableBalance, ClaimableBalanceFilter, ClaimableBalanceStats,
+    ClaimableBalanceTracker,
+};
+
+#[derive(Deserialize)]
+pub struct ListBalancesParams {
+    #[serde(flatten)]
+    filter: ClaimableBalanceFilter,
+    #[serde(default = "default_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+pub fn routes(tracker: Arc<ClaimableBalanceTracker>) -> Router {
+    Router::new()
+        .route("/", get(list_balances))
+        .route("/stats", get(get_stats))
+        .route("/anchors", get(get_unclaimed_by_anchor))
+        .route("/:balance_id", get(get_balance))
+        .with_state(tracker)
+}
+
+/// GET /api/claimable-balances - Balances filtered by status, asset or claimant
+async fn list_balances(
+    State(tracker): State<Arc<ClaimableBalanceTracker>>,
+    Query(params): Query<ListBalancesParams>,
+) -> ApiResult<Json<Vec<ClaimableBalance>>> {
+    let limit = params.limit.clamp(1, 200);
+    let balances = tracker
+        .list_balances(&params.filter, limit, params.offset.max(0))
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?;
+    Ok(Json(balances))
+}
+
+/// GET /api/claimable-balances/stats - Status counts, unclaimed value per asset
+/// and time-to-claim distribution
+async fn get_stats(
+    State(tracker): State<Arc<ClaimableBalanceTracker>>,
+) -> ApiResult<Json<ClaimableBalanceStats>> {
+    let stats = tracker
+        .get_stats()
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?;
+    Ok(Json(stats))
+}
+
+/// GET /api/claimable-balances/anchors - Unclaimed value of each anchor's assets
+async fn get_unclaimed_by_anchor(
+    State(tracker): State<Arc<ClaimableBalanceTracker>>,
+) -> ApiResult<Json<Vec<AnchorUnclaimedValue>>> {
+    let anchors = tracker
+        .get_unclaimed_by_anchor()
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?;
+    Ok(Json(anchors))
+}
+
+/// GET /api/claimable-balances/:balance_id
+async fn get_balance(
+    State(tracker): State<Arc<ClaimableBalanceTracker>>,
+    Path(balance_id): Path<String>,
+) -> ApiResult<Json<ClaimableBalance>> {
+    tracker
+        .get_balance(&balance_id)
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "NOT_FOUND",
+                format!("Claimable balance {} not found", balance_id),
+            )
+        })
+}
@@ -0,0 +1,97 @@
+//! Holt-Winters forecasts of corridor volume, transaction count and success rate
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::corridor_forecast::{CorridorForecast, CorridorForecaster};
+
+#[derive(Deserialize)]
+pub struct ForecastParams {
+    /// Hours ahead: `48h`, `2d` or a bare number of hours
+    #[serde(default = "default_horizon")]
+    horizon: String,
+}
+
+fn default_horizon() -> String {
+    "24h".to_string()
+}
+
+pub fn routes(forecaster: Arc<CorridorForecaster>) -> Router {
+    Router::new()
+        .route("/:corridor_key/forecast", get(get_corridor_forecast))
+        .with_state(forecaster)
+}
+
+/// GET /api/corridors/:corridor_key/forecast - Hourly point forecasts with
+/// prediction intervals over the requested horizon
+async fn get_corridor_forecast(
+    State(forecaster): State<Arc<CorridorForecaster>>,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<ForecastParams>,
+) -> ApiResult<Json<CorridorForecast>> {
+    let corridor = crate::ml::parse_corridor(&corridor_key).ok_or_else(|| {
+        ApiError::bad_request(
+            "INVALID_CORRIDOR",
+            "Corridor must be formatted as CODE:ISSUER->CODE:ISSUER",
+        )
+    })?;
+    let max_horizon = forecaster.config().max_horizon_hours;
+    let horizon = parse_horizon(&params.horizon)
+        .filter(|h| (1..=max_horizon).contains(h))
+        .ok_or_else(|| {
+            ApiError::bad_request(
+                "INVALID_HORIZON",
+                format!(
+                    "horizon must be between 1h and {}h, e.g. 48h or 2d",
+                    max_horizon
+                ),
+            )
+        })?;
+
+    let corridor_key = corridor.to_string_key();
+    let forecast = forecaster
+        .get_forecast(&corridor_key, horizon)
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "FORECAST_NOT_FOUND",
+                format!("Not enough hourly history to forecast {}", corridor_key),
+            )
+        })?;
+    Ok(Json(forecast))
+}
+
+/// Hours in `48h`, `2d` or `48`
+fn parse_horizon(horizon: &str) -> Option<usize> {
+    let horizon = horizon.trim().to_ascii_lowercase();
+    if let Some(days) = horizon.strip_suffix('d') {
+        return days.parse::<usize>().ok()?.checked_mul(24);
+    }
+    horizon
+        .strip_suffix('h')
+        .unwrap_or(&horizon)
+        .parse::<usize>()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_horizon() {
+        assert_eq!(parse_horizon("48h"), Some(48));
+        assert_eq!(parse_horizon("48"), Some(48));
+        assert_eq!(parse_horizon("2d"), Some(48));
+        assert_eq!(parse_horizon(" 12H "), Some(12));
+        assert_eq!(parse_horizon("-1h"), None);
+        assert_eq!(parse_horizon("soon"), None);
+    }
+}
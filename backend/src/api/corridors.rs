@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use crate::error::{ApiError, ApiResult};
 use crate::models::corridor::{Corridor, CorridorMetrics};
 use crate::models::SortBy;
+use crate::services::corridor_health::{CorridorHistory, HealthBreakdown};
 use crate::state::AppState;
 use crate::validation;
 
@@ -31,6 +32,8 @@ pub struct CorridorResponse {
     pub liquidity_volume_24h_usd: f64,
     pub liquidity_trend: String,
     pub health_score: f64,
+    #[serde(default)]
+    pub health_breakdown: HealthBreakdown,
     pub last_updated: String,
 }
 
@@ -85,39 +88,16 @@ fn default_limit() -> i64 {
     50
 }
 
-/// Calculate health score based on success rate, volume, and transaction count
-fn calculate_health_score(success_rate: f64, total_transactions: i64, volume_usd: f64) -> f64 {
-    let success_weight = 0.6;
-    let volume_weight = 0.2;
-    let transaction_weight = 0.2;
-
-    // Normalize volume and transactions (using logarithmic scale)
-    let volume_score = if volume_usd > 0.0 {
-        ((volume_usd.ln() / 15.0) * 100.0).min(100.0)
-    } else {
-        0.0
-    };
-
-    let transaction_score = if total_transactions > 0 {
-        ((total_transactions as f64).ln() / 10.0 * 100.0).min(100.0)
-    } else {
-        0.0
-    };
-
-    success_rate * success_weight
-        + volume_score * volume_weight
-        + transaction_score * transaction_weight
-}
-
-/// Determine liquidity trend (simple heuristic based on recent data)
-fn get_liquidity_trend(volume_usd: f64) -> String {
-    if volume_usd > 10_000_000.0 {
-        "increasing".to_string()
-    } else if volume_usd > 1_000_000.0 {
-        "stable".to_string()
-    } else {
-        "decreasing".to_string()
-    }
+/// Recent hourly history of all corridors for the shared health scorer
+async fn load_health(app_state: &AppState) -> HashMap<String, CorridorHistory> {
+    app_state
+        .health_scorer
+        .load_history()
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to load corridor history for health scores: {}", e);
+            HashMap::new()
+        })
 }
 
 /// GET /api/corridors - List all corridors
@@ -236,12 +216,13 @@ pub async fn list_corridors(
         })
         .collect();
 
+    let history = load_health(&app_state).await;
     let corridors: Vec<CorridorResponse> = filtered_metrics
         .iter()
         .map(|m| {
-            let health_score =
-                calculate_health_score(m.success_rate, m.total_transactions, m.volume_usd);
-            let liquidity_trend = get_liquidity_trend(m.volume_usd);
+            let health = app_state
+                .health_scorer
+                .assess(&m.corridor_key, m.success_rate, &history);
             let avg_latency = 400.0 + (m.success_rate * 2.0);
 
             CorridorResponse {
@@ -258,8 +239,9 @@ pub async fn list_corridors(
                 p99_latency_ms: avg_latency * 4.0,
                 liquidity_depth_usd: m.volume_usd,
                 liquidity_volume_24h_usd: m.volume_usd * 0.1,
-                liquidity_trend,
-                health_score,
+                liquidity_trend: health.liquidity_trend,
+                health_score: health.score,
+                health_breakdown: health.breakdown,
                 last_updated: m.updated_at.to_rfc3339(),
             }
         })
@@ -324,12 +306,11 @@ pub async fn get_corridor_detail(
     }
 
     let latest = metrics.first().unwrap();
-    let health_score = calculate_health_score(
-        latest.success_rate,
-        latest.total_transactions,
-        latest.volume_usd,
-    );
-    let liquidity_trend = get_liquidity_trend(latest.volume_usd);
+    let history = load_health(&app_state).await;
+    let health =
+        app_state
+            .health_scorer
+            .assess(&latest.corridor_key, latest.success_rate, &history);
     let avg_latency = 400.0 + (latest.success_rate * 2.0);
 
     let corridor_response = CorridorResponse {
@@ -346,8 +327,9 @@ pub async fn get_corridor_detail(
         p99_latency_ms: avg_latency * 4.0,
         liquidity_depth_usd: latest.volume_usd,
         liquidity_volume_24h_usd: latest.volume_usd * 0.1,
-        liquidity_trend,
-        health_score,
+        liquidity_trend: health.liquidity_trend,
+        health_score: health.score,
+        health_breakdown: health.breakdown,
         last_updated: latest.updated_at.to_rfc3339(),
     };
 
@@ -416,9 +398,9 @@ pub async fn get_corridor_detail(
         .filter(|m| m.corridor_key != latest.corridor_key)
         .take(3)
         .map(|m| {
-            let health_score =
-                calculate_health_score(m.success_rate, m.total_transactions, m.volume_usd);
-            let liquidity_trend = get_liquidity_trend(m.volume_usd);
+            let health = app_state
+                .health_scorer
+                .assess(&m.corridor_key, m.success_rate, &history);
             let avg_latency = 400.0 + (m.success_rate * 2.0);
 
             CorridorResponse {
@@ -435,8 +417,9 @@ pub async fn get_corridor_detail(
                 p99_latency_ms: avg_latency * 4.0,
                 liquidity_depth_usd: m.volume_usd,
                 liquidity_volume_24h_usd: m.volume_usd * 0.1,
-                liquidity_trend,
-                health_score,
+                liquidity_trend: health.liquidity_trend,
+                health_score: health.score,
+                health_breakdown: health.breakdown,
                 last_updated: m.updated_at.to_rfc3339(),
             }
         })
@@ -496,6 +479,7 @@ mod tests {
             liquidity_volume_24h_usd: metrics.volume_usd * 0.1,
             liquidity_trend: "stable".to_string(),
             health_score: 95.0,
+            health_breakdown: HealthBreakdown::default(),
             last_updated: metrics.updated_at.to_rfc3339(),
         };
 
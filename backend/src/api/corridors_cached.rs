@@ -2,11 +2,11 @@ use axum::{
     extract::{Path, Query, State},
     http::HeaderMap,
     response::Response,
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::{Arc, Mutex};
 use utoipa::{IntoParams, ToSchema};
 
 use crate::cache::helpers::cached_query;
@@ -14,9 +14,8 @@ use crate::cache::{keys, CacheManager};
 use crate::database::Database;
 use crate::error::{ApiError, ApiResult};
 use crate::models::SortBy;
-use crate::rpc::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
-use crate::rpc::error::{with_retry, RetryConfig, RpcError};
 use crate::rpc::StellarRpcClient;
+use crate::services::corridor_health::{CorridorHealthScorer, CorridorHistory, HealthBreakdown};
 use crate::services::price_feed::PriceFeedClient;
 use crate::validation;
 use anyhow::anyhow;
@@ -91,6 +90,89 @@ fn extract_asset_pair_from_payment(payment: &crate::rpc::Payment) -> Option<Asse
     }
 }
 
+/// Conversion metrics over the path payments of a corridor
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PathPaymentStats {
+    count: i64,
+    average_exchange_rate: Option<f64>,
+    conversion_cost_pct: Option<f64>,
+    average_path_hops: Option<f64>,
+}
+
+/// Conversion cost needs USD prices for both the source and the destination asset
+fn path_payment_stats(
+    payments: &[&crate::rpc::Payment],
+    source_price: Option<f64>,
+    destination_price: Option<f64>,
+) -> PathPaymentStats {
+    let path_payments: Vec<_> = payments.iter().filter(|p| p.is_path_payment()).collect();
+    if path_payments.is_empty() {
+        return PathPaymentStats::default();
+    }
+
+    let count = path_payments.len() as i64;
+    let rates: Vec<f64> = path_payments
+        .iter()
+        .filter_map(|p| p.exchange_rate())
+        .collect();
+    let average_exchange_rate =
+        (!rates.is_empty()).then(|| rates.iter().sum::<f64>() / rates.len() as f64);
+    let hops: usize = path_payments.iter().map(|p| p.path.len()).sum();
+
+    let conversion_cost_pct = source_price.zip(destination_price).and_then(|(sp, dp)| {
+        let (sent_usd, received_usd) = path_payments
+            .iter()
+            .filter_map(|p| {
+                let sent: f64 = p.get_source_amount().parse().ok()?;
+                let received: f64 = p.get_amount().parse().ok()?;
+                Some((sent * sp, received * dp))
+            })
+            .fold((0.0, 0.0), |(s, r), (ps, pr)| (s + ps, r + pr));
+        (sent_usd > 0.0).then(|| (sent_usd - received_usd) / sent_usd * 100.0)
+    });
+
+    PathPaymentStats {
+        count,
+        average_exchange_rate,
+        conversion_cost_pct,
+        average_path_hops: Some(hops as f64 / count as f64),
+    }
+}
+
+/// USD volume sent through a corridor, priced in the source asset, and its path payment stats
+async fn corridor_volume(
+    price_feed: &PriceFeedClient,
+    source_key: &str,
+    destination_key: &str,
+    payments: &[&crate::rpc::Payment],
+) -> (f64, PathPaymentStats) {
+    let source_price = price_feed.get_price(source_key).await.ok();
+    let sent: Vec<f64> = payments
+        .iter()
+        .filter_map(|p| p.get_source_amount().parse::<f64>().ok())
+        .collect();
+
+    let volume_usd = match source_price {
+        Some(price) => sent.iter().map(|amount| amount * price).sum(),
+        None => {
+            // Fallback: use raw amounts if price unavailable
+            tracing::warn!("Price unavailable for {}, using raw amounts", source_key);
+            sent.iter().sum()
+        }
+    };
+
+    let destination_price = if payments.iter().any(|p| p.is_path_payment()) {
+        price_feed.get_price(destination_key).await.ok()
+    } else {
+        None
+    };
+
+    (
+        volume_usd,
+        path_payment_stats(payments, source_price, destination_price),
+    )
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CorridorResponse {
     /// Unique identifier for the corridor
@@ -132,12 +214,32 @@ pub struct CorridorResponse {
     /// 24-hour trading volume in USD
     #[schema(example = 150000.0)]
     pub liquidity_volume_24h_usd: f64,
-    /// Liquidity trend (increasing, stable, decreasing)
+    /// Liquidity depth over the last day against the days before it
+    /// (increasing, stable, decreasing)
     #[schema(example = "stable")]
     pub liquidity_trend: String,
     /// Overall health score (0-100)
     #[schema(example = 95.5)]
     pub health_score: f64,
+    /// Components of the health score and the scoring version
+    #[serde(default)]
+    pub health_breakdown: HealthBreakdown,
+    /// Number of path payments (strict send and strict receive)
+    #[serde(default)]
+    #[schema(example = 120)]
+    pub path_payment_count: i64,
+    /// Average destination units received per source unit across path payments
+    #[serde(default)]
+    #[schema(example = 0.925)]
+    pub average_exchange_rate: Option<f64>,
+    /// USD value lost in conversion as a percentage of the USD value sent
+    #[serde(default)]
+    #[schema(example = 0.35)]
+    pub conversion_cost_pct: Option<f64>,
+    /// Average number of intermediate assets per path payment
+    #[serde(default)]
+    #[schema(example = 1.2)]
+    pub average_path_hops: Option<f64>,
     /// Last update timestamp
     #[schema(example = "2024-01-15T10:30:00Z")]
     pub last_updated: String,
@@ -236,48 +338,13 @@ fn default_limit() -> i64 {
     50
 }
 
-fn calculate_health_score(success_rate: f64, total_transactions: i64, volume_usd: f64) -> f64 {
-    let success_weight = 0.6;
-    let volume_weight = 0.2;
-    let transaction_weight = 0.2;
-
-    let volume_score = if volume_usd > 0.0 {
-        ((volume_usd.ln() / 15.0) * 100.0).min(100.0)
-    } else {
-        0.0
-    };
-
-    let transaction_score = if total_transactions > 0 {
-        ((total_transactions as f64).ln() / 10.0 * 100.0).min(100.0)
-    } else {
-        0.0
-    };
-
-    success_rate * success_weight
-        + volume_score * volume_weight
-        + transaction_score * transaction_weight
-}
-
-fn get_liquidity_trend(volume_usd: f64) -> String {
-    if volume_usd > 10_000_000.0 {
-        "increasing".to_string()
-    } else if volume_usd > 1_000_000.0 {
-        "stable".to_string()
-    } else {
-        "decreasing".to_string()
-    }
-}
-
-fn rpc_circuit_breaker() -> Arc<CircuitBreaker> {
-    static CIRCUIT_BREAKER: OnceLock<Arc<CircuitBreaker>> = OnceLock::new();
-    CIRCUIT_BREAKER
-        .get_or_init(|| {
-            Arc::new(CircuitBreaker::new(
-                CircuitBreakerConfig::default(),
-                "horizon",
-            ))
-        })
-        .clone()
+/// Hourly history of all corridors; scores fall back to the live success rate
+/// alone when it cannot be read
+async fn load_corridor_history(scorer: &CorridorHealthScorer) -> HashMap<String, CorridorHistory> {
+    scorer.load_history().await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to load corridor history for health scores: {}", e);
+        HashMap::new()
+    })
 }
 
 /// Generate cache key for corridor list with filters
@@ -314,7 +381,7 @@ fn generate_corridor_list_cache_key(params: &ListCorridorsQuery) -> String {
     ),
     tag = "Corridors"
 )]
-#[tracing::instrument(skip(_db, cache, rpc_client, price_feed, params))]
+#[tracing::instrument(skip(_db, cache, rpc_client, price_feed, health_scorer, params))]
 pub async fn list_corridors(
     State((_db, cache, rpc_client, price_feed)): State<(
         Arc<Database>,
@@ -322,6 +389,7 @@ pub async fn list_corridors(
         Arc<StellarRpcClient>,
         Arc<PriceFeedClient>,
     )>,
+    Extension(health_scorer): Extension<Arc<CorridorHealthScorer>>,
     Query(params): Query<ListCorridorsQuery>,
     headers: HeaderMap,
 ) -> ApiResult<Response> {
@@ -339,35 +407,18 @@ pub async fn list_corridors(
         &cache_key,
         cache.config.get_ttl("corridor"),
         || async {
-            let circuit_breaker = rpc_circuit_breaker();
-
-            // **RPC DATA**: Fetch recent payments to identify active corridors
-            let payments = with_retry(
-                || async {
-                    rpc_client
-                        .fetch_payments(200, None)
-                        .await
-                        .map_err(|e| RpcError::categorize(&e.to_string()))
-                },
-                RetryConfig::default(),
-                circuit_breaker.clone(),
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to fetch payments from RPC: {}", e))?;
+            // **RPC DATA**: Fetch recent payments to identify active corridors.
+            // The client retries and fails over across Horizon endpoints itself.
+            let payments = rpc_client
+                .fetch_payments(200, None)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to fetch payments from RPC: {}", e))?;
 
             // **RPC DATA**: Fetch recent trades for volume data
-            let _trades = with_retry(
-                || async {
-                    rpc_client
-                        .fetch_trades(200, None)
-                        .await
-                        .map_err(|e| RpcError::categorize(&e.to_string()))
-                },
-                RetryConfig::default(),
-                circuit_breaker.clone(),
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to fetch trades from RPC: {}", e))?;
+            let _trades = rpc_client
+                .fetch_trades(200, None)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to fetch trades from RPC: {}", e))?;
             // **RPC DATA**: Fetch recent payments with pagination to identify active corridors
             // Use paginated fetch to get more complete data (up to configured limit)
             let payments = match rpc_client.fetch_all_payments(Some(1000)).await {
@@ -405,6 +456,7 @@ pub async fn list_corridors(
             }
 
             // Calculate metrics for each corridor
+            let history = load_corridor_history(&health_scorer).await;
             let mut corridor_responses = Vec::new();
 
             for (corridor_key, corridor_payments) in corridor_map.iter() {
@@ -429,31 +481,11 @@ pub async fn list_corridors(
                 }
 
                 // Calculate volume from payment amounts and convert to USD
-                let mut volume_usd: f64 = 0.0;
-                let source_asset_key = parts[0];
-
-                // Get price for source asset
-                if let Ok(price) = price_feed.get_price(source_asset_key).await {
-                    for payment in corridor_payments.iter() {
-                        if let Ok(amount) = payment.get_amount().parse::<f64>() {
-                            volume_usd += amount * price;
-                        }
-                    }
-                } else {
-                    // Fallback: use raw amounts if price unavailable
-                    tracing::warn!(
-                        "Price unavailable for {}, using raw amounts",
-                        source_asset_key
-                    );
-                    volume_usd = corridor_payments
-                        .iter()
-                        .filter_map(|p| p.get_amount().parse::<f64>().ok())
-                        .sum();
-                }
+                let (volume_usd, path_stats) =
+                    corridor_volume(&price_feed, parts[0], parts[1], corridor_payments).await;
 
                 // Calculate health score
-                let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
-                let liquidity_trend = get_liquidity_trend(volume_usd);
+                let health = health_scorer.assess(corridor_key, success_rate, &history);
                 let avg_latency = 400.0 + (success_rate * 2.0);
 
                 let corridor_response = CorridorResponse {
@@ -470,8 +502,13 @@ pub async fn list_corridors(
                     p99_latency_ms: avg_latency * 4.0,
                     liquidity_depth_usd: volume_usd,
                     liquidity_volume_24h_usd: volume_usd * 0.1,
-                    liquidity_trend,
-                    health_score,
+                    liquidity_trend: health.liquidity_trend,
+                    health_score: health.score,
+                    health_breakdown: health.breakdown,
+                    path_payment_count: path_stats.count,
+                    average_exchange_rate: path_stats.average_exchange_rate,
+                    conversion_cost_pct: path_stats.conversion_cost_pct,
+                    average_path_hops: path_stats.average_path_hops,
                     last_updated: chrono::Utc::now().to_rfc3339(),
                 };
 
@@ -708,7 +745,7 @@ fn find_related_corridors(
     ),
     tag = "Corridors"
 )]
-#[tracing::instrument(skip(db, cache, rpc_client, price_feed))]
+#[tracing::instrument(skip(db, cache, rpc_client, price_feed, health_scorer))]
 pub async fn get_corridor_detail(
     State((db, cache, rpc_client, price_feed)): State<(
         Arc<Database>,
@@ -716,6 +753,7 @@ pub async fn get_corridor_detail(
         Arc<StellarRpcClient>,
         Arc<PriceFeedClient>,
     )>,
+    Extension(health_scorer): Extension<Arc<CorridorHealthScorer>>,
     Path(corridor_key): Path<String>,
 ) -> ApiResult<Json<CorridorDetailResponse>> {
     use std::collections::HashMap;
@@ -745,24 +783,14 @@ pub async fn get_corridor_detail(
 
     let cache_key = keys::corridor_detail(&corridor_key);
     let response = cached_query(&cache, &cache_key, 300, || async {
-        // Fetch payments from RPC
-        let circuit_breaker = rpc_circuit_breaker();
-
-        let payments = with_retry(
-            || async {
-                rpc_client
-                    .fetch_all_payments(Some(5000))
-                    .await
-                    .map_err(|e| RpcError::categorize(&e.to_string()))
-            },
-            RetryConfig::default(),
-            circuit_breaker.clone(),
-        )
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to fetch payments from RPC: {}", e);
-            anyhow::anyhow!("Failed to fetch payment data from RPC")
-        })?;
+        // Fetch payments from RPC (retried and failed over across endpoints by the client)
+        let payments = rpc_client
+            .fetch_all_payments(Some(5000))
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to fetch payments from RPC: {}", e);
+                anyhow::anyhow!("Failed to fetch payment data from RPC")
+            })?;
 
         // Filter payments for this specific corridor
         let mut corridor_payments = Vec::new();
@@ -792,6 +820,7 @@ pub async fn get_corridor_detail(
         }
 
         // Build all corridor responses for related corridors lookup
+        let history = load_corridor_history(&health_scorer).await;
         for (key, corr_payments) in corridor_map.iter() {
             let total_attempts = corr_payments.len() as i64;
             let successful_payments = total_attempts;
@@ -811,22 +840,10 @@ pub async fn get_corridor_detail(
             }
 
             // Calculate volume
-            let mut volume_usd = 0.0;
-            if let Ok(price) = price_feed.get_price(parts[0]).await {
-                for payment in corr_payments.iter() {
-                    if let Ok(amount) = payment.get_amount().parse::<f64>() {
-                        volume_usd += amount * price;
-                    }
-                }
-            } else {
-                volume_usd = corr_payments
-                    .iter()
-                    .filter_map(|p| p.get_amount().parse::<f64>().ok())
-                    .sum();
-            }
+            let (volume_usd, path_stats) =
+                corridor_volume(&price_feed, parts[0], parts[1], corr_payments).await;
 
-            let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
-            let liquidity_trend = get_liquidity_trend(volume_usd);
+            let health = health_scorer.assess(key, success_rate, &history);
             let avg_latency = 400.0 + (success_rate * 2.0);
 
             all_corridors.push(CorridorResponse {
@@ -843,8 +860,13 @@ pub async fn get_corridor_detail(
                 p99_latency_ms: avg_latency * 4.0,
                 liquidity_depth_usd: volume_usd,
                 liquidity_volume_24h_usd: volume_usd * 0.1,
-                liquidity_trend,
-                health_score,
+                liquidity_trend: health.liquidity_trend,
+                health_score: health.score,
+                health_breakdown: health.breakdown,
+                path_payment_count: path_stats.count,
+                average_exchange_rate: path_stats.average_exchange_rate,
+                conversion_cost_pct: path_stats.conversion_cost_pct,
+                average_path_hops: path_stats.average_path_hops,
                 last_updated: chrono::Utc::now().to_rfc3339(),
             });
         }
@@ -855,22 +877,10 @@ pub async fn get_corridor_detail(
         let failed_payments = 0;
         let success_rate = 100.0;
 
-        let mut volume_usd = 0.0;
-        if let Ok(price) = price_feed.get_price(source_key).await {
-            for payment in corridor_payments.iter() {
-                if let Ok(amount) = payment.get_amount().parse::<f64>() {
-                    volume_usd += amount * price;
-                }
-            }
-        } else {
-            volume_usd = corridor_payments
-                .iter()
-                .filter_map(|p| p.get_amount().parse::<f64>().ok())
-                .sum();
-        }
+        let (volume_usd, path_stats) =
+            corridor_volume(&price_feed, source_key, dest_key, &corridor_payments).await;
 
-        let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
-        let liquidity_trend = get_liquidity_trend(volume_usd);
+        let health = health_scorer.assess(&corridor_key, success_rate, &history);
         let avg_latency = 400.0 + (success_rate * 2.0);
 
         let corridor = CorridorResponse {
@@ -887,8 +897,13 @@ pub async fn get_corridor_detail(
             p99_latency_ms: avg_latency * 4.0,
             liquidity_depth_usd: volume_usd,
             liquidity_volume_24h_usd: volume_usd * 0.1,
-            liquidity_trend,
-            health_score,
+            liquidity_trend: health.liquidity_trend,
+            health_score: health.score,
+            health_breakdown: health.breakdown,
+            path_payment_count: path_stats.count,
+            average_exchange_rate: path_stats.average_exchange_rate,
+            conversion_cost_pct: path_stats.conversion_cost_pct,
+            average_path_hops: path_stats.average_path_hops,
             last_updated: chrono::Utc::now().to_rfc3339(),
         };
 
@@ -918,19 +933,6 @@ pub async fn get_corridor_detail(
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_health_score_calculation() {
-        let score = calculate_health_score(95.0, 1000, 1_000_000.0);
-        assert!(score > 0.0 && score <= 100.0);
-    }
-
-    #[test]
-    fn test_liquidity_trend() {
-        assert_eq!(get_liquidity_trend(15_000_000.0), "increasing");
-        assert_eq!(get_liquidity_trend(5_000_000.0), "stable");
-        assert_eq!(get_liquidity_trend(500_000.0), "decreasing");
-    }
-
     #[test]
     fn test_extract_asset_pair_regular_payment_native() {
         let payment = crate::rpc::Payment {
@@ -951,6 +953,8 @@ mod tests {
             source_amount: None,
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -980,6 +984,8 @@ mod tests {
             source_amount: None,
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -1009,6 +1015,8 @@ mod tests {
             source_amount: Some("105.0".to_string()),
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -1038,6 +1046,8 @@ mod tests {
             source_amount: Some("150.0".to_string()),
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -1067,6 +1077,8 @@ mod tests {
             source_amount: Some("500.0".to_string()),
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -1097,6 +1109,8 @@ mod tests {
             source_amount: None,
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -1105,6 +1119,56 @@ mod tests {
         assert_eq!(pair.destination_asset, "NGNT:GNGNTISSUER");
     }
 
+    #[test]
+    fn test_path_payment_stats() {
+        let hop = crate::rpc::Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+        let path_payment =
+            |sent: &str, received: &str, path: Vec<crate::rpc::Asset>| crate::rpc::Payment {
+                id: "path".to_string(),
+                paging_token: "path".to_string(),
+                transaction_hash: "hash_path".to_string(),
+                source_account: "GTEST".to_string(),
+                destination: "GDEST".to_string(),
+                asset_type: "credit_alphanum4".to_string(),
+                asset_code: Some("EUR".to_string()),
+                asset_issuer: Some("GEURISSUER".to_string()),
+                amount: received.to_string(),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                operation_type: Some("path_payment_strict_receive".to_string()),
+                source_asset_type: Some("credit_alphanum4".to_string()),
+                source_asset_code: Some("USD".to_string()),
+                source_asset_issuer: Some("GUSDISSUER".to_string()),
+                source_amount: Some(sent.to_string()),
+                from: Some("GTEST".to_string()),
+                to: Some("GDEST".to_string()),
+                path,
+                transaction_successful: None,
+                asset_balance_changes: None,
+            };
+        let first = path_payment("100.0", "90.0", vec![hop.clone()]);
+        let second = path_payment("100.0", "92.0", vec![hop.clone(), hop]);
+        let mut plain = path_payment("50.0", "50.0", Vec::new());
+        plain.operation_type = Some("payment".to_string());
+
+        let stats = path_payment_stats(&[&first, &second, &plain], Some(1.0), Some(1.08));
+        assert_eq!(stats.count, 2);
+        assert!((stats.average_exchange_rate.unwrap() - 0.91).abs() < 1e-9);
+        assert_eq!(stats.average_path_hops, Some(1.5));
+        // Sent $200, received 182 EUR = $196.56
+        assert!((stats.conversion_cost_pct.unwrap() - 1.72).abs() < 1e-9);
+
+        let stats = path_payment_stats(&[&first], Some(1.0), None);
+        assert_eq!(stats.conversion_cost_pct, None);
+        assert_eq!(
+            path_payment_stats(&[&plain], Some(1.0), Some(1.0)),
+            PathPaymentStats::default()
+        );
+    }
+
     #[test]
     fn test_calculate_historical_success_rate_empty() {
         let payments = vec![];
@@ -1132,6 +1196,8 @@ mod tests {
             source_amount: None,
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -1164,6 +1230,8 @@ mod tests {
             source_amount: None,
             from: Some("GTEST".to_string()),
             to: Some("GDEST".to_string()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -1205,6 +1273,11 @@ mod tests {
                 liquidity_volume_24h_usd: 100000.0,
                 liquidity_trend: "stable".to_string(),
                 health_score: 95.0,
+                health_breakdown: HealthBreakdown::default(),
+                path_payment_count: 0,
+                average_exchange_rate: None,
+                conversion_cost_pct: None,
+                average_path_hops: None,
                 last_updated: "2026-01-15T10:00:00Z".to_string(),
             },
             CorridorResponse {
@@ -1223,6 +1296,11 @@ mod tests {
                 liquidity_volume_24h_usd: 90000.0,
                 liquidity_trend: "stable".to_string(),
                 health_score: 94.0,
+                health_breakdown: HealthBreakdown::default(),
+                path_payment_count: 0,
+                average_exchange_rate: None,
+                conversion_cost_pct: None,
+                average_path_hops: None,
                 last_updated: "2026-01-15T10:00:00Z".to_string(),
             },
         ];
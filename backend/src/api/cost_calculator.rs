@@ -12,6 +12,7 @@ use utoipa::ToSchema;
 
 use crate::error::DomainError;
 use crate::http_cache::cached_json_response;
+use crate::services::fee_market::FeeMarketService;
 use crate::services::price_feed::PriceFeedClient;
 
 const DEFAULT_CACHE_TTL_SECONDS: usize = 60;
@@ -93,6 +94,8 @@ pub struct CostCalculationResponse {
     pub source_usd_rate: f64,
     pub destination_usd_rate: f64,
     pub mid_market_rate: f64,
+    /// `fee_market` when network fees come from recent ledgers, `static` otherwise
+    pub network_fee_basis: String,
     pub best_route: RouteEstimate,
     pub routes: Vec<RouteEstimate>,
 }
@@ -153,7 +156,7 @@ impl RouteFees {
     tag = "Cost Calculator"
 )]
 pub async fn estimate_costs(
-    State(price_feed): State<Arc<PriceFeedClient>>,
+    State(state): State<CostCalculatorState>,
     request_headers: HeaderMap,
     Json(request): Json<CostCalculationRequest>,
 ) -> Response {
@@ -192,12 +195,13 @@ pub async fn estimate_costs(
         return error_response(StatusCode::BAD_REQUEST, "at least one route is required");
     }
 
-    let source_usd_rate = match resolve_usd_rate(&price_feed, &source_currency).await {
+    let price_feed = &state.price_feed;
+    let source_usd_rate = match resolve_usd_rate(price_feed, &source_currency).await {
         Ok(rate) => rate,
         Err(error) => return error_response(StatusCode::BAD_REQUEST, &error.to_string()),
     };
 
-    let destination_usd_rate = match resolve_usd_rate(&price_feed, &destination_currency).await {
+    let destination_usd_rate = match resolve_usd_rate(price_feed, &destination_currency).await {
         Ok(rate) => rate,
         Err(error) => return error_response(StatusCode::BAD_REQUEST, &error.to_string()),
     };
@@ -207,6 +211,7 @@ pub async fn estimate_costs(
     }
 
     let mid_market_rate = source_usd_rate / destination_usd_rate;
+    let network_fee_source = live_network_fee_source(&state, source_usd_rate).await;
 
     let mut route_estimates: Vec<RouteEstimate> = unique_routes
         .into_iter()
@@ -216,6 +221,7 @@ pub async fn estimate_costs(
                 request.source_amount,
                 request.destination_amount,
                 mid_market_rate,
+                network_fee_source,
             )
         })
         .collect();
@@ -242,6 +248,12 @@ pub async fn estimate_costs(
         source_usd_rate,
         destination_usd_rate,
         mid_market_rate,
+        network_fee_basis: if network_fee_source.is_some() {
+            "fee_market"
+        } else {
+            "static"
+        }
+        .to_string(),
         best_route,
         routes: route_estimates,
     };
@@ -276,13 +288,17 @@ pub async fn estimate_costs(
     }
 }
 
-fn estimate_route(
+pub(crate) fn estimate_route(
     route: PaymentRoute,
     source_amount: f64,
     destination_target: Option<f64>,
     mid_market_rate: f64,
+    network_fee_source: Option<f64>,
 ) -> RouteEstimate {
-    let fees = RouteFees::for_route(route);
+    let mut fees = RouteFees::for_route(route);
+    if let Some(network_fee_source) = network_fee_source {
+        fees.network_fee_source = network_fee_source;
+    }
     let slippage_bps = (fees.slippage_base_bps
         + (source_amount / 10_000.0) * fees.slippage_per_10k_bps)
         .min(200.0);
@@ -352,7 +368,19 @@ fn estimate_route(
     }
 }
 
-async fn resolve_usd_rate(
+/// Recommended network fee for a single-operation transaction, converted
+/// from XLM into the source currency. `None` until the fee market has data.
+async fn live_network_fee_source(state: &CostCalculatorState, source_usd_rate: f64) -> Option<f64> {
+    let fee_market = state.fee_market.as_ref()?;
+    let recommended = fee_market.recommended_fee(None, 1).await.ok()?;
+    if recommended.based_on_ledgers == 0 {
+        return None;
+    }
+    let xlm_usd_rate = resolve_usd_rate(&state.price_feed, "XLM").await.ok()?;
+    Some(recommended.medium_fee_xlm(1) * xlm_usd_rate / source_usd_rate)
+}
+
+pub(crate) async fn resolve_usd_rate(
     price_feed: &PriceFeedClient,
     currency: &str,
 ) -> Result<f64, DomainError> {
@@ -445,10 +473,34 @@ fn error_response(status: StatusCode, message: &str) -> Response {
         .into_response()
 }
 
+#[derive(Clone)]
+pub struct CostCalculatorState {
+    price_feed: Arc<PriceFeedClient>,
+    fee_market: Option<Arc<FeeMarketService>>,
+}
+
 pub fn routes(price_feed: Arc<PriceFeedClient>) -> Router {
+    build_routes(CostCalculatorState {
+        price_feed,
+        fee_market: None,
+    })
+}
+
+/// Like [`routes`], with network fees taken from recent ledgers
+pub fn routes_with_fee_market(
+    price_feed: Arc<PriceFeedClient>,
+    fee_market: Arc<FeeMarketService>,
+) -> Router {
+    build_routes(CostCalculatorState {
+        price_feed,
+        fee_market: Some(fee_market),
+    })
+}
+
+fn build_routes(state: CostCalculatorState) -> Router {
     Router::new()
         .route("/estimate", post(estimate_costs))
-        .with_state(price_feed)
+        .with_state(state)
 }
 
 #[cfg(test)]
@@ -472,11 +524,20 @@ mod tests {
             1_000.0,
             Some(1_500_000.0),
             1_538.0,
+            None,
         );
         assert!(estimate.breakdown.total_fees_source > 0.0);
         assert!(estimate.breakdown.estimated_destination_amount > 0.0);
     }
 
+    #[test]
+    fn test_estimate_route_uses_network_fee_override() {
+        let estimate = estimate_route(PaymentRoute::AnchorDirect, 1_000.0, None, 1.0, Some(0.0001));
+        assert_eq!(estimate.breakdown.network_fee_source, 0.0001);
+        let fallback = estimate_route(PaymentRoute::AnchorDirect, 1_000.0, None, 1.0, None);
+        assert!(fallback.breakdown.total_fees_source > estimate.breakdown.total_fees_source);
+    }
+
     #[test]
     fn test_fallback_rates_cover_common_assets() {
         assert_eq!(fallback_usd_rate("USD"), Some(1.0));
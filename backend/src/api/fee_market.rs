@@ -0,0 +1,111 @@
+//! Network fee market: per-ledger fee percentiles, surge pricing and
+//! recommended fees
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::fee_market::{
+    FeeMarketService, FeeMarketStats, LedgerFeeStats, RecommendedFee, SurgePeriod,
+};
+
+#[derive(Deserialize)]
+pub struct LedgerParams {
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+#[derive(Deserialize)]
+pub struct WindowParams {
+    #[serde(default = "default_hours")]
+    hours: i64,
+}
+
+#[derive(Deserialize)]
+pub struct RecommendedFeeParams {
+    /// Recent ledgers to base the recommendation on
+    ledgers: Option<i64>,
+    #[serde(default = "default_operations")]
+    operations: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+fn default_hours() -> i64 {
+    24
+}
+
+fn default_operations() -> i64 {
+    1
+}
+
+pub fn routes(service: Arc<FeeMarketService>) -> Router {
+    Router::new()
+        .route("/ledgers", get(get_ledgers))
+        .route("/stats", get(get_stats))
+        .route("/surge-periods", get(get_surge_periods))
+        .route("/recommended-fee", get(get_recommended_fee))
+        .with_state(service)
+}
+
+fn internal(e: anyhow::Error) -> ApiError {
+    ApiError::internal("INTERNAL_ERROR", e.to_string())
+}
+
+/// GET /api/fee-market/ledgers - Fee percentiles of recent ledgers, newest first
+async fn get_ledgers(
+    State(service): State<Arc<FeeMarketService>>,
+    Query(params): Query<LedgerParams>,
+) -> ApiResult<Json<Vec<LedgerFeeStats>>> {
+    let ledgers = service
+        .get_recent_ledgers(params.limit.clamp(1, 1000))
+        .await
+        .map_err(internal)?;
+    Ok(Json(ledgers))
+}
+
+/// GET /api/fee-market/stats - Aggregate fee market statistics over a window
+async fn get_stats(
+    State(service): State<Arc<FeeMarketService>>,
+    Query(params): Query<WindowParams>,
+) -> ApiResult<Json<FeeMarketStats>> {
+    let since = Utc::now() - Duration::hours(params.hours.clamp(1, 24 * 30));
+    let stats = service.get_stats(since).await.map_err(internal)?;
+    Ok(Json(stats))
+}
+
+/// GET /api/fee-market/surge-periods - Runs of surge-priced ledgers, newest first
+async fn get_surge_periods(
+    State(service): State<Arc<FeeMarketService>>,
+    Query(params): Query<WindowParams>,
+) -> ApiResult<Json<Vec<SurgePeriod>>> {
+    let since = Utc::now() - Duration::hours(params.hours.clamp(1, 24 * 30));
+    let periods = service.get_surge_periods(since).await.map_err(internal)?;
+    Ok(Json(periods))
+}
+
+/// GET /api/fee-market/recommended-fee - Suggested fee per operation
+async fn get_recommended_fee(
+    State(service): State<Arc<FeeMarketService>>,
+    Query(params): Query<RecommendedFeeParams>,
+) -> ApiResult<Json<RecommendedFee>> {
+    if params.operations < 1 || params.operations > 100 {
+        return Err(ApiError::bad_request(
+            "INVALID_OPERATIONS",
+            "operations must be between 1 and 100",
+        ));
+    }
+    let recommended = service
+        .recommended_fee(params.ledgers.map(|l| l.clamp(1, 1000)), params.operations)
+        .await
+        .map_err(internal)?;
+    Ok(Json(recommended))
+}
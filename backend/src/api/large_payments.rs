@@ -0,0 +1,82 @@
+//! Large payments flagged against per-asset thresholds and corridor norms
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::large_payment_detector::{
+    CorridorPaymentHour, LargePayment, LargePaymentDetector, LargePaymentFilter,
+};
+
+#[derive(Deserialize)]
+pub struct LargePaymentParams {
+    corridor_key: Option<String>,
+    asset: Option<String>,
+    min_amount_usd: Option<f64>,
+    #[serde(default = "default_limit")]
+    limit: i64,
+}
+
+#[derive(Deserialize)]
+pub struct DistributionParams {
+    #[serde(default = "default_hours")]
+    hours: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+fn default_hours() -> i64 {
+    24
+}
+
+pub fn routes(detector: Arc<LargePaymentDetector>) -> Router {
+    Router::new()
+        .route("/", get(get_large_payments))
+        .route(
+            "/corridors/:corridor_key/distribution",
+            get(get_corridor_distribution),
+        )
+        .with_state(detector)
+}
+
+fn internal(e: anyhow::Error) -> ApiError {
+    ApiError::internal("INTERNAL_ERROR", e.to_string())
+}
+
+/// GET /api/large-payments - Recently flagged large payments
+async fn get_large_payments(
+    State(detector): State<Arc<LargePaymentDetector>>,
+    Query(params): Query<LargePaymentParams>,
+) -> ApiResult<Json<Vec<LargePayment>>> {
+    let filter = LargePaymentFilter {
+        corridor_key: params.corridor_key,
+        asset: params.asset,
+        min_amount_usd: params.min_amount_usd,
+        limit: params.limit.clamp(1, 1000),
+    };
+    let payments = detector
+        .get_large_payments(&filter)
+        .await
+        .map_err(internal)?;
+    Ok(Json(payments))
+}
+
+/// GET /api/large-payments/corridors/:corridor_key/distribution - Hourly payment sizes of a corridor
+async fn get_corridor_distribution(
+    State(detector): State<Arc<LargePaymentDetector>>,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<DistributionParams>,
+) -> ApiResult<Json<Vec<CorridorPaymentHour>>> {
+    let hours = detector
+        .get_corridor_distribution(&corridor_key, params.hours.clamp(1, 24 * 30))
+        .await
+        .map_err(internal)?;
+    Ok(Json(hours))
+}
@@ -1,30 +0,0 @@
-use axum::{routing::get, Json, Router};
-use serde::Serialize;
-
-// Define the schema for the metrics overview response
-#[derive(Serialize)]
-pub struct MetricsOverview {
-    pub total_volume: f64,
-    pub total_transactions: u64,
-    pub active_users: u64,
-    pub average_transaction_value: f64,
-    pub corridor_count: u32,
-    // Add more KPIs as needed
-}
-
-/// Handler for GET /api/metrics/overview
-pub async fn metrics_overview() -> Json<MetricsOverview> {
-    // Placeholder: Replace with real data aggregation logic
-    let overview = MetricsOverview {
-        total_volume: 1234567.89,
-        total_transactions: 98765,
-        active_users: 4321,
-        average_transaction_value: 28.56,
-        corridor_count: 12,
-    };
-    Json(overview)
-}
-
-pub fn routes() -> Router {
-    Router::new().route("/api/metrics/overview", get(metrics_overview))
-}
@@ -1,71 +1,59 @@
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::HeaderMap,
-    response::{IntoResponse, Response},
+    response::Response,
     routing::get,
-    Json, Router,
+    Router,
 };
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::cache::helpers::cached_query;
 use crate::cache::{keys, CacheManager};
+use crate::error::{ApiError, ApiResult};
+use crate::services::metrics_overview::{MetricsOverviewService, OverviewWindow};
 
-#[derive(Serialize, Deserialize, Clone)]
-pub struct MetricsOverview {
-    pub total_volume: f64,
-    pub total_transactions: u64,
-    pub active_users: u64,
-    pub average_transaction_value: f64,
-    pub corridor_count: u32,
+#[derive(Debug, Deserialize)]
+pub struct MetricsOverviewQuery {
+    /// `24h` (default), `7d` or `30d`
+    #[serde(default = "default_window")]
+    pub window: String,
 }
 
-/// Handler for GET /api/metrics/overview (cached with 1 min TTL)
+fn default_window() -> String {
+    "24h".to_string()
+}
+
+/// Handler for GET /api/metrics/overview (cached with 1 min TTL, invalidated
+/// by payment ingestion and the hourly corridor rollup)
 pub async fn metrics_overview(
-    State(cache): State<Arc<CacheManager>>,
+    State((overview_service, cache)): State<(Arc<MetricsOverviewService>, Arc<CacheManager>)>,
+    Query(params): Query<MetricsOverviewQuery>,
     headers: HeaderMap,
-) -> Response {
-    let cache_key = keys::metrics_overview();
+) -> ApiResult<Response> {
+    let window = params
+        .window
+        .parse::<OverviewWindow>()
+        .map_err(|e| ApiError::bad_request("INVALID_WINDOW", e))?;
+    let cache_key = keys::metrics_overview(window.as_str());
 
     let overview = cached_query(
         &cache,
         &cache_key,
         cache.config.get_ttl("dashboard"),
-        || async {
-            // Placeholder: Replace with real data aggregation logic
-            Ok(MetricsOverview {
-                total_volume: 1234567.89,
-                total_transactions: 98765,
-                active_users: 4321,
-                average_transaction_value: 28.56,
-                corridor_count: 12,
-            })
-        },
+        || async { overview_service.overview(window).await },
     )
-    .await
-    .unwrap_or_else(|_| MetricsOverview {
-        total_volume: 0.0,
-        total_transactions: 0,
-        active_users: 0,
-        average_transaction_value: 0.0,
-        corridor_count: 0,
-    });
+    .await?;
 
     let ttl = cache.config.get_ttl("dashboard");
-    match crate::http_cache::cached_json_response(&headers, &cache_key, &overview, ttl) {
-        Ok(response) => response,
-        Err(e) => (
-            axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({ "error": e.to_string() })),
-        )
-            .into_response(),
-    }
+    let response = crate::http_cache::cached_json_response(&headers, &cache_key, &overview, ttl)?;
+    Ok(response)
 }
 
-pub fn routes(cache: Arc<CacheManager>) -> Router {
+pub fn routes(overview_service: Arc<MetricsOverviewService>, cache: Arc<CacheManager>) -> Router {
     Router::new()
         .route("/api/metrics/overview", get(metrics_overview))
-        .with_state(cache)
+        .with_state((overview_service, cache))
 }
 
 #[cfg(test)]
@@ -73,17 +61,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_metrics_overview_structure() {
-        let overview = MetricsOverview {
-            total_volume: 1000.0,
-            total_transactions: 100,
-            active_users: 50,
-            average_transaction_value: 10.0,
-            corridor_count: 5,
-        };
-
-        assert_eq!(overview.total_volume, 1000.0);
-        assert_eq!(overview.total_transactions, 100);
-        assert_eq!(overview.corridor_count, 5);
+    fn test_cache_key_per_window() {
+        assert_eq!(
+            keys::metrics_overview(OverviewWindow::Day.as_str()),
+            "metrics:overview:24h"
+        );
+        assert_eq!(
+            keys::metrics_overview(OverviewWindow::Month.as_str()),
+            "metrics:overview:30d"
+        );
     }
 }
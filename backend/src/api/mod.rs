@@ -1,13 +1,22 @@
 pub mod account_merges;
+pub mod accounts;
 pub mod achievements;
 pub mod alerts;
+pub mod anchor_transfers;
 pub mod anchors;
 pub mod anchors_cached;
+pub mod anomalies;
 pub mod api_keys;
+pub mod asset_authorizations;
+pub mod asset_supply;
 pub mod asset_verification;
 
 pub mod auth;
+pub mod backfill;
 pub mod cache_stats;
+pub mod candles;
+pub mod claimable_balances;
+pub mod corridor_forecast;
 pub mod corridors;
 pub mod corridors_cached;
 pub mod cost_calculator;
@@ -15,18 +24,21 @@ pub mod cost_calculator;
 pub mod api_analytics;
 pub mod contract_events;
 pub mod fee_bump;
+pub mod fee_market;
 pub mod governance;
+pub mod large_payments;
 pub mod liquidity_pools;
-pub mod metrics;
 pub mod metrics_cached;
 pub mod network;
 pub mod oauth;
+pub mod order_books;
 pub mod prediction;
 pub mod price_feed;
 pub mod replay_handlers;
 pub mod sep10;
 pub mod sep24_proxy;
 pub mod sep31_proxy;
+pub mod sponsorships;
 pub mod transactions;
 pub mod trustlines;
 pub mod v1;
@@ -0,0 +1,78 @@
+//! Historical order book depth and spread per corridor pair
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::order_book_snapshot::{
+    DepthSeries, OrderBookSnapshotRecord, OrderBookSnapshotService, TrackedPair,
+};
+
+#[derive(Deserialize)]
+pub struct HistoryParams {
+    #[serde(default = "default_hours")]
+    hours: i64,
+    /// Slippage band used for the depth trend
+    slippage_pct: Option<f64>,
+}
+
+fn default_hours() -> i64 {
+    24
+}
+
+pub fn routes(service: Arc<OrderBookSnapshotService>) -> Router {
+    Router::new()
+        .route("/", get(list_pairs))
+        .route("/:pair_key", get(get_latest_snapshot))
+        .route("/:pair_key/history", get(get_depth_history))
+        .with_state(service)
+}
+
+/// GET /api/order-books - Pairs with stored snapshots
+async fn list_pairs(
+    State(service): State<Arc<OrderBookSnapshotService>>,
+) -> ApiResult<Json<Vec<TrackedPair>>> {
+    let pairs = service
+        .get_tracked_pairs()
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?;
+    Ok(Json(pairs))
+}
+
+/// GET /api/order-books/:pair_key - Latest snapshot with price levels
+async fn get_latest_snapshot(
+    State(service): State<Arc<OrderBookSnapshotService>>,
+    Path(pair_key): Path<String>,
+) -> ApiResult<Json<OrderBookSnapshotRecord>> {
+    service
+        .get_latest_snapshot(&pair_key)
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "NOT_FOUND",
+                format!("No order book snapshots for {}", pair_key),
+            )
+        })
+}
+
+/// GET /api/order-books/:pair_key/history - Depth and spread time series
+async fn get_depth_history(
+    State(service): State<Arc<OrderBookSnapshotService>>,
+    Path(pair_key): Path<String>,
+    Query(params): Query<HistoryParams>,
+) -> ApiResult<Json<DepthSeries>> {
+    let since = Utc::now() - Duration::hours(params.hours.clamp(1, 24 * 90));
+    let series = service
+        .get_depth_series(&pair_key, since, params.slippage_pct)
+        .await
+        .map_err(|e| ApiError::internal("INTERNAL_ERROR", e.to_string()))?;
+    Ok(Json(series))
+}
@@ -1,36 +1,346 @@
-use axum::{extract::Query, Json};
-use rand::Rng;
+//! Payment success prediction with ranked alternative routes
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Duration, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::api::cost_calculator::{estimate_route, resolve_usd_rate, PaymentRoute};
+use crate::error::{ApiError, ApiResult};
+use crate::ml::{parse_asset, parse_corridor, CorridorPool, MLService, PredictionResult};
+use crate::services::price_feed::PriceFeedClient;
+use crate::utils::asset_key;
+
+/// Alternative issuers of the destination asset that are tried
+const MAX_ALTERNATIVE_ANCHORS: i64 = 2;
 
 #[derive(Debug, Deserialize)]
 pub struct PredictionQuery {
+    /// `CODE:ISSUER`, or `XLM` for the native asset
     pub source_asset: String,
     pub destination_asset: String,
+    /// In units of the source asset
     pub amount: f64,
+    /// `HH:MM` UTC; the next occurrence of that time is predicted
     pub time_of_day: String,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteKind {
+    Direct,
+    ViaXlm,
+    AlternativeAnchor,
+    LiquidityPool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RoutePrediction {
+    pub route: RouteKind,
+    /// Assets the payment passes through, source first
+    pub path: Vec<String>,
+    /// The pool swapped through, for `liquidity_pool` routes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pool_id: Option<String>,
+    pub success_probability: f64,
+    pub confidence_interval: (f64, f64),
+    pub estimated_cost_usd: f64,
+    /// `success_probability * (amount_usd - estimated_cost_usd)`, the ranking key
+    pub expected_delivered_usd: f64,
+}
+
 #[derive(Debug, Serialize)]
 pub struct PredictionResponse {
     pub success_probability: f64,
     pub confidence_interval: (f64, f64),
-    pub alternative_routes: Vec<String>,
-}
-
-/// POST /api/predict/success - Predict payment success
-pub async fn predict_success(Query(_params): Query<PredictionQuery>) -> Json<PredictionResponse> {
-    // Mock implementation
-    let mut rng = rand::thread_rng();
-    let probability = rng.gen_range(0.8..0.98);
-
-    let response = PredictionResponse {
-        success_probability: probability,
-        confidence_interval: (probability - 0.05, probability + 0.02),
-        alternative_routes: vec![
-            "Route via XLM".to_string(),
-            "Route via secondary anchor".to_string(),
-        ],
+    pub model_version: String,
+    pub amount_usd: f64,
+    /// Every route that could be evaluated, best first
+    pub alternative_routes: Vec<RoutePrediction>,
+}
+
+#[derive(Clone)]
+pub struct PredictionState {
+    ml_service: Arc<RwLock<MLService>>,
+    price_feed: Arc<PriceFeedClient>,
+}
+
+pub fn routes(ml_service: Arc<RwLock<MLService>>, price_feed: Arc<PriceFeedClient>) -> Router {
+    Router::new()
+        .route("/api/predict/success", get(predict_success))
+        .with_state(PredictionState {
+            ml_service,
+            price_feed,
+        })
+}
+
+fn internal(e: anyhow::Error) -> ApiError {
+    ApiError::internal("INTERNAL_ERROR", e.to_string())
+}
+
+/// GET /api/predict/success - Predict payment success and rank alternative routes
+pub async fn predict_success(
+    State(state): State<PredictionState>,
+    Query(params): Query<PredictionQuery>,
+) -> ApiResult<Json<PredictionResponse>> {
+    let (Some(source), Some(destination)) = (
+        parse_asset(&params.source_asset),
+        parse_asset(&params.destination_asset),
+    ) else {
+        return Err(ApiError::bad_request(
+            "INVALID_ASSET",
+            "Assets must be CODE:ISSUER or XLM",
+        ));
+    };
+    if source == destination {
+        return Err(ApiError::bad_request(
+            "SAME_ASSET",
+            "Source and destination assets must differ",
+        ));
+    }
+    if !(params.amount.is_finite() && params.amount > 0.0) {
+        return Err(ApiError::bad_request(
+            "INVALID_AMOUNT",
+            "amount must be a positive number",
+        ));
+    }
+    let time = NaiveTime::parse_from_str(params.time_of_day.trim(), "%H:%M")
+        .map_err(|_| ApiError::bad_request("INVALID_TIME", "time_of_day must be HH:MM"))?;
+    let timestamp = next_occurrence(time, Utc::now());
+
+    let source_key = asset_key(&source.0, &source.1);
+    let destination_key = asset_key(&destination.0, &destination.1);
+    let usd_rate = resolve_usd_rate(&state.price_feed, &source_key)
+        .await
+        .map_err(|e| ApiError::bad_request("UNSUPPORTED_ASSET", e.to_string()))?;
+    let amount_usd = params.amount * usd_rate;
+
+    let ml = state.ml_service.read().await;
+    let predict = |from: String, to: String| {
+        let ml = &ml;
+        async move {
+            ml.predict_payment_success(&format!("{}->{}", from, to), amount_usd, timestamp)
+                .await
+        }
     };
 
-    Json(response)
+    let direct = predict(source_key.clone(), destination_key.clone())
+        .await
+        .map_err(internal)?;
+    let mut routes = vec![route_prediction(
+        RouteKind::Direct,
+        vec![source_key.clone(), destination_key.clone()],
+        &[&direct],
+        route_cost_usd(PaymentRoute::StellarDex, amount_usd),
+        amount_usd,
+    )];
+
+    // Two DEX legs through XLM
+    let xlm_key = "XLM:native".to_string();
+    if source_key != xlm_key && destination_key != xlm_key {
+        let first = predict(source_key.clone(), xlm_key.clone())
+            .await
+            .map_err(internal)?;
+        let second = predict(xlm_key.clone(), destination_key.clone())
+            .await
+            .map_err(internal)?;
+        let first_cost = route_cost_usd(PaymentRoute::StellarDex, amount_usd);
+        let cost = first_cost + route_cost_usd(PaymentRoute::StellarDex, amount_usd - first_cost);
+        routes.push(route_prediction(
+            RouteKind::ViaXlm,
+            vec![source_key.clone(), xlm_key, destination_key.clone()],
+            &[&first, &second],
+            cost,
+            amount_usd,
+        ));
+    }
+
+    // A swap through the largest pool holding both assets, scored with the
+    // pool's value as the corridor's liquidity depth
+    let corridor_key = format!("{}->{}", source_key, destination_key);
+    if let Some(corridor) = parse_corridor(&corridor_key) {
+        let pool = ml.find_corridor_pool(&corridor).await.map_err(internal)?;
+        if let Some((pool, cost)) =
+            pool.and_then(|pool| pool_cost_usd(&pool, amount_usd).map(|cost| (pool, cost)))
+        {
+            let prediction = ml
+                .predict_pool_swap_success(&corridor_key, &pool, amount_usd, timestamp)
+                .await
+                .map_err(internal)?;
+            routes.push(RoutePrediction {
+                pool_id: Some(pool.pool_id),
+                ..route_prediction(
+                    RouteKind::LiquidityPool,
+                    vec![source_key.clone(), destination_key.clone()],
+                    &[&prediction],
+                    cost,
+                    amount_usd,
+                )
+            });
+        }
+    }
+
+    // The same asset code from another anchor
+    let issuers = ml
+        .alternative_issuers(
+            &destination.0,
+            &destination.1,
+            (&source.0, &source.1),
+            MAX_ALTERNATIVE_ANCHORS,
+        )
+        .await
+        .map_err(internal)?;
+    for issuer in issuers {
+        let alternative_key = format!("{}:{}", destination.0, issuer);
+        let prediction = predict(source_key.clone(), alternative_key.clone())
+            .await
+            .map_err(internal)?;
+        routes.push(route_prediction(
+            RouteKind::AlternativeAnchor,
+            vec![source_key.clone(), alternative_key],
+            &[&prediction],
+            route_cost_usd(PaymentRoute::AnchorDirect, amount_usd),
+            amount_usd,
+        ));
+    }
+
+    routes.sort_by(|a, b| {
+        b.expected_delivered_usd
+            .total_cmp(&a.expected_delivered_usd)
+            .then_with(|| a.estimated_cost_usd.total_cmp(&b.estimated_cost_usd))
+    });
+
+    Ok(Json(PredictionResponse {
+        success_probability: direct.success_probability as f64,
+        confidence_interval: (
+            direct.confidence_interval.0 as f64,
+            direct.confidence_interval.1 as f64,
+        ),
+        model_version: direct.model_version,
+        amount_usd,
+        alternative_routes: routes,
+    }))
+}
+
+/// Combine the legs of a route; legs are treated as independent
+fn route_prediction(
+    route: RouteKind,
+    path: Vec<String>,
+    legs: &[&PredictionResult],
+    estimated_cost_usd: f64,
+    amount_usd: f64,
+) -> RoutePrediction {
+    let success_probability: f64 = legs.iter().map(|l| l.success_probability as f64).product();
+    let lower: f64 = legs
+        .iter()
+        .map(|l| l.confidence_interval.0 as f64)
+        .product();
+    let upper: f64 = legs
+        .iter()
+        .map(|l| l.confidence_interval.1 as f64)
+        .product();
+    RoutePrediction {
+        route,
+        path,
+        pool_id: None,
+        success_probability,
+        confidence_interval: (lower, upper),
+        estimated_cost_usd,
+        expected_delivered_usd: success_probability * (amount_usd - estimated_cost_usd).max(0.0),
+    }
+}
+
+/// Fees, spread and slippage of a route, using the cost calculator's model
+fn route_cost_usd(route: PaymentRoute, amount_usd: f64) -> f64 {
+    estimate_route(route, amount_usd.max(0.0), None, 1.0, None)
+        .breakdown
+        .total_fees_source
+}
+
+/// Pool fee plus constant-product price impact against half the pool's
+/// value, plus the network fee; `None` for a pool without known value
+fn pool_cost_usd(pool: &CorridorPool, amount_usd: f64) -> Option<f64> {
+    let reserve_usd = pool.total_value_usd / 2.0;
+    if reserve_usd <= 0.0 {
+        return None;
+    }
+    let fee = amount_usd * pool.fee_bp as f64 / 10_000.0;
+    let price_impact = amount_usd * amount_usd / (reserve_usd + amount_usd);
+    let network_fee = estimate_route(PaymentRoute::LiquidityPool, amount_usd, None, 1.0, None)
+        .breakdown
+        .network_fee_source;
+    Some(fee + price_impact + network_fee)
+}
+
+fn next_occurrence(time: NaiveTime, now: DateTime<Utc>) -> DateTime<Utc> {
+    let today = now.date_naive().and_time(time).and_utc();
+    if today < now {
+        today + Duration::days(1)
+    } else {
+        today
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_next_occurrence() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 12, 30, 0).unwrap();
+        let later = NaiveTime::from_hms_opt(18, 0, 0).unwrap();
+        let earlier = NaiveTime::from_hms_opt(9, 15, 0).unwrap();
+        assert_eq!(
+            next_occurrence(later, now),
+            Utc.with_ymd_and_hms(2024, 3, 1, 18, 0, 0).unwrap()
+        );
+        assert_eq!(
+            next_occurrence(earlier, now),
+            Utc.with_ymd_and_hms(2024, 3, 2, 9, 15, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_route_prediction_combines_legs() {
+        let leg = |p: f32, lo: f32, hi: f32| PredictionResult {
+            success_probability: p,
+            confidence: 1.0 - (hi - lo),
+            confidence_interval: (lo, hi),
+            model_version: "test".to_string(),
+        };
+        let first = leg(0.9, 0.8, 0.95);
+        let second = leg(0.5, 0.4, 0.6);
+        let route = route_prediction(RouteKind::ViaXlm, vec![], &[&first, &second], 10.0, 110.0);
+        assert!((route.success_probability - 0.45).abs() < 1e-6);
+        assert!((route.confidence_interval.0 - 0.32).abs() < 1e-6);
+        assert!((route.confidence_interval.1 - 0.57).abs() < 1e-6);
+        assert!((route.expected_delivered_usd - 45.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_pool_cost_grows_with_size() {
+        let pool = CorridorPool {
+            pool_id: "pool".to_string(),
+            fee_bp: 30,
+            total_value_usd: 200_000.0,
+        };
+        let small = pool_cost_usd(&pool, 100.0).unwrap();
+        let large = pool_cost_usd(&pool, 50_000.0).unwrap();
+        assert!(small < large);
+        assert!(large / 50_000.0 > small / 100.0);
+        assert!(pool_cost_usd(
+            &CorridorPool {
+                total_value_usd: 0.0,
+                ..pool
+            },
+            100.0
+        )
+        .is_none());
+    }
 }
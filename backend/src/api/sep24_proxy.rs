@@ -13,6 +13,10 @@ use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::services::anchor_transfer_tracker::{
+    AnchorTransferTracker, NewAnchorTransfer, TransferProtocol,
+};
+
 /// Allowed transfer server hosts (env: SEP24_ALLOWED_ORIGINS, comma-separated).
 /// If unset, any origin is allowed (use in dev only).
 fn allowed_origins() -> Vec<String> {
@@ -37,6 +41,8 @@ fn is_origin_allowed(transfer_server: &str) -> bool {
 #[derive(Clone)]
 pub struct Sep24State {
     pub client: Arc<Client>,
+    /// Records transactions created through the proxy for lifecycle tracking
+    pub tracker: Option<Arc<AnchorTransferTracker>>,
 }
 
 impl Sep24State {
@@ -47,6 +53,28 @@ impl Sep24State {
             .unwrap_or_else(|_| Client::new());
         Self {
             client: Arc::new(client),
+            tracker: None,
+        }
+    }
+
+    pub fn with_tracker(mut self, tracker: Arc<AnchorTransferTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Start tracking a transaction the anchor just created; tracking
+    /// failures never fail the proxied request
+    async fn track_initiated(&self, transfer: NewAnchorTransfer) {
+        let Some(tracker) = &self.tracker else {
+            return;
+        };
+        let transaction_id = transfer.transaction_id.clone();
+        if let Err(e) = tracker.record_initiated(transfer).await {
+            tracing::warn!(
+                "Failed to track SEP-24 transaction {}: {}",
+                transaction_id,
+                e
+            );
         }
     }
 }
@@ -158,6 +186,21 @@ pub async fn post_deposit_interactive(
     if !status.is_success() {
         return Err(Sep24Error::Anchor(status.as_u16(), data));
     }
+    if let Some(transaction_id) = data.get("id").and_then(Value::as_str) {
+        state
+            .track_initiated(NewAnchorTransfer {
+                protocol: TransferProtocol::Sep24,
+                kind: "deposit".to_string(),
+                transfer_server: body.transfer_server.clone(),
+                transaction_id: transaction_id.to_string(),
+                asset_code: body.asset_code.clone(),
+                asset_issuer: None,
+                counter_asset: None,
+                amount: body.amount.clone(),
+                jwt: body.jwt.clone(),
+            })
+            .await;
+    }
     Ok(Json(data))
 }
 
@@ -230,6 +273,21 @@ pub async fn post_withdraw_interactive(
     if !status.is_success() {
         return Err(Sep24Error::Anchor(status.as_u16(), data));
     }
+    if let Some(transaction_id) = data.get("id").and_then(Value::as_str) {
+        state
+            .track_initiated(NewAnchorTransfer {
+                protocol: TransferProtocol::Sep24,
+                kind: "withdrawal".to_string(),
+                transfer_server: body.transfer_server.clone(),
+                transaction_id: transaction_id.to_string(),
+                asset_code: body.asset_code.clone(),
+                asset_issuer: None,
+                counter_asset: None,
+                amount: body.amount.clone(),
+                jwt: body.jwt.clone(),
+            })
+            .await;
+    }
     Ok(Json(data))
 }
 
@@ -389,7 +447,15 @@ impl IntoResponse for Sep24Error {
 
 /// Build SEP-24 API router
 pub fn routes() -> axum::Router {
-    let state = Sep24State::new();
+    router(Sep24State::new())
+}
+
+/// Router that also records created transactions with the lifecycle tracker
+pub fn routes_with_tracker(tracker: Arc<AnchorTransferTracker>) -> axum::Router {
+    router(Sep24State::new().with_tracker(tracker))
+}
+
+fn router(state: Sep24State) -> axum::Router {
     axum::Router::new()
         .route("/api/sep24/info", axum::routing::get(get_info))
         .route(
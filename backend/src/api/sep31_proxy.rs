@@ -13,6 +13,10 @@ use serde_json::Value;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::services::anchor_transfer_tracker::{
+    AnchorTransferTracker, NewAnchorTransfer, TransferProtocol,
+};
+
 fn allowed_origins() -> Vec<String> {
     std::env::var("SEP31_ALLOWED_ORIGINS")
         .ok()
@@ -32,6 +36,8 @@ fn is_origin_allowed(transfer_server: &str) -> bool {
 #[derive(Clone)]
 pub struct Sep31State {
     pub client: Arc<Client>,
+    /// Records transactions created through the proxy for lifecycle tracking
+    pub tracker: Option<Arc<AnchorTransferTracker>>,
 }
 
 impl Sep31State {
@@ -42,6 +48,28 @@ impl Sep31State {
             .unwrap_or_else(|_| Client::new());
         Self {
             client: Arc::new(client),
+            tracker: None,
+        }
+    }
+
+    pub fn with_tracker(mut self, tracker: Arc<AnchorTransferTracker>) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
+    /// Start tracking a transaction the anchor just created; tracking
+    /// failures never fail the proxied request
+    async fn track_initiated(&self, transfer: NewAnchorTransfer) {
+        let Some(tracker) = &self.tracker else {
+            return;
+        };
+        let transaction_id = transfer.transaction_id.clone();
+        if let Err(e) = tracker.record_initiated(transfer).await {
+            tracing::warn!(
+                "Failed to track SEP-31 transaction {}: {}",
+                transaction_id,
+                e
+            );
         }
     }
 }
@@ -166,6 +194,28 @@ pub async fn post_transaction(
     if !status.is_success() {
         return Err(Sep31Error::Anchor(status.as_u16(), data));
     }
+    if let Some(transaction_id) = data.get("id").and_then(Value::as_str) {
+        let field = |name: &str| {
+            body.payload.get(name).and_then(|v| match v {
+                Value::String(s) => Some(s.clone()),
+                Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+        };
+        state
+            .track_initiated(NewAnchorTransfer {
+                protocol: TransferProtocol::Sep31,
+                kind: "send".to_string(),
+                transfer_server: body.transfer_server.clone(),
+                transaction_id: transaction_id.to_string(),
+                asset_code: field("asset_code"),
+                asset_issuer: field("asset_issuer"),
+                counter_asset: field("destination_asset"),
+                amount: field("amount"),
+                jwt: body.jwt.clone(),
+            })
+            .await;
+    }
     Ok(Json(data))
 }
 
@@ -403,7 +453,15 @@ impl IntoResponse for Sep31Error {
 }
 
 pub fn routes() -> axum::Router {
-    let state = Sep31State::new();
+    router(Sep31State::new())
+}
+
+/// Router that also records created transactions with the lifecycle tracker
+pub fn routes_with_tracker(tracker: Arc<AnchorTransferTracker>) -> axum::Router {
+    router(Sep31State::new().with_tracker(tracker))
+}
+
+fn router(state: Sep31State) -> axum::Router {
     axum::Router::new()
         .route("/api/sep31/info", axum::routing::get(get_info))
         .route("/api/sep31/quote", axum::routing::post(post_quote))
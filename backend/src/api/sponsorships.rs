@@ -0,0 +1,134 @@
+//! Sponsored reserves API, derived from ingested sponsorship operations
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::error::{ApiError, ApiResult};
+use crate::services::sponsorship_tracker::{
+    SponsorLeaderboard, Sponsorship, SponsorshipAnalytics, SponsorshipHistory,
+    SponsorshipTrackerService,
+};
+
+#[derive(Deserialize)]
+pub struct PaginationParams {
+    #[serde(default = "default_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Deserialize)]
+pub struct LeaderboardParams {
+    #[serde(default = "default_leaderboard_limit")]
+    limit: i64,
+}
+
+fn default_leaderboard_limit() -> i64 {
+    100
+}
+
+pub fn routes(tracker: Arc<SponsorshipTrackerService>) -> Router {
+    Router::new()
+        .route("/", get(get_all_sponsorships))
+        .route("/leaderboard", get(get_sponsor_leaderboard))
+        .route("/analytics", get(get_analytics_summary))
+        .route("/by-sponsor/:sponsor", get(get_sponsorships_by_sponsor))
+        .route("/by-account/:account", get(get_sponsorships_by_account))
+        .route("/:id", get(get_sponsorship))
+        .route("/:id/history", get(get_sponsorship_history))
+        .with_state(tracker)
+}
+
+fn internal(e: anyhow::Error) -> ApiError {
+    ApiError::internal("INTERNAL_ERROR", e.to_string())
+}
+
+/// GET /api/sponsorships - Sponsorships ordered by sponsored reserves
+async fn get_all_sponsorships(
+    State(tracker): State<Arc<SponsorshipTrackerService>>,
+    Query(params): Query<PaginationParams>,
+) -> ApiResult<Json<Vec<Sponsorship>>> {
+    let sponsorships = tracker
+        .get_all_sponsorships(params.limit.clamp(1, 1000), params.offset.max(0))
+        .await
+        .map_err(internal)?;
+    Ok(Json(sponsorships))
+}
+
+/// GET /api/sponsorships/leaderboard - Top sponsors by reserve exposure
+async fn get_sponsor_leaderboard(
+    State(tracker): State<Arc<SponsorshipTrackerService>>,
+    Query(params): Query<LeaderboardParams>,
+) -> ApiResult<Json<Vec<SponsorLeaderboard>>> {
+    let leaderboard = tracker
+        .get_sponsor_leaderboard(params.limit.clamp(1, 1000))
+        .await
+        .map_err(internal)?;
+    Ok(Json(leaderboard))
+}
+
+/// GET /api/sponsorships/analytics - Network-wide sponsored reserve summary
+async fn get_analytics_summary(
+    State(tracker): State<Arc<SponsorshipTrackerService>>,
+) -> ApiResult<Json<SponsorshipAnalytics>> {
+    let analytics = tracker.get_analytics().await.map_err(internal)?;
+    Ok(Json(analytics))
+}
+
+/// GET /api/sponsorships/by-sponsor/:sponsor
+async fn get_sponsorships_by_sponsor(
+    State(tracker): State<Arc<SponsorshipTrackerService>>,
+    Path(sponsor): Path<String>,
+) -> ApiResult<Json<Vec<Sponsorship>>> {
+    let sponsorships = tracker
+        .get_sponsorships_by_sponsor(&sponsor)
+        .await
+        .map_err(internal)?;
+    Ok(Json(sponsorships))
+}
+
+/// GET /api/sponsorships/by-account/:account
+async fn get_sponsorships_by_account(
+    State(tracker): State<Arc<SponsorshipTrackerService>>,
+    Path(account): Path<String>,
+) -> ApiResult<Json<Vec<Sponsorship>>> {
+    let sponsorships = tracker
+        .get_sponsorships_for_account(&account)
+        .await
+        .map_err(internal)?;
+    Ok(Json(sponsorships))
+}
+
+/// GET /api/sponsorships/:id
+async fn get_sponsorship(
+    State(tracker): State<Arc<SponsorshipTrackerService>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Sponsorship>> {
+    tracker
+        .get_sponsorship(&id)
+        .await
+        .map_err(internal)?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("NOT_FOUND", format!("Sponsorship {} not found", id)))
+}
+
+/// GET /api/sponsorships/:id/history
+async fn get_sponsorship_history(
+    State(tracker): State<Arc<SponsorshipTrackerService>>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<SponsorshipHistory>>> {
+    let history = tracker
+        .get_sponsorship_history(&id)
+        .await
+        .map_err(internal)?;
+    Ok(Json(history))
+}
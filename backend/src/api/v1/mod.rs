@@ -10,14 +10,16 @@ use crate::rate_limit::{rate_limit_middleware, RateLimiter};
 use crate::rpc::StellarRpcClient;
 use crate::rpc_handlers;
 use crate::services::account_merge_detector::AccountMergeDetector;
+use crate::services::corridor_health::CorridorHealthScorer;
 use crate::services::fee_bump_tracker::FeeBumpTrackerService;
 use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
+use crate::services::metrics_overview::MetricsOverviewService;
 use crate::services::price_feed::PriceFeedClient;
 use crate::state::AppState;
 use axum::{
     middleware,
     routing::{get, put},
-    Router,
+    Extension, Router,
 };
 use std::sync::Arc;
 use tower_http::cors::CorsLayer;
@@ -35,6 +37,8 @@ pub fn routes(
     account_merge_detector: Arc<AccountMergeDetector>,
     lp_analyzer: Arc<LiquidityPoolAnalyzer>,
     price_feed: Arc<PriceFeedClient>,
+    health_scorer: Arc<CorridorHealthScorer>,
+    metrics_overview: Arc<MetricsOverviewService>,
     rate_limiter: Arc<RateLimiter>,
     cors: CorsLayer,
     pool: sqlx::SqlitePool,
@@ -48,7 +52,8 @@ pub fn routes(
             "/corridors/:corridor_key",
             get(corridors_cached::get_corridor_detail),
         )
-        .with_state(cached_state);
+        .with_state(cached_state)
+        .layer(Extension(health_scorer));
 
     // 2. Public anchor routes
     let public_anchor_routes = Router::new()
@@ -106,7 +111,7 @@ pub fn routes(
         .nest("/prices", price_feed_api::routes(price_feed.clone()))
         .nest("/cost-calculator", cost_calculator::routes(price_feed))
         .nest("/cache/stats", cache_stats::routes(cache.clone()))
-        .nest("/metrics", metrics_cached::routes(cache));
+        .nest("/metrics", metrics_cached::routes(metrics_overview, cache));
 
     // 6. OAuth routes
     let oauth_routes = oauth::routes(pool);
@@ -0,0 +1,76 @@
+//! Ingest ledgers from a Stellar history archive instead of live RPC.
+//!
+//! Usage: archive_ingest <start_ledger> [end_ledger]
+//! The archive is read from HISTORY_ARCHIVE_URL (local directory or HTTP mirror).
+//! Without an end ledger, ingestion runs up to the archive's latest checkpoint.
+
+use anyhow::{anyhow, Context};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::ingestion::history_archive::HistoryArchive;
+use stellar_insights_backend::ingestion::ledger::{LedgerIngestionService, LedgerProcessors};
+use stellar_insights_backend::network::NetworkConfig;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::alert_service::AlertService;
+use stellar_insights_backend::services::price_feed::{
+    default_asset_mapping, PriceFeedClient, PriceFeedConfig,
+};
+use stellar_insights_backend::websocket::WsState;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+    tracing_subscriber::fmt::init();
+
+    let mut args = std::env::args().skip(1);
+    let start = args
+        .next()
+        .ok_or_else(|| anyhow!("Usage: archive_ingest <start_ledger> [end_ledger]"))?
+        .parse::<u64>()
+        .context("start_ledger must be a number")?;
+    let end = args
+        .next()
+        .map(|a| a.parse::<u64>())
+        .transpose()
+        .context("end_ledger must be a number")?;
+
+    let archive =
+        HistoryArchive::from_env().ok_or_else(|| anyhow!("HISTORY_ARCHIVE_URL is not set"))?;
+    let end = match end {
+        Some(end) => end,
+        None => archive.current_ledger().await?,
+    };
+
+    let database_url = std::env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "sqlite:./stellar_insights.db".to_string());
+    let pool = SqlitePool::connect(&database_url).await?;
+
+    // Ledgers are read from the archive, not Horizon; the client supplies the
+    // network passphrase used to pair archived envelopes with their results
+    let rpc_client = Arc::new(StellarRpcClient::new_with_network(
+        NetworkConfig::from_env().network,
+        false,
+    ));
+    // Same processor set as the server, so archived ledgers are extracted the same way
+    let processors = LedgerProcessors::from_env(
+        pool.clone(),
+        Arc::clone(&rpc_client),
+        Arc::new(AlertService::new()),
+        Arc::new(PriceFeedClient::new(
+            PriceFeedConfig::from_env(),
+            default_asset_mapping(),
+        )),
+        Arc::new(WsState::new()),
+    );
+    let ingestion = LedgerIngestionService::with_processors(rpc_client, &processors, pool);
+
+    let count = archive.ingest_range(&ingestion, start, end).await?;
+    println!(
+        "Ingested {} ledgers ({}-{}) from {:?}",
+        count,
+        start,
+        end,
+        archive.location()
+    );
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! Re-check the hash chain of the stored ledgers and report every break.
+//!
+//! Usage: verify_chain [start_ledger] [end_ledger]
+//! Exits with status 1 when a break is found.
+
+use sqlx::SqlitePool;
+use stellar_insights_backend::ingestion::chain::verify_chain;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    dotenvy::dotenv().ok();
+
+    let mut args = std::env::args().skip(1);
+    let start = args.next().map(|a| a.parse::<u64>()).transpose()?;
+    let end = args.next().map(|a| a.parse::<u64>()).transpose()?;
+
+    let database_url = std::env::var("DATABASE_URL")
+        .unwrap_or_else(|_| "sqlite:./stellar_insights.db".to_string());
+    let pool = SqlitePool::connect(&database_url).await?;
+
+    let report = verify_chain(&pool, start, end).await?;
+    println!(
+        "Checked {} ledgers: {} links verified, {} unverified (previous hash not decoded)",
+        report.ledgers_checked, report.links_verified, report.links_unverified
+    );
+
+    for chain_break in &report.breaks {
+        println!(
+            "BREAK at ledger {}: previous hash {} does not match ledger {} hash {}",
+            chain_break.sequence,
+            chain_break.previous_hash,
+            chain_break.sequence - 1,
+            chain_break.predecessor_hash
+        );
+    }
+
+    if !report.is_intact() {
+        println!("{} chain break(s) found", report.breaks.len());
+        std::process::exit(1);
+    }
+
+    println!("Chain intact");
+    Ok(())
+}
@@ -326,8 +326,8 @@ pub mod keys {
         "dashboard:stats".to_string()
     }
 
-    pub fn metrics_overview() -> String {
-        "metrics:overview".to_string()
+    pub fn metrics_overview(window: &str) -> String {
+        format!("metrics:overview:{}", window)
     }
 
     /// Pattern for invalidating all anchor-related caches
@@ -344,6 +344,16 @@ pub mod keys {
     pub fn dashboard_pattern() -> String {
         "dashboard:*".to_string()
     }
+
+    /// Pattern for invalidating all metrics caches
+    pub fn metrics_pattern() -> String {
+        "metrics:*".to_string()
+    }
+
+    /// Pattern for invalidating the overview of every window
+    pub fn metrics_overview_pattern() -> String {
+        "metrics:overview:*".to_string()
+    }
 }
 
 #[cfg(test)]
@@ -67,7 +67,17 @@ impl CacheInvalidationService {
     /// Invalidate metrics caches
     pub async fn invalidate_metrics(&self) -> anyhow::Result<()> {
         tracing::info!("Invalidating metrics caches");
-        self.cache.delete(&keys::metrics_overview()).await
+        self.cache.delete_pattern(&keys::metrics_pattern()).await?;
+        Ok(())
+    }
+
+    /// Invalidate the metrics overview of every window
+    pub async fn invalidate_metrics_overview(&self) -> anyhow::Result<()> {
+        tracing::info!("Invalidating metrics overview caches");
+        self.cache
+            .delete_pattern(&keys::metrics_overview_pattern())
+            .await?;
+        Ok(())
     }
 
     /// Full cache invalidation (use sparingly)
@@ -90,5 +100,8 @@ mod tests {
         assert_eq!(keys::anchor_pattern(), "anchor:*");
         assert_eq!(keys::corridor_pattern(), "corridor:*");
         assert_eq!(keys::dashboard_pattern(), "dashboard:*");
+        assert_eq!(keys::metrics_pattern(), "metrics:*");
+        assert_eq!(keys::metrics_overview_pattern(), "metrics:overview:*");
+        assert_eq!(keys::metrics_overview("7d"), "metrics:overview:7d");
     }
 }
@@ -179,7 +179,8 @@ pub struct AnchorRpcUpdate {
     pub successful_transactions: i64,
     pub failed_transactions: i64,
     pub total_volume_usd: f64,
-    pub avg_settlement_time_ms: i32,
+    /// Keeps the stored value when `None`
+    pub avg_settlement_time_ms: Option<i32>,
     pub reliability_score: f64,
     pub status: String,
 }
@@ -680,7 +681,7 @@ impl Database {
                 successful_transactions = $2,
                 failed_transactions = $3,
                 total_volume_usd = $4,
-                avg_settlement_time_ms = $5,
+                avg_settlement_time_ms = COALESCE($5, avg_settlement_time_ms),
                 reliability_score = $6,
                 status = $7,
                 updated_at = $8
@@ -765,11 +766,20 @@ impl Database {
 
         let assets = self.get_assets_by_anchor(anchor_id).await?;
         let metrics_history = self.get_anchor_metrics_history(anchor_id, 30).await?;
+        let unclaimed_balances = crate::services::claimable_balance_tracker::unclaimed_by_anchor(
+            &self.pool,
+            Some(&anchor_id.to_string()),
+        )
+        .await?
+        .into_iter()
+        .flat_map(|a| a.assets)
+        .collect();
 
         Ok(Some(AnchorDetailResponse {
             anchor,
             assets,
             metrics_history,
+            unclaimed_balances,
         }))
     }
 
@@ -1021,9 +1031,10 @@ impl Database {
                 r#"
                 INSERT INTO payments (
                     id, transaction_hash, source_account, destination_account,
-                    asset_type, asset_code, asset_issuer, amount, created_at
+                    asset_type, asset_code, asset_issuer, amount, network, created_at,
+                    successful, source_asset_code, source_asset_issuer
                 )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
                 ON CONFLICT (id) DO NOTHING
                 "#,
             )
@@ -1035,7 +1046,11 @@ impl Database {
             .bind(&payment.asset_code)
             .bind(&payment.asset_issuer)
             .bind(payment.amount)
+            .bind(&payment.network)
             .bind(payment.created_at)
+            .bind(payment.successful)
+            .bind(Some(&payment.source_asset_code).filter(|code| !code.is_empty()))
+            .bind(Some(&payment.source_asset_issuer).filter(|issuer| !issuer.is_empty()))
             .execute(&self.pool)
             .await?;
         }
@@ -1105,6 +1120,10 @@ impl Database {
             .await
     }
 
+    pub async fn last_processed_hour(&self) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        self.aggregation_db().last_processed_hour().await
+    }
+
     pub async fn get_job_retry_count(&self, job_id: &str) -> Result<i32> {
         self.aggregation_db().get_job_retry_count(job_id).await
     }
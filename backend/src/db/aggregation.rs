@@ -13,7 +13,7 @@ impl AggregationDb {
         Self { pool }
     }
 
-    /// Fetch payments within a time range
+    /// Fetch payments created in `[start_time, end_time)`
     pub async fn fetch_payments_by_timerange(
         &self,
         start_time: DateTime<Utc>,
@@ -30,10 +30,13 @@ impl AggregationDb {
                 asset_type,
                 asset_code,
                 asset_issuer,
+                source_asset_code,
+                source_asset_issuer,
                 amount,
+                successful,
                 created_at
             FROM payments
-            WHERE created_at >= ? AND created_at <= ?
+            WHERE created_at >= ? AND created_at < ?
             ORDER BY created_at ASC
             LIMIT ?
             "#,
@@ -54,23 +57,32 @@ impl AggregationDb {
                     .ok()?
                     .with_timezone(&Utc);
 
-                // For now, assume all payments are successful
-                // In a real system, you'd have a status field
-                let successful = true;
+                // Lumens are stored without a code; rows from before the
+                // source columns sent what they delivered
+                let (destination_asset_code, destination_asset_issuer) =
+                    if row.asset_type == "native" {
+                        ("XLM".to_string(), "native".to_string())
+                    } else {
+                        (
+                            row.asset_code.unwrap_or_default(),
+                            row.asset_issuer.unwrap_or_default(),
+                        )
+                    };
+                let source_asset_code = row
+                    .source_asset_code
+                    .unwrap_or_else(|| destination_asset_code.clone());
+                let source_asset_issuer = row
+                    .source_asset_issuer
+                    .unwrap_or_else(|| destination_asset_issuer.clone());
 
                 Some(crate::models::corridor::PaymentRecord {
-                    id: uuid::Uuid::parse_str(&row.id).ok()?,
-                    source_asset_code: row.asset_code.clone().unwrap_or_else(|| "XLM".to_string()),
-                    source_asset_issuer: row
-                        .asset_issuer
-                        .clone()
-                        .unwrap_or_else(|| "native".to_string()),
-                    destination_asset_code: row.asset_code.unwrap_or_else(|| "XLM".to_string()),
-                    destination_asset_issuer: row
-                        .asset_issuer
-                        .unwrap_or_else(|| "native".to_string()),
+                    id: row.id,
+                    source_asset_code,
+                    source_asset_issuer,
+                    destination_asset_code,
+                    destination_asset_issuer,
                     amount: row.amount,
-                    successful,
+                    successful: row.successful,
                     timestamp,
                     submission_time: None,
                     confirmation_time: None,
@@ -81,6 +93,24 @@ impl AggregationDb {
         Ok(payment_records)
     }
 
+    /// Start of the hour after the last one a completed rollup covered
+    pub async fn last_processed_hour(&self) -> Result<Option<DateTime<Utc>>> {
+        let last_hour: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT MAX(last_processed_hour)
+            FROM aggregation_jobs
+            WHERE status = 'completed'
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to fetch last processed hour")?;
+
+        Ok(last_hour
+            .and_then(|hour| DateTime::parse_from_rfc3339(&hour).ok())
+            .map(|hour| hour.with_timezone(&Utc)))
+    }
+
     /// Upserts a single hourly corridor metric row into `corridor_metrics_hourly`.
     ///
     /// # Merge Strategy
@@ -373,11 +403,13 @@ struct PaymentRecordRow {
     source_account: String,
     #[allow(dead_code)] // Fetched from DB but not used in conversion to PaymentRecord
     destination_account: String,
-    #[allow(dead_code)] // Fetched from DB but not used in conversion to PaymentRecord
     asset_type: String,
     asset_code: Option<String>,
     asset_issuer: Option<String>,
+    source_asset_code: Option<String>,
+    source_asset_issuer: Option<String>,
     amount: f64,
+    successful: bool,
     created_at: String,
 }
 
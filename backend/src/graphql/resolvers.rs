@@ -3,6 +3,7 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 
 use super::types::*;
+use crate::services::trade_aggregator::{CandleQuery, MarketType, Resolution, TradeAggregator};
 
 pub struct QueryRoot {
     pub pool: Arc<SqlitePool>,
@@ -238,6 +239,41 @@ impl QueryRoot {
         Ok(snapshot)
     }
 
+    /// Get OHLCV candles for an asset pair (BASE->COUNTER) or liquidity pool
+    async fn candles(
+        &self,
+        ctx: &Context<'_>,
+        market_key: String,
+        market_type: Option<String>,
+        resolution: Option<String>,
+        time_range: Option<TimeRangeInput>,
+        limit: Option<i32>,
+    ) -> Result<Vec<CandleType>> {
+        let aggregator = TradeAggregator::new(self.pool.as_ref().clone());
+        let resolution = match resolution {
+            Some(r) => r.parse::<Resolution>().map_err(Error::new)?,
+            None => Resolution::OneHour,
+        };
+        let query = CandleQuery {
+            start: time_range.as_ref().map(|tr| tr.start),
+            end: time_range.as_ref().map(|tr| tr.end),
+            limit: limit.unwrap_or(200).clamp(1, 1000) as i64,
+        };
+
+        let candles = match market_type.as_deref() {
+            None | Some("pair") => aggregator.get_pair_candles(&market_key, resolution, &query).await,
+            Some("liquidity_pool") => {
+                aggregator
+                    .get_candles(MarketType::LiquidityPool, &market_key, resolution, &query)
+                    .await
+            }
+            Some(other) => return Err(Error::new(format!("Invalid market type: {}", other))),
+        }
+        .map_err(|e| Error::new(e.to_string()))?;
+
+        Ok(candles.into_iter().map(CandleType::from).collect())
+    }
+
     /// Search across anchors and corridors
     async fn search(
         &self,
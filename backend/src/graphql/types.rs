@@ -168,6 +168,52 @@ pub struct TrustlineStatType {
     pub updated_at: DateTime<Utc>,
 }
 
+/// OHLCV candle for an asset pair or liquidity pool
+#[derive(Debug, Clone, Serialize, Deserialize, SimpleObject)]
+#[graphql(name = "Candle")]
+pub struct CandleType {
+    /// Pair key (BASE->COUNTER) or liquidity pool ID
+    pub market_key: String,
+    /// Market type (pair, liquidity_pool)
+    pub market_type: String,
+    /// Resolution (1m, 5m, 1h, 1d, 1w)
+    pub resolution: String,
+    /// Start of the candle
+    pub bucket_start: String,
+    /// Opening price in counter units per base unit
+    pub open: f64,
+    /// Highest price
+    pub high: f64,
+    /// Lowest price
+    pub low: f64,
+    /// Closing price
+    pub close: f64,
+    /// Volume in base asset units
+    pub base_volume: f64,
+    /// Volume in counter asset units
+    pub counter_volume: f64,
+    /// Number of trades
+    pub trade_count: i64,
+}
+
+impl From<crate::services::trade_aggregator::Candle> for CandleType {
+    fn from(candle: crate::services::trade_aggregator::Candle) -> Self {
+        Self {
+            market_key: candle.market_key,
+            market_type: candle.market_type,
+            resolution: candle.resolution,
+            bucket_start: candle.bucket_start,
+            open: candle.open,
+            high: candle.high,
+            low: candle.low,
+            close: candle.close,
+            base_volume: candle.base_volume,
+            counter_volume: candle.counter_volume,
+            trade_count: candle.trade_count,
+        }
+    }
+}
+
 /// Pagination input
 #[derive(Debug, Clone, InputObject)]
 pub struct PaginationInput {
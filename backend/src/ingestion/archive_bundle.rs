@@ -0,0 +1,649 @@
+//! Ledger bundles built from history archive XDR.
+//!
+//! An archived ledger carries its transaction set and results, which is enough
+//! to rebuild the transactions, operations and payments Horizon returns for the
+//! ledger, so archive ingestion makes no Horizon calls. Archives have no
+//! transaction meta, so effects are marked unavailable and processors that read
+//! them are skipped. Ids follow Horizon's (operation TOIDs, transaction hashes),
+//! so rows match those written by live ingestion.
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::{DateTime, SecondsFormat};
+use std::collections::HashMap;
+use stellar_xdr::curr::{
+    Asset, ClaimAtom, DecoratedSignature, FeeBumpTransactionInnerTx, InnerTransactionResultResult,
+    LedgerKey, MuxedAccount, Operation, OperationBody, OperationResult, OperationResultTr,
+    PathPaymentStrictReceiveResult, PathPaymentStrictSendResult, RevokeSponsorshipOp,
+    TransactionEnvelope, TransactionResultResult,
+};
+
+use crate::ingestion::ledger_meta::{
+    applied_transactions, close_meta_header, decode_transaction, envelope_operations,
+    parse_ledger_close_meta, DecodedTransaction,
+};
+use crate::ingestion::processor::{BundlePart, LedgerBundle};
+use crate::rpc::{self, HorizonOperation, HorizonTransaction, Payment, RpcLedger};
+use crate::utils::format_time;
+
+const AUTHORIZED_FLAG: u32 = 1;
+const AUTHORIZED_TO_MAINTAIN_LIABILITIES_FLAG: u32 = 2;
+
+const TRUSTLINE_FLAGS: [(u32, &str); 3] = [
+    (AUTHORIZED_FLAG, "authorized"),
+    (
+        AUTHORIZED_TO_MAINTAIN_LIABILITIES_FLAG,
+        "authorized_to_maintain_liabilities",
+    ),
+    (4, "clawback_enabled"),
+];
+
+const ACCOUNT_FLAGS: [(u32, &str); 4] = [
+    (1, "auth_required"),
+    (2, "auth_revocable"),
+    (4, "auth_immutable"),
+    (8, "auth_clawback_enabled"),
+];
+
+/// Bundle for a ledger read from a history archive. Like Horizon's ledger
+/// endpoints, transactions include failed ones while operations and payments
+/// only come from successful transactions.
+pub fn archive_bundle(ledger: &RpcLedger, network_passphrase: &str) -> Result<LedgerBundle> {
+    let metadata_xdr = ledger
+        .metadata_xdr
+        .as_deref()
+        .ok_or_else(|| anyhow!("Archived ledger {} has no metadata", ledger.sequence))?;
+    let meta = parse_ledger_close_meta(metadata_xdr)?;
+    let header = &close_meta_header(&meta).header;
+    let sequence = header.ledger_seq;
+    let created_at = DateTime::from_timestamp(header.scp_value.close_time.0 as i64, 0)
+        .map(|t| format_time(t, SecondsFormat::Secs))
+        .unwrap_or_default();
+
+    let mut bundle = LedgerBundle::new(u64::from(sequence));
+    bundle.unavailable.push(BundlePart::Effects);
+
+    for (index, (envelope, pair)) in applied_transactions(&meta, network_passphrase)?
+        .iter()
+        .enumerate()
+    {
+        let tx = decode_transaction(index as u32 + 1, envelope, pair);
+        bundle
+            .transactions
+            .push(horizon_transaction(sequence, &tx, envelope, &created_at));
+        if !tx.successful {
+            continue;
+        }
+
+        let results = operation_results(&pair.result.result);
+        let tx_source = envelope_source(envelope);
+        // Sponsors of open begin/end sponsoring sandwiches, by sponsored account
+        let mut sponsors = HashMap::new();
+
+        for (op_index, op) in envelope_operations(envelope).iter().enumerate() {
+            let id = toid(sequence, tx.application_order, op_index as u32 + 1).to_string();
+            let source = op
+                .source_account
+                .as_ref()
+                .map_or_else(|| tx_source.clone(), account);
+            let operation = OperationContext {
+                id: &id,
+                transaction_hash: &tx.hash,
+                source: &source,
+                operation_type: &tx.operations[op_index].operation_type,
+                created_at: &created_at,
+            };
+
+            if let Some(payment) = operation.payment(op, results.get(op_index)) {
+                bundle.payments.push(payment);
+            }
+            bundle.operations.push(operation.horizon(op, &mut sponsors));
+        }
+    }
+
+    Ok(bundle)
+}
+
+/// Horizon's total order id: ledger, application order and operation index
+fn toid(sequence: u32, application_order: u32, operation_index: u32) -> i64 {
+    (i64::from(sequence) << 32) | (i64::from(application_order) << 12) | i64::from(operation_index)
+}
+
+/// Horizon formats amounts with seven decimals
+fn amount(stroops: i64) -> String {
+    let sign = if stroops < 0 { "-" } else { "" };
+    let stroops = stroops.unsigned_abs();
+    format!(
+        "{}{}.{:07}",
+        sign,
+        stroops / 10_000_000,
+        stroops % 10_000_000
+    )
+}
+
+/// Underlying `G...` account of a possibly muxed account, as Horizon reports it
+fn account(muxed: &MuxedAccount) -> String {
+    match muxed {
+        MuxedAccount::Ed25519(_) => muxed.to_string(),
+        MuxedAccount::MuxedEd25519(m) => MuxedAccount::Ed25519(m.ed25519.clone()).to_string(),
+    }
+}
+
+fn asset_parts(asset: &Asset) -> rpc::Asset {
+    let (asset_type, code, issuer) = match asset {
+        Asset::Native => ("native", None, None),
+        Asset::CreditAlphanum4(a) => (
+            "credit_alphanum4",
+            Some(a.asset_code.to_string()),
+            Some(a.issuer.to_string()),
+        ),
+        Asset::CreditAlphanum12(a) => (
+            "credit_alphanum12",
+            Some(a.asset_code.to_string()),
+            Some(a.issuer.to_string()),
+        ),
+    };
+    rpc::Asset {
+        asset_type: asset_type.to_string(),
+        asset_code: code,
+        asset_issuer: issuer,
+    }
+}
+
+fn flag_names(flags: u32, names: &[(u32, &str)]) -> Vec<String> {
+    names
+        .iter()
+        .filter(|(flag, _)| flags & flag != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+fn signatures(signatures: &[DecoratedSignature]) -> Vec<String> {
+    signatures
+        .iter()
+        .map(|s| BASE64.encode(s.signature.as_slice()))
+        .collect()
+}
+
+fn envelope_source(envelope: &TransactionEnvelope) -> String {
+    match envelope {
+        TransactionEnvelope::TxV0(env) => {
+            MuxedAccount::Ed25519(env.tx.source_account_ed25519.clone()).to_string()
+        }
+        TransactionEnvelope::Tx(env) => account(&env.tx.source_account),
+        TransactionEnvelope::TxFeeBump(env) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &env.tx.inner_tx;
+            account(&inner.tx.source_account)
+        }
+    }
+}
+
+fn operation_results(result: &TransactionResultResult) -> &[OperationResult] {
+    match result {
+        TransactionResultResult::TxSuccess(results)
+        | TransactionResultResult::TxFailed(results) => results.as_slice(),
+        TransactionResultResult::TxFeeBumpInnerSuccess(inner)
+        | TransactionResultResult::TxFeeBumpInnerFailed(inner) => match &inner.result.result {
+            InnerTransactionResultResult::TxSuccess(results)
+            | InnerTransactionResultResult::TxFailed(results) => results.as_slice(),
+            _ => &[],
+        },
+        _ => &[],
+    }
+}
+
+fn horizon_transaction(
+    sequence: u32,
+    tx: &DecodedTransaction,
+    envelope: &TransactionEnvelope,
+    created_at: &str,
+) -> HorizonTransaction {
+    let (fee_account, fee_bump_transaction, inner_transaction) = match envelope {
+        TransactionEnvelope::TxFeeBump(env) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &env.tx.inner_tx;
+            (
+                account(&env.tx.fee_source),
+                Some(rpc::FeeBumpTransactionInfo {
+                    hash: tx.hash.clone(),
+                    signatures: signatures(&env.signatures),
+                }),
+                Some(rpc::InnerTransaction {
+                    hash: tx.inner_transaction_hash.clone().unwrap_or_default(),
+                    max_fee: Some(inner.tx.fee.to_string()),
+                    signatures: signatures(&inner.signatures),
+                }),
+            )
+        }
+        _ => (envelope_source(envelope), None, None),
+    };
+
+    HorizonTransaction {
+        id: tx.hash.clone(),
+        hash: tx.hash.clone(),
+        ledger: u64::from(sequence),
+        created_at: created_at.to_string(),
+        source_account: envelope_source(envelope),
+        fee_account: Some(fee_account),
+        fee_charged: Some(tx.fee_charged.to_string()),
+        max_fee: Some(tx.max_fee.to_string()),
+        operation_count: tx.operations.len() as u32,
+        successful: tx.successful,
+        paging_token: toid(sequence, tx.application_order, 0).to_string(),
+        fee_bump_transaction,
+        inner_transaction,
+    }
+}
+
+/// Sum the sender paid into a strict-receive path payment: what the offers
+/// crossed on its first hop were bought with, or the delivered amount when
+/// the payment crossed no offers
+fn strict_receive_sent(offers: &[ClaimAtom], send_asset: &Asset, dest_amount: i64) -> i64 {
+    if offers.is_empty() {
+        return dest_amount;
+    }
+    offers
+        .iter()
+        .filter_map(|atom| {
+            let (asset_bought, amount_bought) = match atom {
+                ClaimAtom::V0(a) => (&a.asset_bought, a.amount_bought),
+                ClaimAtom::OrderBook(a) => (&a.asset_bought, a.amount_bought),
+                ClaimAtom::LiquidityPool(a) => (&a.asset_bought, a.amount_bought),
+            };
+            (asset_bought == send_asset).then_some(amount_bought)
+        })
+        .sum()
+}
+
+/// Fields shared by the operation and payment records of one operation
+struct OperationContext<'a> {
+    id: &'a str,
+    transaction_hash: &'a str,
+    source: &'a str,
+    operation_type: &'a str,
+    created_at: &'a str,
+}
+
+impl OperationContext<'_> {
+    fn horizon(&self, op: &Operation, sponsors: &mut HashMap<String, String>) -> HorizonOperation {
+        let mut operation = HorizonOperation {
+            id: self.id.to_string(),
+            paging_token: self.id.to_string(),
+            transaction_hash: self.transaction_hash.to_string(),
+            source_account: self.source.to_string(),
+            operation_type: self.operation_type.to_string(),
+            created_at: self.created_at.to_string(),
+            ..HorizonOperation::default()
+        };
+
+        match &op.body {
+            OperationBody::CreateAccount(create) => {
+                operation.account = Some(create.destination.to_string());
+            }
+            OperationBody::Payment(payment) => {
+                operation.amount = Some(amount(payment.amount));
+            }
+            OperationBody::AccountMerge(into) => {
+                operation.account = Some(self.source.to_string());
+                operation.into = Some(account(into));
+            }
+            OperationBody::BeginSponsoringFutureReserves(begin) => {
+                let sponsored = begin.sponsored_id.to_string();
+                sponsors.insert(sponsored.clone(), self.source.to_string());
+                operation.sponsored_id = Some(sponsored);
+            }
+            OperationBody::EndSponsoringFutureReserves => {
+                operation.begin_sponsor = sponsors.remove(self.source);
+            }
+            OperationBody::RevokeSponsorship(revoke) => match revoke {
+                RevokeSponsorshipOp::LedgerEntry(LedgerKey::Account(key)) => {
+                    operation.account_id = Some(key.account_id.to_string());
+                }
+                RevokeSponsorshipOp::LedgerEntry(LedgerKey::Trustline(key)) => {
+                    operation.trustline_account_id = Some(key.account_id.to_string());
+                }
+                RevokeSponsorshipOp::LedgerEntry(LedgerKey::Data(key)) => {
+                    operation.data_account_id = Some(key.account_id.to_string());
+                }
+                RevokeSponsorshipOp::Signer(signer) => {
+                    operation.signer_account_id = Some(signer.account_id.to_string());
+                }
+                RevokeSponsorshipOp::LedgerEntry(_) => {}
+            },
+            // Only the issuer can authorize its trustlines
+            OperationBody::AllowTrust(allow) => {
+                operation.trustor = Some(allow.trustor.to_string());
+                operation.asset_code = Some(allow.asset.to_string());
+                operation.asset_issuer = Some(self.source.to_string());
+                operation.authorize = Some(allow.authorize & AUTHORIZED_FLAG != 0);
+                operation.authorize_to_maintain_liabilities =
+                    Some(allow.authorize & AUTHORIZED_TO_MAINTAIN_LIABILITIES_FLAG != 0);
+            }
+            OperationBody::SetTrustLineFlags(flags) => {
+                let asset = asset_parts(&flags.asset);
+                operation.trustor = Some(flags.trustor.to_string());
+                operation.asset_code = asset.asset_code;
+                operation.asset_issuer = asset.asset_issuer;
+                operation.set_flags_s = flag_names(flags.set_flags, &TRUSTLINE_FLAGS);
+                operation.clear_flags_s = flag_names(flags.clear_flags, &TRUSTLINE_FLAGS);
+            }
+            OperationBody::Clawback(clawback) => {
+                let asset = asset_parts(&clawback.asset);
+                operation.from = Some(account(&clawback.from));
+                operation.asset_code = asset.asset_code;
+                operation.asset_issuer = asset.asset_issuer;
+                operation.amount = Some(amount(clawback.amount));
+            }
+            OperationBody::ClawbackClaimableBalance(clawback) => {
+                operation.balance_id = Some(clawback.balance_id.to_string());
+            }
+            OperationBody::SetOptions(options) => {
+                operation.set_flags_s = flag_names(options.set_flags.unwrap_or(0), &ACCOUNT_FLAGS);
+                operation.clear_flags_s =
+                    flag_names(options.clear_flags.unwrap_or(0), &ACCOUNT_FLAGS);
+            }
+            _ => {}
+        }
+
+        operation
+    }
+
+    /// Payment record for payments and path payments; path payment amounts
+    /// that the operation leaves open come from its result
+    fn payment(&self, op: &Operation, result: Option<&OperationResult>) -> Option<Payment> {
+        let (destination, asset, delivered, source_leg) = match &op.body {
+            OperationBody::Payment(p) => (&p.destination, &p.asset, p.amount, None),
+            OperationBody::PathPaymentStrictSend(p) => {
+                let Some(OperationResult::OpInner(OperationResultTr::PathPaymentStrictSend(
+                    PathPaymentStrictSendResult::Success(success),
+                ))) = result
+                else {
+                    return None;
+                };
+                (
+                    &p.destination,
+                    &p.dest_asset,
+                    success.last.amount,
+                    Some((&p.send_asset, p.send_amount, p.path.as_slice())),
+                )
+            }
+            OperationBody::PathPaymentStrictReceive(p) => {
+                let Some(OperationResult::OpInner(OperationResultTr::PathPaymentStrictReceive(
+                    PathPaymentStrictReceiveResult::Success(success),
+                ))) = result
+                else {
+                    return None;
+                };
+                let sent = strict_receive_sent(&success.offers, &p.send_asset, p.dest_amount);
+                (
+                    &p.destination,
+                    &p.dest_asset,
+                    p.dest_amount,
+                    Some((&p.send_asset, sent, p.path.as_slice())),
+                )
+            }
+            _ => return None,
+        };
+
+        let asset = asset_parts(asset);
+        let source_asset = source_leg.map(|(asset, _, _)| asset_parts(asset));
+        Some(Payment {
+            id: self.id.to_string(),
+            paging_token: self.id.to_string(),
+            transaction_hash: self.transaction_hash.to_string(),
+            source_account: self.source.to_string(),
+            destination: String::new(),
+            asset_type: asset.asset_type,
+            asset_code: asset.asset_code,
+            asset_issuer: asset.asset_issuer,
+            amount: amount(delivered),
+            created_at: self.created_at.to_string(),
+            operation_type: Some(self.operation_type.to_string()),
+            source_asset_type: source_asset.as_ref().map(|a| a.asset_type.clone()),
+            source_asset_code: source_asset.as_ref().and_then(|a| a.asset_code.clone()),
+            source_asset_issuer: source_asset.and_then(|a| a.asset_issuer),
+            source_amount: source_leg.map(|(_, sent, _)| amount(sent)),
+            path: source_leg
+                .map(|(_, _, path)| path.iter().map(asset_parts).collect())
+                .unwrap_or_default(),
+            from: Some(self.source.to_string()),
+            to: Some(account(destination)),
+            transaction_successful: Some(true),
+            asset_balance_changes: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::ledger_meta::transaction_hash;
+    use sha2::{Digest, Sha256};
+    use stellar_xdr::curr::{
+        AccountId, AllowTrustOp, AllowTrustResult, AlphaNum4, AssetCode, AssetCode4,
+        ClaimOfferAtom, Hash, LedgerCloseMeta, LedgerCloseMetaV0, LedgerEntryChanges, LedgerHeader,
+        LedgerHeaderExt, LedgerHeaderHistoryEntry, LedgerHeaderHistoryEntryExt, Limits, Memo,
+        PathPaymentStrictReceiveOp, PathPaymentStrictReceiveResultSuccess, PaymentOp,
+        Preconditions, PublicKey, SequenceNumber, SimplePaymentResult, StellarValue,
+        StellarValueExt, TimePoint, Transaction, TransactionExt, TransactionMeta,
+        TransactionResult, TransactionResultExt, TransactionResultMeta, TransactionResultPair,
+        TransactionSet, TransactionV1Envelope, Uint256, UpgradeType, VecM, WriteXdr,
+    };
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn key(seed: u8) -> AccountId {
+        AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([seed; 32])))
+    }
+
+    fn credit(code: &[u8; 4], issuer: u8) -> Asset {
+        Asset::CreditAlphanum4(AlphaNum4 {
+            asset_code: AssetCode4(*code),
+            issuer: key(issuer),
+        })
+    }
+
+    fn envelope(source: u8, operations: Vec<OperationBody>) -> TransactionEnvelope {
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([source; 32])),
+                fee: 300,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: operations
+                    .into_iter()
+                    .map(|body| Operation {
+                        source_account: None,
+                        body,
+                    })
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        })
+    }
+
+    fn result(
+        envelope: &TransactionEnvelope,
+        result: TransactionResultResult,
+    ) -> TransactionResultMeta {
+        let network_id = Hash(Sha256::digest(PASSPHRASE.as_bytes()).into());
+        TransactionResultMeta {
+            result: TransactionResultPair {
+                transaction_hash: Hash(transaction_hash(envelope, &network_id).unwrap()),
+                result: TransactionResult {
+                    fee_charged: 200,
+                    result,
+                    ext: TransactionResultExt::V0,
+                },
+            },
+            fee_processing: LedgerEntryChanges::default(),
+            tx_apply_processing: TransactionMeta::V0(VecM::default()),
+        }
+    }
+
+    fn archived_ledger(
+        envelopes: Vec<TransactionEnvelope>,
+        results: Vec<TransactionResultMeta>,
+    ) -> RpcLedger {
+        let upgrades: VecM<UpgradeType, 6> = VecM::default();
+        let meta = LedgerCloseMeta::V0(LedgerCloseMetaV0 {
+            ledger_header: LedgerHeaderHistoryEntry {
+                hash: Hash([9; 32]),
+                header: LedgerHeader {
+                    ledger_version: 21,
+                    previous_ledger_hash: Hash([8; 32]),
+                    scp_value: StellarValue {
+                        tx_set_hash: Hash([0; 32]),
+                        close_time: TimePoint(1_734_032_457),
+                        upgrades,
+                        ext: StellarValueExt::Basic,
+                    },
+                    tx_set_result_hash: Hash([0; 32]),
+                    bucket_list_hash: Hash([0; 32]),
+                    ledger_seq: 2000,
+                    total_coins: 0,
+                    fee_pool: 0,
+                    inflation_seq: 0,
+                    id_pool: 0,
+                    base_fee: 100,
+                    base_reserve: 5_000_000,
+                    max_tx_set_size: 1000,
+                    skip_list: [Hash([0; 32]), Hash([0; 32]), Hash([0; 32]), Hash([0; 32])],
+                    ext: LedgerHeaderExt::V0,
+                },
+                ext: LedgerHeaderHistoryEntryExt::V0,
+            },
+            tx_set: TransactionSet {
+                previous_ledger_hash: Hash([8; 32]),
+                txs: envelopes.try_into().unwrap(),
+            },
+            tx_processing: results.try_into().unwrap(),
+            upgrades_processing: VecM::default(),
+            scp_info: VecM::default(),
+        });
+        RpcLedger {
+            hash: hex::encode([9; 32]),
+            sequence: 2000,
+            ledger_close_time: "1734032457".to_string(),
+            header_xdr: None,
+            metadata_xdr: Some(BASE64.encode(meta.to_xdr(Limits::none()).unwrap())),
+        }
+    }
+
+    #[test]
+    fn test_rebuilds_horizon_records_from_archive_xdr() {
+        let usdc = credit(b"USDC", 10);
+        let eurt = credit(b"EURT", 11);
+        let succeeded = envelope(
+            1,
+            vec![
+                OperationBody::PathPaymentStrictReceive(PathPaymentStrictReceiveOp {
+                    send_asset: usdc.clone(),
+                    send_max: 60_000_000,
+                    destination: MuxedAccount::Ed25519(Uint256([2; 32])),
+                    dest_asset: eurt.clone(),
+                    dest_amount: 50_000_000,
+                    path: VecM::default(),
+                }),
+                OperationBody::AllowTrust(AllowTrustOp {
+                    trustor: key(3),
+                    asset: AssetCode::CreditAlphanum4(AssetCode4(*b"GOLD")),
+                    authorize: AUTHORIZED_TO_MAINTAIN_LIABILITIES_FLAG,
+                }),
+            ],
+        );
+        let failed = envelope(
+            4,
+            vec![OperationBody::Payment(PaymentOp {
+                destination: MuxedAccount::Ed25519(Uint256([5; 32])),
+                asset: Asset::Native,
+                amount: 10_000_000,
+            })],
+        );
+
+        let op_results: Vec<OperationResult> = vec![
+            OperationResult::OpInner(OperationResultTr::PathPaymentStrictReceive(
+                PathPaymentStrictReceiveResult::Success(PathPaymentStrictReceiveResultSuccess {
+                    offers: vec![ClaimAtom::OrderBook(ClaimOfferAtom {
+                        seller_id: key(6),
+                        offer_id: 1,
+                        asset_sold: eurt.clone(),
+                        amount_sold: 50_000_000,
+                        asset_bought: usdc.clone(),
+                        amount_bought: 55_000_000,
+                    })]
+                    .try_into()
+                    .unwrap(),
+                    last: SimplePaymentResult {
+                        destination: key(2),
+                        asset: eurt,
+                        amount: 50_000_000,
+                    },
+                }),
+            )),
+            OperationResult::OpInner(OperationResultTr::AllowTrust(AllowTrustResult::Success)),
+        ];
+        let ledger = archived_ledger(
+            vec![succeeded.clone(), failed.clone()],
+            vec![
+                result(
+                    &succeeded,
+                    TransactionResultResult::TxSuccess(op_results.try_into().unwrap()),
+                ),
+                result(&failed, TransactionResultResult::TxBadSeq),
+            ],
+        );
+
+        let bundle = archive_bundle(&ledger, PASSPHRASE).unwrap();
+        assert_eq!(bundle.sequence, 2000);
+        assert!(bundle.missing.is_empty());
+        assert!(!bundle.has(BundlePart::Effects));
+
+        assert_eq!(bundle.transactions.len(), 2);
+        assert!(bundle.transactions[0].successful);
+        assert!(!bundle.transactions[1].successful);
+        assert_eq!(bundle.transactions[0].fee_charged.as_deref(), Some("200"));
+        assert_eq!(bundle.transactions[0].created_at, "2024-12-12T19:40:57Z");
+
+        // The failed transaction's payment is left out, as Horizon does
+        assert_eq!(bundle.operations.len(), 2);
+        assert_eq!(bundle.payments.len(), 1);
+
+        let payment = &bundle.payments[0];
+        assert_eq!(payment.id, ((2000_i64 << 32) | (1 << 12) | 1).to_string());
+        assert_eq!(
+            payment.operation_type.as_deref(),
+            Some("path_payment_strict_receive")
+        );
+        assert_eq!(payment.asset_code.as_deref(), Some("EURT"));
+        assert_eq!(payment.amount, "5.0000000");
+        assert_eq!(payment.source_asset_code.as_deref(), Some("USDC"));
+        assert_eq!(payment.source_amount.as_deref(), Some("5.5000000"));
+        assert_eq!(
+            payment.to.as_deref(),
+            Some(MuxedAccount::Ed25519(Uint256([2; 32])).to_string().as_str())
+        );
+        assert_eq!(payment.transaction_successful, Some(true));
+
+        let allow_trust = &bundle.operations[1];
+        assert_eq!(allow_trust.operation_type, "allow_trust");
+        assert_eq!(allow_trust.asset_code.as_deref(), Some("GOLD"));
+        assert_eq!(
+            allow_trust.asset_issuer.as_deref(),
+            Some(bundle.transactions[0].source_account.as_str())
+        );
+        assert_eq!(allow_trust.trustor, Some(key(3).to_string()));
+        assert_eq!(allow_trust.authorize, Some(false));
+        assert_eq!(allow_trust.authorize_to_maintain_liabilities, Some(true));
+    }
+
+    #[test]
+    fn test_formats_amounts_like_horizon() {
+        assert_eq!(amount(10_000_000), "1.0000000");
+        assert_eq!(amount(1), "0.0000001");
+        assert_eq!(amount(-25_000_000), "-2.5000000");
+    }
+}
@@ -0,0 +1,772 @@
+//! Parallel historical backfill of ledgers.
+//!
+//! A `[start, end]` range is split into fixed-size shards that are ingested
+//! concurrently through [`LedgerIngestionService`]. Every outbound call goes
+//! through the RPC client's per-endpoint rate limiters, so the concurrency
+//! setting only bounds how many shards compete for them at once. Each shard
+//! checkpoints the last ledger it finished, so an interrupted job resumes
+//! where it stopped. The gap scanner finds sequences missing from the
+//! `ledgers` table and turns them into a backfill job of their own.
+
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::ingestion::ledger::LedgerIngestionService;
+use crate::observability::metrics as obs_metrics;
+use crate::rpc::{RpcLedger, StellarRpcClient};
+
+const DEFAULT_SHARD_SIZE: u64 = 1_000;
+const DEFAULT_CONCURRENCY: usize = 4;
+const DEFAULT_BATCH_SIZE: u32 = 50;
+const MAX_CONCURRENCY: usize = 16;
+/// Upper bound on a single job so one request cannot queue millions of RPC calls
+const MAX_RANGE_LEDGERS: u64 = 1_000_000;
+
+#[derive(Debug, thiserror::Error)]
+pub enum BackfillError {
+    #[error("Invalid backfill range: {0}")]
+    InvalidRange(String),
+
+    #[error("Backfill job not found: {0}")]
+    JobNotFound(String),
+
+    #[error("Backfill job already running: {0}")]
+    AlreadyRunning(String),
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<sqlx::Error> for BackfillError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Other(e.into())
+    }
+}
+
+pub type BackfillResult<T> = std::result::Result<T, BackfillError>;
+
+#[derive(Debug, Clone)]
+pub struct BackfillConfig {
+    /// Ledgers per shard
+    pub shard_size: u64,
+    /// Shards processed at the same time
+    pub concurrency: usize,
+    /// Ledgers requested per `getLedgers` call
+    pub batch_size: u32,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            shard_size: DEFAULT_SHARD_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
+impl BackfillConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+
+        let shard_size = std::env::var("BACKFILL_SHARD_SIZE")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(default.shard_size);
+
+        let concurrency = std::env::var("BACKFILL_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(default.concurrency)
+            .min(MAX_CONCURRENCY);
+
+        let batch_size = std::env::var("BACKFILL_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse::<u32>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(default.batch_size);
+
+        Self {
+            shard_size,
+            concurrency,
+            batch_size,
+        }
+    }
+}
+
+/// Request to backfill an explicit ledger range
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackfillRequest {
+    pub start_ledger: u64,
+    pub end_ledger: u64,
+    pub shard_size: Option<u64>,
+    pub concurrency: Option<usize>,
+}
+
+/// Request to re-ingest every gap found in the `ledgers` table
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GapFillRequest {
+    /// Defaults to the lowest stored sequence
+    pub start_ledger: Option<u64>,
+    /// Defaults to the highest stored sequence
+    pub end_ledger: Option<u64>,
+    pub shard_size: Option<u64>,
+    pub concurrency: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct BackfillJob {
+    pub id: String,
+    /// `range` or `gaps`
+    pub kind: String,
+    pub start_ledger: i64,
+    pub end_ledger: i64,
+    pub shard_size: i64,
+    pub concurrency: i64,
+    /// `pending`, `running`, `completed` or `failed`
+    pub status: String,
+    pub ledgers_ingested: i64,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct BackfillShard {
+    pub job_id: String,
+    pub shard_index: i64,
+    pub start_ledger: i64,
+    pub end_ledger: i64,
+    /// Last ledger this shard finished; processing resumes after it
+    pub checkpoint_ledger: Option<i64>,
+    pub status: String,
+    pub ledgers_ingested: i64,
+    pub error: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackfillJobDetail {
+    #[serde(flatten)]
+    pub job: BackfillJob,
+    pub shards: Vec<BackfillShard>,
+}
+
+/// Inclusive run of ledger sequences missing from the `ledgers` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LedgerGap {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl LedgerGap {
+    pub fn ledger_count(&self) -> u64 {
+        self.end - self.start + 1
+    }
+}
+
+/// Split `[start, end]` into consecutive inclusive shards of at most `shard_size` ledgers
+pub fn plan_shards(start: u64, end: u64, shard_size: u64) -> Vec<(u64, u64)> {
+    let shard_size = shard_size.max(1);
+    let mut shards = Vec::new();
+    let mut shard_start = start;
+    while shard_start <= end {
+        let shard_end = shard_start.saturating_add(shard_size - 1).min(end);
+        shards.push((shard_start, shard_end));
+        if shard_end == u64::MAX {
+            break;
+        }
+        shard_start = shard_end + 1;
+    }
+    shards
+}
+
+pub struct BackfillService {
+    ingestion: Arc<LedgerIngestionService>,
+    rpc_client: Arc<StellarRpcClient>,
+    pool: SqlitePool,
+    config: BackfillConfig,
+    /// Jobs currently executing in this process
+    running: Mutex<HashSet<String>>,
+}
+
+impl BackfillService {
+    pub fn new(
+        ingestion: Arc<LedgerIngestionService>,
+        rpc_client: Arc<StellarRpcClient>,
+        pool: SqlitePool,
+    ) -> Self {
+        Self {
+            ingestion,
+            rpc_client,
+            pool,
+            config: BackfillConfig::from_env(),
+            running: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn with_config(mut self, config: BackfillConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Create a job for an explicit range. The range must lie inside the RPC
+    /// retention window, since `getLedgers` cannot serve older ledgers.
+    pub async fn create_range_job(&self, req: BackfillRequest) -> BackfillResult<BackfillJob> {
+        if req.start_ledger == 0 || req.end_ledger < req.start_ledger {
+            return Err(BackfillError::InvalidRange(format!(
+                "start_ledger ({}) must be positive and not greater than end_ledger ({})",
+                req.start_ledger, req.end_ledger
+            )));
+        }
+        if req.end_ledger - req.start_ledger + 1 > MAX_RANGE_LEDGERS {
+            return Err(BackfillError::InvalidRange(format!(
+                "range covers more than {} ledgers",
+                MAX_RANGE_LEDGERS
+            )));
+        }
+
+        let (oldest, latest) = self.retention_window().await?;
+        if req.start_ledger < oldest || req.end_ledger > latest {
+            return Err(BackfillError::InvalidRange(format!(
+                "range {}-{} is outside the RPC retention window {}-{}",
+                req.start_ledger, req.end_ledger, oldest, latest
+            )));
+        }
+
+        let shard_size = self.shard_size(req.shard_size);
+        let shards = plan_shards(req.start_ledger, req.end_ledger, shard_size);
+        self.insert_job(
+            "range",
+            req.start_ledger,
+            req.end_ledger,
+            shard_size,
+            self.concurrency(req.concurrency),
+            &shards,
+        )
+        .await
+    }
+
+    /// Create a job that re-ingests every gap in the given range. Gaps older
+    /// than the RPC retention window are skipped. Returns `None` when there is
+    /// nothing to fill.
+    pub async fn create_gap_job(&self, req: GapFillRequest) -> BackfillResult<Option<BackfillJob>> {
+        let gaps = self.find_gaps(req.start_ledger, req.end_ledger).await?;
+        if gaps.is_empty() {
+            return Ok(None);
+        }
+
+        let (oldest, latest) = self.retention_window().await?;
+        let shard_size = self.shard_size(req.shard_size);
+        let mut shards = Vec::new();
+        let mut skipped = 0u64;
+        for gap in &gaps {
+            let start = gap.start.max(oldest);
+            let end = gap.end.min(latest);
+            if start > end {
+                skipped += gap.ledger_count();
+                continue;
+            }
+            skipped += gap.ledger_count() - (end - start + 1);
+            shards.extend(plan_shards(start, end, shard_size));
+        }
+
+        if skipped > 0 {
+            warn!(
+                "Skipping {} missing ledgers outside the RPC retention window {}-{}",
+                skipped, oldest, latest
+            );
+        }
+        let (Some(first), Some(last)) = (shards.first(), shards.last()) else {
+            return Ok(None);
+        };
+        let (start, end) = (first.0, last.1);
+
+        let job = self
+            .insert_job(
+                "gaps",
+                start,
+                end,
+                shard_size,
+                self.concurrency(req.concurrency),
+                &shards,
+            )
+            .await?;
+        Ok(Some(job))
+    }
+
+    /// Spawn a job on the runtime and return immediately
+    pub fn spawn(self: &Arc<Self>, job_id: String) {
+        let service = Arc::clone(self);
+        tokio::spawn(async move {
+            if let Err(e) = service.run_job(&job_id).await {
+                error!("Backfill job {} failed to run: {}", job_id, e);
+            }
+        });
+    }
+
+    /// Re-run every unfinished shard of an existing job in the background
+    pub async fn resume(self: &Arc<Self>, job_id: &str) -> BackfillResult<BackfillJob> {
+        let job = self
+            .get_job_row(job_id)
+            .await?
+            .ok_or_else(|| BackfillError::JobNotFound(job_id.to_string()))?;
+        if self.is_running(job_id) {
+            return Err(BackfillError::AlreadyRunning(job_id.to_string()));
+        }
+        self.spawn(job_id.to_string());
+        Ok(job)
+    }
+
+    /// Execute a job to completion, processing unfinished shards concurrently
+    pub async fn run_job(self: &Arc<Self>, job_id: &str) -> BackfillResult<BackfillJob> {
+        let job = self
+            .get_job_row(job_id)
+            .await?
+            .ok_or_else(|| BackfillError::JobNotFound(job_id.to_string()))?;
+
+        {
+            let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+            if !running.insert(job_id.to_string()) {
+                return Err(BackfillError::AlreadyRunning(job_id.to_string()));
+            }
+        }
+
+        let result = self.execute(&job).await;
+        self.running
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(job_id);
+        result?;
+
+        self.get_job_row(job_id)
+            .await?
+            .ok_or_else(|| BackfillError::JobNotFound(job_id.to_string()))
+    }
+
+    async fn execute(self: &Arc<Self>, job: &BackfillJob) -> Result<()> {
+        sqlx::query(
+            "UPDATE backfill_jobs SET status = 'running', error = NULL, started_at = $1, completed_at = NULL WHERE id = $2",
+        )
+        .bind(Utc::now())
+        .bind(&job.id)
+        .execute(&self.pool)
+        .await?;
+
+        let shards: Vec<BackfillShard> = sqlx::query_as(
+            "SELECT * FROM backfill_shards WHERE job_id = $1 AND status != 'completed' ORDER BY shard_index",
+        )
+        .bind(&job.id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        info!(
+            "Backfill job {} processing {} shards of ledgers {}-{} with concurrency {}",
+            job.id,
+            shards.len(),
+            job.start_ledger,
+            job.end_ledger,
+            job.concurrency
+        );
+
+        let concurrency = (job.concurrency.max(1) as usize).min(MAX_CONCURRENCY);
+        let failed = stream::iter(shards)
+            .map(|shard| {
+                let service = Arc::clone(self);
+                async move { service.execute_shard(shard).await }
+            })
+            .buffer_unordered(concurrency)
+            .filter(|ok| futures::future::ready(!ok))
+            .count()
+            .await;
+
+        let (status, error) = if failed == 0 {
+            ("completed", None)
+        } else {
+            ("failed", Some(format!("{} shard(s) failed", failed)))
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE backfill_jobs SET
+                status = $1,
+                error = $2,
+                completed_at = $3,
+                ledgers_ingested = (
+                    SELECT COALESCE(SUM(ledgers_ingested), 0) FROM backfill_shards WHERE job_id = $4
+                )
+            WHERE id = $4
+            "#,
+        )
+        .bind(status)
+        .bind(&error)
+        .bind(Utc::now())
+        .bind(&job.id)
+        .execute(&self.pool)
+        .await?;
+
+        obs_metrics::record_background_job(
+            "ledger_backfill",
+            if failed == 0 { "success" } else { "error" },
+        );
+        info!("Backfill job {} finished with status {}", job.id, status);
+        Ok(())
+    }
+
+    /// Run one shard and record its outcome; returns whether it completed
+    async fn execute_shard(&self, shard: BackfillShard) -> bool {
+        let job_id = shard.job_id.clone();
+        let index = shard.shard_index;
+
+        if let Err(e) = self.set_shard_status(&job_id, index, "running", None).await {
+            warn!(
+                "Failed to mark backfill shard {}/{} running: {}",
+                job_id, index, e
+            );
+        }
+
+        let (status, error) = match self.run_shard(&shard).await {
+            Ok(()) => ("completed", None),
+            Err(e) => {
+                warn!(
+                    "Backfill shard {}/{} ({}-{}) failed: {:#}",
+                    job_id, index, shard.start_ledger, shard.end_ledger, e
+                );
+                ("failed", Some(format!("{:#}", e)))
+            }
+        };
+
+        if let Err(e) = self
+            .set_shard_status(&job_id, index, status, error.as_deref())
+            .await
+        {
+            warn!(
+                "Failed to record backfill shard {}/{} status: {}",
+                job_id, index, e
+            );
+        }
+        status == "completed"
+    }
+
+    async fn run_shard(&self, shard: &BackfillShard) -> Result<()> {
+        let end = shard.end_ledger as u64;
+        let mut next = shard
+            .checkpoint_ledger
+            .map_or(shard.start_ledger as u64, |c| c as u64 + 1);
+
+        while next <= end {
+            let limit = (end - next + 1).min(u64::from(self.config.batch_size)) as u32;
+            let result = self
+                .rpc_client
+                .fetch_ledgers(Some(next), limit, None)
+                .await
+                .with_context(|| format!("Failed to fetch ledgers from {}", next))?;
+
+            let ledgers: Vec<RpcLedger> = result
+                .ledgers
+                .into_iter()
+                .filter(|l| l.sequence >= next && l.sequence <= end)
+                .collect();
+            let Some(last) = ledgers.last().map(|l| l.sequence) else {
+                bail!(
+                    "RPC returned no ledgers starting at {} (latest {})",
+                    next,
+                    result.latest_ledger
+                );
+            };
+
+            let existing = self.existing_sequences(next, last).await?;
+            let missing: Vec<RpcLedger> = ledgers
+                .into_iter()
+                .filter(|l| !existing.contains(&l.sequence))
+                .collect();
+            let (ingested, _) = self.ingestion.process_ledgers(&missing).await?;
+
+            // Ledgers left for a retry, that failed or were quarantined are not
+            // stored; the checkpoint stops short of the first one so a rerun retries it
+            let stored = self.existing_sequences(next, last).await?;
+            let failed = missing
+                .iter()
+                .map(|l| l.sequence)
+                .find(|seq| !stored.contains(seq));
+            let checkpoint = failed.map_or(last, |seq| seq - 1);
+
+            sqlx::query(
+                r#"
+                UPDATE backfill_shards SET
+                    checkpoint_ledger = $1,
+                    ledgers_ingested = ledgers_ingested + $2,
+                    updated_at = $3
+                WHERE job_id = $4 AND shard_index = $5
+                "#,
+            )
+            .bind(checkpoint as i64)
+            .bind(ingested as i64)
+            .bind(Utc::now())
+            .bind(&shard.job_id)
+            .bind(shard.shard_index)
+            .execute(&self.pool)
+            .await?;
+
+            if let Some(seq) = failed {
+                bail!("Ledger {} was not ingested; shard resumes from it", seq);
+            }
+            next = last + 1;
+        }
+
+        Ok(())
+    }
+
+    /// Missing sequences in `[start, end]`; either bound defaults to the
+    /// lowest or highest stored ledger
+    pub async fn find_gaps(
+        &self,
+        start: Option<u64>,
+        end: Option<u64>,
+    ) -> BackfillResult<Vec<LedgerGap>> {
+        let (min_stored, max_stored): (Option<i64>, Option<i64>) =
+            sqlx::query_as("SELECT MIN(sequence), MAX(sequence) FROM ledgers")
+                .fetch_one(&self.pool)
+                .await?;
+
+        let start = start.or(min_stored.map(|s| s as u64));
+        let end = end.or(max_stored.map(|s| s as u64));
+        let (Some(start), Some(end)) = (start, end) else {
+            return Ok(Vec::new());
+        };
+        if end < start {
+            return Err(BackfillError::InvalidRange(format!(
+                "start_ledger ({}) is greater than end_ledger ({})",
+                start, end
+            )));
+        }
+
+        let (first, last): (Option<i64>, Option<i64>) = sqlx::query_as(
+            "SELECT MIN(sequence), MAX(sequence) FROM ledgers WHERE sequence BETWEEN $1 AND $2",
+        )
+        .bind(start as i64)
+        .bind(end as i64)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let (Some(first), Some(last)) = (first, last) else {
+            return Ok(vec![LedgerGap { start, end }]);
+        };
+
+        let interior: Vec<(i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT prev + 1 AS gap_start, sequence - 1 AS gap_end
+            FROM (
+                SELECT sequence, LAG(sequence) OVER (ORDER BY sequence) AS prev
+                FROM ledgers
+                WHERE sequence BETWEEN $1 AND $2
+            )
+            WHERE prev IS NOT NULL AND sequence - prev > 1
+            ORDER BY gap_start
+            "#,
+        )
+        .bind(start as i64)
+        .bind(end as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut gaps = Vec::with_capacity(interior.len() + 2);
+        if first as u64 > start {
+            gaps.push(LedgerGap {
+                start,
+                end: first as u64 - 1,
+            });
+        }
+        gaps.extend(interior.into_iter().map(|(s, e)| LedgerGap {
+            start: s as u64,
+            end: e as u64,
+        }));
+        if (last as u64) < end {
+            gaps.push(LedgerGap {
+                start: last as u64 + 1,
+                end,
+            });
+        }
+        Ok(gaps)
+    }
+
+    pub async fn list_jobs(&self, limit: i64) -> BackfillResult<Vec<BackfillJob>> {
+        let jobs =
+            sqlx::query_as("SELECT * FROM backfill_jobs ORDER BY created_at DESC, id LIMIT $1")
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(jobs)
+    }
+
+    pub async fn get_job(&self, job_id: &str) -> BackfillResult<Option<BackfillJobDetail>> {
+        let Some(job) = self.get_job_row(job_id).await? else {
+            return Ok(None);
+        };
+        let shards =
+            sqlx::query_as("SELECT * FROM backfill_shards WHERE job_id = $1 ORDER BY shard_index")
+                .bind(job_id)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(Some(BackfillJobDetail { job, shards }))
+    }
+
+    pub fn is_running(&self, job_id: &str) -> bool {
+        self.running
+            .lock()
+            .map(|running| running.contains(job_id))
+            .unwrap_or(false)
+    }
+
+    async fn get_job_row(&self, job_id: &str) -> Result<Option<BackfillJob>> {
+        let job = sqlx::query_as("SELECT * FROM backfill_jobs WHERE id = $1")
+            .bind(job_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(job)
+    }
+
+    async fn insert_job(
+        &self,
+        kind: &str,
+        start: u64,
+        end: u64,
+        shard_size: u64,
+        concurrency: usize,
+        shards: &[(u64, u64)],
+    ) -> BackfillResult<BackfillJob> {
+        let id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO backfill_jobs (id, kind, start_ledger, end_ledger, shard_size, concurrency, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, 'pending', $7)
+            "#,
+        )
+        .bind(&id)
+        .bind(kind)
+        .bind(start as i64)
+        .bind(end as i64)
+        .bind(shard_size as i64)
+        .bind(concurrency as i64)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        for (index, (shard_start, shard_end)) in shards.iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO backfill_shards (job_id, shard_index, start_ledger, end_ledger, status, updated_at)
+                VALUES ($1, $2, $3, $4, 'pending', $5)
+                "#,
+            )
+            .bind(&id)
+            .bind(index as i64)
+            .bind(*shard_start as i64)
+            .bind(*shard_end as i64)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        info!(
+            "Created {} backfill job {} for ledgers {}-{} ({} shards)",
+            kind,
+            id,
+            start,
+            end,
+            shards.len()
+        );
+
+        self.get_job_row(&id)
+            .await?
+            .ok_or_else(|| anyhow!("Backfill job {} vanished after insert", id).into())
+    }
+
+    async fn set_shard_status(
+        &self,
+        job_id: &str,
+        shard_index: i64,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE backfill_shards SET status = $1, error = $2, updated_at = $3 WHERE job_id = $4 AND shard_index = $5",
+        )
+        .bind(status)
+        .bind(error)
+        .bind(Utc::now())
+        .bind(job_id)
+        .bind(shard_index)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn existing_sequences(&self, start: u64, end: u64) -> Result<HashSet<u64>> {
+        let rows: Vec<(i64,)> =
+            sqlx::query_as("SELECT sequence FROM ledgers WHERE sequence BETWEEN $1 AND $2")
+                .bind(start as i64)
+                .bind(end as i64)
+                .fetch_all(&self.pool)
+                .await?;
+        Ok(rows.into_iter().map(|(s,)| s as u64).collect())
+    }
+
+    async fn retention_window(&self) -> Result<(u64, u64)> {
+        let health = self
+            .rpc_client
+            .check_health()
+            .await
+            .context("Failed to read RPC retention window")?;
+        Ok((health.oldest_ledger, health.latest_ledger))
+    }
+
+    fn shard_size(&self, requested: Option<u64>) -> u64 {
+        requested
+            .filter(|s| *s > 0)
+            .unwrap_or(self.config.shard_size)
+    }
+
+    fn concurrency(&self, requested: Option<usize>) -> usize {
+        requested
+            .filter(|c| *c > 0)
+            .unwrap_or(self.config.concurrency)
+            .min(MAX_CONCURRENCY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_shards_covers_range_without_overlap() {
+        assert_eq!(
+            plan_shards(100, 349, 100),
+            vec![(100, 199), (200, 299), (300, 349)]
+        );
+        assert_eq!(plan_shards(5, 5, 10), vec![(5, 5)]);
+        assert!(plan_shards(10, 9, 10).is_empty());
+        assert_eq!(plan_shards(1, 3, 0), vec![(1, 1), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn ledger_gap_count_is_inclusive() {
+        assert_eq!(LedgerGap { start: 7, end: 7 }.ledger_count(), 1);
+        assert_eq!(LedgerGap { start: 10, end: 19 }.ledger_count(), 10);
+    }
+}
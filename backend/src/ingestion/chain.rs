@@ -0,0 +1,240 @@
+//! Ledger hash-chain continuity checks.
+//!
+//! Every ledger header commits to the hash of its predecessor. Ingestion
+//! checks each incoming ledger against its stored neighbours before
+//! persisting it, and [`verify_chain`] re-checks the stored chain end to end.
+
+use anyhow::Result;
+use futures::TryStreamExt;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+/// Result of comparing a ledger's previous-ledger hash with its stored predecessor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkStatus {
+    Linked,
+    /// Predecessor not stored or previous hash not decoded
+    Unknown,
+    Broken {
+        expected: String,
+        actual: String,
+    },
+}
+
+/// Why an incoming ledger was rejected from the stored chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainViolation {
+    /// Incoming previous hash does not match the stored predecessor's hash
+    PreviousHashMismatch { expected: String, actual: String },
+    /// Stored successor does not point back at the incoming ledger's hash
+    SuccessorMismatch {
+        successor_sequence: u64,
+        expected: String,
+        actual: String,
+    },
+    /// A different ledger is already stored at this sequence (fork)
+    HashConflict { stored_hash: String },
+}
+
+impl ChainViolation {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::PreviousHashMismatch { .. } => "previous_hash_mismatch",
+            Self::SuccessorMismatch { .. } => "successor_mismatch",
+            Self::HashConflict { .. } => "hash_conflict",
+        }
+    }
+
+    /// Hash the stored chain expected at the mismatched link
+    pub fn expected_hash(&self) -> &str {
+        match self {
+            Self::PreviousHashMismatch { expected, .. }
+            | Self::SuccessorMismatch { expected, .. } => expected,
+            Self::HashConflict { stored_hash } => stored_hash,
+        }
+    }
+}
+
+/// Link between two stored ledgers that does not match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChainBreak {
+    pub sequence: u64,
+    pub hash: String,
+    pub previous_hash: String,
+    pub predecessor_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainReport {
+    pub start_ledger: Option<u64>,
+    pub end_ledger: Option<u64>,
+    pub ledgers_checked: u64,
+    pub links_verified: u64,
+    /// Links that could not be checked because a previous hash was not decoded
+    pub links_unverified: u64,
+    pub breaks: Vec<ChainBreak>,
+}
+
+impl ChainReport {
+    pub fn is_intact(&self) -> bool {
+        self.breaks.is_empty()
+    }
+}
+
+/// Hex hashes from RPC and from decoded XDR may differ in case
+fn hashes_match(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+pub fn check_link(predecessor_hash: Option<&str>, previous_hash: Option<&str>) -> LinkStatus {
+    match (predecessor_hash, previous_hash) {
+        (Some(expected), Some(actual)) if hashes_match(expected, actual) => LinkStatus::Linked,
+        (Some(expected), Some(actual)) => LinkStatus::Broken {
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        },
+        _ => LinkStatus::Unknown,
+    }
+}
+
+/// Check an incoming ledger against the stored ledgers on either side of it
+pub async fn check_incoming(
+    pool: &SqlitePool,
+    sequence: u64,
+    hash: &str,
+    previous_hash: Option<&str>,
+) -> Result<Option<ChainViolation>> {
+    let neighbours: Vec<(i64, String, Option<String>)> = sqlx::query_as(
+        "SELECT sequence, hash, previous_hash FROM ledgers WHERE sequence BETWEEN $1 AND $2",
+    )
+    .bind(sequence.saturating_sub(1) as i64)
+    .bind(sequence.saturating_add(1) as i64)
+    .fetch_all(pool)
+    .await?;
+
+    let find = |seq: u64| neighbours.iter().find(|(s, _, _)| *s as u64 == seq);
+
+    if let Some((_, stored_hash, _)) = find(sequence) {
+        if !hashes_match(stored_hash, hash) {
+            return Ok(Some(ChainViolation::HashConflict {
+                stored_hash: stored_hash.clone(),
+            }));
+        }
+        // Already stored with the same hash
+        return Ok(None);
+    }
+
+    if sequence > 0 {
+        let predecessor = find(sequence - 1).map(|(_, h, _)| h.as_str());
+        if let LinkStatus::Broken { expected, actual } = check_link(predecessor, previous_hash) {
+            return Ok(Some(ChainViolation::PreviousHashMismatch {
+                expected,
+                actual,
+            }));
+        }
+    }
+
+    if let Some((successor_sequence, _, successor_previous)) = find(sequence + 1) {
+        if let LinkStatus::Broken { expected, actual } =
+            check_link(Some(hash), successor_previous.as_deref())
+        {
+            return Ok(Some(ChainViolation::SuccessorMismatch {
+                successor_sequence: *successor_sequence as u64,
+                expected,
+                actual,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Re-check every link in the stored chain, optionally limited to `[start, end]`
+pub async fn verify_chain(
+    pool: &SqlitePool,
+    start: Option<u64>,
+    end: Option<u64>,
+) -> Result<ChainReport> {
+    let lower = start.map_or(i64::MIN, |s| s as i64);
+    let upper = end.map_or(i64::MAX, |e| e as i64);
+
+    let ledgers_checked: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM ledgers WHERE sequence BETWEEN $1 AND $2")
+            .bind(lower)
+            .bind(upper)
+            .fetch_one(pool)
+            .await?;
+
+    let mut report = ChainReport {
+        start_ledger: start,
+        end_ledger: end,
+        ledgers_checked: ledgers_checked as u64,
+        links_verified: 0,
+        links_unverified: 0,
+        breaks: Vec::new(),
+    };
+
+    let mut links = sqlx::query_as::<_, (i64, String, Option<String>, String)>(
+        r#"
+        SELECT l.sequence, l.hash, l.previous_hash, p.hash
+        FROM ledgers l
+        JOIN ledgers p ON p.sequence = l.sequence - 1
+        WHERE l.sequence BETWEEN $1 AND $2
+        ORDER BY l.sequence
+        "#,
+    )
+    .bind(lower)
+    .bind(upper)
+    .fetch(pool);
+
+    while let Some((sequence, hash, previous_hash, predecessor_hash)) = links.try_next().await? {
+        match check_link(Some(&predecessor_hash), previous_hash.as_deref()) {
+            LinkStatus::Linked => report.links_verified += 1,
+            LinkStatus::Unknown => report.links_unverified += 1,
+            LinkStatus::Broken { actual, .. } => report.breaks.push(ChainBreak {
+                sequence: sequence as u64,
+                hash,
+                previous_hash: actual,
+                predecessor_hash,
+            }),
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_link_compares_hashes_case_insensitively() {
+        assert_eq!(check_link(Some("abcd"), Some("ABCD")), LinkStatus::Linked);
+        assert_eq!(check_link(None, Some("abcd")), LinkStatus::Unknown);
+        assert_eq!(check_link(Some("abcd"), None), LinkStatus::Unknown);
+        assert_eq!(
+            check_link(Some("abcd"), Some("ef01")),
+            LinkStatus::Broken {
+                expected: "abcd".to_string(),
+                actual: "ef01".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn violation_reason_and_expected_hash() {
+        let violation = ChainViolation::SuccessorMismatch {
+            successor_sequence: 11,
+            expected: "aa".to_string(),
+            actual: "bb".to_string(),
+        };
+        assert_eq!(violation.reason(), "successor_mismatch");
+        assert_eq!(violation.expected_hash(), "aa");
+
+        let fork = ChainViolation::HashConflict {
+            stored_hash: "cc".to_string(),
+        };
+        assert_eq!(fork.reason(), "hash_conflict");
+        assert_eq!(fork.expected_hash(), "cc");
+    }
+}
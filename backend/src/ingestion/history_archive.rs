@@ -0,0 +1,570 @@
+//! Ingestion from a Stellar history archive.
+//!
+//! History archives publish a checkpoint every 64 ledgers. Each checkpoint has
+//! gzipped, record-marked XDR files of ledger headers, transaction sets and
+//! transaction results. Checkpoints are read from a local directory or an HTTP
+//! mirror and turned into [`RpcLedger`]s whose metadata is a `LedgerCloseMeta`
+//! built from those files, so they go through the same decode, persist and
+//! process path as ledgers fetched from RPC.
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use flate2::read::MultiGzDecoder;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+use stellar_xdr::curr::{
+    LedgerCloseMeta, LedgerCloseMetaV0, LedgerEntryChanges, LedgerHeaderHistoryEntry, Limits,
+    ReadXdr, TransactionEnvelope, TransactionHistoryEntry, TransactionHistoryEntryExt,
+    TransactionHistoryResultEntry, TransactionMeta, TransactionResultMeta, TransactionSet, VecM,
+    WriteXdr,
+};
+use tracing::{info, warn};
+
+use crate::ingestion::ledger::LedgerIngestionService;
+use crate::ingestion::ledger_meta::generalized_set_envelopes;
+use crate::rpc::RpcLedger;
+
+/// Ledgers per history archive checkpoint
+pub const CHECKPOINT_FREQUENCY: u64 = 64;
+
+const HAS_PATH: &str = ".well-known/stellar-history.json";
+
+/// Last ledger of the checkpoint that contains `ledger`
+pub fn checkpoint_containing(ledger: u64) -> u64 {
+    (ledger / CHECKPOINT_FREQUENCY + 1) * CHECKPOINT_FREQUENCY - 1
+}
+
+/// Archive-relative path of a checkpoint file, e.g. `ledger/00/00/7f/ledger-0000007f.xdr.gz`
+pub fn checkpoint_path(category: &str, checkpoint: u64) -> String {
+    let hex = format!("{:08x}", checkpoint);
+    format!(
+        "{category}/{}/{}/{}/{category}-{hex}.xdr.gz",
+        &hex[0..2],
+        &hex[2..4],
+        &hex[4..6]
+    )
+}
+
+/// Where the archive lives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArchiveLocation {
+    Directory(PathBuf),
+    Http(String),
+}
+
+impl ArchiveLocation {
+    /// `http(s)://` URLs are read over HTTP; anything else is a local path
+    pub fn parse(location: &str) -> Self {
+        let location = location.trim();
+        if location.starts_with("http://") || location.starts_with("https://") {
+            Self::Http(location.trim_end_matches('/').to_string())
+        } else {
+            Self::Directory(PathBuf::from(
+                location.strip_prefix("file://").unwrap_or(location),
+            ))
+        }
+    }
+}
+
+/// History Archive State, published at `.well-known/stellar-history.json`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryArchiveState {
+    current_ledger: u64,
+}
+
+pub struct HistoryArchive {
+    location: ArchiveLocation,
+    client: reqwest::Client,
+}
+
+impl HistoryArchive {
+    pub fn new(location: ArchiveLocation) -> Self {
+        Self {
+            location,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Archive configured by `HISTORY_ARCHIVE_URL`, if any
+    pub fn from_env() -> Option<Self> {
+        std::env::var("HISTORY_ARCHIVE_URL")
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| Self::new(ArchiveLocation::parse(&v)))
+    }
+
+    pub fn location(&self) -> &ArchiveLocation {
+        &self.location
+    }
+
+    /// Latest checkpointed ledger published by the archive
+    pub async fn current_ledger(&self) -> Result<u64> {
+        let bytes = self
+            .fetch(HAS_PATH)
+            .await?
+            .ok_or_else(|| anyhow!("History archive has no {}", HAS_PATH))?;
+        let state: HistoryArchiveState =
+            serde_json::from_slice(&bytes).context("Invalid history archive state")?;
+        Ok(state.current_ledger)
+    }
+
+    /// Read every ledger in a checkpoint, ordered by sequence
+    pub async fn read_checkpoint(&self, checkpoint: u64) -> Result<Vec<RpcLedger>> {
+        let headers: Vec<LedgerHeaderHistoryEntry> = self
+            .read_records(&checkpoint_path("ledger", checkpoint))
+            .await?
+            .ok_or_else(|| anyhow!("Checkpoint {} has no ledger headers", checkpoint))?;
+
+        // Checkpoints without transactions may omit these files
+        let mut tx_sets: HashMap<u32, TransactionHistoryEntry> = self
+            .read_records::<TransactionHistoryEntry>(&checkpoint_path("transactions", checkpoint))
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| (entry.ledger_seq, entry))
+            .collect();
+        let mut results: HashMap<u32, TransactionHistoryResultEntry> = self
+            .read_records::<TransactionHistoryResultEntry>(&checkpoint_path("results", checkpoint))
+            .await?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| (entry.ledger_seq, entry))
+            .collect();
+
+        let mut ledgers = headers
+            .into_iter()
+            .map(|header| {
+                let seq = header.header.ledger_seq;
+                archived_ledger(header, tx_sets.remove(&seq), results.remove(&seq))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ledgers.sort_by_key(|l| l.sequence);
+        Ok(ledgers)
+    }
+
+    /// Ingest `[start, end]` checkpoint by checkpoint. Ledgers go through the
+    /// same persist-and-process path as live ingestion, with bundles built from
+    /// the archive instead of Horizon (see [`crate::ingestion::archive_bundle`]).
+    pub async fn ingest_range(
+        &self,
+        ingestion: &LedgerIngestionService,
+        start: u64,
+        end: u64,
+    ) -> Result<u64> {
+        if start == 0 || end < start {
+            bail!("Invalid ledger range {}-{}", start, end);
+        }
+
+        let mut ingested = 0u64;
+        let mut checkpoint = checkpoint_containing(start);
+        let last_checkpoint = checkpoint_containing(end);
+
+        while checkpoint <= last_checkpoint {
+            let ledgers: Vec<RpcLedger> = self
+                .read_checkpoint(checkpoint)
+                .await
+                .with_context(|| format!("Failed to read checkpoint {}", checkpoint))?
+                .into_iter()
+                .filter(|l| l.sequence >= start && l.sequence <= end)
+                .collect();
+
+            if ledgers.is_empty() {
+                warn!("Checkpoint {} has no ledgers in range", checkpoint);
+            }
+            let (count, retry_from) = ingestion.process_archived_ledgers(&ledgers).await?;
+            ingested += count;
+            if let Some(sequence) = retry_from {
+                bail!(
+                    "Ledger {} could not be ingested ({} ledgers stored); rerun from {}",
+                    sequence,
+                    ingested,
+                    sequence
+                );
+            }
+            info!(
+                "Ingested checkpoint {} from history archive ({} ledgers so far)",
+                checkpoint, ingested
+            );
+
+            checkpoint += CHECKPOINT_FREQUENCY;
+        }
+
+        Ok(ingested)
+    }
+
+    async fn read_records<T: ReadXdr>(&self, path: &str) -> Result<Option<Vec<T>>> {
+        let Some(compressed) = self.fetch(path).await? else {
+            return Ok(None);
+        };
+        let mut bytes = Vec::new();
+        MultiGzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut bytes)
+            .with_context(|| format!("Failed to decompress {}", path))?;
+        let records =
+            read_xdr_records(&bytes).with_context(|| format!("Invalid XDR in {}", path))?;
+        Ok(Some(records))
+    }
+
+    /// Raw file contents, or `None` when the archive does not have the file
+    async fn fetch(&self, path: &str) -> Result<Option<Vec<u8>>> {
+        match &self.location {
+            ArchiveLocation::Directory(root) => match tokio::fs::read(root.join(path)).await {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(e).with_context(|| format!("Failed to read {}", path)),
+            },
+            ArchiveLocation::Http(base) => {
+                let response = self
+                    .client
+                    .get(format!("{}/{}", base, path))
+                    .send()
+                    .await
+                    .with_context(|| format!("Failed to fetch {}", path))?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                let response = response
+                    .error_for_status()
+                    .with_context(|| format!("Failed to fetch {}", path))?;
+                Ok(Some(response.bytes().await?.to_vec()))
+            }
+        }
+    }
+}
+
+/// Split an XDR stream using RFC 5531 record marking: each record is prefixed
+/// by a big-endian u32 whose high bit flags the last fragment
+pub fn read_xdr_records<T: ReadXdr>(bytes: &[u8]) -> Result<Vec<T>> {
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let mark: [u8; 4] = bytes
+            .get(offset..offset + 4)
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| anyhow!("Truncated record mark at byte {}", offset))?;
+        let mark = u32::from_be_bytes(mark);
+        if mark & 0x8000_0000 == 0 {
+            bail!("Multi-fragment XDR records are not supported");
+        }
+        let len = (mark & 0x7fff_ffff) as usize;
+        let start = offset + 4;
+        let record = bytes
+            .get(start..start + len)
+            .ok_or_else(|| anyhow!("Truncated record at byte {}", start))?;
+        records.push(T::from_xdr(record, Limits::none())?);
+        offset = start + len;
+    }
+    Ok(records)
+}
+
+/// Build an `RpcLedger` for an archived ledger. The archive has no
+/// per-transaction meta, so the `LedgerCloseMeta` carries the header,
+/// transaction set and results with empty change sets.
+fn archived_ledger(
+    header: LedgerHeaderHistoryEntry,
+    tx_set: Option<TransactionHistoryEntry>,
+    results: Option<TransactionHistoryResultEntry>,
+) -> Result<RpcLedger> {
+    let envelopes: Vec<TransactionEnvelope> = match tx_set {
+        Some(TransactionHistoryEntry {
+            ext: TransactionHistoryEntryExt::V1(generalized),
+            ..
+        }) => generalized_set_envelopes(&generalized),
+        Some(entry) => entry.tx_set.txs.to_vec(),
+        None => Vec::new(),
+    };
+
+    let tx_processing: Vec<TransactionResultMeta> = results
+        .map(|entry| entry.tx_result_set.results.to_vec())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|result| TransactionResultMeta {
+            result,
+            fee_processing: LedgerEntryChanges::default(),
+            tx_apply_processing: TransactionMeta::V0(VecM::default()),
+        })
+        .collect();
+
+    let sequence = u64::from(header.header.ledger_seq);
+    let hash = hex::encode(header.hash.0);
+    let ledger_close_time = header.header.scp_value.close_time.0.to_string();
+    let header_xdr = BASE64.encode(header.to_xdr(Limits::none())?);
+
+    let meta = LedgerCloseMeta::V0(LedgerCloseMetaV0 {
+        tx_set: TransactionSet {
+            previous_ledger_hash: header.header.previous_ledger_hash.clone(),
+            txs: envelopes.try_into()?,
+        },
+        tx_processing: tx_processing.try_into()?,
+        upgrades_processing: VecM::default(),
+        scp_info: VecM::default(),
+        ledger_header: header,
+    });
+
+    Ok(RpcLedger {
+        hash,
+        sequence,
+        ledger_close_time,
+        header_xdr: Some(header_xdr),
+        metadata_xdr: Some(BASE64.encode(meta.to_xdr(Limits::none())?)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ingestion::ledger_meta::{decode_ledger_close_meta, transaction_hash};
+    use crate::rpc::StellarRpcClient;
+    use crate::services::account_merge_detector::AccountMergeDetector;
+    use crate::services::fee_bump_tracker::FeeBumpTrackerService;
+    use flate2::{write::GzEncoder, Compression};
+    use sha2::{Digest, Sha256};
+    use sqlx::SqlitePool;
+    use std::io::Write;
+    use std::sync::Arc;
+    use stellar_xdr::curr::{
+        Asset, Hash, LedgerHeader, LedgerHeaderExt, LedgerHeaderHistoryEntryExt, Memo,
+        MuxedAccount, Operation, OperationBody, OperationResult, PaymentOp, Preconditions,
+        SequenceNumber, StellarValue, StellarValueExt, TimePoint, Transaction, TransactionExt,
+        TransactionHistoryResultEntryExt, TransactionResult, TransactionResultExt,
+        TransactionResultPair, TransactionResultResult, TransactionResultSet,
+        TransactionV1Envelope, Uint256, UpgradeType,
+    };
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn header(seq: u32) -> LedgerHeaderHistoryEntry {
+        let upgrades: VecM<UpgradeType, 6> = VecM::default();
+        LedgerHeaderHistoryEntry {
+            hash: Hash([seq as u8; 32]),
+            header: LedgerHeader {
+                ledger_version: 21,
+                previous_ledger_hash: Hash([(seq - 1) as u8; 32]),
+                scp_value: StellarValue {
+                    tx_set_hash: Hash([0; 32]),
+                    close_time: TimePoint(1_734_032_457 + u64::from(seq) * 5),
+                    upgrades,
+                    ext: StellarValueExt::Basic,
+                },
+                tx_set_result_hash: Hash([0; 32]),
+                bucket_list_hash: Hash([0; 32]),
+                ledger_seq: seq,
+                total_coins: 0,
+                fee_pool: 0,
+                inflation_seq: 0,
+                id_pool: 0,
+                base_fee: 100,
+                base_reserve: 5_000_000,
+                max_tx_set_size: 1000,
+                skip_list: [Hash([0; 32]), Hash([0; 32]), Hash([0; 32]), Hash([0; 32])],
+                ext: LedgerHeaderExt::V0,
+            },
+            ext: LedgerHeaderHistoryEntryExt::V0,
+        }
+    }
+
+    fn payment_envelope() -> TransactionEnvelope {
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+                fee: 200,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![Operation {
+                    source_account: None,
+                    body: OperationBody::Payment(PaymentOp {
+                        destination: MuxedAccount::Ed25519(Uint256([2; 32])),
+                        asset: Asset::Native,
+                        amount: 10_000_000,
+                    }),
+                }]
+                .try_into()
+                .unwrap(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        })
+    }
+
+    fn write_records<T: WriteXdr>(root: &std::path::Path, path: &str, records: &[T]) {
+        let mut raw = Vec::new();
+        for record in records {
+            let bytes = record.to_xdr(Limits::none()).unwrap();
+            raw.extend_from_slice(&(0x8000_0000 | bytes.len() as u32).to_be_bytes());
+            raw.extend_from_slice(&bytes);
+        }
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&raw).unwrap();
+
+        let file = root.join(path);
+        std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+        std::fs::write(file, encoder.finish().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn checkpoint_math_and_paths() {
+        assert_eq!(checkpoint_containing(1), 63);
+        assert_eq!(checkpoint_containing(63), 63);
+        assert_eq!(checkpoint_containing(64), 127);
+        assert_eq!(
+            checkpoint_path("ledger", 127),
+            "ledger/00/00/00/ledger-0000007f.xdr.gz"
+        );
+        assert_eq!(
+            checkpoint_path("results", 51_565_823),
+            "results/03/12/d4/results-0312d4ff.xdr.gz"
+        );
+    }
+
+    #[test]
+    fn archive_location_parses_urls_and_paths() {
+        assert_eq!(
+            ArchiveLocation::parse("http://localhost:8000/archive/"),
+            ArchiveLocation::Http("http://localhost:8000/archive".to_string())
+        );
+        assert_eq!(
+            ArchiveLocation::parse("file:///data/archive"),
+            ArchiveLocation::Directory(PathBuf::from("/data/archive"))
+        );
+        assert_eq!(
+            ArchiveLocation::parse("./archive"),
+            ArchiveLocation::Directory(PathBuf::from("./archive"))
+        );
+    }
+
+    /// Checkpoint 127 with ledgers 125-127; ledger 126 has one native payment.
+    /// Returns the payment transaction's hash.
+    fn write_checkpoint(root: &std::path::Path) -> [u8; 32] {
+        let envelope = payment_envelope();
+        let network_id = Hash(Sha256::digest(PASSPHRASE.as_bytes()).into());
+        let tx_hash = transaction_hash(&envelope, &network_id).unwrap();
+
+        write_records(
+            root,
+            &checkpoint_path("ledger", 127),
+            &[header(125), header(126), header(127)],
+        );
+        write_records(
+            root,
+            &checkpoint_path("transactions", 127),
+            &[TransactionHistoryEntry {
+                ledger_seq: 126,
+                tx_set: TransactionSet {
+                    previous_ledger_hash: Hash([125; 32]),
+                    txs: vec![envelope].try_into().unwrap(),
+                },
+                ext: TransactionHistoryEntryExt::V0,
+            }],
+        );
+        let ops: VecM<OperationResult> = VecM::default();
+        write_records(
+            root,
+            &checkpoint_path("results", 127),
+            &[TransactionHistoryResultEntry {
+                ledger_seq: 126,
+                tx_result_set: TransactionResultSet {
+                    results: vec![TransactionResultPair {
+                        transaction_hash: Hash(tx_hash),
+                        result: TransactionResult {
+                            fee_charged: 100,
+                            result: TransactionResultResult::TxSuccess(ops),
+                            ext: TransactionResultExt::V0,
+                        },
+                    }]
+                    .try_into()
+                    .unwrap(),
+                },
+                ext: TransactionHistoryResultEntryExt::V0,
+            }],
+        );
+        std::fs::create_dir_all(root.join(".well-known")).unwrap();
+        std::fs::write(
+            root.join(HAS_PATH),
+            r#"{"version": 1, "currentLedger": 127}"#,
+        )
+        .unwrap();
+
+        tx_hash
+    }
+
+    #[tokio::test]
+    async fn reads_checkpoint_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let tx_hash = write_checkpoint(dir.path());
+
+        let archive = HistoryArchive::new(ArchiveLocation::Directory(dir.path().to_path_buf()));
+        assert_eq!(archive.current_ledger().await.unwrap(), 127);
+
+        let ledgers = archive.read_checkpoint(127).await.unwrap();
+        assert_eq!(
+            ledgers.iter().map(|l| l.sequence).collect::<Vec<_>>(),
+            vec![125, 126, 127]
+        );
+        assert_eq!(ledgers[1].hash, hex::encode([126; 32]));
+        assert_eq!(
+            ledgers[1].ledger_close_time,
+            (1_734_032_457 + 126 * 5).to_string()
+        );
+
+        let decoded =
+            decode_ledger_close_meta(ledgers[1].metadata_xdr.as_deref().unwrap(), PASSPHRASE)
+                .unwrap();
+        assert_eq!(decoded.sequence, 126);
+        assert_eq!(decoded.previous_hash, ledgers[0].hash);
+        assert_eq!(decoded.transaction_count(), 1);
+        assert_eq!(decoded.transactions[0].hash, hex::encode(tx_hash));
+        assert_eq!(
+            decoded.transactions[0].operations[0].operation_type,
+            "payment"
+        );
+
+        let empty =
+            decode_ledger_close_meta(ledgers[2].metadata_xdr.as_deref().unwrap(), PASSPHRASE)
+                .unwrap();
+        assert_eq!(empty.transaction_count(), 0);
+
+        assert!(archive.read_checkpoint(191).await.is_err());
+    }
+
+    #[sqlx::test]
+    async fn ingests_range_without_horizon(pool: SqlitePool) {
+        let dir = tempfile::tempdir().unwrap();
+        write_checkpoint(dir.path());
+
+        // Nothing listens here, so any Horizon request would leave a ledger
+        // for a retry and fail the range
+        let unreachable = "http://127.0.0.1:9/testnet".to_string();
+        let rpc_client = Arc::new(StellarRpcClient::new_with_endpoints(
+            vec![unreachable.clone()],
+            vec![unreachable],
+            false,
+        ));
+        let ingestion = LedgerIngestionService::new(
+            Arc::clone(&rpc_client),
+            Arc::new(FeeBumpTrackerService::new(pool.clone())),
+            Arc::new(AccountMergeDetector::new(pool.clone(), rpc_client)),
+            pool.clone(),
+        );
+
+        let archive = HistoryArchive::new(ArchiveLocation::Directory(dir.path().to_path_buf()));
+        assert_eq!(archive.ingest_range(&ingestion, 125, 127).await.unwrap(), 3);
+
+        let payments: Vec<(String, String, String)> =
+            sqlx::query_as("SELECT operation_id, destination, amount FROM ledger_payments")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            payments,
+            vec![(
+                ((126_i64 << 32) | (1 << 12) | 1).to_string(),
+                MuxedAccount::Ed25519(Uint256([2; 32])).to_string(),
+                "1.0000000".to_string(),
+            )]
+        );
+    }
+}
@@ -4,29 +4,166 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 use tracing::{info, warn};
 
-use crate::rpc::{GetLedgersResult, RpcLedger, StellarRpcClient};
+use crate::ingestion::archive_bundle::archive_bundle;
+use crate::ingestion::chain::{self, ChainViolation};
+use crate::ingestion::ledger_meta::{decode_ledger_close_meta, DecodedLedger, DecodedTransaction};
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor, ProcessorRegistry};
+use crate::rpc::{Asset, Payment, RpcLedger, StellarRpcClient};
 use crate::services::account_merge_detector::AccountMergeDetector;
+use crate::services::alert_service::AlertService;
+use crate::services::asset_authorization::AssetAuthorizationTracker;
+use crate::services::asset_supply::{AssetSupplyConfig, AssetSupplyTracker};
+use crate::services::claimable_balance_tracker::ClaimableBalanceTracker;
 use crate::services::fee_bump_tracker::FeeBumpTrackerService;
+use crate::services::fee_market::{FeeMarketConfig, FeeMarketService};
+use crate::services::large_payment_detector::{LargePaymentConfig, LargePaymentDetector};
+use crate::services::price_feed::PriceFeedClient;
+use crate::services::sponsorship_tracker::SponsorshipTrackerService;
+use crate::websocket::WsState;
+
+/// Where a ledger's bundle comes from
+#[derive(Debug, Clone, Copy)]
+enum BundleSource {
+    /// Fetched from Horizon
+    Horizon,
+    /// Built from history archive XDR
+    Archive,
+}
 
 /// Ledger ingestion service that fetches and persists ledgers sequentially
 pub struct LedgerIngestionService {
     rpc_client: Arc<StellarRpcClient>,
-    fee_bump_tracker: Arc<FeeBumpTrackerService>,
-    account_merge_detector: Arc<AccountMergeDetector>,
+    processors: ProcessorRegistry,
+    alert_service: Arc<AlertService>,
     pool: SqlitePool,
 }
 
+/// The built-in ledger processors. The server and archive ingestion both build
+/// them here so every ingestion path runs the same set; the server also hands
+/// them to the API routes that read what they extract.
+#[derive(Clone)]
+pub struct LedgerProcessors {
+    /// Shared by the processors that raise alerts and by chain continuity checks
+    pub alert_service: Arc<AlertService>,
+    pub fee_bump_tracker: Arc<FeeBumpTrackerService>,
+    pub account_merge_detector: Arc<AccountMergeDetector>,
+    pub claimable_balance_tracker: Arc<ClaimableBalanceTracker>,
+    pub sponsorship_tracker: Arc<SponsorshipTrackerService>,
+    pub fee_market_service: Arc<FeeMarketService>,
+    pub asset_supply_tracker: Arc<AssetSupplyTracker>,
+    pub asset_authorization_tracker: Arc<AssetAuthorizationTracker>,
+    pub large_payment_detector: Arc<LargePaymentDetector>,
+}
+
+impl LedgerProcessors {
+    /// I'm building every processor with its configuration from the environment
+    pub fn from_env(
+        pool: SqlitePool,
+        rpc_client: Arc<StellarRpcClient>,
+        alert_service: Arc<AlertService>,
+        price_feed: Arc<PriceFeedClient>,
+        ws_state: Arc<WsState>,
+    ) -> Self {
+        Self {
+            fee_bump_tracker: Arc::new(FeeBumpTrackerService::new(pool.clone())),
+            account_merge_detector: Arc::new(AccountMergeDetector::new(
+                pool.clone(),
+                Arc::clone(&rpc_client),
+            )),
+            claimable_balance_tracker: Arc::new(ClaimableBalanceTracker::new(pool.clone())),
+            sponsorship_tracker: Arc::new(
+                SponsorshipTrackerService::new(pool.clone())
+                    .with_alert_service(Arc::clone(&alert_service)),
+            ),
+            fee_market_service: Arc::new(FeeMarketService::new(
+                pool.clone(),
+                FeeMarketConfig::from_env(),
+            )),
+            asset_supply_tracker: Arc::new(
+                AssetSupplyTracker::new(pool.clone(), rpc_client, AssetSupplyConfig::from_env())
+                    .with_alert_service(Arc::clone(&alert_service)),
+            ),
+            asset_authorization_tracker: Arc::new(AssetAuthorizationTracker::new(pool.clone())),
+            large_payment_detector: Arc::new(LargePaymentDetector::new(
+                pool,
+                price_feed,
+                ws_state,
+                LargePaymentConfig::from_env(),
+            )),
+            alert_service,
+        }
+    }
+}
+
 /// Represents a payment operation extracted from a ledger
+///
+/// `asset_*` and `amount` describe what the destination received; for path
+/// payments the `source_*` fields describe what the sender paid.
 #[derive(Debug, Clone)]
 pub struct ExtractedPayment {
+    /// Horizon operation id; unique per payment
+    pub operation_id: String,
     pub ledger_sequence: u64,
     pub transaction_hash: String,
     pub operation_type: String,
     pub source_account: String,
     pub destination: String,
+    pub asset_type: String,
     pub asset_code: Option<String>,
     pub asset_issuer: Option<String>,
     pub amount: String,
+    pub source_asset_type: Option<String>,
+    pub source_asset_code: Option<String>,
+    pub source_asset_issuer: Option<String>,
+    pub source_amount: Option<String>,
+    /// Destination units received per source unit
+    pub exchange_rate: Option<f64>,
+    /// Intermediate assets between source and destination
+    pub path: Vec<Asset>,
+}
+
+impl ExtractedPayment {
+    /// I'm converting a Horizon payment record; helper methods cover both the old
+    /// and new Horizon formats
+    pub fn from_payment(ledger_sequence: u64, payment: &Payment) -> Self {
+        let is_path_payment = payment.is_path_payment();
+        let asset_type = payment
+            .asset_balance_changes
+            .as_ref()
+            .and_then(|changes| changes.first())
+            .map_or_else(|| payment.asset_type.clone(), |c| c.asset_type.clone());
+
+        Self {
+            operation_id: payment.id.clone(),
+            ledger_sequence,
+            transaction_hash: payment.transaction_hash.clone(),
+            operation_type: payment
+                .operation_type
+                .clone()
+                .unwrap_or_else(|| "payment".to_string()),
+            source_account: payment.source_account.clone(),
+            destination: payment.get_destination().unwrap_or_default(),
+            asset_type,
+            asset_code: payment.get_asset_code(),
+            asset_issuer: payment.get_asset_issuer(),
+            amount: payment.get_amount(),
+            source_asset_type: payment
+                .source_asset_type
+                .clone()
+                .filter(|_| is_path_payment),
+            source_asset_code: payment
+                .source_asset_code
+                .clone()
+                .filter(|_| is_path_payment),
+            source_asset_issuer: payment
+                .source_asset_issuer
+                .clone()
+                .filter(|_| is_path_payment),
+            source_amount: payment.source_amount.clone().filter(|_| is_path_payment),
+            exchange_rate: payment.exchange_rate(),
+            path: payment.path.clone(),
+        }
+    }
 }
 
 impl LedgerIngestionService {
@@ -36,14 +173,57 @@ impl LedgerIngestionService {
         account_merge_detector: Arc<AccountMergeDetector>,
         pool: SqlitePool,
     ) -> Self {
+        let mut processors = ProcessorRegistry::from_env();
+        processors.register(fee_bump_tracker);
+        processors.register(account_merge_detector);
+
         Self {
             rpc_client,
-            fee_bump_tracker,
-            account_merge_detector,
+            processors,
+            alert_service: Arc::new(AlertService::new()),
             pool,
         }
     }
 
+    /// I'm building the service with every built-in processor registered, in the
+    /// order they run
+    pub fn with_processors(
+        rpc_client: Arc<StellarRpcClient>,
+        processors: &LedgerProcessors,
+        pool: SqlitePool,
+    ) -> Self {
+        Self::new(
+            rpc_client,
+            Arc::clone(&processors.fee_bump_tracker),
+            Arc::clone(&processors.account_merge_detector),
+            pool,
+        )
+        .with_alert_service(Arc::clone(&processors.alert_service))
+        .with_processor(processors.claimable_balance_tracker.clone())
+        .with_processor(processors.sponsorship_tracker.clone())
+        .with_processor(processors.fee_market_service.clone())
+        .with_processor(processors.asset_supply_tracker.clone())
+        .with_processor(processors.asset_authorization_tracker.clone())
+        .with_processor(processors.large_payment_detector.clone())
+    }
+
+    /// I'm appending a custom processor; it runs after the built-in ones and can be
+    /// disabled through LEDGER_PROCESSORS_DISABLED like them
+    pub fn with_processor(mut self, processor: Arc<dyn LedgerProcessor>) -> Self {
+        self.processors.register(processor);
+        self
+    }
+
+    pub fn processors(&self) -> &ProcessorRegistry {
+        &self.processors
+    }
+
+    /// I'm swapping in a shared alert service for chain continuity alerts
+    pub fn with_alert_service(mut self, alert_service: Arc<AlertService>) -> Self {
+        self.alert_service = alert_service;
+        self
+    }
+
     /// I'm running the main ingestion loop - fetches ledgers and persists them
     pub async fn run_ingestion(&self, batch_size: u32) -> Result<u64> {
         let cursor = self.get_cursor().await?;
@@ -70,149 +250,317 @@ impl LedgerIngestionService {
             .await
             .context("Failed to fetch ledgers")?;
 
-        let count = self.process_ledgers(&result).await?;
+        let (count, retry_from) = self
+            .ingest_ledgers(&result.ledgers, BundleSource::Horizon)
+            .await?;
 
-        // I'm saving cursor for restart safety
-        if let Some(new_cursor) = &result.cursor {
-            self.save_cursor(new_cursor, result.ledgers.last().map(|l| l.sequence))
-                .await?;
+        // I'm saving cursor for restart safety; an incomplete ledger drops the RPC
+        // cursor so the next run starts again at that sequence
+        match retry_from {
+            Some(sequence) => self.save_cursor(None, Some(sequence - 1)).await?,
+            None => {
+                if let Some(new_cursor) = &result.cursor {
+                    self.save_cursor(Some(new_cursor), result.ledgers.last().map(|l| l.sequence))
+                        .await?;
+                }
+            }
         }
 
         Ok(count)
     }
 
-    /// I'm processing and persisting fetched ledgers
-    async fn process_ledgers(&self, result: &GetLedgersResult) -> Result<u64> {
+    /// I'm processing and persisting fetched ledgers; also used by the backfill shards
+    /// and history archive ingestion. Returns the number of ledgers stored and, when
+    /// a ledger's Horizon parts could not all be fetched, the sequence to retry from;
+    /// that ledger and the ones after it were not stored.
+    pub async fn process_ledgers(&self, ledgers: &[RpcLedger]) -> Result<(u64, Option<u64>)> {
+        self.ingest_ledgers(ledgers, BundleSource::Horizon).await
+    }
+
+    /// I'm processing ledgers read from a history archive; their bundles are built
+    /// from the archived XDR, so nothing is fetched from Horizon. Returns the same
+    /// count and retry sequence as `process_ledgers`.
+    pub async fn process_archived_ledgers(
+        &self,
+        ledgers: &[RpcLedger],
+    ) -> Result<(u64, Option<u64>)> {
+        self.ingest_ledgers(ledgers, BundleSource::Archive).await
+    }
+
+    /// I'm ingesting ledgers in order and stopping at the first one whose bundle
+    /// parts could not all be fetched; that ledger is not stored, and its sequence
+    /// is returned so the caller can retry from it
+    async fn ingest_ledgers(
+        &self,
+        ledgers: &[RpcLedger],
+        source: BundleSource,
+    ) -> Result<(u64, Option<u64>)> {
         let mut count = 0u64;
 
-        for ledger in &result.ledgers {
-            if let Err(e) = self.persist_ledger(ledger).await {
-                warn!("Failed to persist ledger {}: {}", ledger.sequence, e);
-                continue;
-            }
+        // Payments are always fetched; other parts only when an enabled processor needs them
+        let mut parts = self.processors.required_parts();
+        if !parts.contains(&BundlePart::Payments) {
+            parts.push(BundlePart::Payments);
+        }
 
-            // Fetch real payments from Horizon
-            match self
-                .rpc_client
-                .fetch_payments_for_ledger(ledger.sequence)
-                .await
-            {
-                Ok(payments) => {
-                    for payment in payments {
-                        // Convert RPC Payment to ExtractedPayment
-                        // Uses helper methods to support both old and new Horizon formats
-                        let extracted = ExtractedPayment {
-                            ledger_sequence: ledger.sequence,
-                            transaction_hash: payment.transaction_hash.clone(),
-                            operation_type: "payment".to_string(), // Horizon 'payments' endpoint returns payments
-                            source_account: payment.source_account.clone(),
-                            destination: payment.get_destination().unwrap_or_default(),
-                            asset_code: payment.get_asset_code(),
-                            asset_issuer: payment.get_asset_issuer(),
-                            amount: payment.get_amount(),
-                        };
-
-                        if let Err(e) = self.persist_payment(&extracted).await {
-                            warn!("Failed to persist payment: {}", e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to fetch payments for ledger {}: {}",
-                        ledger.sequence, e
-                    );
-                    // Non-fatal, continue ingesting ledgers
+        for ledger in ledgers {
+            let bundle = match source {
+                BundleSource::Horizon => {
+                    LedgerBundle::fetch(&self.rpc_client, ledger.sequence, &parts).await
                 }
+                BundleSource::Archive => self.archived_bundle(ledger, &parts),
+            };
+            if !bundle.missing.is_empty() {
+                warn!(
+                    "Ledger {} is missing {:?}; leaving it for the next run",
+                    ledger.sequence, bundle.missing
+                );
+                info!("Processed {} ledgers", count);
+                return Ok((count, Some(ledger.sequence)));
             }
 
-            // Fetch and process transactions for fee bumps
-            match self
-                .rpc_client
-                .fetch_transactions_for_ledger(ledger.sequence)
-                .await
-            {
-                Ok(transactions) => {
-                    if let Err(e) = self
-                        .fee_bump_tracker
-                        .process_transactions(&transactions)
-                        .await
-                    {
-                        warn!("Failed to process transactions for fee bumps: {}", e);
-                    }
-                }
+            match self.persist_ledger(ledger).await {
+                Ok(true) => {}
+                // Quarantined; the gap scanner picks the sequence up again later
+                Ok(false) => continue,
                 Err(e) => {
-                    warn!(
-                        "Failed to fetch transactions for ledger {}: {}",
-                        ledger.sequence, e
-                    );
+                    warn!("Failed to persist ledger {}: {}", ledger.sequence, e);
+                    continue;
                 }
             }
 
-            if let Err(e) = self
-                .account_merge_detector
-                .process_ledger_operations(ledger.sequence)
-                .await
-            {
-                warn!(
-                    "Failed to process account merge operations for ledger {}: {}",
-                    ledger.sequence, e
-                );
+            for payment in &bundle.payments {
+                let extracted = ExtractedPayment::from_payment(ledger.sequence, payment);
+                if let Err(e) = self.persist_payment(&extracted).await {
+                    warn!("Failed to persist payment: {}", e);
+                }
             }
 
+            self.processors.run(&bundle).await;
+
             count += 1;
         }
 
         info!("Processed {} ledgers", count);
-        Ok(count)
+        Ok((count, None))
     }
 
-    /// I'm persisting a single ledger to the database
-    async fn persist_ledger(&self, ledger: &RpcLedger) -> Result<()> {
+    /// I'm building an archived ledger's bundle from its XDR; a ledger whose XDR
+    /// cannot be turned into one is reported as missing every part
+    fn archived_bundle(&self, ledger: &RpcLedger, parts: &[BundlePart]) -> LedgerBundle {
+        let passphrase = &self.rpc_client.network_config().network_passphrase;
+        archive_bundle(ledger, passphrase).unwrap_or_else(|e| {
+            warn!(
+                "Failed to build bundle for archived ledger {}: {:#}",
+                ledger.sequence, e
+            );
+            LedgerBundle {
+                missing: parts.to_vec(),
+                ..LedgerBundle::new(ledger.sequence)
+            }
+        })
+    }
+
+    /// I'm persisting a single ledger with its decoded transactions and operations.
+    /// Returns false when the ledger does not link into the stored chain and was quarantined.
+    async fn persist_ledger(&self, ledger: &RpcLedger) -> Result<bool> {
         let close_time = self.parse_ledger_time(&ledger.ledger_close_time)?;
+        let decoded = self.decode_ledger(ledger);
+        let previous_hash = decoded.as_ref().map(|d| d.previous_hash.as_str());
+
+        if let Some(violation) =
+            chain::check_incoming(&self.pool, ledger.sequence, &ledger.hash, previous_hash).await?
+        {
+            self.quarantine_ledger(ledger, previous_hash, close_time, &violation)
+                .await?;
+            return Ok(false);
+        }
+
+        let mut db_tx = self.pool.begin().await?;
 
         sqlx::query(
             r#"
-            INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO ledgers (
+                sequence, hash, close_time, transaction_count, operation_count,
+                previous_hash, protocol_version, base_fee, base_reserve, max_tx_set_size
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             ON CONFLICT (sequence) DO NOTHING
             "#,
         )
         .bind(ledger.sequence as i64)
         .bind(&ledger.hash)
         .bind(close_time)
-        .bind(0i32) // I'd get real counts from XDR parsing
-        .bind(0i32)
-        .execute(&self.pool)
+        .bind(decoded.as_ref().map_or(0, |d| d.transaction_count() as i32))
+        .bind(decoded.as_ref().map_or(0, |d| d.operation_count() as i32))
+        .bind(decoded.as_ref().map(|d| d.previous_hash.clone()))
+        .bind(decoded.as_ref().map(|d| i64::from(d.protocol_version)))
+        .bind(decoded.as_ref().map(|d| i64::from(d.base_fee)))
+        .bind(decoded.as_ref().map(|d| i64::from(d.base_reserve)))
+        .bind(decoded.as_ref().map(|d| i64::from(d.max_tx_set_size)))
+        .execute(&mut *db_tx)
         .await?;
 
-        // I'm also storing a placeholder transaction for the ledger
-        let tx_hash = format!("tx_{}", ledger.sequence);
+        for tx in decoded.iter().flat_map(|d| d.transactions.iter()) {
+            self.persist_transaction(&mut db_tx, ledger.sequence, tx)
+                .await?;
+        }
+
+        db_tx.commit().await?;
+        Ok(true)
+    }
+
+    /// I'm setting aside a ledger that breaks chain continuity and raising an alert
+    async fn quarantine_ledger(
+        &self,
+        ledger: &RpcLedger,
+        previous_hash: Option<&str>,
+        close_time: DateTime<Utc>,
+        violation: &ChainViolation,
+    ) -> Result<()> {
+        warn!(
+            "Quarantining ledger {} ({}): {}",
+            ledger.sequence,
+            ledger.hash,
+            violation.reason()
+        );
+
         sqlx::query(
             r#"
-            INSERT INTO transactions (hash, ledger_sequence, source_account, fee, operation_count, successful)
+            INSERT INTO quarantined_ledgers (sequence, hash, previous_hash, reason, expected_hash, close_time)
             VALUES ($1, $2, $3, $4, $5, $6)
-            ON CONFLICT (hash) DO NOTHING
+            ON CONFLICT (sequence, hash, reason) DO NOTHING
             "#,
         )
-        .bind(&tx_hash)
         .bind(ledger.sequence as i64)
-        .bind("GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF")
-        .bind(100i64)
-        .bind(1i32)
-        .bind(true)
+        .bind(&ledger.hash)
+        .bind(previous_hash)
+        .bind(violation.reason())
+        .bind(violation.expected_hash())
+        .bind(close_time)
         .execute(&self.pool)
         .await?;
 
+        let alert = match violation {
+            ChainViolation::PreviousHashMismatch { expected, actual } => {
+                self.alert_service
+                    .alert_ledger_chain_break(ledger.sequence, expected.clone(), actual.clone())
+                    .await
+            }
+            ChainViolation::SuccessorMismatch {
+                successor_sequence,
+                expected,
+                actual,
+            } => {
+                self.alert_service
+                    .alert_ledger_chain_break(*successor_sequence, expected.clone(), actual.clone())
+                    .await
+            }
+            ChainViolation::HashConflict { stored_hash } => {
+                self.alert_service
+                    .alert_ledger_fork(ledger.sequence, stored_hash.clone(), ledger.hash.clone())
+                    .await
+            }
+        };
+        if let Err(e) = alert {
+            warn!(
+                "Failed to send chain alert for ledger {}: {}",
+                ledger.sequence, e
+            );
+        }
+
+        Ok(())
+    }
+
+    /// I'm decoding the ledger metadata XDR, returning None when it is missing or invalid
+    fn decode_ledger(&self, ledger: &RpcLedger) -> Option<DecodedLedger> {
+        let metadata_xdr = ledger.metadata_xdr.as_deref()?;
+        let passphrase = &self.rpc_client.network_config().network_passphrase;
+        match decode_ledger_close_meta(metadata_xdr, passphrase) {
+            Ok(decoded) => Some(decoded),
+            Err(e) => {
+                warn!(
+                    "Failed to decode metadata for ledger {}, storing header only: {:#}",
+                    ledger.sequence, e
+                );
+                None
+            }
+        }
+    }
+
+    /// I'm persisting a decoded transaction together with its operations
+    async fn persist_transaction(
+        &self,
+        db_tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+        ledger_sequence: u64,
+        tx: &DecodedTransaction,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO transactions (
+                hash, ledger_sequence, source_account, fee, operation_count, successful,
+                application_order, fee_account, max_fee, result_code, inner_transaction_hash
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (hash) DO NOTHING
+            "#,
+        )
+        .bind(&tx.hash)
+        .bind(ledger_sequence as i64)
+        .bind(&tx.source_account)
+        .bind(tx.fee_charged)
+        .bind(tx.operations.len() as i32)
+        .bind(tx.successful)
+        .bind(i64::from(tx.application_order))
+        .bind(&tx.fee_account)
+        .bind(tx.max_fee)
+        .bind(&tx.result_code)
+        .bind(&tx.inner_transaction_hash)
+        .execute(&mut **db_tx)
+        .await?;
+
+        for op in &tx.operations {
+            sqlx::query(
+                r#"
+                INSERT INTO ledger_operations (
+                    transaction_hash, application_order, ledger_sequence,
+                    operation_type, source_account, successful
+                )
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (transaction_hash, application_order) DO NOTHING
+                "#,
+            )
+            .bind(&tx.hash)
+            .bind(i64::from(op.application_order))
+            .bind(ledger_sequence as i64)
+            .bind(&op.operation_type)
+            .bind(&op.source_account)
+            .bind(tx.successful)
+            .execute(&mut **db_tx)
+            .await?;
+        }
+
         Ok(())
     }
 
     /// I'm persisting an extracted payment to the database
     async fn persist_payment(&self, payment: &ExtractedPayment) -> Result<()> {
+        let path = if payment.path.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&payment.path)?)
+        };
+
         sqlx::query(
             r#"
-            INSERT INTO ledger_payments (ledger_sequence, transaction_hash, operation_type, source_account, destination, asset_code, asset_issuer, amount)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO ledger_payments (
+                ledger_sequence, transaction_hash, operation_type, source_account, destination,
+                asset_type, asset_code, asset_issuer, amount,
+                source_asset_type, source_asset_code, source_asset_issuer, source_amount,
+                exchange_rate, path, path_hops, operation_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+            ON CONFLICT (operation_id) DO NOTHING
             "#,
         )
         .bind(payment.ledger_sequence as i64)
@@ -220,9 +568,18 @@ impl LedgerIngestionService {
         .bind(&payment.operation_type)
         .bind(&payment.source_account)
         .bind(&payment.destination)
+        .bind(&payment.asset_type)
         .bind(&payment.asset_code)
         .bind(&payment.asset_issuer)
         .bind(&payment.amount)
+        .bind(&payment.source_asset_type)
+        .bind(&payment.source_asset_code)
+        .bind(&payment.source_asset_issuer)
+        .bind(&payment.source_amount)
+        .bind(payment.exchange_rate)
+        .bind(path)
+        .bind(payment.path.len() as i64)
+        .bind(&payment.operation_id)
         .execute(&self.pool)
         .await?;
 
@@ -248,7 +605,7 @@ impl LedgerIngestionService {
     }
 
     /// I'm saving cursor and last ledger for restart safety
-    async fn save_cursor(&self, cursor: &str, last_ledger: Option<u64>) -> Result<()> {
+    async fn save_cursor(&self, cursor: Option<&str>, last_ledger: Option<u64>) -> Result<()> {
         let seq = last_ledger.unwrap_or(0) as i64;
         sqlx::query(
             r#"
@@ -0,0 +1,497 @@
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use stellar_xdr::curr::{
+    FeeBumpTransactionInnerTx, GeneralizedTransactionSet, Hash, InnerTransactionResultResult,
+    LedgerCloseMeta, LedgerHeaderHistoryEntry, Limits, MuxedAccount, Operation, Preconditions,
+    ReadXdr, Transaction, TransactionEnvelope, TransactionExt, TransactionPhase,
+    TransactionResultPair, TransactionResultResult, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, TransactionV0Envelope, TxSetComponent, WriteXdr,
+};
+
+/// Ledger decoded from the `LedgerCloseMeta` XDR returned by RPC `getLedgers`
+#[derive(Debug, Clone)]
+pub struct DecodedLedger {
+    pub sequence: u64,
+    pub hash: String,
+    pub previous_hash: String,
+    pub protocol_version: u32,
+    pub base_fee: u32,
+    pub base_reserve: u32,
+    pub max_tx_set_size: u32,
+    pub transactions: Vec<DecodedTransaction>,
+}
+
+/// Transaction applied in a ledger, combined with its result and fee charge
+#[derive(Debug, Clone)]
+pub struct DecodedTransaction {
+    pub hash: String,
+    /// 1-based position in the ledger's apply order
+    pub application_order: u32,
+    pub source_account: String,
+    /// Account paying the fee; differs from the source for fee-bump transactions
+    pub fee_account: String,
+    pub max_fee: i64,
+    pub fee_charged: i64,
+    pub successful: bool,
+    /// Horizon-style result code, e.g. `tx_success` or `tx_bad_seq`
+    pub result_code: String,
+    pub inner_transaction_hash: Option<String>,
+    pub operations: Vec<DecodedOperation>,
+}
+
+/// Operation contained in a decoded transaction
+#[derive(Debug, Clone)]
+pub struct DecodedOperation {
+    /// 1-based position within the transaction
+    pub application_order: u32,
+    /// Horizon-style operation type, e.g. `payment` or `path_payment_strict_send`
+    pub operation_type: String,
+    pub source_account: String,
+}
+
+impl DecodedLedger {
+    pub fn transaction_count(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn operation_count(&self) -> usize {
+        self.transactions.iter().map(|tx| tx.operations.len()).sum()
+    }
+}
+
+impl DecodedTransaction {
+    pub fn is_fee_bump(&self) -> bool {
+        self.inner_transaction_hash.is_some()
+    }
+}
+
+/// Decodes a base64 `LedgerCloseMeta` into ledger, transaction and operation rows.
+///
+/// The network passphrase is required because the transaction set only carries
+/// envelopes; hashes are recomputed to pair each envelope with its result.
+pub fn decode_ledger_close_meta(
+    metadata_xdr: &str,
+    network_passphrase: &str,
+) -> Result<DecodedLedger> {
+    let meta = parse_ledger_close_meta(metadata_xdr)?;
+    let transactions = applied_transactions(&meta, network_passphrase)?
+        .iter()
+        .enumerate()
+        .map(|(index, (envelope, pair))| decode_transaction(index as u32 + 1, envelope, pair))
+        .collect();
+
+    Ok(decode_header(close_meta_header(&meta), transactions))
+}
+
+pub(crate) fn parse_ledger_close_meta(metadata_xdr: &str) -> Result<LedgerCloseMeta> {
+    let bytes = BASE64
+        .decode(metadata_xdr.trim())
+        .context("LedgerCloseMeta is not valid base64")?;
+    LedgerCloseMeta::from_xdr(bytes, Limits::none()).context("Failed to decode LedgerCloseMeta XDR")
+}
+
+pub(crate) fn close_meta_header(meta: &LedgerCloseMeta) -> &LedgerHeaderHistoryEntry {
+    match meta {
+        LedgerCloseMeta::V0(v0) => &v0.ledger_header,
+        LedgerCloseMeta::V1(v1) => &v1.ledger_header,
+    }
+}
+
+/// Results in apply order, each paired with its envelope from the transaction set
+pub(crate) fn applied_transactions(
+    meta: &LedgerCloseMeta,
+    network_passphrase: &str,
+) -> Result<Vec<(TransactionEnvelope, TransactionResultPair)>> {
+    let (envelopes, tx_processing) = match meta {
+        LedgerCloseMeta::V0(v0) => (
+            v0.tx_set.txs.iter().cloned().collect::<Vec<_>>(),
+            v0.tx_processing.as_slice(),
+        ),
+        LedgerCloseMeta::V1(v1) => (
+            generalized_set_envelopes(&v1.tx_set),
+            v1.tx_processing.as_slice(),
+        ),
+    };
+
+    let network_id = Hash(Sha256::digest(network_passphrase.as_bytes()).into());
+    let mut envelopes_by_hash = HashMap::with_capacity(envelopes.len());
+    for envelope in envelopes {
+        let hash = transaction_hash(&envelope, &network_id)?;
+        envelopes_by_hash.insert(hash, envelope);
+    }
+
+    tx_processing
+        .iter()
+        .map(|result_meta| {
+            let pair = &result_meta.result;
+            let envelope = envelopes_by_hash
+                .get(&pair.transaction_hash.0)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "No envelope in transaction set for result {}",
+                        hex::encode(pair.transaction_hash.0)
+                    )
+                })?;
+            Ok((envelope.clone(), pair.clone()))
+        })
+        .collect()
+}
+
+fn decode_header(
+    entry: &LedgerHeaderHistoryEntry,
+    transactions: Vec<DecodedTransaction>,
+) -> DecodedLedger {
+    let header = &entry.header;
+    DecodedLedger {
+        sequence: u64::from(header.ledger_seq),
+        hash: hex::encode(entry.hash.0),
+        previous_hash: hex::encode(header.previous_ledger_hash.0),
+        protocol_version: header.ledger_version,
+        base_fee: header.base_fee,
+        base_reserve: header.base_reserve,
+        max_tx_set_size: header.max_tx_set_size,
+        transactions,
+    }
+}
+
+pub(crate) fn generalized_set_envelopes(
+    tx_set: &GeneralizedTransactionSet,
+) -> Vec<TransactionEnvelope> {
+    let GeneralizedTransactionSet::V1(set) = tx_set;
+    set.phases
+        .iter()
+        .flat_map(|phase| {
+            let TransactionPhase::V0(components) = phase;
+            components.iter()
+        })
+        .flat_map(|component| {
+            let TxSetComponent::TxsetCompTxsMaybeDiscountedFee(c) = component;
+            c.txs.iter().cloned()
+        })
+        .collect()
+}
+
+pub(crate) fn decode_transaction(
+    application_order: u32,
+    envelope: &TransactionEnvelope,
+    pair: &TransactionResultPair,
+) -> DecodedTransaction {
+    let result = &pair.result.result;
+    let successful = matches!(
+        result,
+        TransactionResultResult::TxSuccess(_) | TransactionResultResult::TxFeeBumpInnerSuccess(_)
+    );
+    let inner_transaction_hash = match result {
+        TransactionResultResult::TxFeeBumpInnerSuccess(inner)
+        | TransactionResultResult::TxFeeBumpInnerFailed(inner) => {
+            Some(hex::encode(inner.transaction_hash.0))
+        }
+        _ => None,
+    };
+    // Fee-bump outer results only say whether the inner transaction failed;
+    // the specific code lives on the inner result.
+    let result_code = match result {
+        TransactionResultResult::TxFeeBumpInnerFailed(inner)
+            if !matches!(
+                inner.result.result,
+                InnerTransactionResultResult::TxFailed(_)
+            ) =>
+        {
+            to_snake_case(inner.result.result.name())
+        }
+        _ => to_snake_case(result.name()),
+    };
+
+    let source_account = envelope_source_account(envelope);
+    let (fee_account, max_fee) = match envelope {
+        TransactionEnvelope::TxV0(env) => (source_account.clone(), i64::from(env.tx.fee)),
+        TransactionEnvelope::Tx(env) => (source_account.clone(), i64::from(env.tx.fee)),
+        TransactionEnvelope::TxFeeBump(env) => (env.tx.fee_source.to_string(), env.tx.fee),
+    };
+
+    let operations = envelope_operations(envelope)
+        .iter()
+        .enumerate()
+        .map(|(index, op)| decode_operation(index as u32 + 1, op, &source_account))
+        .collect();
+
+    DecodedTransaction {
+        hash: hex::encode(pair.transaction_hash.0),
+        application_order,
+        source_account,
+        fee_account,
+        max_fee,
+        fee_charged: pair.result.fee_charged,
+        successful,
+        result_code,
+        inner_transaction_hash,
+        operations,
+    }
+}
+
+/// Source of the transaction itself; the inner transaction's for fee bumps
+fn envelope_source_account(envelope: &TransactionEnvelope) -> String {
+    match envelope {
+        TransactionEnvelope::TxV0(env) => {
+            MuxedAccount::Ed25519(env.tx.source_account_ed25519.clone()).to_string()
+        }
+        TransactionEnvelope::Tx(env) => env.tx.source_account.to_string(),
+        TransactionEnvelope::TxFeeBump(env) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &env.tx.inner_tx;
+            inner.tx.source_account.to_string()
+        }
+    }
+}
+
+pub(crate) fn envelope_operations(envelope: &TransactionEnvelope) -> &[Operation] {
+    match envelope {
+        TransactionEnvelope::TxV0(env) => env.tx.operations.as_slice(),
+        TransactionEnvelope::Tx(env) => env.tx.operations.as_slice(),
+        TransactionEnvelope::TxFeeBump(env) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &env.tx.inner_tx;
+            inner.tx.operations.as_slice()
+        }
+    }
+}
+
+fn decode_operation(application_order: u32, op: &Operation, tx_source: &str) -> DecodedOperation {
+    DecodedOperation {
+        application_order,
+        operation_type: to_snake_case(op.body.name()),
+        source_account: op
+            .source_account
+            .as_ref()
+            .map_or_else(|| tx_source.to_string(), ToString::to_string),
+    }
+}
+
+/// Computes the network-specific transaction hash the same way stellar-core does
+pub(crate) fn transaction_hash(
+    envelope: &TransactionEnvelope,
+    network_id: &Hash,
+) -> Result<[u8; 32]> {
+    let tagged_transaction = match envelope {
+        TransactionEnvelope::TxV0(env) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(v0_as_v1(env))
+        }
+        TransactionEnvelope::Tx(env) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(env.tx.clone())
+        }
+        TransactionEnvelope::TxFeeBump(env) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(env.tx.clone())
+        }
+    };
+    let payload = TransactionSignaturePayload {
+        network_id: network_id.clone(),
+        tagged_transaction,
+    };
+    let bytes = payload
+        .to_xdr(Limits::none())
+        .context("Failed to encode transaction signature payload")?;
+    Ok(Sha256::digest(bytes).into())
+}
+
+/// Pre-protocol-13 envelopes are hashed as their V1 equivalent
+fn v0_as_v1(env: &TransactionV0Envelope) -> Transaction {
+    Transaction {
+        source_account: MuxedAccount::Ed25519(env.tx.source_account_ed25519.clone()),
+        fee: env.tx.fee,
+        seq_num: env.tx.seq_num.clone(),
+        cond: env
+            .tx
+            .time_bounds
+            .clone()
+            .map_or(Preconditions::None, Preconditions::Time),
+        memo: env.tx.memo.clone(),
+        operations: env.tx.operations.clone(),
+        ext: TransactionExt::V0,
+    }
+}
+
+/// Turns XDR variant names (`PathPaymentStrictSend`, `TxBadSeq`) into the
+/// snake_case identifiers Horizon uses (`path_payment_strict_send`, `tx_bad_seq`)
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(ch.to_ascii_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{
+        Asset, ExtensionPoint, LedgerCloseMetaV0, LedgerEntryChanges, LedgerHeader,
+        LedgerHeaderExt, LedgerHeaderHistoryEntryExt, Memo, OperationBody, OperationResult,
+        PaymentOp, SequenceNumber, StellarValue, StellarValueExt, TimePoint, TransactionMeta,
+        TransactionMetaV3, TransactionResult, TransactionResultExt, TransactionResultMeta,
+        TransactionSet, TransactionV1Envelope, Uint256, UpgradeType, VecM,
+    };
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn payment_envelope(seed: u8) -> TransactionEnvelope {
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::Payment(PaymentOp {
+                destination: MuxedAccount::Ed25519(Uint256([seed + 1; 32])),
+                asset: Asset::Native,
+                amount: 10_000_000,
+            }),
+        };
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([seed; 32])),
+                fee: 200,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: vec![op].try_into().unwrap(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        })
+    }
+
+    fn result_meta(hash: [u8; 32], successful: bool) -> TransactionResultMeta {
+        let ops: VecM<OperationResult> = VecM::default();
+        TransactionResultMeta {
+            result: TransactionResultPair {
+                transaction_hash: Hash(hash),
+                result: TransactionResult {
+                    fee_charged: 100,
+                    result: if successful {
+                        TransactionResultResult::TxSuccess(ops)
+                    } else {
+                        TransactionResultResult::TxBadSeq
+                    },
+                    ext: TransactionResultExt::V0,
+                },
+            },
+            fee_processing: LedgerEntryChanges::default(),
+            tx_apply_processing: TransactionMeta::V3(TransactionMetaV3 {
+                ext: ExtensionPoint::V0,
+                tx_changes_before: LedgerEntryChanges::default(),
+                operations: VecM::default(),
+                tx_changes_after: LedgerEntryChanges::default(),
+                soroban_meta: None,
+            }),
+        }
+    }
+
+    fn encode_meta(
+        envelopes: Vec<TransactionEnvelope>,
+        results: Vec<TransactionResultMeta>,
+    ) -> String {
+        let upgrades: VecM<UpgradeType, 6> = VecM::default();
+        let meta = LedgerCloseMeta::V0(LedgerCloseMetaV0 {
+            ledger_header: LedgerHeaderHistoryEntry {
+                hash: Hash([7; 32]),
+                header: LedgerHeader {
+                    ledger_version: 21,
+                    previous_ledger_hash: Hash([6; 32]),
+                    scp_value: StellarValue {
+                        tx_set_hash: Hash([0; 32]),
+                        close_time: TimePoint(1_734_032_457),
+                        upgrades,
+                        ext: StellarValueExt::Basic,
+                    },
+                    tx_set_result_hash: Hash([0; 32]),
+                    bucket_list_hash: Hash([0; 32]),
+                    ledger_seq: 1000,
+                    total_coins: 0,
+                    fee_pool: 0,
+                    inflation_seq: 0,
+                    id_pool: 0,
+                    base_fee: 100,
+                    base_reserve: 5_000_000,
+                    max_tx_set_size: 1000,
+                    skip_list: [Hash([0; 32]), Hash([0; 32]), Hash([0; 32]), Hash([0; 32])],
+                    ext: LedgerHeaderExt::V0,
+                },
+                ext: LedgerHeaderHistoryEntryExt::V0,
+            },
+            tx_set: TransactionSet {
+                previous_ledger_hash: Hash([6; 32]),
+                txs: envelopes.try_into().unwrap(),
+            },
+            tx_processing: results.try_into().unwrap(),
+            upgrades_processing: VecM::default(),
+            scp_info: VecM::default(),
+        });
+        BASE64.encode(meta.to_xdr(Limits::none()).unwrap())
+    }
+
+    #[test]
+    fn test_decodes_transactions_in_apply_order() {
+        let network_id = Hash(Sha256::digest(PASSPHRASE.as_bytes()).into());
+        let first = payment_envelope(1);
+        let second = payment_envelope(2);
+        let first_hash = transaction_hash(&first, &network_id).unwrap();
+        let second_hash = transaction_hash(&second, &network_id).unwrap();
+
+        // Apply order differs from the order of the transaction set
+        let xdr = encode_meta(
+            vec![first, second],
+            vec![
+                result_meta(second_hash, false),
+                result_meta(first_hash, true),
+            ],
+        );
+        let ledger = decode_ledger_close_meta(&xdr, PASSPHRASE).unwrap();
+
+        assert_eq!(ledger.sequence, 1000);
+        assert_eq!(ledger.hash, hex::encode([7; 32]));
+        assert_eq!(ledger.previous_hash, hex::encode([6; 32]));
+        assert_eq!(ledger.transaction_count(), 2);
+        assert_eq!(ledger.operation_count(), 2);
+
+        let failed = &ledger.transactions[0];
+        assert_eq!(failed.hash, hex::encode(second_hash));
+        assert!(!failed.successful);
+        assert_eq!(failed.result_code, "tx_bad_seq");
+        assert_eq!(
+            failed.source_account,
+            MuxedAccount::Ed25519(Uint256([2; 32])).to_string()
+        );
+
+        let succeeded = &ledger.transactions[1];
+        assert_eq!(succeeded.application_order, 2);
+        assert!(succeeded.successful);
+        assert_eq!(succeeded.fee_charged, 100);
+        assert_eq!(succeeded.max_fee, 200);
+        assert_eq!(succeeded.operations[0].operation_type, "payment");
+        assert_eq!(
+            succeeded.operations[0].source_account,
+            succeeded.source_account
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_metadata() {
+        assert!(decode_ledger_close_meta("mock_metadata", PASSPHRASE).is_err());
+    }
+
+    #[test]
+    fn test_snake_case_matches_horizon_names() {
+        assert_eq!(
+            to_snake_case("PathPaymentStrictSend"),
+            "path_payment_strict_send"
+        );
+        assert_eq!(
+            to_snake_case("TxFeeBumpInnerSuccess"),
+            "tx_fee_bump_inner_success"
+        );
+        assert_eq!(to_snake_case("SetTrustLineFlags"), "set_trust_line_flags");
+    }
+}
@@ -1,5 +1,11 @@
 // I'm exporting the ledger ingestion module as required by issue #2
+pub mod archive_bundle;
+pub mod backfill;
+pub mod chain;
+pub mod history_archive;
 pub mod ledger;
+pub mod ledger_meta;
+pub mod processor;
 
 use anyhow::{Context, Result};
 use serde::Serialize;
@@ -60,7 +66,6 @@ impl DataIngestionService {
         let mut successful = 0;
         let failed = 0;
         let mut total_volume = 0.0;
-        let settlement_times = Vec::new(); // Removed mut as it's never pushed to
 
         for payment in &payments {
             let amount: f64 = payment.get_amount().parse().unwrap_or(0.0);
@@ -78,12 +83,6 @@ impl DataIngestionService {
 
         let reliability_score = self.calculate_reliability_score(success_rate, failed as i64);
 
-        let avg_settlement_time = if !settlement_times.is_empty() {
-            settlement_times.iter().sum::<i32>() / settlement_times.len() as i32
-        } else {
-            1000
-        };
-
         let status = if success_rate >= 98.0 {
             "green"
         } else if success_rate >= 95.0 {
@@ -99,7 +98,8 @@ impl DataIngestionService {
                 successful_transactions: successful as i64,
                 failed_transactions: failed as i64,
                 total_volume_usd: total_volume,
-                avg_settlement_time_ms: avg_settlement_time,
+                // Measured by the anchor transfer tracker from SEP-24/31 status polling
+                avg_settlement_time_ms: None,
                 reliability_score,
                 status: status.to_string(),
             })
@@ -0,0 +1,454 @@
+//! Pluggable per-ledger processors.
+//!
+//! The ingestion loop fetches each ledger's Horizon data once into a
+//! [`LedgerBundle`] and hands it to every enabled [`LedgerProcessor`] in
+//! registration order. Only the parts some enabled processor requires are
+//! fetched. A failing processor is logged and counted in metrics; it does not
+//! stop the processors after it or the ingestion of the ledger. A ledger whose
+//! parts could not all be fetched is not stored and is retried on the next run.
+//! History archive ledgers get bundles built from their own XDR instead (see
+//! [`crate::ingestion::archive_bundle`]).
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, warn};
+
+use crate::observability::metrics;
+use crate::rpc::{HorizonEffect, HorizonOperation, HorizonTransaction, Payment, StellarRpcClient};
+
+/// Horizon data that can be fetched for a ledger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundlePart {
+    Payments,
+    Transactions,
+    Operations,
+    Effects,
+}
+
+impl BundlePart {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Payments => "payments",
+            Self::Transactions => "transactions",
+            Self::Operations => "operations",
+            Self::Effects => "effects",
+        }
+    }
+}
+
+/// Everything fetched for one ledger, shared by all processors
+#[derive(Debug, Clone, Default)]
+pub struct LedgerBundle {
+    pub sequence: u64,
+    pub payments: Vec<Payment>,
+    pub transactions: Vec<HorizonTransaction>,
+    pub operations: Vec<HorizonOperation>,
+    pub effects: Vec<HorizonEffect>,
+    /// Parts that were requested but could not be fetched
+    pub missing: Vec<BundlePart>,
+    /// Parts the bundle's source does not have, such as effects for ledgers
+    /// read from a history archive; unlike `missing` these are not retried
+    pub unavailable: Vec<BundlePart>,
+}
+
+impl LedgerBundle {
+    pub fn new(sequence: u64) -> Self {
+        Self {
+            sequence,
+            ..Self::default()
+        }
+    }
+
+    /// Fetch the requested parts concurrently; failed parts are left empty and
+    /// recorded in `missing`
+    pub async fn fetch(rpc_client: &StellarRpcClient, sequence: u64, parts: &[BundlePart]) -> Self {
+        let wants = |part| parts.contains(&part);
+
+        let (payments, transactions, operations, effects) = tokio::join!(
+            async {
+                if wants(BundlePart::Payments) {
+                    Some(rpc_client.fetch_payments_for_ledger(sequence).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                if wants(BundlePart::Transactions) {
+                    Some(rpc_client.fetch_transactions_for_ledger(sequence).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                if wants(BundlePart::Operations) {
+                    Some(rpc_client.fetch_operations_for_ledger(sequence).await)
+                } else {
+                    None
+                }
+            },
+            async {
+                if wants(BundlePart::Effects) {
+                    Some(rpc_client.fetch_effects_for_ledger(sequence).await)
+                } else {
+                    None
+                }
+            },
+        );
+
+        let mut bundle = Self::new(sequence);
+        bundle.payments = bundle.take_part(BundlePart::Payments, payments);
+        bundle.transactions = bundle.take_part(BundlePart::Transactions, transactions);
+        bundle.operations = bundle.take_part(BundlePart::Operations, operations);
+        bundle.effects = bundle.take_part(BundlePart::Effects, effects);
+        bundle
+    }
+
+    fn take_part<T, E: std::fmt::Display>(
+        &mut self,
+        part: BundlePart,
+        fetched: Option<Result<Vec<T>, E>>,
+    ) -> Vec<T> {
+        match fetched {
+            Some(Ok(records)) => records,
+            Some(Err(e)) => {
+                warn!(
+                    "Failed to fetch {} for ledger {}: {}",
+                    part.as_str(),
+                    self.sequence,
+                    e
+                );
+                self.missing.push(part);
+                Vec::new()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    pub fn has(&self, part: BundlePart) -> bool {
+        !self.missing.contains(&part) && !self.unavailable.contains(&part)
+    }
+
+    /// Effects produced by one operation
+    pub fn effects_for_operation<'a>(
+        &'a self,
+        operation_id: &'a str,
+    ) -> impl Iterator<Item = &'a HorizonEffect> + 'a {
+        self.effects
+            .iter()
+            .filter(move |effect| effect.operation_id() == Some(operation_id))
+    }
+}
+
+/// Extracts data from a fetched ledger
+#[async_trait]
+pub trait LedgerProcessor: Send + Sync {
+    /// Stable name used in configuration and metrics
+    fn name(&self) -> &'static str;
+
+    /// Bundle parts this processor reads
+    fn requires(&self) -> &'static [BundlePart];
+
+    /// Process one ledger; returns the number of records extracted
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ProcessorResult {
+    Processed {
+        records: u64,
+    },
+    /// A required bundle part could not be fetched
+    Skipped {
+        missing: BundlePart,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl ProcessorResult {
+    fn status(&self) -> &'static str {
+        match self {
+            Self::Processed { .. } => "success",
+            Self::Skipped { .. } => "skipped",
+            Self::Failed { .. } => "failure",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessorOutcome {
+    pub processor: &'static str,
+    #[serde(flatten)]
+    pub result: ProcessorResult,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessorStatus {
+    pub name: &'static str,
+    pub enabled: bool,
+    pub requires: &'static [BundlePart],
+}
+
+struct RegisteredProcessor {
+    processor: Arc<dyn LedgerProcessor>,
+    enabled: AtomicBool,
+}
+
+/// Ordered set of ledger processors
+#[derive(Default)]
+pub struct ProcessorRegistry {
+    processors: Vec<RegisteredProcessor>,
+    /// Names registered disabled
+    disabled: Vec<String>,
+}
+
+impl ProcessorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry that registers the processors named in `LEDGER_PROCESSORS_DISABLED`
+    /// (comma separated) as disabled
+    pub fn from_env() -> Self {
+        let disabled = std::env::var("LEDGER_PROCESSORS_DISABLED").unwrap_or_default();
+        Self {
+            processors: Vec::new(),
+            disabled: disabled
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect(),
+        }
+    }
+
+    /// Append a processor; it runs after those already registered. A processor
+    /// registered under an existing name replaces it in place.
+    pub fn register(&mut self, processor: Arc<dyn LedgerProcessor>) {
+        let enabled = !self.disabled.iter().any(|name| name == processor.name());
+        let entry = RegisteredProcessor {
+            processor,
+            enabled: AtomicBool::new(enabled),
+        };
+        match self
+            .processors
+            .iter_mut()
+            .find(|p| p.processor.name() == entry.processor.name())
+        {
+            Some(existing) => *existing = entry,
+            None => self.processors.push(entry),
+        }
+    }
+
+    /// Returns false if no processor has this name
+    pub fn set_enabled(&self, name: &str, enabled: bool) -> bool {
+        match self.processors.iter().find(|p| p.processor.name() == name) {
+            Some(entry) => {
+                entry.enabled.store(enabled, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn statuses(&self) -> Vec<ProcessorStatus> {
+        self.processors
+            .iter()
+            .map(|p| ProcessorStatus {
+                name: p.processor.name(),
+                enabled: p.enabled.load(Ordering::Relaxed),
+                requires: p.processor.requires(),
+            })
+            .collect()
+    }
+
+    fn enabled(&self) -> impl Iterator<Item = &Arc<dyn LedgerProcessor>> {
+        self.processors
+            .iter()
+            .filter(|p| p.enabled.load(Ordering::Relaxed))
+            .map(|p| &p.processor)
+    }
+
+    /// Bundle parts needed by the enabled processors
+    pub fn required_parts(&self) -> Vec<BundlePart> {
+        let mut parts = Vec::new();
+        for part in self.enabled().flat_map(|p| p.requires()) {
+            if !parts.contains(part) {
+                parts.push(*part);
+            }
+        }
+        parts
+    }
+
+    /// Run every enabled processor in order, isolating failures
+    pub async fn run(&self, bundle: &LedgerBundle) -> Vec<ProcessorOutcome> {
+        let mut outcomes = Vec::new();
+
+        for processor in self.enabled() {
+            let started = Instant::now();
+            let result = match processor.requires().iter().find(|part| !bundle.has(**part)) {
+                Some(missing) => ProcessorResult::Skipped { missing: *missing },
+                None => match processor.process(bundle).await {
+                    Ok(records) => ProcessorResult::Processed { records },
+                    Err(e) => {
+                        warn!(
+                            "Ledger processor {} failed on ledger {}: {:#}",
+                            processor.name(),
+                            bundle.sequence,
+                            e
+                        );
+                        ProcessorResult::Failed {
+                            error: e.to_string(),
+                        }
+                    }
+                },
+            };
+            debug!(
+                "Ledger processor {} on ledger {}: {:?}",
+                processor.name(),
+                bundle.sequence,
+                result
+            );
+
+            metrics::record_ledger_processor(
+                processor.name(),
+                result.status(),
+                started.elapsed().as_secs_f64(),
+            );
+            outcomes.push(ProcessorOutcome {
+                processor: processor.name(),
+                result,
+            });
+        }
+
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU64;
+
+    struct Counting {
+        name: &'static str,
+        requires: &'static [BundlePart],
+        fail: bool,
+        calls: AtomicU64,
+    }
+
+    impl Counting {
+        fn new(name: &'static str, requires: &'static [BundlePart], fail: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                requires,
+                fail,
+                calls: AtomicU64::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl LedgerProcessor for Counting {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn requires(&self) -> &'static [BundlePart] {
+            self.requires
+        }
+
+        async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            if self.fail {
+                anyhow::bail!("boom");
+            }
+            Ok(bundle.operations.len() as u64)
+        }
+    }
+
+    #[tokio::test]
+    async fn failing_processor_does_not_stop_the_others() {
+        let failing = Counting::new("failing", &[BundlePart::Operations], true);
+        let counting = Counting::new("counting", &[BundlePart::Operations], false);
+        let mut registry = ProcessorRegistry::new();
+        registry.register(failing.clone());
+        registry.register(counting.clone());
+
+        let outcomes = registry.run(&LedgerBundle::new(7)).await;
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].processor, "failing");
+        assert!(matches!(outcomes[0].result, ProcessorResult::Failed { .. }));
+        assert_eq!(
+            outcomes[1].result,
+            ProcessorResult::Processed { records: 0 }
+        );
+        assert_eq!(counting.calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn disabled_and_starved_processors_do_not_run() {
+        let ops = Counting::new("ops", &[BundlePart::Operations], false);
+        let txs = Counting::new("txs", &[BundlePart::Transactions], false);
+        let effects = Counting::new(
+            "effects",
+            &[BundlePart::Operations, BundlePart::Effects],
+            false,
+        );
+        let mut registry = ProcessorRegistry {
+            processors: Vec::new(),
+            disabled: vec!["txs".to_string()],
+        };
+        registry.register(ops.clone());
+        registry.register(txs.clone());
+        registry.register(effects.clone());
+
+        assert!(!registry.statuses()[1].enabled);
+        assert!(registry.set_enabled("txs", false));
+        assert!(!registry.set_enabled("unknown", false));
+        assert_eq!(
+            registry.required_parts(),
+            vec![BundlePart::Operations, BundlePart::Effects]
+        );
+
+        let mut bundle = LedgerBundle::new(7);
+        bundle.missing.push(BundlePart::Effects);
+        let outcomes = registry.run(&bundle).await;
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(
+            outcomes[1].result,
+            ProcessorResult::Skipped {
+                missing: BundlePart::Effects
+            }
+        );
+        assert_eq!(ops.calls.load(Ordering::Relaxed), 1);
+        assert_eq!(txs.calls.load(Ordering::Relaxed), 0);
+        assert_eq!(effects.calls.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn mock_bundle_links_effects_to_operations() {
+        let rpc_client = StellarRpcClient::new_with_defaults(true);
+        let bundle = LedgerBundle::fetch(
+            &rpc_client,
+            100,
+            &[BundlePart::Operations, BundlePart::Effects],
+        )
+        .await;
+
+        assert!(bundle.missing.is_empty());
+        assert!(bundle.payments.is_empty());
+        assert_eq!(bundle.operations.len(), 3);
+        assert_eq!(bundle.effects_for_operation("op_100_0").count(), 1);
+        assert_eq!(bundle.effects_for_operation("op_100_1").count(), 0);
+        assert_eq!(bundle.effects_for_operation("op_100_2").count(), 2);
+    }
+}
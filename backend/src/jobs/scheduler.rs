@@ -8,6 +8,8 @@ use crate::cache::CacheManager;
 use crate::database::Database;
 use crate::ingestion::DataIngestionService;
 use crate::rpc::StellarRpcClient;
+use crate::services::asset_supply::AssetSupplyTracker;
+use crate::services::order_book_snapshot::OrderBookSnapshotService;
 use crate::services::price_feed::PriceFeedClient;
 
 #[derive(Clone)]
@@ -87,6 +89,8 @@ impl JobScheduler {
         rpc: Arc<StellarRpcClient>,
         ingestion: Arc<DataIngestionService>,
         price_feed: Arc<PriceFeedClient>,
+        order_books: Arc<OrderBookSnapshotService>,
+        asset_supply: Arc<AssetSupplyTracker>,
     ) -> Self {
         let mut scheduler = Self::new();
 
@@ -130,6 +134,26 @@ impl JobScheduler {
             })
         });
 
+        // Order book snapshot job
+        let config = JobConfig::from_env("order-book-snapshot", 300);
+        scheduler.add_job(config, move || {
+            let order_books = Arc::clone(&order_books);
+            Box::pin(async move {
+                order_books.snapshot_all().await?;
+                Ok(())
+            })
+        });
+
+        // Asset supply snapshot job
+        let config = JobConfig::from_env("asset-supply-snapshot", 900);
+        scheduler.add_job(config, move || {
+            let asset_supply = Arc::clone(&asset_supply);
+            Box::pin(async move {
+                asset_supply.snapshot_supply().await?;
+                Ok(())
+            })
+        });
+
         // Cache cleanup job
         let config = JobConfig::from_env("cache-cleanup", 3600);
         let cache_clone = Arc::clone(&cache);
@@ -44,6 +44,7 @@ pub mod shutdown;
 pub mod snapshot;
 pub mod snapshot_handlers;
 pub mod state;
+pub mod utils;
 pub mod validation;
 pub mod vault;
 pub mod webhooks;
@@ -6,7 +6,7 @@ use axum::response::{Html, IntoResponse};
 use axum::{
     http::Method,
     routing::{get, post, put},
-    Router,
+    Extension, Router,
 };
 use dotenvy::dotenv;
 use std::sync::Arc;
@@ -20,17 +20,33 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use stellar_insights_backend::alerts::AlertManager;
 use stellar_insights_backend::api::account_merges;
+use stellar_insights_backend::api::accounts;
+use stellar_insights_backend::api::anchor_transfers;
 use stellar_insights_backend::api::anchors_cached::get_anchors;
+use stellar_insights_backend::api::anomalies;
 use stellar_insights_backend::api::api_analytics;
 use stellar_insights_backend::api::api_keys;
+use stellar_insights_backend::api::asset_authorizations;
+use stellar_insights_backend::api::asset_supply;
 use stellar_insights_backend::api::asset_verification;
+use stellar_insights_backend::api::backfill;
 use stellar_insights_backend::api::cache_stats;
+use stellar_insights_backend::api::candles;
+use stellar_insights_backend::api::claimable_balances;
+use stellar_insights_backend::api::corridor_forecast;
 use stellar_insights_backend::api::corridors_cached::{get_corridor_detail, list_corridors};
 use stellar_insights_backend::api::cost_calculator;
 use stellar_insights_backend::api::fee_bump;
+use stellar_insights_backend::api::fee_market;
+use stellar_insights_backend::api::large_payments;
 use stellar_insights_backend::api::liquidity_pools;
 use stellar_insights_backend::api::metrics_cached;
 use stellar_insights_backend::api::oauth;
+use stellar_insights_backend::api::order_books;
+use stellar_insights_backend::api::prediction;
+use stellar_insights_backend::api::sep24_proxy;
+use stellar_insights_backend::api::sep31_proxy;
+use stellar_insights_backend::api::sponsorships;
 use stellar_insights_backend::api::verification_rewards;
 use stellar_insights_backend::api::webhooks;
 use stellar_insights_backend::auth::AuthService;
@@ -42,12 +58,15 @@ use stellar_insights_backend::elk_health;
 // use stellar_insights_backend::graphql::{build_schema, AppSchema};
 // use stellar_insights_backend::gdpr::{GdprService, handlers as gdpr_handlers};
 use stellar_insights_backend::handlers::*;
-use stellar_insights_backend::ingestion::ledger::LedgerIngestionService;
+use stellar_insights_backend::ingestion::backfill::BackfillService;
+use stellar_insights_backend::ingestion::ledger::{LedgerIngestionService, LedgerProcessors};
 use stellar_insights_backend::ingestion::DataIngestionService;
 use stellar_insights_backend::ip_whitelist_middleware::{
     ip_whitelist_middleware, IpWhitelistConfig,
 };
-use stellar_insights_backend::jobs::JobScheduler;
+use stellar_insights_backend::jobs::{JobConfig, JobScheduler};
+use stellar_insights_backend::ml::MLService;
+use stellar_insights_backend::ml_handlers;
 use stellar_insights_backend::monitor::CorridorMonitor;
 use stellar_insights_backend::network::NetworkConfig;
 use stellar_insights_backend::observability::{metrics as obs_metrics, tracing as obs_tracing};
@@ -56,16 +75,31 @@ use stellar_insights_backend::rate_limit::{
     rate_limit_middleware, ClientRateLimits, RateLimitConfig, RateLimiter,
 };
 use stellar_insights_backend::request_id::request_id_middleware;
-use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::rpc::{Cassette, HorizonStream, StellarRpcClient};
 use stellar_insights_backend::rpc_handlers;
-use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use stellar_insights_backend::services::account_profile::AccountProfileService;
+use stellar_insights_backend::services::aggregation::{AggregationConfig, AggregationService};
+use stellar_insights_backend::services::alert_service::AlertService;
 use stellar_insights_backend::services::anchor_monitor::AnchorMonitor;
-use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+use stellar_insights_backend::services::anchor_transfer_tracker::{
+    AnchorTransferConfig, AnchorTransferTracker,
+};
+use stellar_insights_backend::services::anomaly_detector::{AnomalyConfig, AnomalyDetector};
+use stellar_insights_backend::services::corridor_forecast::{CorridorForecaster, ForecastConfig};
+use stellar_insights_backend::services::corridor_health::{
+    CorridorHealthScorer, HealthScoreConfig,
+};
+use stellar_insights_backend::services::indexing::IndexingService;
 use stellar_insights_backend::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
+use stellar_insights_backend::services::metrics_overview::MetricsOverviewService;
+use stellar_insights_backend::services::order_book_snapshot::{
+    OrderBookSnapshotConfig, OrderBookSnapshotService,
+};
 use stellar_insights_backend::services::price_feed::{
     default_asset_mapping, PriceFeedClient, PriceFeedConfig,
 };
 use stellar_insights_backend::services::realtime_broadcaster::RealtimeBroadcaster;
+use stellar_insights_backend::services::trade_aggregator::TradeAggregator;
 use stellar_insights_backend::services::trustline_analyzer::TrustlineAnalyzer;
 use stellar_insights_backend::services::webhook_dispatcher::WebhookDispatcher;
 use stellar_insights_backend::shutdown::{
@@ -164,19 +198,31 @@ async fn main() -> Result<()> {
         mock_mode
     );
 
-    let rpc_client = if mock_mode {
-        Arc::new(StellarRpcClient::new_with_network(
-            network_config.network,
-            true,
-        ))
+    let mut rpc_client = if mock_mode {
+        StellarRpcClient::new_with_network(network_config.network, true)
     } else {
-        Arc::new(StellarRpcClient::new(
+        StellarRpcClient::new(
             network_config.rpc_url.clone(),
             network_config.horizon_url.clone(),
             false,
-        ))
+        )
     };
 
+    // Record or replay RPC/Horizon traffic (RPC_CASSETTE_MODE); ignored in mock mode
+    if let Some(cassette) = Cassette::from_env()? {
+        if mock_mode {
+            tracing::warn!("RPC_CASSETTE_MODE is ignored while RPC_MOCK_MODE is enabled");
+        } else {
+            tracing::info!(
+                "RPC cassette {:?} mode using {}",
+                cassette.mode(),
+                cassette.path().display()
+            );
+            rpc_client = rpc_client.with_cassette(cassette);
+        }
+    }
+    let rpc_client = Arc::new(rpc_client);
+
     // Initialize WebSocket state
     let ws_state = Arc::new(WsState::new());
     tracing::info!("WebSocket state initialized");
@@ -187,13 +233,43 @@ async fn main() -> Result<()> {
         Arc::clone(&db),
     ));
 
-    // Initialize Fee Bump Tracker Service
-    let fee_bump_tracker = Arc::new(FeeBumpTrackerService::new(pool.clone()));
+    // Initialize Alert Service (shared by the trackers that raise alerts)
+    let alert_service = Arc::new(AlertService::new());
 
-    // Initialize Account Merge Detector Service
-    let account_merge_detector = Arc::new(AccountMergeDetector::new(
+    // Initialize Price Feed Client
+    let price_feed_config = PriceFeedConfig::from_env();
+    let asset_mapping = default_asset_mapping();
+    let price_feed = Arc::new(PriceFeedClient::new(price_feed_config, asset_mapping));
+    tracing::info!("Price feed client initialized");
+
+    // Initialize the ledger processors (fee bumps, account merges, claimable balances,
+    // sponsored reserves, fee market, asset supply, asset authorizations and large
+    // payments); archive ingestion builds the same set
+    let ledger_processors = LedgerProcessors::from_env(
         pool.clone(),
         Arc::clone(&rpc_client),
+        Arc::clone(&alert_service),
+        Arc::clone(&price_feed),
+        Arc::clone(&ws_state),
+    );
+    let LedgerProcessors {
+        fee_bump_tracker,
+        account_merge_detector,
+        claimable_balance_tracker,
+        sponsorship_tracker,
+        fee_market_service,
+        asset_supply_tracker,
+        asset_authorization_tracker,
+        large_payment_detector,
+        ..
+    } = ledger_processors.clone();
+
+    // Initialize Account Profile Service (combines the trackers above)
+    let account_profiles = Arc::new(AccountProfileService::new(
+        Arc::clone(&rpc_client),
+        Arc::clone(&account_merge_detector),
+        Arc::clone(&fee_bump_tracker),
+        Arc::clone(&sponsorship_tracker),
     ));
 
     // Initialize Liquidity Pool Analyzer
@@ -202,11 +278,62 @@ async fn main() -> Result<()> {
         Arc::clone(&rpc_client),
     ));
 
-    // Initialize Price Feed Client
-    let price_feed_config = PriceFeedConfig::from_env();
-    let asset_mapping = default_asset_mapping();
-    let price_feed = Arc::new(PriceFeedClient::new(price_feed_config, asset_mapping));
-    tracing::info!("Price feed client initialized");
+    // Initialize Order Book Snapshot Service
+    let order_book_snapshots = Arc::new(OrderBookSnapshotService::new(
+        pool.clone(),
+        Arc::clone(&rpc_client),
+        OrderBookSnapshotConfig::from_env(),
+    ));
+
+    // Initialize Trade Aggregator (OHLCV candles)
+    let trade_aggregator = Arc::new(TradeAggregator::new(pool.clone()));
+
+    // Initialize Anchor Transfer Tracker (SEP-24/31 lifecycles and settlement times)
+    let anchor_transfer_tracker = Arc::new(AnchorTransferTracker::new(
+        Arc::clone(&db),
+        AnchorTransferConfig::from_env(),
+    ));
+
+    // Initialize Aggregation Service (hourly payment rollup into corridor_metrics_hourly,
+    // which the ML model, anomaly detector, forecasts and overview read)
+    let aggregation_service = Arc::new(AggregationService::new(
+        Arc::clone(&db),
+        AggregationConfig::default(),
+    ));
+
+    // Initialize Anomaly Detector (seasonal and EWMA baselines over hourly metrics)
+    let anomaly_detector = Arc::new(AnomalyDetector::new(
+        Arc::clone(&db),
+        Arc::clone(&ws_state),
+        AnomalyConfig::from_env(),
+    ));
+
+    // Initialize Corridor Forecaster (Holt-Winters models over hourly corridor metrics)
+    let corridor_forecaster = Arc::new(CorridorForecaster::new(
+        Arc::clone(&db),
+        ForecastConfig::from_env(),
+    ));
+
+    // Initialize Corridor Health Scorer (configurable weights, per-component breakdown)
+    let corridor_health_scorer = Arc::new(CorridorHealthScorer::new(
+        Arc::clone(&db),
+        HealthScoreConfig::from_env(),
+    ));
+
+    // Initialize Metrics Overview Service (dashboard KPIs for the configured network)
+    let metrics_overview_service = Arc::new(MetricsOverviewService::new(
+        Arc::clone(&db),
+        rpc_client.network_config().network,
+    ));
+
+    // Initialize ML Service (payment success model, starts on the active stored version)
+    let mut ml = MLService::new(Database::new(pool.clone()))?;
+    match ml.load_active_model().await {
+        Ok(Some(model)) => tracing::info!("Loaded payment success model {}", model.version),
+        Ok(None) => tracing::info!("No trained payment success model yet, using baseline"),
+        Err(e) => tracing::warn!("Failed to load payment success model: {}", e),
+    }
+    let ml_service = Arc::new(tokio::sync::RwLock::new(ml));
 
     // Initialize Trustline Analyzer
     let trustline_analyzer = Arc::new(TrustlineAnalyzer::new(
@@ -215,10 +342,16 @@ async fn main() -> Result<()> {
     ));
 
     // Initialize Ledger Ingestion Service
-    let ledger_ingestion_service = Arc::new(LedgerIngestionService::new(
+    let ledger_ingestion_service = Arc::new(LedgerIngestionService::with_processors(
+        Arc::clone(&rpc_client),
+        &ledger_processors,
+        pool.clone(),
+    ));
+
+    // Initialize Backfill Service (sharded historical ingestion)
+    let backfill_service = Arc::new(BackfillService::new(
+        Arc::clone(&ledger_ingestion_service),
         Arc::clone(&rpc_client),
-        Arc::clone(&fee_bump_tracker),
-        Arc::clone(&account_merge_detector),
         pool.clone(),
     ));
 
@@ -259,6 +392,7 @@ async fn main() -> Result<()> {
         Arc::clone(&db),
         Arc::clone(&ws_state),
         Arc::clone(&ingestion_service),
+        Arc::clone(&corridor_health_scorer),
     );
 
     // Create cached state tuple for cached API handlers
@@ -387,22 +521,6 @@ async fn main() -> Result<()> {
     // let gdpr_service = Arc::new(GdprService::new(pool.clone()));
     // tracing::info!("GDPR service initialized");
 
-    // ML Retraining task (commented out)
-    /*
-    let ml_service_clone = ml_service.clone();
-    tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(7 * 24 * 3600)); // 7 days
-        loop {
-            interval.tick().await;
-            if let Ok(mut service) = ml_service_clone.try_write() {
-                if let Err(e) = service.retrain_weekly().await {
-                    tracing::error!("Weekly ML retraining failed: {}", e);
-                }
-            }
-        }
-    });
-    */
-
     // Ledger ingestion task
     let ledger_ingestion_clone = Arc::clone(&ledger_ingestion_service);
     let shutdown_rx2 = shutdown_coordinator.subscribe();
@@ -437,6 +555,62 @@ async fn main() -> Result<()> {
     });
     background_tasks.push(task);
 
+    // Horizon payment streaming task (opt-in; complements polling ingestion)
+    let streaming_enabled = std::env::var("HORIZON_STREAMING_ENABLED")
+        .ok()
+        .and_then(|v| v.parse::<bool>().ok())
+        .unwrap_or(false);
+    if streaming_enabled {
+        let indexing_service = IndexingService::new(Arc::clone(&rpc_client), Arc::clone(&db))
+            .with_cache_invalidation(Arc::clone(&cache_invalidation));
+        let shutdown_rx_stream = shutdown_coordinator.subscribe();
+        let task = tokio::spawn(async move {
+            tracing::info!("Starting Horizon payment stream background task");
+            let mut shutdown_rx = shutdown_rx_stream;
+            tokio::select! {
+                result = indexing_service.run_payment_stream() => {
+                    if let Err(e) = result {
+                        tracing::error!("Horizon payment stream failed: {}", e);
+                        obs_metrics::record_background_job("payment_stream", "error");
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    tracing::info!("Horizon payment stream task shutting down");
+                }
+            }
+        });
+        background_tasks.push(task);
+    }
+
+    // Trade candle aggregation task (opt-in; consumes the Horizon trades stream)
+    let trade_aggregation_enabled = std::env::var("TRADE_AGGREGATION_ENABLED")
+        .ok()
+        .and_then(|v| v.parse::<bool>().ok())
+        .unwrap_or(false);
+    if trade_aggregation_enabled {
+        let trade_aggregator_clone = Arc::clone(&trade_aggregator);
+        let trades = HorizonStream::new(Arc::clone(&rpc_client))
+            .with_cursor_store(Arc::clone(&db))
+            .trades();
+        let shutdown_rx_trades = shutdown_coordinator.subscribe();
+        let task = tokio::spawn(async move {
+            tracing::info!("Starting trade aggregation background task");
+            let mut shutdown_rx = shutdown_rx_trades;
+            tokio::select! {
+                result = trade_aggregator_clone.run(trades) => {
+                    if let Err(e) = result {
+                        tracing::error!("Trade aggregation failed: {}", e);
+                        obs_metrics::record_background_job("trade_aggregation", "error");
+                    }
+                }
+                _ = shutdown_rx.recv() => {
+                    tracing::info!("Trade aggregation task shutting down");
+                }
+            }
+        });
+        background_tasks.push(task);
+    }
+
     // Liquidity pool sync background task
     let liquidity_pool_analyzer_clone = Arc::clone(&liquidity_pool_analyzer);
     let shutdown_rx3 = shutdown_coordinator.subscribe();
@@ -632,14 +806,75 @@ async fn main() -> Result<()> {
 
     // Start background job scheduler
     tracing::info!("Starting background job scheduler...");
-    let _job_scheduler = JobScheduler::start(
+    let mut job_scheduler = JobScheduler::start(
         Arc::clone(&db),
         Arc::clone(&cache),
         Arc::clone(&rpc_client),
         Arc::clone(&ingestion_service),
         Arc::clone(&price_feed),
+        Arc::clone(&order_book_snapshots),
+        Arc::clone(&asset_supply_tracker),
     )
     .await;
+
+    // Anchor transfer polling job: advance SEP-24/31 transfers and feed
+    // settlement times into anchor metrics when any transfer finished
+    let tracker_for_job = Arc::clone(&anchor_transfer_tracker);
+    job_scheduler.add_job(JobConfig::from_env("anchor-transfer-poll", 60), move || {
+        let tracker = Arc::clone(&tracker_for_job);
+        Box::pin(async move {
+            let summary = tracker.poll_pending().await?;
+            if summary.finished > 0 {
+                tracker.refresh_anchor_metrics().await?;
+            }
+            Ok(())
+        })
+    });
+
+    // Hourly corridor rollup of the payments ingested in the completed hours
+    let aggregation_for_job = Arc::clone(&aggregation_service);
+    let overview_invalidation = Arc::clone(&cache_invalidation);
+    job_scheduler.add_job(
+        JobConfig::from_env("corridor-aggregation", 3600),
+        move || {
+            let aggregation = Arc::clone(&aggregation_for_job);
+            let cache_invalidation = Arc::clone(&overview_invalidation);
+            Box::pin(async move {
+                aggregation.run_hourly_aggregation().await?;
+                cache_invalidation.invalidate_metrics_overview().await
+            })
+        },
+    );
+
+    // Anomaly detection over the hours completed since the previous run
+    let detector_for_job = Arc::clone(&anomaly_detector);
+    job_scheduler.add_job(JobConfig::from_env("anomaly-detection", 900), move || {
+        let detector = Arc::clone(&detector_for_job);
+        Box::pin(async move {
+            detector.run().await?;
+            Ok(())
+        })
+    });
+
+    // Hourly refit of the corridor forecasts
+    let forecaster_for_job = Arc::clone(&corridor_forecaster);
+    job_scheduler.add_job(JobConfig::from_env("corridor-forecast", 3600), move || {
+        let forecaster = Arc::clone(&forecaster_for_job);
+        Box::pin(async move {
+            forecaster.refresh_all().await?;
+            Ok(())
+        })
+    });
+
+    // Weekly payment success model retraining
+    let ml_for_job = Arc::clone(&ml_service);
+    job_scheduler.add_job(
+        JobConfig::from_env("ml-retrain", 7 * 24 * 3600),
+        move || {
+            let ml_service = Arc::clone(&ml_for_job);
+            Box::pin(async move { MLService::retrain_weekly(&ml_service).await })
+        },
+    );
     tracing::info!("Background job scheduler started");
 
     // Initialize rate limiter with database support for API key validation
@@ -911,6 +1146,7 @@ async fn main() -> Result<()> {
         .route("/api/corridors", get(list_corridors))
         .route("/api/corridors/:corridor_key", get(get_corridor_detail))
         .with_state(cached_state.clone())
+        .layer(Extension(Arc::clone(&corridor_health_scorer)))
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
             rate_limit_middleware,
@@ -975,7 +1211,8 @@ async fn main() -> Result<()> {
         .layer(cors.clone());
 
     // Build metrics routes (public)
-    let metrics_routes = metrics_cached::routes(Arc::clone(&cache));
+    let metrics_routes =
+        metrics_cached::routes(Arc::clone(&metrics_overview_service), Arc::clone(&cache));
 
     // Build RPC router
     let rpc_routes = Router::new()
@@ -1022,6 +1259,169 @@ async fn main() -> Result<()> {
         )))
         .layer(cors.clone());
 
+    // Build account profile routes
+    let account_routes = Router::new()
+        .nest(
+            "/api/accounts",
+            accounts::routes(Arc::clone(&account_profiles)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build claimable balance routes
+    let claimable_balance_routes = Router::new()
+        .nest(
+            "/api/claimable-balances",
+            claimable_balances::routes(Arc::clone(&claimable_balance_tracker)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build sponsorship routes
+    let sponsorship_routes = Router::new()
+        .nest(
+            "/api/sponsorships",
+            sponsorships::routes(Arc::clone(&sponsorship_tracker)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build trade candle routes
+    let candle_routes = Router::new()
+        .nest(
+            "/api/candles",
+            candles::routes(Arc::clone(&trade_aggregator)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build fee market routes
+    let fee_market_routes = Router::new()
+        .nest(
+            "/api/fee-market",
+            fee_market::routes(Arc::clone(&fee_market_service)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build asset supply routes
+    let asset_supply_routes = Router::new()
+        .nest(
+            "/api/asset-supply",
+            asset_supply::routes(Arc::clone(&asset_supply_tracker)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build asset authorization routes
+    let asset_authorization_routes = Router::new()
+        .nest(
+            "/api/asset-authorizations",
+            asset_authorizations::routes(Arc::clone(&asset_authorization_tracker)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build anchor transfer routes (lifecycle tracking and SEP-24/31 proxies)
+    let anchor_transfer_routes = Router::new()
+        .nest(
+            "/api/anchor-transfers",
+            anchor_transfers::routes(Arc::clone(&anchor_transfer_tracker)),
+        )
+        .merge(sep24_proxy::routes_with_tracker(Arc::clone(
+            &anchor_transfer_tracker,
+        )))
+        .merge(sep31_proxy::routes_with_tracker(Arc::clone(
+            &anchor_transfer_tracker,
+        )))
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build ML prediction routes
+    let ml_routes = Router::new()
+        .nest("/api/ml", ml_handlers::routes(Arc::clone(&ml_service)))
+        .merge(prediction::routes(
+            Arc::clone(&ml_service),
+            Arc::clone(&price_feed),
+        ))
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build large payment routes
+    let large_payment_routes = Router::new()
+        .nest(
+            "/api/large-payments",
+            large_payments::routes(Arc::clone(&large_payment_detector)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build anomaly routes
+    let anomaly_routes = Router::new()
+        .nest(
+            "/api/anomalies",
+            anomalies::routes(Arc::clone(&anomaly_detector)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build corridor forecast routes
+    let corridor_forecast_routes = Router::new()
+        .nest(
+            "/api/corridors",
+            corridor_forecast::routes(Arc::clone(&corridor_forecaster)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build order book history routes
+    let order_book_routes = Router::new()
+        .nest(
+            "/api/order-books",
+            order_books::routes(Arc::clone(&order_book_snapshots)),
+        )
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
     // Build liquidity pool routes
     let liquidity_pool_routes = Router::new()
         .nest(
@@ -1050,7 +1450,10 @@ async fn main() -> Result<()> {
     let cost_calculator_routes = Router::new()
         .nest(
             "/api/cost-calculator",
-            cost_calculator::routes(Arc::clone(&price_feed)),
+            cost_calculator::routes_with_fee_market(
+                Arc::clone(&price_feed),
+                Arc::clone(&fee_market_service),
+            ),
         )
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
@@ -1168,6 +1571,44 @@ async fn main() -> Result<()> {
         )
         .layer(cors.clone());
 
+    // Build ML training routes (ADMIN - IP whitelisted)
+    let ml_admin_routes = Router::new()
+        .nest(
+            "/api/admin/ml",
+            ml_handlers::admin_routes(Arc::clone(&ml_service)),
+        )
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    ip_whitelist_config.clone(),
+                    ip_whitelist_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build backfill routes (ADMIN - IP whitelisted)
+    let backfill_routes = Router::new()
+        .nest(
+            "/api/admin/backfill",
+            backfill::routes(Arc::clone(&backfill_service)),
+        )
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    ip_whitelist_config.clone(),
+                    ip_whitelist_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
     // Build governance routes
     let governance_routes = Router::new()
         .nest(
@@ -1292,8 +1733,21 @@ async fn main() -> Result<()> {
         .merge(protected_anchor_routes)
         .merge(rpc_routes)
         .merge(fee_bump_routes)
+        .merge(fee_market_routes)
+        .merge(asset_supply_routes)
+        .merge(asset_authorization_routes)
+        .merge(large_payment_routes)
+        .merge(anomaly_routes)
+        .merge(corridor_forecast_routes)
+        .merge(anchor_transfer_routes)
+        .merge(ml_routes)
         .merge(account_merge_routes)
+        .merge(account_routes)
+        .merge(claimable_balance_routes)
+        .merge(sponsorship_routes)
         .merge(liquidity_pool_routes)
+        .merge(order_book_routes)
+        .merge(candle_routes)
         .merge(price_routes)
         .merge(cost_calculator_routes)
         .merge(trustline_routes)
@@ -1305,6 +1759,8 @@ async fn main() -> Result<()> {
         .merge(metrics_routes)
         // .merge(graphql_routes) // Add GraphQL routes
         .merge(admin_db_routes)
+        .merge(backfill_routes)
+        .merge(ml_admin_routes)
         .merge(verification_routes)
         .merge(asset_verification_routes)
         // .merge(gdpr_routes)
@@ -1,125 +1,789 @@
+//! Payment success prediction.
+//!
+//! The model is a logistic regression over hourly corridor observations:
+//! each `corridor_metrics_hourly` row is one sample whose label is the share
+//! of successful payments in that hour, weighted by the number of payments.
+//! Features only use data from before the hour (payment activity from
+//! `payments`, the corridor's trailing success rate and its liquidity from
+//! the hourly metrics and `liquidity_pool_snapshots`), so the same features
+//! can be computed at prediction time. Trained models are stored in
+//! `ml_models` and one of them is active at a time.
+//!
+//! The service is shared behind a `RwLock`; [`retrain`] trains on a copy of
+//! it and only takes the write lock to promote the new model, so predictions
+//! keep being served while a model trains.
+
 use crate::database::Database;
-use chrono::{DateTime, Datelike, Timelike, Utc};
+use crate::models::corridor::Corridor;
+use crate::utils::{asset_key, format_rollup_time, parse_time};
+use anyhow::{bail, Result};
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use ndarray::{Array1, Array2};
 use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::RwLock;
+
+/// Model inputs, in the order used by the stored weights
+pub const FEATURE_NAMES: [&str; 6] = [
+    "payment_activity",
+    "amount_usd",
+    "hour_of_day",
+    "day_of_week",
+    "liquidity_depth",
+    "recent_success_rate",
+];
+
+/// Version of the untrained fallback model
+pub const BASELINE_MODEL_VERSION: &str = "1.0.0";
+
+/// Index of `recent_success_rate` in `FEATURE_NAMES`
+const RECENT_SUCCESS_RATE: usize = 5;
+
+/// Success rate assumed by the baseline model for corridors without history
+const BASELINE_SUCCESS_RATE: f32 = 0.8;
+
+/// Trailing window for the activity, success rate and liquidity features
+const LOOKBACK_HOURS: i64 = 24;
+
+/// Equal-width predicted-probability bins used for calibration
+const CALIBRATION_BINS: usize = 10;
+
+/// z-score of the 95% confidence intervals
+const INTERVAL_Z: f64 = 1.96;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PredictionFeatures {
-    pub corridor_hash: f32,
+    /// log10(1 + payments of either corridor asset in the previous 24 hours)
+    pub payment_activity: f32,
+    /// log10(1 + amount in USD)
     pub amount_usd: f32,
     pub hour_of_day: f32,
     pub day_of_week: f32,
+    /// log10(1 + corridor liquidity in USD)
     pub liquidity_depth: f32,
+    /// Corridor success rate over the previous 24 hours
     pub recent_success_rate: f32,
 }
 
+impl PredictionFeatures {
+    fn to_vec(&self) -> Vec<f32> {
+        vec![
+            self.payment_activity,
+            self.amount_usd,
+            self.hour_of_day,
+            self.day_of_week,
+            self.liquidity_depth,
+            self.recent_success_rate,
+        ]
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PredictionResult {
     pub success_probability: f32,
+    /// One minus the width of `confidence_interval`
     pub confidence: f32,
+    /// 95% interval of the success rate seen on held-out hours where the
+    /// model predicted a similar probability
+    pub confidence_interval: (f32, f32),
     pub model_version: String,
 }
 
+/// Held-out outcomes of the corridor hours whose prediction fell in one bin
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalibrationBin {
+    pub samples: u32,
+    pub successes: f64,
+    pub failures: f64,
+}
+
+/// One corridor hour: features known before the hour and its payment outcomes
+#[derive(Debug, Clone)]
+pub struct TrainingSample {
+    pub features: Vec<f32>,
+    pub successes: f64,
+    pub failures: f64,
+}
+
+impl TrainingSample {
+    fn total(&self) -> f64 {
+        self.successes + self.failures
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MLTrainingConfig {
+    /// Days of hourly corridor history used for training
+    pub window_days: i64,
+    /// Fewest corridor hours a model is trained on
+    pub min_samples: usize,
+    /// Share of the most recent hours held out to compute AUC and log-loss
+    pub validation_fraction: f64,
+    pub epochs: usize,
+    pub learning_rate: f64,
+    /// L2 penalty on the weights
+    pub l2: f64,
+}
+
+impl MLTrainingConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            window_days: std::env::var("ML_TRAINING_WINDOW_DAYS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| (1..=365).contains(v))
+                .unwrap_or(defaults.window_days),
+            min_samples: std::env::var("ML_MIN_TRAINING_SAMPLES")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v >= 10)
+                .unwrap_or(defaults.min_samples),
+            ..defaults
+        }
+    }
+}
+
+impl Default for MLTrainingConfig {
+    fn default() -> Self {
+        Self {
+            window_days: 30,
+            min_samples: 50,
+            validation_fraction: 0.2,
+            epochs: 500,
+            learning_rate: 0.5,
+            l2: 0.001,
+        }
+    }
+}
+
+/// A stored model's version, metrics and training window
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ModelInfo {
+    pub version: String,
+    pub auc: Option<f64>,
+    pub log_loss: Option<f64>,
+    pub training_samples: i64,
+    pub validation_samples: i64,
+    pub window_start: String,
+    pub window_end: String,
+    pub is_active: bool,
+    pub trained_at: String,
+}
+
+#[derive(Debug, FromRow)]
+struct StoredModel {
+    version: String,
+    weights: String,
+    feature_means: String,
+    feature_stds: String,
+    bias: f64,
+    calibration: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct SimpleMLModel {
     weights: Vec<f32>,
     bias: f32,
+    /// Standardization applied before the weights
+    feature_means: Vec<f32>,
+    feature_stds: Vec<f32>,
+    /// Empty until evaluated on held-out data
+    calibration: Vec<CalibrationBin>,
+    trained: bool,
     version: String,
 }
 
+impl Default for SimpleMLModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SimpleMLModel {
+    /// Untrained baseline used until a model has been trained
     pub fn new() -> Self {
-        // Simple linear model weights (trained offline)
         Self {
             weights: vec![0.1, 0.3, 0.05, 0.02, 0.4, 0.6], // 6 features
             bias: 0.2,
-            version: "1.0.0".to_string(),
+            feature_means: vec![0.0; FEATURE_NAMES.len()],
+            feature_stds: vec![1.0; FEATURE_NAMES.len()],
+            calibration: Vec::new(),
+            trained: false,
+            version: BASELINE_MODEL_VERSION.to_string(),
         }
     }
 
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn is_trained(&self) -> bool {
+        self.trained
+    }
+
     pub fn predict(&self, features: PredictionFeatures) -> PredictionResult {
-        let input = vec![
-            features.corridor_hash,
-            features.amount_usd,
-            features.hour_of_day,
-            features.day_of_week,
-            features.liquidity_depth,
-            features.recent_success_rate,
-        ];
+        let prob = self.probability(&features.to_vec());
+        let (lower, upper) = confidence_interval(prob, &self.calibration);
+
+        PredictionResult {
+            success_probability: prob as f32,
+            confidence: (1.0 - (upper - lower)) as f32,
+            confidence_interval: (lower as f32, upper as f32),
+            model_version: self.version.clone(),
+        }
+    }
 
-        let mut score = self.bias;
+    fn probability(&self, features: &[f32]) -> f64 {
+        let mut score = self.bias as f64;
         for (i, &weight) in self.weights.iter().enumerate() {
-            score += weight * input[i];
+            let x = (features[i] - self.feature_means[i]) / self.feature_stds[i];
+            score += weight as f64 * x as f64;
         }
+        sigmoid(score)
+    }
 
-        // Sigmoid activation
-        let prob = 1.0 / (1.0 + (-score).exp());
+    /// Fit weights to the samples with batch gradient descent
+    pub fn train(&mut self, samples: &[TrainingSample], config: &MLTrainingConfig) {
+        let n_features = FEATURE_NAMES.len();
+        let x = Array2::from_shape_fn((samples.len(), n_features), |(i, j)| {
+            samples[i].features[j] as f64
+        });
+        let means = x
+            .mean_axis(ndarray::Axis(0))
+            .unwrap_or_else(|| Array1::zeros(n_features));
+        let stds = x
+            .std_axis(ndarray::Axis(0), 0.0)
+            .mapv(|s| if s > 1e-9 { s } else { 1.0 });
+        let x = (&x - &means) / &stds;
 
-        PredictionResult {
-            success_probability: prob,
-            confidence: if prob > 0.7 || prob < 0.3 { 0.9 } else { 0.7 },
-            model_version: self.version.clone(),
+        let y = Array1::from_iter(samples.iter().map(|s| s.successes / s.total().max(1.0)));
+        let weights = Array1::from_iter(samples.iter().map(|s| s.total()));
+        let (w, b) = fit_logistic_regression(&x, &y, &weights, config);
+
+        self.weights = w.iter().map(|&v| v as f32).collect();
+        self.bias = b as f32;
+        self.feature_means = means.iter().map(|&v| v as f32).collect();
+        self.feature_stds = stds.iter().map(|&v| v as f32).collect();
+        self.trained = true;
+    }
+
+    /// Weighted AUC and log-loss on the samples
+    pub fn evaluate(&self, samples: &[TrainingSample]) -> (Option<f64>, Option<f64>) {
+        let scored: Vec<(f64, f64, f64)> = samples
+            .iter()
+            .map(|s| (self.probability(&s.features), s.successes, s.failures))
+            .collect();
+        (weighted_auc(&scored), weighted_log_loss(&scored))
+    }
+
+    /// Bin held-out samples by predicted probability
+    pub fn calibrate(&mut self, samples: &[TrainingSample]) {
+        let mut bins = vec![CalibrationBin::default(); CALIBRATION_BINS];
+        for sample in samples {
+            let bin = &mut bins[calibration_bin(self.probability(&sample.features))];
+            bin.samples += 1;
+            bin.successes += sample.successes;
+            bin.failures += sample.failures;
         }
+        self.calibration = bins;
     }
+}
+
+fn calibration_bin(probability: f64) -> usize {
+    ((probability * CALIBRATION_BINS as f64) as usize).min(CALIBRATION_BINS - 1)
+}
 
-    pub fn train(&mut self, _training_data: &[(Vec<f32>, f32)]) {
-        // Simple gradient descent (placeholder)
-        // In production, this would implement actual training
-        println!("Training model with {} samples", _training_data.len());
+/// Wilson interval of the success rate observed in the probability's bin,
+/// widened to contain the probability itself. Corridor hours rather than
+/// payments count as observations, since payments within an hour are not
+/// independent. Without held-out data the interval is `(0, 1)`.
+pub(crate) fn confidence_interval(probability: f64, calibration: &[CalibrationBin]) -> (f64, f64) {
+    let Some(bin) = calibration.get(calibration_bin(probability)) else {
+        return (0.0, 1.0);
+    };
+    let total = bin.successes + bin.failures;
+    if bin.samples == 0 || total <= 0.0 {
+        return (0.0, 1.0);
+    }
+    let (lower, upper) = wilson_interval(bin.successes / total, bin.samples as f64);
+    (lower.min(probability), upper.max(probability))
+}
+
+fn wilson_interval(rate: f64, n: f64) -> (f64, f64) {
+    let z2 = INTERVAL_Z * INTERVAL_Z;
+    let denominator = 1.0 + z2 / n;
+    let center = (rate + z2 / (2.0 * n)) / denominator;
+    let margin = INTERVAL_Z * (rate * (1.0 - rate) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+/// Weighted logistic regression on standardized features; returns the
+/// weights and bias. `y` holds success shares in [0, 1].
+pub(crate) fn fit_logistic_regression(
+    x: &Array2<f64>,
+    y: &Array1<f64>,
+    sample_weights: &Array1<f64>,
+    config: &MLTrainingConfig,
+) -> (Array1<f64>, f64) {
+    let total_weight = sample_weights.sum().max(f64::EPSILON);
+    let base_rate = ((y * sample_weights).sum() / total_weight).clamp(1e-4, 1.0 - 1e-4);
+
+    let mut w = Array1::<f64>::zeros(x.ncols());
+    // Start from the base rate so the weights only have to explain deviations
+    let mut b = (base_rate / (1.0 - base_rate)).ln();
+    for _ in 0..config.epochs {
+        let p = (x.dot(&w) + b).mapv(sigmoid);
+        let err = (&p - y) * sample_weights;
+        let grad_w = x.t().dot(&err) / total_weight + &w * config.l2;
+        let grad_b = err.sum() / total_weight;
+        w = w - grad_w * config.learning_rate;
+        b -= grad_b * config.learning_rate;
+    }
+    (w, b)
+}
+
+/// AUC where each sample contributes `successes` positives and `failures`
+/// negatives at its score; `None` without both classes
+pub(crate) fn weighted_auc(scored: &[(f64, f64, f64)]) -> Option<f64> {
+    let mut sorted = scored.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let positives: f64 = sorted.iter().map(|s| s.1).sum();
+    let negatives: f64 = sorted.iter().map(|s| s.2).sum();
+    if positives <= 0.0 || negatives <= 0.0 {
+        return None;
+    }
+
+    let mut area = 0.0;
+    let mut negatives_below = 0.0;
+    let mut i = 0;
+    while i < sorted.len() {
+        // Ties count half
+        let mut j = i;
+        let (mut pos, mut neg) = (0.0, 0.0);
+        while j < sorted.len() && sorted[j].0 == sorted[i].0 {
+            pos += sorted[j].1;
+            neg += sorted[j].2;
+            j += 1;
+        }
+        area += pos * (negatives_below + 0.5 * neg);
+        negatives_below += neg;
+        i = j;
+    }
+    Some(area / (positives * negatives))
+}
 
-        // Update version after training
-        self.version = format!("1.0.{}", chrono::Utc::now().timestamp() % 1000);
+pub(crate) fn weighted_log_loss(scored: &[(f64, f64, f64)]) -> Option<f64> {
+    let total: f64 = scored.iter().map(|s| s.1 + s.2).sum();
+    if total <= 0.0 {
+        return None;
     }
+    let loss: f64 = scored
+        .iter()
+        .map(|&(p, successes, failures)| {
+            let p = p.clamp(1e-7, 1.0 - 1e-7);
+            -(successes * p.ln() + failures * (1.0 - p).ln())
+        })
+        .sum();
+    Some(loss / total)
 }
 
 pub struct MLService {
     model: SimpleMLModel,
-    #[allow(dead_code)] // Reserved for future ML model training from database
+    active: Option<ModelInfo>,
     db: Database,
+    config: MLTrainingConfig,
+    predictions: AtomicU64,
 }
 
 impl MLService {
     pub fn new(db: Database) -> anyhow::Result<Self> {
-        let model = SimpleMLModel::new();
-        Ok(Self { model, db })
+        Ok(Self::with_config(db, MLTrainingConfig::from_env()))
     }
 
-    pub async fn train_model(&mut self) -> anyhow::Result<()> {
-        let training_data = self.prepare_training_data().await?;
-        self.model.train(&training_data);
-        Ok(())
+    pub fn with_config(db: Database, config: MLTrainingConfig) -> Self {
+        Self {
+            model: SimpleMLModel::new(),
+            active: None,
+            db,
+            config,
+            predictions: AtomicU64::new(0),
+        }
+    }
+
+    /// A service on the same database and training config, with the baseline
+    /// model, to train on without holding the shared service's lock
+    pub fn training_copy(&self) -> Self {
+        Self::with_config(Database::new(self.db.pool().clone()), self.config.clone())
+    }
+
+    pub fn model_version(&self) -> &str {
+        self.model.version()
+    }
+
+    /// The stored model currently used, `None` while on the baseline
+    pub fn active_model(&self) -> Option<&ModelInfo> {
+        self.active.as_ref()
+    }
+
+    pub fn total_predictions(&self) -> u64 {
+        self.predictions.load(Ordering::Relaxed)
+    }
+
+    /// Load the model marked active in `ml_models`, if any
+    pub async fn load_active_model(&mut self) -> Result<Option<ModelInfo>> {
+        let version: Option<(String,)> =
+            sqlx::query_as("SELECT version FROM ml_models WHERE is_active = 1")
+                .fetch_optional(self.db.pool())
+                .await?;
+        match version {
+            Some((version,)) => self.load_model(&version).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Switch the model used for predictions to a stored version
+    pub async fn activate_model(&mut self, version: &str) -> Result<Option<ModelInfo>> {
+        let exists: Option<(String,)> =
+            sqlx::query_as("SELECT version FROM ml_models WHERE version = $1")
+                .bind(version)
+                .fetch_optional(self.db.pool())
+                .await?;
+        if exists.is_none() {
+            return Ok(None);
+        }
+
+        let mut tx = self.db.pool().begin().await?;
+        sqlx::query("UPDATE ml_models SET is_active = 0 WHERE is_active = 1")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("UPDATE ml_models SET is_active = 1 WHERE version = $1")
+            .bind(version)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        self.load_model(version).await
+    }
+
+    async fn load_model(&mut self, version: &str) -> Result<Option<ModelInfo>> {
+        let stored = sqlx::query_as::<_, StoredModel>(
+            "SELECT version, weights, feature_means, feature_stds, bias, calibration FROM ml_models WHERE version = $1",
+        )
+        .bind(version)
+        .fetch_optional(self.db.pool())
+        .await?;
+        let Some(stored) = stored else {
+            return Ok(None);
+        };
+
+        let weights: Vec<f32> = serde_json::from_str(&stored.weights)?;
+        let feature_means: Vec<f32> = serde_json::from_str(&stored.feature_means)?;
+        let feature_stds: Vec<f32> = serde_json::from_str(&stored.feature_stds)?;
+        if [weights.len(), feature_means.len(), feature_stds.len()]
+            .iter()
+            .any(|&len| len != FEATURE_NAMES.len())
+        {
+            bail!("Model {} has the wrong number of features", version);
+        }
+        let calibration: Vec<CalibrationBin> = match &stored.calibration {
+            Some(calibration) => serde_json::from_str(calibration)?,
+            None => Vec::new(),
+        };
+
+        self.model = SimpleMLModel {
+            weights,
+            bias: stored.bias as f32,
+            feature_means,
+            feature_stds,
+            calibration,
+            trained: true,
+            version: stored.version,
+        };
+        self.active = self.get_model(version).await?;
+        Ok(self.active.clone())
     }
 
-    async fn prepare_training_data(&self) -> anyhow::Result<Vec<(Vec<f32>, f32)>> {
-        // Mock training data for now
-        let mut training_data = Vec::new();
+    /// Stored models, most recent first
+    pub async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let models = sqlx::query_as::<_, ModelInfo>(
+            r#"
+            SELECT version, auc, log_loss, training_samples, validation_samples,
+                   window_start, window_end, is_active, trained_at
+            FROM ml_models
+            ORDER BY trained_at DESC
+            "#,
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+        Ok(models)
+    }
+
+    async fn get_model(&self, version: &str) -> Result<Option<ModelInfo>> {
+        let model = sqlx::query_as::<_, ModelInfo>(
+            r#"
+            SELECT version, auc, log_loss, training_samples, validation_samples,
+                   window_start, window_end, is_active, trained_at
+            FROM ml_models
+            WHERE version = $1
+            "#,
+        )
+        .bind(version)
+        .fetch_optional(self.db.pool())
+        .await?;
+        Ok(model)
+    }
+
+    /// Train on the configured window and store the model. It becomes active
+    /// when there is no active model or its AUC is at least the active one's.
+    pub async fn train_model(&mut self) -> anyhow::Result<ModelInfo> {
+        let version = self.fit_model().await?;
+        self.promote_model(&version).await
+    }
+
+    /// Train on the configured window and store the model without activating
+    /// it; returns its version
+    pub async fn fit_model(&self) -> anyhow::Result<String> {
+        let window_end = Utc::now();
+        let window_start = window_end - Duration::days(self.config.window_days);
+        let samples = self.prepare_training_data(window_start, window_end).await?;
+        if samples.len() < self.config.min_samples {
+            bail!(
+                "Not enough corridor history to train: {} hourly samples, need {}",
+                samples.len(),
+                self.config.min_samples
+            );
+        }
+
+        // Hold out the most recent hours; samples are in hour order
+        let validation_len =
+            ((samples.len() as f64 * self.config.validation_fraction).ceil() as usize).max(1);
+        let (train, validation) = samples.split_at(samples.len() - validation_len);
+
+        let trained_at = Utc::now();
+        let mut model = SimpleMLModel::new();
+        model.train(train, &self.config);
+        model.version = format!("lr-{}", trained_at.format("%Y%m%d%H%M%S%3f"));
+        let (auc, log_loss) = model.evaluate(validation);
+        model.calibrate(validation);
+
+        sqlx::query(
+            r#"
+            INSERT INTO ml_models (
+                version, weights, feature_means, feature_stds, bias, calibration, auc,
+                log_loss, training_samples, validation_samples, window_start, window_end,
+                trained_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            "#,
+        )
+        .bind(&model.version)
+        .bind(serde_json::to_string(&model.weights)?)
+        .bind(serde_json::to_string(&model.feature_means)?)
+        .bind(serde_json::to_string(&model.feature_stds)?)
+        .bind(model.bias as f64)
+        .bind(serde_json::to_string(&model.calibration)?)
+        .bind(auc)
+        .bind(log_loss)
+        .bind(train.len() as i64)
+        .bind(validation.len() as i64)
+        .bind(format_rollup_time(window_start))
+        .bind(format_rollup_time(window_end))
+        .bind(format_rollup_time(trained_at))
+        .execute(self.db.pool())
+        .await?;
+
+        tracing::info!(
+            "Trained payment success model {} on {} samples (AUC {:?}, log-loss {:?})",
+            model.version,
+            train.len(),
+            auc,
+            log_loss
+        );
+
+        Ok(model.version)
+    }
+
+    /// Activate a stored model when there is no active model or its AUC is at
+    /// least the active one's
+    pub async fn promote_model(&mut self, version: &str) -> anyhow::Result<ModelInfo> {
+        let model = self
+            .get_model(version)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Model {} was not stored", version))?;
+        let active_auc = self.active.as_ref().and_then(|m| m.auc);
+        let promote = match (&self.active, active_auc) {
+            (None, _) | (Some(_), None) => true,
+            (Some(_), Some(active_auc)) => model.auc.is_some_and(|auc| auc >= active_auc),
+        };
+        if !promote {
+            return Ok(model);
+        }
+        self.activate_model(version)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Model {} was not stored", version))
+    }
+
+    /// One sample per corridor hour in the window, in hour order
+    async fn prepare_training_data(
+        &self,
+        window_start: DateTime<Utc>,
+        window_end: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<TrainingSample>> {
+        let history_start = window_start - Duration::hours(LOOKBACK_HOURS);
+        let rows = sqlx::query_as::<_, HourlyRow>(
+            r#"
+            SELECT corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                   hour_bucket, total_transactions, successful_transactions,
+                   volume_usd, liquidity_depth_usd
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= $1 AND hour_bucket < $2
+            ORDER BY hour_bucket ASC
+            "#,
+        )
+        .bind(format_rollup_time(history_start))
+        .bind(format_rollup_time(window_end))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let activity = self
+            .hourly_payment_counts(history_start, window_end)
+            .await?;
+        let pools = self.pool_liquidity_history(window_end).await?;
 
-        // Generate some sample data
-        for i in 0..1000 {
-            let features = vec![
-                (i % 100) as f32 / 100.0,      // corridor_hash
-                (i % 50) as f32 / 10.0,        // amount (log scale)
-                (i % 24) as f32 / 24.0,        // hour
-                (i % 7) as f32 / 7.0,          // day
-                5.0 + (i % 10) as f32,         // liquidity
-                0.7 + (i % 30) as f32 / 100.0, // success rate
-            ];
+        let mut history: HashMap<String, VecDeque<HourlyObservation>> = HashMap::new();
+        let mut samples = Vec::new();
+        for row in rows {
+            let Some(hour) = parse_time(&row.hour_bucket) else {
+                continue;
+            };
+            let corridor_history = history.entry(row.corridor_key.clone()).or_default();
+            while corridor_history
+                .front()
+                .is_some_and(|o| o.hour < hour - Duration::hours(LOOKBACK_HOURS))
+            {
+                corridor_history.pop_front();
+            }
 
-            // Simple target: higher success rate = higher probability
-            let target = if features[5] > 0.8 { 1.0 } else { 0.0 };
-            training_data.push((features, target));
+            let total = row.total_transactions.max(0) as f64;
+            let successes = (row.successful_transactions.max(0) as f64).min(total);
+            if hour >= window_start && total > 0.0 {
+                let assets = [
+                    (row.asset_a_code.as_str(), row.asset_a_issuer.as_str()),
+                    (row.asset_b_code.as_str(), row.asset_b_issuer.as_str()),
+                ];
+                let recent = trailing_success_rate(corridor_history);
+                let depth = corridor_history
+                    .iter()
+                    .rev()
+                    .map(|o| o.liquidity_depth_usd)
+                    .find(|d| *d > 0.0)
+                    .unwrap_or(0.0)
+                    .max(pools.liquidity_at(assets, hour));
+                let features = PredictionFeatures {
+                    payment_activity: log_scale(activity.count_before(assets, hour)),
+                    amount_usd: log_scale(row.volume_usd.unwrap_or(0.0) / total),
+                    hour_of_day: hour.hour() as f32 / 24.0,
+                    day_of_week: hour.weekday().num_days_from_monday() as f32 / 7.0,
+                    liquidity_depth: log_scale(depth),
+                    recent_success_rate: recent.unwrap_or(BASELINE_SUCCESS_RATE),
+                };
+                samples.push(TrainingSample {
+                    features: features.to_vec(),
+                    successes,
+                    failures: total - successes,
+                });
+            }
+
+            corridor_history.push_back(HourlyObservation {
+                hour,
+                total,
+                successes,
+                liquidity_depth_usd: row.liquidity_depth_usd.unwrap_or(0.0),
+            });
         }
 
-        Ok(training_data)
+        Ok(samples)
     }
 
-    fn hash_corridor(&self, asset_code: &Option<String>, asset_issuer: &Option<String>) -> f32 {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+    /// Payments per asset and hour between `start` and `end`
+    async fn hourly_payment_counts(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<PaymentActivity> {
+        let rows: Vec<(String, String, String, i64)> = sqlx::query_as(
+            r#"
+            SELECT COALESCE(asset_code, 'XLM'), COALESCE(asset_issuer, 'native'),
+                   substr(created_at, 1, 13), COUNT(*)
+            FROM payments
+            WHERE created_at >= $1 AND created_at < $2
+            GROUP BY 1, 2, 3
+            "#,
+        )
+        .bind(format_rollup_time(start))
+        .bind(format_rollup_time(end))
+        .fetch_all(self.db.pool())
+        .await?;
 
-        let mut hasher = DefaultHasher::new();
-        asset_code.hash(&mut hasher);
-        asset_issuer.hash(&mut hasher);
-        (hasher.finish() % 1000) as f32 / 1000.0
+        let mut activity = PaymentActivity::default();
+        for (code, issuer, hour, count) in rows {
+            activity
+                .by_asset
+                .entry(asset_key(&code, &issuer))
+                .or_default()
+                .insert(hour.replace(' ', "T"), count);
+        }
+        Ok(activity)
+    }
+
+    /// Liquidity pool value snapshots up to `end`, with each pool's assets
+    async fn pool_liquidity_history(&self, end: DateTime<Utc>) -> Result<PoolLiquidity> {
+        let pools: Vec<PoolAssetsRow> = sqlx::query_as(
+            r#"
+                SELECT pool_id, reserve_a_asset_code, reserve_a_asset_issuer,
+                       reserve_b_asset_code, reserve_b_asset_issuer
+                FROM liquidity_pools
+                "#,
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+        let snapshots: Vec<(String, DateTime<Utc>, f64)> = sqlx::query_as(
+            r#"
+            SELECT pool_id, snapshot_at, total_value_usd
+            FROM liquidity_pool_snapshots
+            WHERE snapshot_at < $1
+            ORDER BY snapshot_at ASC
+            "#,
+        )
+        .bind(end)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut liquidity = PoolLiquidity::default();
+        for (pool_id, a_code, a_issuer, b_code, b_issuer) in pools {
+            let a = asset_key(&a_code, a_issuer.as_deref().unwrap_or("native"));
+            let b = asset_key(&b_code, b_issuer.as_deref().unwrap_or("native"));
+            liquidity.pools.insert(
+                pool_id,
+                PoolHistory {
+                    assets: (a, b),
+                    snapshots: Vec::new(),
+                },
+            );
+        }
+        for (pool_id, at, value) in snapshots {
+            if let Some(pool) = liquidity.pools.get_mut(&pool_id) {
+                pool.snapshots.push((at, value));
+            }
+        }
+        Ok(liquidity)
     }
 
     pub async fn predict_payment_success(
@@ -128,48 +792,381 @@ impl MLService {
         amount_usd: f64,
         timestamp: DateTime<Utc>,
     ) -> anyhow::Result<PredictionResult> {
-        let parts: Vec<&str> = corridor.split('-').collect();
-        let corridor_hash = self.hash_corridor(
-            &Some(parts.get(0).unwrap_or(&"").to_string()),
-            &Some(parts.get(1).unwrap_or(&"").to_string()),
-        );
+        self.predict_corridor(corridor, amount_usd, timestamp, None)
+            .await
+    }
 
-        let liquidity = self
-            .get_corridor_liquidity(corridor)
+    /// A swap through `pool`, scored with the corridor model: the pool's
+    /// value stands in for the corridor's liquidity depth, since the swap
+    /// only draws on that pool
+    pub async fn predict_pool_swap_success(
+        &self,
+        corridor: &str,
+        pool: &CorridorPool,
+        amount_usd: f64,
+        timestamp: DateTime<Utc>,
+    ) -> anyhow::Result<PredictionResult> {
+        self.predict_corridor(corridor, amount_usd, timestamp, Some(pool.total_value_usd))
             .await
-            .unwrap_or(1000.0);
-        let recent_success = self.get_recent_success_rate(corridor).await.unwrap_or(0.8);
+    }
+
+    async fn predict_corridor(
+        &self,
+        corridor: &str,
+        amount_usd: f64,
+        timestamp: DateTime<Utc>,
+        liquidity_depth_usd: Option<f64>,
+    ) -> anyhow::Result<PredictionResult> {
+        let Some(corridor) = parse_corridor(corridor) else {
+            bail!(
+                "Invalid corridor '{}', expected CODE:ISSUER->CODE:ISSUER",
+                corridor
+            );
+        };
+        let assets = [
+            (
+                corridor.asset_a_code.as_str(),
+                corridor.asset_a_issuer.as_str(),
+            ),
+            (
+                corridor.asset_b_code.as_str(),
+                corridor.asset_b_issuer.as_str(),
+            ),
+        ];
+
+        // Conditions up to now stand in for those at a future payment time
+        let as_of = timestamp.min(Utc::now());
+        let liquidity = match liquidity_depth_usd {
+            Some(depth) => depth,
+            None => self
+                .get_corridor_liquidity(&corridor, as_of)
+                .await?
+                .unwrap_or(0.0),
+        };
+        let recent_success = match self
+            .get_recent_success_rate(&corridor.to_string_key(), as_of)
+            .await?
+        {
+            Some(rate) => rate,
+            // Unknown corridors get the average rate the model was trained on
+            None if self.model.is_trained() => self.model.feature_means[RECENT_SUCCESS_RATE],
+            None => BASELINE_SUCCESS_RATE,
+        };
+        let activity = self.get_payment_activity(assets, as_of).await?;
 
         let features = PredictionFeatures {
-            corridor_hash,
-            amount_usd: amount_usd.log10().max(0.0) as f32,
+            payment_activity: log_scale(activity),
+            amount_usd: log_scale(amount_usd.max(0.0)),
             hour_of_day: timestamp.hour() as f32 / 24.0,
             day_of_week: timestamp.weekday().num_days_from_monday() as f32 / 7.0,
-            liquidity_depth: liquidity.log10() as f32,
+            liquidity_depth: log_scale(liquidity),
             recent_success_rate: recent_success,
         };
 
+        self.predictions.fetch_add(1, Ordering::Relaxed);
         Ok(self.model.predict(features))
     }
 
-    async fn get_corridor_liquidity(&self, corridor: &str) -> Option<f64> {
-        // Mock data for now - in production this would query the database
-        Some(1000.0 + (corridor.len() as f64 * 100.0))
+    /// Latest corridor liquidity in USD from hourly metrics and pool snapshots
+    async fn get_corridor_liquidity(
+        &self,
+        corridor: &Corridor,
+        at: DateTime<Utc>,
+    ) -> Result<Option<f64>> {
+        let hourly: Option<(Option<f64>,)> = sqlx::query_as(
+            r#"
+            SELECT liquidity_depth_usd
+            FROM corridor_metrics_hourly
+            WHERE corridor_key = $1 AND hour_bucket >= $2 AND hour_bucket < $3
+              AND liquidity_depth_usd > 0
+            ORDER BY hour_bucket DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(corridor.to_string_key())
+        .bind(format_rollup_time(at - Duration::hours(LOOKBACK_HOURS)))
+        .bind(format_rollup_time(at))
+        .fetch_optional(self.db.pool())
+        .await?;
+
+        let (pool_value,): (f64,) = sqlx::query_as(
+            r#"
+            SELECT COALESCE(SUM(s.total_value_usd), 0.0)
+            FROM liquidity_pools p
+            JOIN liquidity_pool_snapshots s ON s.pool_id = p.pool_id
+            WHERE s.id = (
+                SELECT id FROM liquidity_pool_snapshots
+                WHERE pool_id = p.pool_id AND snapshot_at <= $1
+                ORDER BY snapshot_at DESC
+                LIMIT 1
+            )
+              AND ((p.reserve_a_asset_code = $2 AND COALESCE(p.reserve_a_asset_issuer, 'native') = $3
+                    AND p.reserve_b_asset_code = $4 AND COALESCE(p.reserve_b_asset_issuer, 'native') = $5)
+                OR (p.reserve_a_asset_code = $4 AND COALESCE(p.reserve_a_asset_issuer, 'native') = $5
+                    AND p.reserve_b_asset_code = $2 AND COALESCE(p.reserve_b_asset_issuer, 'native') = $3))
+            "#,
+        )
+        .bind(at)
+        .bind(&corridor.asset_a_code)
+        .bind(&corridor.asset_a_issuer)
+        .bind(&corridor.asset_b_code)
+        .bind(&corridor.asset_b_issuer)
+        .fetch_one(self.db.pool())
+        .await?;
+
+        let depth = hourly.and_then(|(d,)| d).unwrap_or(0.0).max(pool_value);
+        Ok((depth > 0.0).then_some(depth))
     }
 
-    async fn get_recent_success_rate(&self, corridor: &str) -> Option<f32> {
-        // Mock data for now - in production this would query the database
-        Some(0.8 + (corridor.len() as f32 * 0.01) % 0.2)
+    /// Corridor success rate over the 24 hours before `at`
+    async fn get_recent_success_rate(
+        &self,
+        corridor_key: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<f32>> {
+        let (total, successful): (Option<i64>, Option<i64>) = sqlx::query_as(
+            r#"
+            SELECT SUM(total_transactions), SUM(successful_transactions)
+            FROM corridor_metrics_hourly
+            WHERE corridor_key = $1 AND hour_bucket >= $2 AND hour_bucket < $3
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(format_rollup_time(at - Duration::hours(LOOKBACK_HOURS)))
+        .bind(format_rollup_time(at))
+        .fetch_one(self.db.pool())
+        .await?;
+
+        Ok(match (total, successful) {
+            (Some(total), Some(successful)) if total > 0 => {
+                Some((successful as f32 / total as f32).clamp(0.0, 1.0))
+            }
+            _ => None,
+        })
+    }
+
+    /// Payments of either asset in the 24 hours before `at`
+    async fn get_payment_activity(
+        &self,
+        assets: [(&str, &str); 2],
+        at: DateTime<Utc>,
+    ) -> Result<f64> {
+        let (count,): (i64,) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*)
+            FROM payments
+            WHERE created_at >= $1 AND created_at < $2
+              AND ((COALESCE(asset_code, 'XLM') = $3 AND COALESCE(asset_issuer, 'native') = $4)
+                OR (COALESCE(asset_code, 'XLM') = $5 AND COALESCE(asset_issuer, 'native') = $6))
+            "#,
+        )
+        .bind(format_rollup_time(at - Duration::hours(LOOKBACK_HOURS)))
+        .bind(format_rollup_time(at))
+        .bind(assets[0].0)
+        .bind(assets[0].1)
+        .bind(assets[1].0)
+        .bind(assets[1].1)
+        .fetch_one(self.db.pool())
+        .await?;
+        Ok(count as f64)
     }
 
-    pub async fn retrain_weekly(&mut self) -> anyhow::Result<()> {
-        println!("Starting weekly model retraining...");
-        self.train_model().await?;
+    /// Largest liquidity pool holding both assets of the corridor
+    pub async fn find_corridor_pool(&self, corridor: &Corridor) -> Result<Option<CorridorPool>> {
+        let pool = sqlx::query_as::<_, CorridorPool>(
+            r#"
+            SELECT pool_id, fee_bp, total_value_usd
+            FROM liquidity_pools
+            WHERE (reserve_a_asset_code = $1 AND COALESCE(reserve_a_asset_issuer, 'native') = $2
+                   AND reserve_b_asset_code = $3 AND COALESCE(reserve_b_asset_issuer, 'native') = $4)
+               OR (reserve_a_asset_code = $3 AND COALESCE(reserve_a_asset_issuer, 'native') = $4
+                   AND reserve_b_asset_code = $1 AND COALESCE(reserve_b_asset_issuer, 'native') = $2)
+            ORDER BY total_value_usd DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(&corridor.asset_a_code)
+        .bind(&corridor.asset_a_issuer)
+        .bind(&corridor.asset_b_code)
+        .bind(&corridor.asset_b_issuer)
+        .fetch_optional(self.db.pool())
+        .await?;
+        Ok(pool)
+    }
 
-        println!(
-            "Model retrained successfully. Version: {}",
-            self.model.version
+    /// Other issuers of `code` with corridor history against `counter` in the
+    /// last 7 days, busiest first
+    pub async fn alternative_issuers(
+        &self,
+        code: &str,
+        exclude_issuer: &str,
+        counter: (&str, &str),
+        limit: i64,
+    ) -> Result<Vec<String>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT CASE WHEN asset_a_code = $1 AND asset_b_code = $3 AND asset_b_issuer = $4
+                        THEN asset_a_issuer ELSE asset_b_issuer END AS issuer
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= $5
+              AND ((asset_a_code = $1 AND asset_a_issuer != $2
+                    AND asset_b_code = $3 AND asset_b_issuer = $4)
+                OR (asset_b_code = $1 AND asset_b_issuer != $2
+                    AND asset_a_code = $3 AND asset_a_issuer = $4))
+            GROUP BY issuer
+            ORDER BY SUM(total_transactions) DESC
+            LIMIT $6
+            "#,
+        )
+        .bind(code)
+        .bind(exclude_issuer)
+        .bind(counter.0)
+        .bind(counter.1)
+        .bind(format_rollup_time(Utc::now() - Duration::days(7)))
+        .bind(limit)
+        .fetch_all(self.db.pool())
+        .await?;
+        Ok(rows.into_iter().map(|(issuer,)| issuer).collect())
+    }
+
+    pub async fn retrain_weekly(service: &RwLock<MLService>) -> anyhow::Result<()> {
+        tracing::info!("Starting weekly model retraining...");
+        let model = retrain(service).await?;
+
+        tracing::info!(
+            "Model retrained successfully. Version: {} (active: {})",
+            model.version,
+            model.is_active
         );
         Ok(())
     }
 }
+
+/// Train a model on a copy of the shared service, then promote it; the
+/// service is only locked to copy it and to swap in the new model
+pub async fn retrain(service: &RwLock<MLService>) -> anyhow::Result<ModelInfo> {
+    let trainer = service.read().await.training_copy();
+    let version = trainer.fit_model().await?;
+    service.write().await.promote_model(&version).await
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct CorridorPool {
+    pub pool_id: String,
+    pub fee_bp: i64,
+    pub total_value_usd: f64,
+}
+
+#[derive(Debug, FromRow)]
+struct HourlyRow {
+    corridor_key: String,
+    asset_a_code: String,
+    asset_a_issuer: String,
+    asset_b_code: String,
+    asset_b_issuer: String,
+    hour_bucket: String,
+    total_transactions: i64,
+    successful_transactions: i64,
+    volume_usd: Option<f64>,
+    liquidity_depth_usd: Option<f64>,
+}
+
+struct HourlyObservation {
+    hour: DateTime<Utc>,
+    total: f64,
+    successes: f64,
+    liquidity_depth_usd: f64,
+}
+
+fn trailing_success_rate(history: &VecDeque<HourlyObservation>) -> Option<f32> {
+    let total: f64 = history.iter().map(|o| o.total).sum();
+    let successes: f64 = history.iter().map(|o| o.successes).sum();
+    (total > 0.0).then(|| (successes / total) as f32)
+}
+
+#[derive(Default)]
+struct PaymentActivity {
+    /// Asset key -> hour (`YYYY-MM-DDTHH`) -> payments
+    by_asset: HashMap<String, BTreeMap<String, i64>>,
+}
+
+impl PaymentActivity {
+    /// Payments of either asset in the 24 hours before `hour`
+    fn count_before(&self, assets: [(&str, &str); 2], hour: DateTime<Utc>) -> f64 {
+        let from = hour_key(hour - Duration::hours(LOOKBACK_HOURS));
+        let to = hour_key(hour);
+        let mut keys: Vec<String> = assets
+            .iter()
+            .map(|(code, issuer)| asset_key(code, issuer))
+            .collect();
+        keys.dedup();
+        keys.iter()
+            .filter_map(|key| self.by_asset.get(key))
+            .flat_map(|hours| hours.range(from.clone()..to.clone()).map(|(_, c)| *c))
+            .sum::<i64>() as f64
+    }
+}
+
+/// pool_id, reserve A code and issuer, reserve B code and issuer
+type PoolAssetsRow = (String, String, Option<String>, String, Option<String>);
+
+struct PoolHistory {
+    assets: (String, String),
+    /// Value in USD, in time order
+    snapshots: Vec<(DateTime<Utc>, f64)>,
+}
+
+#[derive(Default)]
+struct PoolLiquidity {
+    pools: HashMap<String, PoolHistory>,
+}
+
+impl PoolLiquidity {
+    /// Value of the pools holding both assets, from each pool's latest
+    /// snapshot at or before `at`
+    fn liquidity_at(&self, assets: [(&str, &str); 2], at: DateTime<Utc>) -> f64 {
+        let a = asset_key(assets[0].0, assets[0].1);
+        let b = asset_key(assets[1].0, assets[1].1);
+        self.pools
+            .values()
+            .filter(|p| {
+                let (x, y) = &p.assets;
+                (*x == a && *y == b) || (*x == b && *y == a)
+            })
+            .filter_map(|p| {
+                let idx = p.snapshots.partition_point(|(t, _)| *t <= at);
+                idx.checked_sub(1).map(|i| p.snapshots[i].1)
+            })
+            .sum()
+    }
+}
+
+/// Parse `CODE:ISSUER->CODE:ISSUER`; a bare `XLM` or `native` is the native asset
+pub fn parse_corridor(corridor: &str) -> Option<Corridor> {
+    let (source, destination) = corridor.split_once("->")?;
+    let (a_code, a_issuer) = parse_asset(source)?;
+    let (b_code, b_issuer) = parse_asset(destination)?;
+    Some(Corridor::new(a_code, a_issuer, b_code, b_issuer))
+}
+
+/// Parse `CODE:ISSUER`, `XLM` or `native` into code and issuer
+pub fn parse_asset(asset: &str) -> Option<(String, String)> {
+    let asset = asset.trim();
+    match asset.split_once(':') {
+        Some((code, issuer)) if !code.is_empty() && !issuer.is_empty() => {
+            Some((code.to_string(), issuer.to_string()))
+        }
+        None if asset.eq_ignore_ascii_case("xlm") || asset.eq_ignore_ascii_case("native") => {
+            Some(("XLM".to_string(), "native".to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn log_scale(value: f64) -> f32 {
+    (1.0 + value.max(0.0)).log10() as f32
+}
+
+fn hour_key(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%dT%H").to_string()
+}
@@ -1,5 +1,11 @@
-use crate::ml::{MLService, PredictionResult};
-use axum::{extract::Query, http::StatusCode, response::Json, Extension};
+use crate::ml::{parse_corridor, retrain, MLService, ModelInfo, PredictionResult};
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+    Extension, Router,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -7,6 +13,7 @@ use tokio::sync::RwLock;
 
 #[derive(Debug, Deserialize)]
 pub struct PredictionQuery {
+    /// `CODE:ISSUER->CODE:ISSUER`
     pub corridor: String,
     pub amount_usd: f64,
     #[serde(default = "default_timestamp")]
@@ -21,6 +28,7 @@ fn default_timestamp() -> DateTime<Utc> {
 pub struct PredictionResponse {
     pub success_probability: f32,
     pub confidence: f32,
+    pub confidence_interval: (f32, f32),
     pub model_version: String,
     pub risk_level: String,
     pub recommendation: String,
@@ -43,6 +51,7 @@ impl From<PredictionResult> for PredictionResponse {
         Self {
             success_probability: result.success_probability,
             confidence: result.confidence,
+            confidence_interval: result.confidence_interval,
             model_version: result.model_version,
             risk_level: risk_level.to_string(),
             recommendation: recommendation.to_string(),
@@ -54,6 +63,9 @@ pub async fn predict_payment_success(
     Query(query): Query<PredictionQuery>,
     Extension(ml_service): Extension<Arc<RwLock<MLService>>>,
 ) -> Result<Json<PredictionResponse>, StatusCode> {
+    if parse_corridor(&query.corridor).is_none() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
     let service = ml_service.read().await;
 
     match service
@@ -68,32 +80,87 @@ pub async fn predict_payment_success(
 #[derive(Debug, Serialize)]
 pub struct ModelStatusResponse {
     pub version: String,
-    pub last_trained: String,
-    pub accuracy: f32,
+    /// `None` while the untrained baseline is in use
+    pub last_trained: Option<String>,
+    pub auc: Option<f64>,
+    pub log_loss: Option<f64>,
+    pub training_window_start: Option<String>,
+    pub training_window_end: Option<String>,
     pub total_predictions: u64,
 }
 
 pub async fn get_model_status(
-    Extension(_ml_service): Extension<Arc<RwLock<MLService>>>,
+    Extension(ml_service): Extension<Arc<RwLock<MLService>>>,
 ) -> Json<ModelStatusResponse> {
+    let service = ml_service.read().await;
+    let active = service.active_model();
+
     Json(ModelStatusResponse {
-        version: "1.0.0".to_string(),
-        last_trained: Utc::now().format("%Y-%m-%d").to_string(),
-        accuracy: 0.87,
-        total_predictions: 1000,
+        version: service.model_version().to_string(),
+        last_trained: active.map(|m| m.trained_at.clone()),
+        auc: active.and_then(|m| m.auc),
+        log_loss: active.and_then(|m| m.log_loss),
+        training_window_start: active.map(|m| m.window_start.clone()),
+        training_window_end: active.map(|m| m.window_end.clone()),
+        total_predictions: service.total_predictions(),
     })
 }
 
-pub async fn retrain_model(
+pub async fn list_models(
+    Extension(ml_service): Extension<Arc<RwLock<MLService>>>,
+) -> Result<Json<Vec<ModelInfo>>, StatusCode> {
+    let service = ml_service.read().await;
+
+    match service.list_models().await {
+        Ok(models) => Ok(Json(models)),
+        Err(e) => {
+            tracing::error!("Failed to list models: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn activate_model(
+    Path(version): Path<String>,
     Extension(ml_service): Extension<Arc<RwLock<MLService>>>,
-) -> Result<Json<serde_json::Value>, StatusCode> {
+) -> Result<Json<ModelInfo>, StatusCode> {
     let mut service = ml_service.write().await;
 
-    match service.retrain_weekly().await {
-        Ok(_) => Ok(Json(serde_json::json!({
-            "status": "success",
-            "message": "Model retrained successfully"
-        }))),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    match service.activate_model(&version).await {
+        Ok(Some(model)) => Ok(Json(model)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(e) => {
+            tracing::error!("Failed to activate model {}: {}", version, e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+pub async fn retrain_model(
+    Extension(ml_service): Extension<Arc<RwLock<MLService>>>,
+) -> Result<Json<ModelInfo>, StatusCode> {
+    match retrain(&ml_service).await {
+        Ok(model) => Ok(Json(model)),
+        Err(e) => {
+            tracing::error!("Model retraining failed: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
     }
 }
+
+/// Public prediction and model status routes
+pub fn routes(ml_service: Arc<RwLock<MLService>>) -> Router {
+    Router::new()
+        .route("/predict", get(predict_payment_success))
+        .route("/status", get(get_model_status))
+        .route("/models", get(list_models))
+        .layer(Extension(ml_service))
+}
+
+/// Training and model selection routes, mounted behind the admin whitelist
+pub fn admin_routes(ml_service: Arc<RwLock<MLService>>) -> Router {
+    Router::new()
+        .route("/retrain", post(retrain_model))
+        .route("/models/:version/activate", post(activate_model))
+        .layer(Extension(ml_service))
+}
@@ -3,16 +3,16 @@ use crate::ml::PredictionFeatures;
 #[tokio::test]
 async fn test_ml_prediction() {
     let features = PredictionFeatures {
-        corridor_hash: 0.5,
-        amount_usd: 2.0,      // log10(100)
-        hour_of_day: 0.5,     // 12 PM
-        day_of_week: 0.3,     // Tuesday
-        liquidity_depth: 3.0, // log10(1000)
+        payment_activity: 3.0, // log10(1000)
+        amount_usd: 2.0,       // log10(100)
+        hour_of_day: 0.5,      // 12 PM
+        day_of_week: 0.3,      // Tuesday
+        liquidity_depth: 3.0,  // log10(1000)
         recent_success_rate: 0.85,
     };
 
     // Test that features are in expected ranges
-    assert!(features.corridor_hash >= 0.0 && features.corridor_hash <= 1.0);
+    assert!(features.payment_activity >= 0.0);
     assert!(features.hour_of_day >= 0.0 && features.hour_of_day <= 1.0);
     assert!(features.day_of_week >= 0.0 && features.day_of_week <= 1.0);
     assert!(features.recent_success_rate >= 0.0 && features.recent_success_rate <= 1.0);
@@ -26,6 +26,7 @@ fn test_prediction_result_risk_levels() {
     let high_prob = PredictionResult {
         success_probability: 0.9,
         confidence: 0.8,
+        confidence_interval: (0.8, 0.95),
         model_version: "1.0.0".to_string(),
     };
 
@@ -36,6 +37,7 @@ fn test_prediction_result_risk_levels() {
     let low_prob = PredictionResult {
         success_probability: 0.3,
         confidence: 0.8,
+        confidence_interval: (0.8, 0.95),
         model_version: "1.0.0".to_string(),
     };
 
@@ -50,7 +52,7 @@ fn test_simple_model_prediction() {
 
     let model = SimpleMLModel::new();
     let features = PredictionFeatures {
-        corridor_hash: 0.5,
+        payment_activity: 3.0,
         amount_usd: 2.0,
         hour_of_day: 0.5,
         day_of_week: 0.3,
@@ -63,3 +65,99 @@ fn test_simple_model_prediction() {
     assert!(result.confidence >= 0.0 && result.confidence <= 1.0);
     assert_eq!(result.model_version, "1.0.0");
 }
+
+#[test]
+fn test_training_separates_corridors() {
+    use crate::ml::{MLTrainingConfig, SimpleMLModel, TrainingSample};
+
+    // Corridors with a high trailing success rate keep succeeding
+    let samples: Vec<TrainingSample> = (0..200)
+        .map(|i| {
+            let good = i % 2 == 0;
+            let recent = if good { 0.95 } else { 0.55 };
+            TrainingSample {
+                features: vec![3.0, 2.0, (i % 24) as f32 / 24.0, 0.3, 4.0, recent],
+                successes: if good { 19.0 } else { 11.0 },
+                failures: if good { 1.0 } else { 9.0 },
+            }
+        })
+        .collect();
+
+    let mut model = SimpleMLModel::new();
+    model.train(&samples, &MLTrainingConfig::default());
+    assert!(model.is_trained());
+
+    let (auc, log_loss) = model.evaluate(&samples);
+    assert!(auc.unwrap() > 0.7);
+    // Better than always predicting the base rate (0.75)
+    let base_rate_loss = -(0.75_f64 * 0.75_f64.ln() + 0.25 * 0.25_f64.ln());
+    assert!(log_loss.unwrap() < base_rate_loss);
+
+    let features = |recent: f32| PredictionFeatures {
+        payment_activity: 3.0,
+        amount_usd: 2.0,
+        hour_of_day: 0.5,
+        day_of_week: 0.3,
+        liquidity_depth: 4.0,
+        recent_success_rate: recent,
+    };
+    let good = model.predict(features(0.95)).success_probability;
+    let bad = model.predict(features(0.55)).success_probability;
+    assert!((good - 0.95).abs() < 0.05);
+    assert!((bad - 0.55).abs() < 0.05);
+}
+
+#[test]
+fn test_weighted_auc() {
+    use crate::ml::weighted_auc;
+
+    // (score, successes, failures)
+    assert_eq!(weighted_auc(&[(0.9, 1.0, 0.0), (0.1, 0.0, 1.0)]), Some(1.0));
+    assert_eq!(weighted_auc(&[(0.1, 1.0, 0.0), (0.9, 0.0, 1.0)]), Some(0.0));
+    assert_eq!(weighted_auc(&[(0.5, 3.0, 1.0)]), Some(0.5));
+    assert_eq!(weighted_auc(&[(0.5, 3.0, 0.0)]), None);
+}
+
+#[test]
+fn test_parse_corridor_normalizes_order() {
+    use crate::ml::parse_corridor;
+
+    let corridor = parse_corridor("USDC:GISSUER->XLM").unwrap();
+    assert_eq!(corridor.to_string_key(), "USDC:GISSUER->XLM:native");
+    assert_eq!(
+        parse_corridor("XLM:native->USDC:GISSUER")
+            .unwrap()
+            .to_string_key(),
+        corridor.to_string_key()
+    );
+    assert!(parse_corridor("USDC-XLM").is_none());
+    assert!(parse_corridor("USDC->XLM").is_none());
+}
+
+#[test]
+fn test_confidence_interval_from_calibration() {
+    use crate::ml::{confidence_interval, CalibrationBin};
+
+    // No held-out data: no claim
+    assert_eq!(confidence_interval(0.7, &[]), (0.0, 1.0));
+
+    let mut bins = vec![CalibrationBin::default(); 10];
+    bins[9] = CalibrationBin {
+        samples: 200,
+        successes: 1900.0,
+        failures: 100.0,
+    };
+    let (lower, upper) = confidence_interval(0.95, &bins);
+    assert!(lower < 0.95 && upper > 0.95);
+    assert!(upper - lower < 0.1);
+
+    // Few observations give a wider interval
+    bins[9].samples = 5;
+    let (few_lower, few_upper) = confidence_interval(0.95, &bins);
+    assert!(few_upper - few_lower > upper - lower);
+
+    // The interval always contains the prediction
+    let (lower, upper) = confidence_interval(0.99, &bins);
+    assert!(lower <= 0.99 && upper >= 0.99);
+    assert_eq!(confidence_interval(0.35, &bins), (0.0, 1.0));
+}
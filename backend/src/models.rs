@@ -125,6 +125,9 @@ pub struct AnchorDetailResponse {
     pub anchor: Anchor,
     pub assets: Vec<Asset>,
     pub metrics_history: Vec<AnchorMetricsHistory>,
+    /// Unclaimed claimable balances of the anchor's assets
+    #[serde(default)]
+    pub unclaimed_balances: Vec<crate::services::claimable_balance_tracker::UnclaimedAssetValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -196,6 +199,9 @@ pub struct PaymentRecord {
     pub submission_time: Option<DateTime<Utc>>,
     #[sqlx(default)]
     pub confirmation_time: Option<DateTime<Utc>>,
+    /// Network the payment was ingested from
+    #[sqlx(default)]
+    pub network: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -6,7 +6,7 @@ pub struct AlertRule {
     pub id: String,
     pub user_id: String,
     pub corridor_id: Option<String>,
-    pub metric_type: String, // e.g., "success_rate", "latency", "liquidity"
+    pub metric_type: String, // e.g., "success_rate", "latency", "liquidity", "anomaly"
     pub condition: String,   // e.g., "above", "below", "equals"
     pub threshold: f64,
     pub notify_email: bool,
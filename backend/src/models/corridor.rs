@@ -1,6 +1,5 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::FromRow)]
 pub struct Corridor {
@@ -95,7 +94,8 @@ pub struct CorridorAnalytics {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentRecord {
-    pub id: Uuid,
+    /// Horizon operation id
+    pub id: String,
     pub source_asset_code: String,
     pub source_asset_issuer: String,
     pub destination_asset_code: String,
@@ -150,6 +150,7 @@ pub fn compute_median(values: &mut [i64]) -> Option<i64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use uuid::Uuid;
 
     #[test]
     fn test_corridor_normalization() {
@@ -205,7 +206,7 @@ mod tests {
     #[test]
     fn test_payment_record_get_corridor() {
         let payment = PaymentRecord {
-            id: Uuid::new_v4(),
+            id: Uuid::new_v4().to_string(),
             source_asset_code: "USDC".to_string(),
             source_asset_issuer: "issuer1".to_string(),
             destination_asset_code: "EURC".to_string(),
@@ -228,7 +229,7 @@ mod tests {
         let submitted = now - chrono::Duration::milliseconds(1500);
 
         let payment = PaymentRecord {
-            id: Uuid::new_v4(),
+            id: Uuid::new_v4().to_string(),
             source_asset_code: "USDC".to_string(),
             source_asset_issuer: "issuer1".to_string(),
             destination_asset_code: "EURC".to_string(),
@@ -246,7 +247,7 @@ mod tests {
     #[test]
     fn test_payment_record_settlement_latency_missing_times() {
         let payment = PaymentRecord {
-            id: Uuid::new_v4(),
+            id: Uuid::new_v4().to_string(),
             source_asset_code: "USDC".to_string(),
             source_asset_issuer: "issuer1".to_string(),
             destination_asset_code: "EURC".to_string(),
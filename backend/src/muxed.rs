@@ -4,12 +4,12 @@
 //! sub-accounts via a 64-bit muxed ID. M-addresses are 69 characters and start with 'M'.
 //! See SEP-0023 and [Stellar Muxed Accounts FAQ](https://stellar.org/blog/developers/muxed-accounts-faq).
 
-use data_encoding::BASE32;
+use data_encoding::BASE32_NOPAD;
 use serde::{Deserialize, Serialize};
 
-/// Stellar strkey version bytes
-const VERSION_ACCOUNT_ID: u8 = 6; // G-address
-const VERSION_MUXED_ACCOUNT: u8 = 12; // M-address
+/// Stellar strkey version bytes (base32 index shifted into the top 5 bits)
+const VERSION_ACCOUNT_ID: u8 = 6 << 3; // G-address
+const VERSION_MUXED_ACCOUNT: u8 = 12 << 3; // M-address
 
 /// Length of a Stellar M-address (MUXED_ACCOUNT strkey)
 pub const MUXED_ADDRESS_LEN: usize = 69;
@@ -73,7 +73,7 @@ pub fn parse_muxed_address(addr: &str) -> Option<MuxedAccountInfo> {
         return None;
     }
 
-    let decoded = BASE32.decode(addr.as_bytes()).ok()?;
+    let decoded = BASE32_NOPAD.decode(addr.as_bytes()).ok()?;
     // Muxed: version(1) + account_id(32) + muxed_id(8) + checksum(2) = 43 bytes
     if decoded.len() != 43 {
         return None;
@@ -81,7 +81,8 @@ pub fn parse_muxed_address(addr: &str) -> Option<MuxedAccountInfo> {
     if decoded[0] != VERSION_MUXED_ACCOUNT {
         return None;
     }
-    let checksum = u16::from_be_bytes([decoded[41], decoded[42]]);
+    // Strkey checksums are little-endian
+    let checksum = u16::from_le_bytes([decoded[41], decoded[42]]);
     let payload = &decoded[0..41];
     if crc16(payload) != checksum {
         return None;
@@ -94,10 +95,9 @@ pub fn parse_muxed_address(addr: &str) -> Option<MuxedAccountInfo> {
     let mut g_payload = [0u8; 35];
     g_payload[0] = VERSION_ACCOUNT_ID;
     g_payload[1..33].copy_from_slice(account_id);
-    let c = crc16(&g_payload);
-    g_payload[33] = (c >> 8) as u8;
-    g_payload[34] = (c & 0xff) as u8;
-    let base_account = BASE32.encode(&g_payload);
+    let c = crc16(&g_payload[0..33]);
+    g_payload[33..35].copy_from_slice(&c.to_le_bytes());
+    let base_account = BASE32_NOPAD.encode(&g_payload);
 
     Some(MuxedAccountInfo {
         muxed_address: addr.to_string(),
@@ -165,5 +165,16 @@ mod tests {
         }
         // Too short M string
         assert!(parse_muxed_address("M").is_none());
+
+        // SEP-23 test vector for GA7QYNF7...
+        let info = parse_muxed_address(
+            "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVAAAAAAAAAAAAAJLK",
+        )
+        .unwrap();
+        assert_eq!(
+            info.base_account.as_deref(),
+            Some("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ")
+        );
+        assert_eq!(info.muxed_id, Some(9_223_372_036_854_775_808));
     }
 }
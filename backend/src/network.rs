@@ -37,6 +37,10 @@ pub struct NetworkConfig {
     pub network: StellarNetwork,
     pub rpc_url: String,
     pub horizon_url: String,
+    /// Additional RPC endpoints tried when the primary is slow or failing
+    pub rpc_fallback_urls: Vec<String>,
+    /// Additional Horizon endpoints tried when the primary is slow or failing
+    pub horizon_fallback_urls: Vec<String>,
     pub network_passphrase: String,
 }
 
@@ -76,10 +80,23 @@ impl NetworkConfig {
             ),
         };
 
+        let (rpc_fallback_var, horizon_fallback_var) = match network {
+            StellarNetwork::Mainnet => (
+                "STELLAR_RPC_FALLBACK_URLS_MAINNET",
+                "STELLAR_HORIZON_FALLBACK_URLS_MAINNET",
+            ),
+            StellarNetwork::Testnet => (
+                "STELLAR_RPC_FALLBACK_URLS_TESTNET",
+                "STELLAR_HORIZON_FALLBACK_URLS_TESTNET",
+            ),
+        };
+
         Self {
             network,
             rpc_url,
             horizon_url,
+            rpc_fallback_urls: url_list_from_env(rpc_fallback_var),
+            horizon_fallback_urls: url_list_from_env(horizon_fallback_var),
             network_passphrase,
         }
     }
@@ -116,6 +133,22 @@ impl NetworkConfig {
     }
 }
 
+/// Parse a comma-separated list of URLs, ignoring blank entries
+fn parse_url_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn url_list_from_env(key: &str) -> Vec<String> {
+    std::env::var(key)
+        .map(|v| parse_url_list(&v))
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,4 +193,13 @@ mod tests {
         assert!(testnet_config.is_testnet());
         assert_eq!(testnet_config.display_name(), "Stellar Testnet");
     }
+
+    #[test]
+    fn test_parse_url_list() {
+        assert_eq!(
+            parse_url_list(" https://a.example, ,https://b.example/ "),
+            vec!["https://a.example", "https://b.example/"]
+        );
+        assert!(parse_url_list("").is_empty());
+    }
 }
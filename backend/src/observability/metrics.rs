@@ -26,6 +26,8 @@ struct MetricsState {
     errors_total: Mutex<HashMap<String, u64>>,
     db_query_duration_seconds: Mutex<HashMap<String, DurationSeries>>,
     background_jobs_total: Mutex<HashMap<String, u64>>,
+    ledger_processor_runs_total: Mutex<HashMap<String, u64>>,
+    ledger_processor_duration_seconds: Mutex<HashMap<String, DurationSeries>>,
     active_connections: AtomicI64,
     corridors_tracked: AtomicI64,
     http_in_flight_requests: AtomicI64,
@@ -205,6 +207,34 @@ pub async fn metrics_handler() -> Response {
         ));
     }
 
+    out.push_str(
+        "# HELP ledger_processor_runs_total Ledger processor runs by processor and status\n",
+    );
+    out.push_str("# TYPE ledger_processor_runs_total counter\n");
+    for (key, value) in snapshot_counters(&metrics.ledger_processor_runs_total) {
+        out.push_str(&format!(
+            "ledger_processor_runs_total{} {}\n",
+            key_to_prom_labels(&key),
+            value
+        ));
+    }
+
+    out.push_str(
+        "# HELP ledger_processor_duration_seconds Ledger processor run duration in seconds\n",
+    );
+    out.push_str("# TYPE ledger_processor_duration_seconds summary\n");
+    for (key, series) in snapshot_durations(&metrics.ledger_processor_duration_seconds) {
+        let labels = key_to_prom_labels(&key);
+        out.push_str(&format!(
+            "ledger_processor_duration_seconds_count{} {}\n",
+            labels, series.count
+        ));
+        out.push_str(&format!(
+            "ledger_processor_duration_seconds_sum{} {}\n",
+            labels, series.sum
+        ));
+    }
+
     out.push_str("# HELP active_connections Active websocket connections\n");
     out.push_str("# TYPE active_connections gauge\n");
     out.push_str(&format!(
@@ -309,6 +339,16 @@ pub fn record_background_job(job: &str, status: &str) {
     );
 }
 
+pub fn record_ledger_processor(processor: &str, status: &str, duration_seconds: f64) {
+    let key = make_key(&[("processor", processor), ("status", status)]);
+    inc_counter(&state().ledger_processor_runs_total, key.clone());
+    observe_duration(
+        &state().ledger_processor_duration_seconds,
+        key,
+        duration_seconds,
+    );
+}
+
 pub fn set_corridors_tracked(count: i64) {
     state().corridors_tracked.store(count, Ordering::Relaxed);
 }
@@ -38,6 +38,8 @@ use utoipa::OpenApi;
             crate::api::corridors_cached::SuccessRateDataPoint,
             crate::api::corridors_cached::LatencyDataPoint,
             crate::api::corridors_cached::LiquidityDataPoint,
+            crate::services::corridor_health::HealthBreakdown,
+            crate::services::corridor_health::HealthComponent,
             crate::api::price_feed::PriceResponse,
             crate::api::price_feed::PricesResponse,
             crate::api::price_feed::ConvertResponse,
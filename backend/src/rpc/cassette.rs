@@ -0,0 +1,379 @@
+//! Record/replay cassettes for `StellarRpcClient`.
+//!
+//! In record mode every HTTP exchange with RPC and Horizon is appended to a
+//! JSON Lines cassette file, one interaction per line. In replay mode requests are answered from that file and
+//! nothing goes out on the network, so tests and demos run on real response
+//! shapes deterministically. Requests match on method, path, query and body;
+//! the host is ignored so a cassette recorded against one endpoint replays
+//! against any. Identical requests replay their recorded responses in order
+//! and then keep returning the last one. Horizon SSE streams are not recorded.
+
+use anyhow::{bail, Context, Result};
+use reqwest::{Client, Request, Response, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+
+use crate::rpc::error::RpcError;
+
+const DEFAULT_CASSETTE_PATH: &str = "cassettes/stellar_rpc.jsonl";
+
+/// Query parameters that may carry credentials; their values are never written to disk
+const REDACTED_QUERY_PARAMS: &[&str] = &["api_key", "apikey", "key", "token", "access_token"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteMode {
+    Record,
+    Replay,
+}
+
+/// One recorded request/response pair
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub method: String,
+    /// Path and query, without scheme and host
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    pub status: u16,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    pub response_body: String,
+}
+
+impl Interaction {
+    fn key(&self) -> String {
+        interaction_key(&self.method, &self.path, self.request_body.as_deref())
+    }
+
+    fn to_response(&self) -> Result<Response, RpcError> {
+        let mut builder = http::Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        builder
+            .body(self.response_body.clone())
+            .map(Response::from)
+            .map_err(|e| RpcError::ParseError(format!("Invalid cassette interaction: {}", e)))
+    }
+}
+
+pub struct Cassette {
+    mode: CassetteMode,
+    path: PathBuf,
+    interactions: Mutex<Vec<Interaction>>,
+    /// Next interaction to serve for each request key in replay mode
+    positions: Mutex<HashMap<String, usize>>,
+    /// Serializes appends so recorded lines never interleave
+    writer: tokio::sync::Mutex<()>,
+}
+
+impl Cassette {
+    /// Start a new cassette; the first recorded exchange replaces any existing
+    /// file and later ones are appended to it
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            mode: CassetteMode::Record,
+            path: path.into(),
+            interactions: Mutex::new(Vec::new()),
+            positions: Mutex::new(HashMap::new()),
+            writer: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Load a previously recorded cassette
+    pub fn replay(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read cassette {}", path.display()))?;
+        let interactions = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line)
+                    .with_context(|| format!("Invalid cassette {} line {}", path.display(), i + 1))
+            })
+            .collect::<Result<Vec<Interaction>>>()?;
+        Ok(Self {
+            mode: CassetteMode::Replay,
+            path,
+            interactions: Mutex::new(interactions),
+            positions: Mutex::new(HashMap::new()),
+            writer: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    /// Cassette configured by `RPC_CASSETTE_MODE` (`off`, `record` or `replay`)
+    /// and `RPC_CASSETTE_PATH`
+    pub fn from_env() -> Result<Option<Arc<Self>>> {
+        let mode = std::env::var("RPC_CASSETTE_MODE").unwrap_or_default();
+        let path =
+            std::env::var("RPC_CASSETTE_PATH").unwrap_or_else(|_| DEFAULT_CASSETTE_PATH.into());
+
+        match mode.trim().to_ascii_lowercase().as_str() {
+            "" | "off" => Ok(None),
+            "record" => Ok(Some(Arc::new(Self::record(path)))),
+            "replay" => Ok(Some(Arc::new(Self::replay(path)?))),
+            other => bail!(
+                "Invalid RPC_CASSETTE_MODE: {}. Must be 'off', 'record' or 'replay'",
+                other
+            ),
+        }
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Send the request (record) or answer it from the cassette (replay)
+    pub(crate) async fn exchange(
+        &self,
+        client: &Client,
+        request: Request,
+    ) -> Result<Response, RpcError> {
+        let method = request.method().to_string();
+        let path = request_path(request.url());
+        let request_body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(|b| String::from_utf8_lossy(b).into_owned());
+
+        match self.mode {
+            CassetteMode::Replay => self
+                .next_match(&interaction_key(&method, &path, request_body.as_deref()))
+                .ok_or_else(|| RpcError::ServerError {
+                    status: 404,
+                    message: format!("No recorded interaction for {} {}", method, path),
+                })?
+                .to_response(),
+            CassetteMode::Record => {
+                let response = client.execute(request).await.map_err(transport_error)?;
+                let status = response.status().as_u16();
+                let headers = recorded_headers(response.headers());
+                let response_body = response.text().await.map_err(transport_error)?;
+
+                let interaction = Interaction {
+                    method,
+                    path,
+                    request_body,
+                    status,
+                    headers,
+                    response_body,
+                };
+                let response = interaction.to_response();
+                if let Err(e) = self.append(interaction).await {
+                    warn!("Failed to write cassette {}: {:#}", self.path.display(), e);
+                }
+                response
+            }
+        }
+    }
+
+    fn next_match(&self, key: &str) -> Option<Interaction> {
+        let interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+        let matches: Vec<&Interaction> = interactions.iter().filter(|i| i.key() == key).collect();
+        let last = matches.len().checked_sub(1)?;
+
+        let mut positions = self.positions.lock().unwrap_or_else(|e| e.into_inner());
+        let position = positions.entry(key.to_string()).or_insert(0);
+        let interaction = matches[(*position).min(last)].clone();
+        *position += 1;
+        Some(interaction)
+    }
+
+    async fn append(&self, interaction: Interaction) -> Result<()> {
+        let mut line = serde_json::to_string(&interaction)?;
+        line.push('\n');
+
+        let _writer = self.writer.lock().await;
+        let first = {
+            let mut interactions = self.interactions.lock().unwrap_or_else(|e| e.into_inner());
+            interactions.push(interaction);
+            interactions.len() == 1
+        };
+
+        if first {
+            if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(!first)
+            .truncate(first)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await?;
+        if first {
+            info!("Recording RPC cassette to {}", self.path.display());
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn transport_error(e: reqwest::Error) -> RpcError {
+    if e.is_timeout() {
+        RpcError::TimeoutError(e.to_string())
+    } else {
+        RpcError::NetworkError(e.to_string())
+    }
+}
+
+fn interaction_key(method: &str, path: &str, body: Option<&str>) -> String {
+    format!("{} {} {}", method, path, body.unwrap_or_default())
+}
+
+/// Path and query of a URL with credential-like query values redacted
+fn request_path(url: &Url) -> String {
+    let mut path = url.path().to_string();
+    let query: Vec<String> = url
+        .query_pairs()
+        .map(|(name, value)| {
+            if REDACTED_QUERY_PARAMS.contains(&name.to_ascii_lowercase().as_str()) {
+                format!("{}=REDACTED", name)
+            } else {
+                format!("{}={}", name, value)
+            }
+        })
+        .collect();
+    if !query.is_empty() {
+        path.push('?');
+        path.push_str(&query.join("&"));
+    }
+    path
+}
+
+/// Headers the client reads from responses; everything else is dropped
+fn recorded_headers(headers: &reqwest::header::HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| {
+            let name = name.as_str();
+            name == "content-type" || name == "retry-after" || name.starts_with("x-ratelimit")
+        })
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interaction(path: &str, body: &str) -> Interaction {
+        Interaction {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            request_body: None,
+            status: 200,
+            headers: BTreeMap::from([("content-type".to_string(), "application/json".to_string())]),
+            response_body: body.to_string(),
+        }
+    }
+
+    fn replay_cassette(interactions: Vec<Interaction>) -> Cassette {
+        Cassette {
+            mode: CassetteMode::Replay,
+            path: PathBuf::from("unused.json"),
+            interactions: Mutex::new(interactions),
+            positions: Mutex::new(HashMap::new()),
+            writer: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    #[test]
+    fn request_path_ignores_host_and_redacts_credentials() {
+        let url = Url::parse("https://rpc.example.com/v1?apikey=secret&limit=5").unwrap();
+        assert_eq!(request_path(&url), "/v1?apikey=REDACTED&limit=5");
+
+        let url = Url::parse("http://localhost:8000/payments").unwrap();
+        assert_eq!(request_path(&url), "/payments");
+    }
+
+    #[test]
+    fn repeated_requests_replay_in_order_then_repeat_last() {
+        let cassette = replay_cassette(vec![
+            interaction("/ledgers", "first"),
+            interaction("/payments", "payments"),
+            interaction("/ledgers", "second"),
+        ]);
+        let key = interaction_key("GET", "/ledgers", None);
+
+        let bodies: Vec<String> = (0..3)
+            .map(|_| cassette.next_match(&key).unwrap().response_body)
+            .collect();
+        assert_eq!(bodies, vec!["first", "second", "second"]);
+        assert!(cassette
+            .next_match(&interaction_key("GET", "/trades", None))
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn replay_serves_recorded_response_without_network() {
+        let cassette = replay_cassette(vec![interaction(
+            "/ledgers?order=desc&limit=1",
+            r#"{"ok":true}"#,
+        )]);
+        let client = Client::new();
+
+        let request = client
+            .get("http://unreachable.invalid/ledgers?order=desc&limit=1")
+            .build()
+            .unwrap();
+        let response = cassette.exchange(&client, request).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(
+            response.headers()["content-type"].to_str().unwrap(),
+            "application/json"
+        );
+        assert_eq!(response.text().await.unwrap(), r#"{"ok":true}"#);
+
+        let request = client
+            .get("http://unreachable.invalid/trades")
+            .build()
+            .unwrap();
+        let err = cassette.exchange(&client, request).await.unwrap_err();
+        assert!(matches!(err, RpcError::ServerError { status: 404, .. }));
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn record_appends_cassette_that_replays() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/cassette.jsonl");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "stale\n").unwrap();
+
+        let recorder = Cassette::record(&path);
+        recorder
+            .append(interaction("/ledgers", "recorded"))
+            .await
+            .unwrap();
+        recorder
+            .append(interaction("/payments", "appended"))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+        let replayer = Cassette::replay(&path).unwrap();
+        assert_eq!(replayer.mode(), CassetteMode::Replay);
+        assert_eq!(replayer.interactions(), recorder.interactions());
+    }
+}
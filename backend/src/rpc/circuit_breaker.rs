@@ -4,6 +4,7 @@
 //! fail fast. After a timeout, the circuit moves to half-open and allows
 //! a limited number of test requests; success closes the circuit.
 
+use serde::Serialize;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
@@ -37,6 +38,15 @@ enum CircuitState {
     HalfOpen { success_count: u32 },
 }
 
+/// Externally visible circuit state, used for health reporting and endpoint selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitStatus {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
 /// Circuit breaker for a single logical endpoint (e.g. Horizon API).
 #[derive(Clone)]
 pub struct CircuitBreaker {
@@ -81,6 +91,23 @@ impl CircuitBreaker {
         result
     }
 
+    /// Current state without side effects. An open circuit whose timeout has
+    /// elapsed reports half-open, since the next call will be let through.
+    pub async fn status(&self) -> CircuitStatus {
+        let state = self.state.lock().await;
+        match &*state {
+            CircuitState::Closed { .. } => CircuitStatus::Closed,
+            CircuitState::HalfOpen { .. } => CircuitStatus::HalfOpen,
+            CircuitState::Open { opened_at } => {
+                if opened_at.elapsed() >= self.config.timeout_duration {
+                    CircuitStatus::HalfOpen
+                } else {
+                    CircuitStatus::Open
+                }
+            }
+        }
+    }
+
     async fn is_open(&self) -> bool {
         let mut state = self.state.lock().await;
         let now = Instant::now();
@@ -185,6 +212,7 @@ mod tests {
 
         let r = cb.call(|| async { Ok(()) }).await;
         assert!(matches!(r, Err(RpcError::CircuitBreakerOpen)));
+        assert_eq!(cb.status().await, CircuitStatus::Open);
     }
 
     #[tokio::test]
@@ -0,0 +1,510 @@
+//! Pool of interchangeable RPC or Horizon endpoints with health-weighted failover.
+//!
+//! Every endpoint owns its circuit breaker and outbound rate limiter, so one
+//! failing provider never blocks the others. Requests go to the endpoint with
+//! the best score (average latency weighted by recent error rate) and fail
+//! over to the next candidate on retryable errors.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use crate::rpc::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitStatus};
+use crate::rpc::error::{RetryConfig, RpcError};
+use crate::rpc::rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
+
+/// Weight of the newest sample in the latency and error-rate moving averages
+const EWMA_ALPHA: f64 = 0.2;
+/// A 100% error rate multiplies an endpoint's effective latency by this factor plus one
+const ERROR_RATE_PENALTY: f64 = 10.0;
+/// Recorded error rate halves after this long without new samples, so an
+/// endpoint that stopped receiving traffic is eventually probed again
+const ERROR_RATE_HALF_LIFE_SECS: f64 = 60.0;
+/// Latency assumed for an endpoint that has only ever failed to respond
+const UNMEASURED_LATENCY_MS: f64 = 1_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointKind {
+    Rpc,
+    Horizon,
+}
+
+impl EndpointKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EndpointKind::Rpc => "rpc",
+            EndpointKind::Horizon => "horizon",
+        }
+    }
+}
+
+/// Point-in-time health of a single pooled endpoint, as reported by `/api/rpc/health`
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStatus {
+    pub name: String,
+    pub kind: EndpointKind,
+    /// Position in the configured list (0 = primary)
+    pub priority: usize,
+    pub circuit: CircuitStatus,
+    pub healthy: bool,
+    pub latency_ms: Option<f64>,
+    pub error_rate: f64,
+    /// Lower is better; endpoints are tried in ascending score order
+    pub score: f64,
+    pub total_requests: u64,
+    pub total_errors: u64,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_failure_at: Option<DateTime<Utc>>,
+    pub throttled_requests: u64,
+    pub rate_limited_responses: u64,
+}
+
+#[derive(Debug, Default)]
+struct EndpointStats {
+    latency_ewma_ms: Option<f64>,
+    error_rate: f64,
+    error_rate_updated: Option<Instant>,
+    total_requests: u64,
+    total_errors: u64,
+    consecutive_failures: u32,
+    last_error: Option<String>,
+    last_success_at: Option<DateTime<Utc>>,
+    last_failure_at: Option<DateTime<Utc>>,
+}
+
+impl EndpointStats {
+    fn decayed_error_rate(&self) -> f64 {
+        match self.error_rate_updated {
+            Some(updated) => {
+                let half_lives = updated.elapsed().as_secs_f64() / ERROR_RATE_HALF_LIFE_SECS;
+                self.error_rate * 0.5f64.powf(half_lives)
+            }
+            None => 0.0,
+        }
+    }
+
+    fn score(&self) -> f64 {
+        endpoint_score(
+            self.total_requests,
+            self.latency_ewma_ms,
+            self.decayed_error_rate(),
+        )
+    }
+
+    fn record(&mut self, latency: Option<Duration>, failure: Option<&RpcError>) {
+        self.total_requests += 1;
+
+        if let Some(latency) = latency {
+            let sample_ms = latency.as_secs_f64() * 1000.0;
+            self.latency_ewma_ms = Some(match self.latency_ewma_ms {
+                Some(avg) => avg + EWMA_ALPHA * (sample_ms - avg),
+                None => sample_ms,
+            });
+        }
+
+        let sample = if failure.is_some() { 1.0 } else { 0.0 };
+        let current = self.decayed_error_rate();
+        self.error_rate = current + EWMA_ALPHA * (sample - current);
+        self.error_rate_updated = Some(Instant::now());
+
+        match failure {
+            Some(e) => {
+                self.total_errors += 1;
+                self.consecutive_failures += 1;
+                self.last_error = Some(describe_error(e));
+                self.last_failure_at = Some(Utc::now());
+            }
+            None => {
+                self.consecutive_failures = 0;
+                self.last_success_at = Some(Utc::now());
+            }
+        }
+    }
+}
+
+/// Never-used endpoints score zero so they get probed once; afterwards the
+/// average latency is inflated in proportion to the recent error rate.
+fn endpoint_score(total_requests: u64, latency_ms: Option<f64>, error_rate: f64) -> f64 {
+    if total_requests == 0 {
+        return 0.0;
+    }
+    latency_ms.unwrap_or(UNMEASURED_LATENCY_MS) * (1.0 + ERROR_RATE_PENALTY * error_rate)
+}
+
+/// Error summary safe to expose publicly; transport errors can embed the full
+/// request URL, which for private providers may carry an API key.
+fn describe_error(e: &RpcError) -> String {
+    match e {
+        RpcError::ServerError { status, .. } => format!("{} ({})", e.error_type_label(), status),
+        _ => e.error_type_label().to_string(),
+    }
+}
+
+/// Host (and port) of an endpoint URL, used in logs, metrics and health output
+fn endpoint_name(url: &str, index: usize) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| {
+            u.host_str().map(|host| match u.port() {
+                Some(port) => format!("{}:{}", host, port),
+                None => host.to_string(),
+            })
+        })
+        .unwrap_or_else(|| format!("endpoint-{}", index))
+}
+
+/// A single RPC or Horizon base URL with its own breaker, limiter and stats
+pub struct PoolEndpoint {
+    kind: EndpointKind,
+    priority: usize,
+    url: String,
+    name: String,
+    circuit_breaker: Arc<CircuitBreaker>,
+    rate_limiter: RpcRateLimiter,
+    stats: Mutex<EndpointStats>,
+}
+
+impl PoolEndpoint {
+    /// Base URL without a trailing slash
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rate_limiter(&self) -> &RpcRateLimiter {
+        &self.rate_limiter
+    }
+
+    fn score(&self) -> f64 {
+        self.stats.lock().map(|s| s.score()).unwrap_or(f64::MAX)
+    }
+
+    fn record(&self, latency: Option<Duration>, failure: Option<&RpcError>) {
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.record(latency, failure);
+        }
+    }
+
+    async fn call<F, Fut, T>(self: &Arc<Self>, op: &F) -> Result<T, RpcError>
+    where
+        F: Fn(Arc<PoolEndpoint>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, RpcError>>,
+    {
+        let _permit = self
+            .rate_limiter
+            .acquire()
+            .await
+            .map_err(|_| RpcError::RateLimitError { retry_after: None })?;
+
+        let started = Instant::now();
+        let result = self.circuit_breaker.call(|| op(Arc::clone(self))).await;
+
+        match &result {
+            Ok(_) => self.record(Some(started.elapsed()), None),
+            Err(RpcError::CircuitBreakerOpen) => {}
+            // No response at all, so the elapsed time says nothing about latency
+            Err(e @ RpcError::NetworkError(_)) => self.record(None, Some(e)),
+            Err(e) if e.is_retryable() => self.record(Some(started.elapsed()), Some(e)),
+            // The endpoint answered; the request itself was bad
+            Err(_) => self.record(Some(started.elapsed()), None),
+        }
+
+        result
+    }
+
+    async fn status(&self) -> EndpointStatus {
+        let circuit = self.circuit_breaker.status().await;
+        let limiter = self.rate_limiter.metrics();
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+
+        EndpointStatus {
+            name: self.name.clone(),
+            kind: self.kind,
+            priority: self.priority,
+            circuit,
+            healthy: circuit == CircuitStatus::Closed && stats.consecutive_failures == 0,
+            latency_ms: stats.latency_ewma_ms.map(|ms| (ms * 10.0).round() / 10.0),
+            error_rate: (stats.decayed_error_rate() * 1000.0).round() / 1000.0,
+            score: (stats.score() * 10.0).round() / 10.0,
+            total_requests: stats.total_requests,
+            total_errors: stats.total_errors,
+            consecutive_failures: stats.consecutive_failures,
+            last_error: stats.last_error.clone(),
+            last_success_at: stats.last_success_at,
+            last_failure_at: stats.last_failure_at,
+            throttled_requests: limiter.throttled_requests,
+            rate_limited_responses: limiter.rate_limited_responses,
+        }
+    }
+}
+
+/// Ordered set of interchangeable endpoints of one kind. Clones share endpoint
+/// state, so every clone of a client sees the same health and circuit status.
+#[derive(Clone)]
+pub struct EndpointPool {
+    kind: EndpointKind,
+    endpoints: Vec<Arc<PoolEndpoint>>,
+}
+
+impl EndpointPool {
+    /// Build a pool from base URLs in priority order. Blank and duplicate URLs
+    /// are dropped; each remaining endpoint gets its own breaker and limiter.
+    pub fn new(
+        kind: EndpointKind,
+        urls: Vec<String>,
+        circuit_breaker_config: CircuitBreakerConfig,
+        rate_limit_config: RpcRateLimitConfig,
+    ) -> Self {
+        let mut seen: Vec<String> = Vec::new();
+        for url in urls {
+            let url = url.trim().trim_end_matches('/').to_string();
+            if !url.is_empty() && !seen.contains(&url) {
+                seen.push(url);
+            }
+        }
+
+        let endpoints = seen
+            .into_iter()
+            .enumerate()
+            .map(|(priority, url)| {
+                let name = endpoint_name(&url, priority);
+                Arc::new(PoolEndpoint {
+                    kind,
+                    priority,
+                    circuit_breaker: Arc::new(CircuitBreaker::new(
+                        circuit_breaker_config.clone(),
+                        format!("{}:{}", kind.as_str(), name),
+                    )),
+                    rate_limiter: RpcRateLimiter::new(rate_limit_config.clone()),
+                    stats: Mutex::new(EndpointStats::default()),
+                    name,
+                    url,
+                })
+            })
+            .collect();
+
+        Self { kind, endpoints }
+    }
+
+    pub fn kind(&self) -> EndpointKind {
+        self.kind
+    }
+
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    /// URL of the first configured endpoint
+    pub fn primary_url(&self) -> &str {
+        self.endpoints.first().map_or("", |e| e.url())
+    }
+
+    /// Endpoints in the order they should be tried: circuits that are open go
+    /// last, the rest by ascending score, ties broken by configured priority.
+    pub async fn ranked(&self) -> Vec<Arc<PoolEndpoint>> {
+        let mut candidates = Vec::with_capacity(self.endpoints.len());
+        for endpoint in &self.endpoints {
+            let open = endpoint.circuit_breaker.status().await == CircuitStatus::Open;
+            candidates.push((open, endpoint.score(), Arc::clone(endpoint)));
+        }
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        candidates.into_iter().map(|(_, _, e)| e).collect()
+    }
+
+    /// Run `op` against the best endpoint, failing over to the next one on
+    /// retryable errors or open circuits. Each endpoint is tried at most once.
+    pub async fn call<F, Fut, T>(&self, op: F) -> Result<T, RpcError>
+    where
+        F: Fn(Arc<PoolEndpoint>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, RpcError>>,
+    {
+        self.try_each(&op).await
+    }
+
+    /// Like [`call`](Self::call), repeating the whole failover pass with
+    /// exponential backoff while the last error is transient.
+    pub async fn execute<F, Fut, T>(&self, op: F, config: &RetryConfig) -> Result<T, RpcError>
+    where
+        F: Fn(Arc<PoolEndpoint>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, RpcError>>,
+    {
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            match self.try_each(&op).await {
+                Ok(val) => return Ok(val),
+                Err(e) => {
+                    if !e.is_transient() || attempt >= config.max_attempts {
+                        return Err(e);
+                    }
+
+                    let delay = std::cmp::min(
+                        config
+                            .base_delay_ms
+                            .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1))),
+                        config.max_delay_ms,
+                    );
+
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+            }
+        }
+    }
+
+    async fn try_each<F, Fut, T>(&self, op: &F) -> Result<T, RpcError>
+    where
+        F: Fn(Arc<PoolEndpoint>) -> Fut,
+        Fut: std::future::Future<Output = Result<T, RpcError>>,
+    {
+        let mut last_error =
+            RpcError::NetworkError(format!("No {} endpoints configured", self.kind.as_str()));
+
+        for endpoint in self.ranked().await {
+            match endpoint.call(op).await {
+                Ok(val) => return Ok(val),
+                Err(e) if e.is_retryable() || matches!(e, RpcError::CircuitBreakerOpen) => {
+                    debug!(
+                        "{} endpoint {} failed ({}), trying next endpoint",
+                        self.kind.as_str(),
+                        endpoint.name(),
+                        e
+                    );
+                    last_error = e;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(last_error)
+    }
+
+    /// Health snapshot of every endpoint, in configured priority order
+    pub async fn status(&self) -> Vec<EndpointStatus> {
+        let mut statuses = Vec::with_capacity(self.endpoints.len());
+        for endpoint in &self.endpoints {
+            statuses.push(endpoint.status().await);
+        }
+        statuses
+    }
+
+    /// Outbound rate limiter metrics summed across all endpoints
+    pub fn rate_limit_metrics(&self) -> RpcRateLimitMetrics {
+        self.endpoints
+            .iter()
+            .map(|e| e.rate_limiter.metrics())
+            .fold(RpcRateLimitMetrics::default(), |acc, m| {
+                RpcRateLimitMetrics {
+                    total_requests: acc.total_requests + m.total_requests,
+                    throttled_requests: acc.throttled_requests + m.throttled_requests,
+                    rejected_requests: acc.rejected_requests + m.rejected_requests,
+                    rate_limited_responses: acc.rate_limited_responses + m.rate_limited_responses,
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(urls: &[&str]) -> EndpointPool {
+        EndpointPool::new(
+            EndpointKind::Horizon,
+            urls.iter().map(|u| u.to_string()).collect(),
+            CircuitBreakerConfig {
+                failure_threshold: 2,
+                success_threshold: 1,
+                timeout_duration: Duration::from_secs(30),
+                half_open_max_calls: 1,
+            },
+            RpcRateLimitConfig::default(),
+        )
+    }
+
+    fn unavailable() -> RpcError {
+        RpcError::ServerError {
+            status: 503,
+            message: "unavailable".into(),
+        }
+    }
+
+    #[test]
+    fn score_penalizes_errors_and_probes_unused_endpoints() {
+        assert_eq!(endpoint_score(0, None, 0.0), 0.0);
+        assert!(endpoint_score(5, Some(100.0), 0.5) > endpoint_score(5, Some(300.0), 0.0));
+        assert_eq!(endpoint_score(3, None, 1.0), UNMEASURED_LATENCY_MS * 11.0);
+    }
+
+    #[test]
+    fn new_drops_blank_and_duplicate_urls() {
+        let pool = pool(&[
+            "https://horizon.stellar.org/",
+            "",
+            "https://horizon.stellar.org",
+            "http://localhost:8000",
+        ]);
+        assert_eq!(pool.len(), 2);
+        assert_eq!(pool.primary_url(), "https://horizon.stellar.org");
+        assert_eq!(pool.endpoints[1].name(), "localhost:8000");
+    }
+
+    #[tokio::test]
+    async fn fails_over_and_ranks_healthy_endpoint_first() {
+        let pool = pool(&["https://primary.example", "https://backup.example"]);
+
+        let served_by = pool
+            .call(|endpoint| async move {
+                if endpoint.url().contains("primary") {
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    Err(unavailable())
+                } else {
+                    Ok(endpoint.name().to_string())
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(served_by, "backup.example");
+
+        let ranked = pool.ranked().await;
+        assert_eq!(ranked[0].name(), "backup.example");
+
+        let status = pool.status().await;
+        assert_eq!(status[0].total_errors, 1);
+        assert!(!status[0].healthy);
+        assert_eq!(status[0].last_error.as_deref(), Some("server_error (503)"));
+        assert!(status[1].healthy);
+    }
+
+    #[tokio::test]
+    async fn open_circuit_is_skipped_and_non_retryable_errors_do_not_fail_over() {
+        let pool = pool(&["https://primary.example", "https://backup.example"]);
+
+        for _ in 0..2 {
+            let _ = pool.endpoints[0]
+                .call(&|_| async { Err::<(), _>(unavailable()) })
+                .await;
+        }
+        assert_eq!(pool.status().await[0].circuit, CircuitStatus::Open);
+        assert_eq!(pool.ranked().await[0].name(), "backup.example");
+
+        let result: Result<(), _> = pool
+            .call(|_| async { Err(RpcError::ParseError("bad".into())) })
+            .await;
+        assert!(matches!(result, Err(RpcError::ParseError(_))));
+        assert_eq!(pool.status().await[1].total_errors, 0);
+    }
+}
@@ -1,14 +1,21 @@
+pub mod cassette;
 pub mod circuit_breaker;
 pub mod config;
+pub mod endpoint_pool;
 pub mod error;
 pub mod metrics;
 pub mod rate_limiter;
 pub mod stellar;
+pub mod stream;
 
+pub use cassette::{Cassette, CassetteMode};
+pub use endpoint_pool::{EndpointKind, EndpointPool, EndpointStatus};
 pub use rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
 pub use stellar::{
-    Asset, FeeBumpTransactionInfo, GetLedgersResult, HealthResponse, HorizonAsset, HorizonEffect,
+    Asset, AssetAccounts, AssetBalances, AssetFlags, FeeBumpTransactionInfo, GetLedgersResult,
+    HealthResponse, HorizonAccount, HorizonAsset, HorizonBalance, HorizonEffect,
     HorizonLiquidityPool, HorizonOperation, HorizonPoolReserve, HorizonTransaction,
     InnerTransaction, LedgerInfo, OrderBook, OrderBookEntry, Payment, Price, RpcLedger,
     StellarRpcClient, Trade,
 };
+pub use stream::{HorizonStream, HorizonStreamConfig, HorizonStreamKind};
@@ -1,12 +1,13 @@
 use crate::network::{NetworkConfig, StellarNetwork};
-use crate::rpc::circuit_breaker::CircuitBreaker;
+use crate::rpc::cassette::{transport_error, Cassette};
 use crate::rpc::config::{
     circuit_breaker_config_from_env, initial_backoff_from_env, max_backoff_from_env,
     max_retries_from_env,
 };
-use crate::rpc::error::{with_retry, RetryConfig, RpcError};
+use crate::rpc::endpoint_pool::{EndpointKind, EndpointPool, EndpointStatus, PoolEndpoint};
+use crate::rpc::error::{RetryConfig, RpcError};
 use crate::rpc::metrics;
-use crate::rpc::rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
+use crate::rpc::rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics};
 use anyhow::{anyhow, Context, Result};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -36,6 +37,8 @@ const DEFAULT_MAX_TOTAL_RECORDS: u32 = 1000;
 const MIN_PAGINATION_DELAY_MS: u64 = 50;
 /// Default delay between pagination requests
 const DEFAULT_PAGINATION_DELAY_MS: u64 = 100;
+/// Page size when reading every record of a ledger from Horizon
+const LEDGER_PAGE_LIMIT: usize = 200;
 
 /// Stellar RPC Client for interacting with Stellar network via RPC and Horizon API
 // Asset Models (Horizon API)
@@ -81,12 +84,14 @@ pub struct AssetFlags {
 #[derive(Clone)]
 pub struct StellarRpcClient {
     client: Client,
-    rpc_url: String,
-    horizon_url: String,
+    /// RPC endpoints, primary first; each has its own circuit breaker and rate limiter
+    rpc_pool: EndpointPool,
+    /// Horizon endpoints, primary first; each has its own circuit breaker and rate limiter
+    horizon_pool: EndpointPool,
     network_config: NetworkConfig,
     mock_mode: bool,
-    rate_limiter: RpcRateLimiter,
-    circuit_breaker: Arc<CircuitBreaker>,
+    /// Records or replays HTTP exchanges when set; see [`Cassette`]
+    cassette: Option<Arc<Cassette>>,
     /// Maximum records per single request (default: 200)
     max_records_per_request: u32,
     /// Maximum total records across all paginated requests (default: 10000)
@@ -202,10 +207,17 @@ pub struct Payment {
     pub source_asset_code: Option<String>,
     pub source_asset_issuer: Option<String>,
     pub source_amount: Option<String>,
+    /// Intermediate assets a path payment converted through, in order
+    #[serde(default)]
+    pub path: Vec<Asset>,
     // For regular payments, 'from' field
     pub from: Option<String>,
     // For regular payments, 'to' field
     pub to: Option<String>,
+    /// False when the payment's transaction failed; Horizon only returns
+    /// those with `include_failed=true`
+    #[serde(default)]
+    pub transaction_successful: Option<bool>,
     /// New Horizon API format: Soroban-compatible asset balance changes.
     /// When present the traditional top-level fields may be empty; callers
     /// should use the `get_*` helper methods which transparently check both.
@@ -259,9 +271,36 @@ impl Payment {
         }
         self.asset_issuer.clone()
     }
+
+    /// True for `path_payment_strict_send` and `path_payment_strict_receive`
+    pub fn is_path_payment(&self) -> bool {
+        matches!(
+            self.operation_type.as_deref(),
+            Some("path_payment_strict_send" | "path_payment_strict_receive")
+        )
+    }
+
+    /// Returns the amount debited from the sender in the source asset.
+    /// Plain payments send and receive the same amount.
+    pub fn get_source_amount(&self) -> String {
+        match &self.source_amount {
+            Some(amount) if self.is_path_payment() => amount.clone(),
+            _ => self.get_amount(),
+        }
+    }
+
+    /// Destination units received per source unit, for path payments only
+    pub fn exchange_rate(&self) -> Option<f64> {
+        if !self.is_path_payment() {
+            return None;
+        }
+        let source: f64 = self.source_amount.as_deref()?.parse().ok()?;
+        let destination: f64 = self.get_amount().parse().ok()?;
+        (source > 0.0).then(|| destination / source)
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HorizonOperation {
     pub id: String,
     pub paging_token: String,
@@ -273,16 +312,94 @@ pub struct HorizonOperation {
     pub account: Option<String>,
     pub into: Option<String>,
     pub amount: Option<String>,
+    /// Account whose future reserves are sponsored (`begin_sponsoring_future_reserves`)
+    #[serde(default)]
+    pub sponsored_id: Option<String>,
+    /// Sponsor of the sandwich being closed (`end_sponsoring_future_reserves`)
+    #[serde(default)]
+    pub begin_sponsor: Option<String>,
+    /// Owners of the ledger entry whose sponsorship is revoked (`revoke_sponsorship`)
+    #[serde(default)]
+    pub account_id: Option<String>,
+    #[serde(default)]
+    pub trustline_account_id: Option<String>,
+    #[serde(default)]
+    pub data_account_id: Option<String>,
+    #[serde(default)]
+    pub signer_account_id: Option<String>,
+    /// Asset of trustline authorization and clawback operations
+    #[serde(default)]
+    pub asset_code: Option<String>,
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
+    /// Trustline holder (`allow_trust`, `set_trust_line_flags`)
+    #[serde(default)]
+    pub trustor: Option<String>,
+    /// Authorization granted by `allow_trust`
+    #[serde(default)]
+    pub authorize: Option<bool>,
+    #[serde(default)]
+    pub authorize_to_maintain_liabilities: Option<bool>,
+    /// Account the asset is clawed back from (`clawback`)
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Claimable balance clawed back (`clawback_claimable_balance`)
+    #[serde(default)]
+    pub balance_id: Option<String>,
+    /// Flag names set and cleared by `set_trust_line_flags` and `set_options`
+    #[serde(default)]
+    pub set_flags_s: Vec<String>,
+    #[serde(default)]
+    pub clear_flags_s: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl HorizonOperation {
+    /// Owner of the ledger entry targeted by a `revoke_sponsorship` operation
+    pub fn revoked_entry_owner(&self) -> Option<&str> {
+        self.account_id
+            .as_deref()
+            .or(self.trustline_account_id.as_deref())
+            .or(self.data_account_id.as_deref())
+            .or(self.signer_account_id.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HorizonEffect {
     pub id: String,
+    /// `<operation id>-<effect index>`
+    #[serde(default)]
+    pub paging_token: String,
     #[serde(rename = "type")]
     pub effect_type: String,
     pub account: Option<String>,
     pub amount: Option<String>,
     pub asset_type: Option<String>,
+    /// Canonical asset (`native` or `CODE:ISSUER`) on claimable balance effects
+    #[serde(default)]
+    pub asset: Option<String>,
+    #[serde(default)]
+    pub balance_id: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Sponsor on `*_sponsorship_created` effects
+    #[serde(default)]
+    pub sponsor: Option<String>,
+    /// Previous sponsor on `*_sponsorship_updated` and `*_sponsorship_removed` effects
+    #[serde(default)]
+    pub former_sponsor: Option<String>,
+    /// New sponsor on `*_sponsorship_updated` effects
+    #[serde(default)]
+    pub new_sponsor: Option<String>,
+}
+
+impl HorizonEffect {
+    /// Id of the operation that produced this effect
+    pub fn operation_id(&self) -> Option<&str> {
+        self.paging_token
+            .split_once('-')
+            .map(|(operation_id, _)| operation_id)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -324,13 +441,21 @@ pub struct InnerTransaction {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Trade {
     pub id: String,
+    #[serde(default)]
+    pub paging_token: String,
     pub ledger_close_time: String,
+    /// Empty when the base side is a liquidity pool
+    #[serde(default)]
     pub base_account: String,
+    pub base_liquidity_pool_id: Option<String>,
     pub base_amount: String,
     pub base_asset_type: String,
     pub base_asset_code: Option<String>,
     pub base_asset_issuer: Option<String>,
+    /// Empty when the counter side is a liquidity pool
+    #[serde(default)]
     pub counter_account: String,
+    pub counter_liquidity_pool_id: Option<String>,
     pub counter_amount: String,
     pub counter_asset_type: String,
     pub counter_asset_code: Option<String>,
@@ -373,6 +498,8 @@ pub struct HorizonResponse<T> {
     pub embedded: Option<EmbeddedRecords<T>>,
     #[serde(flatten)]
     pub data: Option<T>,
+    #[serde(rename = "_links", default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<HorizonLinks>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -380,6 +507,17 @@ pub struct EmbeddedRecords<T> {
     pub records: Vec<T>,
 }
 
+/// Paging links of a Horizon collection page
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HorizonLinks {
+    pub next: Option<HorizonLink>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonLink {
+    pub href: String,
+}
+
 // I'm adding structs for getLedgers RPC method as required by issue #2
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RpcLedger {
@@ -428,6 +566,41 @@ pub struct HorizonLiquidityPool {
     pub paging_token: Option<String>,
 }
 
+// ============================================================================
+// Account Models (Horizon API)
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HorizonBalance {
+    pub balance: String,
+    /// "native", "credit_alphanum4", "credit_alphanum12" or "liquidity_pool_shares"
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub liquidity_pool_id: Option<String>,
+    /// Trustline limit; absent for native balances
+    pub limit: Option<String>,
+    pub is_authorized: Option<bool>,
+    pub is_clawback_enabled: Option<bool>,
+    pub sponsor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HorizonAccount {
+    pub account_id: String,
+    pub sequence: String,
+    #[serde(default)]
+    pub subentry_count: u32,
+    #[serde(default)]
+    pub num_sponsoring: u32,
+    #[serde(default)]
+    pub num_sponsored: u32,
+    pub home_domain: Option<String>,
+    pub last_modified_time: Option<String>,
+    #[serde(default)]
+    pub balances: Vec<HorizonBalance>,
+}
+
 // ============================================================================
 // Helpers: map HTTP response to RpcError
 // ============================================================================
@@ -454,7 +627,7 @@ fn status_to_rpc_error(
     }
 }
 
-async fn map_response_error(response: reqwest::Response) -> RpcError {
+pub(crate) async fn map_response_error(response: reqwest::Response) -> RpcError {
     let status = response.status();
     let retry_after = response
         .headers()
@@ -480,22 +653,60 @@ impl StellarRpcClient {
     /// * `horizon_url` - The Horizon API endpoint URL
     /// * `mock_mode` - If true, returns mock data instead of making real API calls
     pub fn new(rpc_url: String, horizon_url: String, mock_mode: bool) -> Self {
+        // Fallback endpoints are configured per network, so pick the one the primary belongs to
+        let network = if horizon_url.contains("testnet") {
+            StellarNetwork::Testnet
+        } else {
+            StellarNetwork::Mainnet
+        };
+
+        let network_config = NetworkConfig::for_network(network);
+        let rpc_urls = std::iter::once(rpc_url)
+            .chain(network_config.rpc_fallback_urls.iter().cloned())
+            .collect();
+        let horizon_urls = std::iter::once(horizon_url)
+            .chain(network_config.horizon_fallback_urls.iter().cloned())
+            .collect();
+
+        Self::new_with_endpoints(rpc_urls, horizon_urls, mock_mode)
+    }
+
+    /// Create a client that fails over across several RPC and Horizon endpoints
+    ///
+    /// # Arguments
+    /// * `rpc_urls` - Stellar RPC endpoint URLs, primary first
+    /// * `horizon_urls` - Horizon API endpoint URLs, primary first
+    /// * `mock_mode` - If true, returns mock data instead of making real API calls
+    pub fn new_with_endpoints(
+        rpc_urls: Vec<String>,
+        horizon_urls: Vec<String>,
+        mock_mode: bool,
+    ) -> Self {
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to build HTTP client");
-        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::from_env());
 
         // Determine network based on URLs
-        let network = if horizon_url.contains("testnet") {
+        let network = if horizon_urls.iter().any(|url| url.contains("testnet")) {
             StellarNetwork::Testnet
         } else {
             StellarNetwork::Mainnet
         };
 
         let network_config = NetworkConfig::for_network(network);
-        let cb_config = circuit_breaker_config_from_env();
-        let circuit_breaker = Arc::new(CircuitBreaker::new(cb_config, "rpc"));
+        let rpc_pool = EndpointPool::new(
+            EndpointKind::Rpc,
+            rpc_urls,
+            circuit_breaker_config_from_env(),
+            RpcRateLimitConfig::from_env(),
+        );
+        let horizon_pool = EndpointPool::new(
+            EndpointKind::Horizon,
+            horizon_urls,
+            circuit_breaker_config_from_env(),
+            RpcRateLimitConfig::from_env(),
+        );
 
         // Load pagination config from environment or use defaults with security limits
         let max_records_per_request = std::env::var("RPC_MAX_RECORDS_PER_REQUEST")
@@ -550,15 +761,19 @@ impl StellarRpcClient {
             "RPC pagination config: max_per_request={}, max_total={}, delay_ms={}",
             max_records_per_request, max_total_records, pagination_delay_ms
         );
+        info!(
+            "RPC endpoint pools: {} rpc, {} horizon",
+            rpc_pool.len(),
+            horizon_pool.len()
+        );
 
         Self {
             client,
-            rpc_url,
-            horizon_url,
+            rpc_pool,
+            horizon_pool,
             network_config,
             mock_mode,
-            rate_limiter,
-            circuit_breaker,
+            cassette: None,
             max_records_per_request,
             max_total_records,
             pagination_delay_ms,
@@ -576,9 +791,22 @@ impl StellarRpcClient {
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to build HTTP client");
-        let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::from_env());
-        let cb_config = circuit_breaker_config_from_env();
-        let circuit_breaker = Arc::new(CircuitBreaker::new(cb_config, "rpc"));
+        let rpc_pool = EndpointPool::new(
+            EndpointKind::Rpc,
+            std::iter::once(network_config.rpc_url.clone())
+                .chain(network_config.rpc_fallback_urls.iter().cloned())
+                .collect(),
+            circuit_breaker_config_from_env(),
+            RpcRateLimitConfig::from_env(),
+        );
+        let horizon_pool = EndpointPool::new(
+            EndpointKind::Horizon,
+            std::iter::once(network_config.horizon_url.clone())
+                .chain(network_config.horizon_fallback_urls.iter().cloned())
+                .collect(),
+            circuit_breaker_config_from_env(),
+            RpcRateLimitConfig::from_env(),
+        );
 
         // Load pagination config from environment or use defaults with security limits
         let max_records_per_request = std::env::var("RPC_MAX_RECORDS_PER_REQUEST")
@@ -601,12 +829,11 @@ impl StellarRpcClient {
 
         Self {
             client,
-            rpc_url: network_config.rpc_url.clone(),
-            horizon_url: network_config.horizon_url.clone(),
+            rpc_pool,
+            horizon_pool,
             network_config,
             mock_mode,
-            rate_limiter,
-            circuit_breaker,
+            cassette: None,
             max_records_per_request,
             max_total_records,
             pagination_delay_ms,
@@ -641,14 +868,100 @@ impl StellarRpcClient {
         self.network_config.is_testnet()
     }
 
-    /// Snapshot current outbound RPC/Horizon rate limiter metrics.
+    /// Snapshot current outbound RPC/Horizon rate limiter metrics, summed over all endpoints.
     pub fn rate_limit_metrics(&self) -> RpcRateLimitMetrics {
-        self.rate_limiter.metrics()
+        let rpc = self.rpc_pool.rate_limit_metrics();
+        let horizon = self.horizon_pool.rate_limit_metrics();
+        RpcRateLimitMetrics {
+            total_requests: rpc.total_requests + horizon.total_requests,
+            throttled_requests: rpc.throttled_requests + horizon.throttled_requests,
+            rejected_requests: rpc.rejected_requests + horizon.rejected_requests,
+            rate_limited_responses: rpc.rate_limited_responses + horizon.rate_limited_responses,
+        }
     }
 
-    async fn execute_with_retry<F, Fut, T>(&self, operation: F) -> Result<T, RpcError>
+    /// Primary Horizon base URL
+    pub fn horizon_url(&self) -> &str {
+        self.horizon_pool.primary_url()
+    }
+
+    /// Horizon endpoints in failover order, used by streaming connections
+    pub(crate) fn horizon_pool(&self) -> &EndpointPool {
+        &self.horizon_pool
+    }
+
+    /// Per-endpoint health of the RPC and Horizon pools
+    pub async fn endpoint_status(&self) -> (Vec<EndpointStatus>, Vec<EndpointStatus>) {
+        (
+            self.rpc_pool.status().await,
+            self.horizon_pool.status().await,
+        )
+    }
+
+    /// Attach a cassette that records or replays every RPC and Horizon exchange
+    pub fn with_cassette(mut self, cassette: Arc<Cassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Send a request, going through the cassette when one is attached
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, RpcError> {
+        let Some(cassette) = &self.cassette else {
+            return request.send().await.map_err(transport_error);
+        };
+        let request = request
+            .build()
+            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        cassette.exchange(&self.client, request).await
+    }
+
+    /// Every record of a Horizon collection, following `_links.next` until a
+    /// page comes back short
+    async fn fetch_all_pages<T: serde::de::DeserializeOwned>(
+        &self,
+        url: String,
+    ) -> Result<Vec<T>, RpcError> {
+        let mut records = Vec::new();
+        let mut next = Some(url);
+
+        while let Some(url) = next.take() {
+            let response = self.send(self.client.get(&url)).await?;
+            if !response.status().is_success() {
+                return Err(map_response_error(response).await);
+            }
+            let page: HorizonResponse<T> = response
+                .json()
+                .await
+                .map_err(|e| RpcError::ParseError(e.to_string()))?;
+            let page_records = page.embedded.map(|e| e.records).unwrap_or_default();
+            if page_records.len() >= LEDGER_PAGE_LIMIT {
+                next = page.links.and_then(|l| l.next).map(|l| l.href);
+            }
+            records.extend(page_records);
+        }
+
+        Ok(records)
+    }
+
+    /// Check if this client serves mock data instead of calling the network
+    pub fn is_mock_mode(&self) -> bool {
+        self.mock_mode
+    }
+
+    /// Initial and maximum backoff used between retries
+    pub(crate) fn backoff_bounds(&self) -> (Duration, Duration) {
+        (self.initial_backoff, self.max_backoff)
+    }
+
+    /// Run `operation` against the healthiest endpoint of `pool`, failing over
+    /// to the others and retrying with backoff on transient errors
+    async fn execute_with_retry<F, Fut, T>(
+        &self,
+        pool: &EndpointPool,
+        operation: F,
+    ) -> Result<T, RpcError>
     where
-        F: Fn() -> Fut,
+        F: Fn(Arc<PoolEndpoint>) -> Fut,
         Fut: std::future::Future<Output = Result<T, RpcError>>,
     {
         let retry_config = RetryConfig {
@@ -657,7 +970,7 @@ impl StellarRpcClient {
             max_delay_ms: self.max_backoff.as_millis() as u64,
         };
 
-        with_retry(operation, retry_config, self.circuit_breaker.clone()).await
+        pool.execute(operation, &retry_config).await
     }
 
     /// Check the health of the RPC endpoint
@@ -666,10 +979,12 @@ impl StellarRpcClient {
             return Ok(Self::mock_health_response());
         }
 
-        info!("Checking RPC health at {}", self.rpc_url);
+        info!("Checking RPC health at {}", self.rpc_pool.primary_url());
 
         let result = self
-            .execute_with_retry(|| self.check_health_internal())
+            .execute_with_retry(&self.rpc_pool, |endpoint| async move {
+                self.check_health_internal(endpoint.url()).await
+            })
             .await;
 
         result.map_err(|e| {
@@ -678,20 +993,14 @@ impl StellarRpcClient {
         })
     }
 
-    async fn check_health_internal(&self) -> Result<HealthResponse, RpcError> {
+    async fn check_health_internal(&self, rpc_url: &str) -> Result<HealthResponse, RpcError> {
         let payload = json!({
             "jsonrpc": "2.0",
             "method": "getHealth",
             "id": 1
         });
 
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.post(rpc_url).json(&payload)).await?;
 
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
@@ -721,7 +1030,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_latest_ledger_internal())
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_latest_ledger_internal(endpoint.url()).await
+            })
             .await;
 
         result.map_err(|e| {
@@ -730,14 +1041,12 @@ impl StellarRpcClient {
         })
     }
 
-    async fn fetch_latest_ledger_internal(&self) -> Result<LedgerInfo, RpcError> {
-        let url = format!("{}/ledgers?order=desc&limit=1", self.horizon_url);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+    async fn fetch_latest_ledger_internal(
+        &self,
+        horizon_url: &str,
+    ) -> Result<LedgerInfo, RpcError> {
+        let url = format!("{}/ledgers?order=desc&limit=1", horizon_url);
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -771,7 +1080,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_ledgers_internal(start_ledger, limit, cursor))
+            .execute_with_retry(&self.rpc_pool, |endpoint| async move {
+                self.fetch_ledgers_internal(endpoint.url(), start_ledger, limit, cursor)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -782,6 +1094,7 @@ impl StellarRpcClient {
 
     async fn fetch_ledgers_internal(
         &self,
+        rpc_url: &str,
         start_ledger: Option<u64>,
         limit: u32,
         cursor: Option<&str>,
@@ -804,13 +1117,7 @@ impl StellarRpcClient {
             "id": 1,
             "params": params
         });
-        let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.post(rpc_url).json(&payload)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -834,6 +1141,24 @@ impl StellarRpcClient {
         &self,
         limit: u32,
         cursor: Option<&str>,
+    ) -> Result<Vec<Payment>, RpcError> {
+        self.fetch_payments_filtered(limit, cursor, false).await
+    }
+
+    /// Fetch recent payments, including those of failed transactions
+    pub async fn fetch_payments_including_failed(
+        &self,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<Payment>, RpcError> {
+        self.fetch_payments_filtered(limit, cursor, true).await
+    }
+
+    async fn fetch_payments_filtered(
+        &self,
+        limit: u32,
+        cursor: Option<&str>,
+        include_failed: bool,
     ) -> Result<Vec<Payment>, RpcError> {
         if self.mock_mode {
             return Ok(Self::mock_payments(limit));
@@ -842,7 +1167,10 @@ impl StellarRpcClient {
         info!("Fetching {} payments from Horizon API", limit);
 
         let result = self
-            .execute_with_retry(|| self.fetch_payments_internal(limit, cursor))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_payments_internal(endpoint.url(), limit, cursor, include_failed)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -853,19 +1181,19 @@ impl StellarRpcClient {
 
     async fn fetch_payments_internal(
         &self,
+        horizon_url: &str,
         limit: u32,
         cursor: Option<&str>,
+        include_failed: bool,
     ) -> Result<Vec<Payment>, RpcError> {
-        let mut url = format!("{}/payments?order=desc&limit={}", self.horizon_url, limit);
+        let mut url = format!("{}/payments?order=desc&limit={}", horizon_url, limit);
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        if include_failed {
+            url.push_str("&include_failed=true");
+        }
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -890,7 +1218,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_trades_internal(limit, cursor))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_trades_internal(endpoint.url(), limit, cursor)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -901,19 +1232,15 @@ impl StellarRpcClient {
 
     async fn fetch_trades_internal(
         &self,
+        horizon_url: &str,
         limit: u32,
         cursor: Option<&str>,
     ) -> Result<Vec<Trade>, RpcError> {
-        let mut url = format!("{}/trades?order=desc&limit={}", self.horizon_url, limit);
+        let mut url = format!("{}/trades?order=desc&limit={}", horizon_url, limit);
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -939,8 +1266,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| {
-                self.fetch_order_book_internal(selling_asset, buying_asset, limit)
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_order_book_internal(endpoint.url(), selling_asset, buying_asset, limit)
+                    .await
             })
             .await;
 
@@ -952,6 +1280,7 @@ impl StellarRpcClient {
 
     async fn fetch_order_book_internal(
         &self,
+        horizon_url: &str,
         selling_asset: &Asset,
         buying_asset: &Asset,
         limit: u32,
@@ -962,14 +1291,9 @@ impl StellarRpcClient {
             .map_err(|e| RpcError::ParseError(e.to_string()))?;
         let url = format!(
             "{}/order_book?{}&{}&limit={}",
-            self.horizon_url, selling_params, buying_params, limit
+            horizon_url, selling_params, buying_params, limit
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -985,7 +1309,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_payments_for_ledger_internal(sequence))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_payments_for_ledger_internal(endpoint.url(), sequence)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -996,29 +1323,14 @@ impl StellarRpcClient {
 
     async fn fetch_payments_for_ledger_internal(
         &self,
+        horizon_url: &str,
         sequence: u64,
     ) -> Result<Vec<Payment>, RpcError> {
-        let url = format!(
-            "{}/ledgers/{}/payments?limit=200",
-            self.horizon_url, sequence
-        );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
-        if !response.status().is_success() {
-            return Err(map_response_error(response).await);
-        }
-        let horizon_response: HorizonResponse<Payment> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
-        Ok(horizon_response
-            .embedded
-            .map(|e| e.records)
-            .unwrap_or_default())
+        self.fetch_all_pages(format!(
+            "{}/ledgers/{}/payments?limit={}",
+            horizon_url, sequence, LEDGER_PAGE_LIMIT
+        ))
+        .await
     }
 
     /// Fetch transactions for a specific ledger
@@ -1031,7 +1343,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_transactions_for_ledger_internal(sequence))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_transactions_for_ledger_internal(endpoint.url(), sequence)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -1042,29 +1357,14 @@ impl StellarRpcClient {
 
     async fn fetch_transactions_for_ledger_internal(
         &self,
+        horizon_url: &str,
         sequence: u64,
     ) -> Result<Vec<HorizonTransaction>, RpcError> {
-        let url = format!(
-            "{}/ledgers/{}/transactions?limit=200&include_failed=true",
-            self.horizon_url, sequence
-        );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
-        if !response.status().is_success() {
-            return Err(map_response_error(response).await);
-        }
-        let horizon_response: HorizonResponse<HorizonTransaction> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
-        Ok(horizon_response
-            .embedded
-            .map(|e| e.records)
-            .unwrap_or_default())
+        self.fetch_all_pages(format!(
+            "{}/ledgers/{}/transactions?limit={}&include_failed=true",
+            horizon_url, sequence, LEDGER_PAGE_LIMIT
+        ))
+        .await
     }
 
     /// Fetch operations for a specific ledger
@@ -1077,7 +1377,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_operations_for_ledger_internal(sequence))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_operations_for_ledger_internal(endpoint.url(), sequence)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -1088,29 +1391,14 @@ impl StellarRpcClient {
 
     async fn fetch_operations_for_ledger_internal(
         &self,
+        horizon_url: &str,
         sequence: u64,
     ) -> Result<Vec<HorizonOperation>, RpcError> {
-        let url = format!(
-            "{}/ledgers/{}/operations?limit=200",
-            self.horizon_url, sequence
-        );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
-        if !response.status().is_success() {
-            return Err(map_response_error(response).await);
-        }
-        let horizon_response: HorizonResponse<HorizonOperation> = response
-            .json()
-            .await
-            .map_err(|e| RpcError::ParseError(e.to_string()))?;
-        Ok(horizon_response
-            .embedded
-            .map(|e| e.records)
-            .unwrap_or_default())
+        self.fetch_all_pages(format!(
+            "{}/ledgers/{}/operations?limit={}",
+            horizon_url, sequence, LEDGER_PAGE_LIMIT
+        ))
+        .await
     }
 
     /// Fetch effects for a specific operation
@@ -1123,7 +1411,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_operation_effects_internal(operation_id))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_operation_effects_internal(endpoint.url(), operation_id)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -1134,18 +1425,14 @@ impl StellarRpcClient {
 
     async fn fetch_operation_effects_internal(
         &self,
+        horizon_url: &str,
         operation_id: &str,
     ) -> Result<Vec<HorizonEffect>, RpcError> {
         let url = format!(
             "{}/operations/{}/effects?limit=200",
-            self.horizon_url, operation_id
+            horizon_url, operation_id
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -1159,6 +1446,43 @@ impl StellarRpcClient {
             .unwrap_or_default())
     }
 
+    /// Fetch all effects produced in a specific ledger
+    pub async fn fetch_effects_for_ledger(
+        &self,
+        sequence: u64,
+    ) -> Result<Vec<HorizonEffect>, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_operations_for_ledger(sequence)
+                .iter()
+                .flat_map(|op| Self::mock_effects_for_operation(&op.id))
+                .collect());
+        }
+
+        let result = self
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_effects_for_ledger_internal(endpoint.url(), sequence)
+                    .await
+            })
+            .await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_effects_for_ledger_internal(
+        &self,
+        horizon_url: &str,
+        sequence: u64,
+    ) -> Result<Vec<HorizonEffect>, RpcError> {
+        self.fetch_all_pages(format!(
+            "{}/ledgers/{}/effects?limit={}",
+            horizon_url, sequence, LEDGER_PAGE_LIMIT
+        ))
+        .await
+    }
+
     /// Fetch payments for a specific account
     pub async fn fetch_account_payments(
         &self,
@@ -1170,7 +1494,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_account_payments_internal(account_id, limit))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_account_payments_internal(endpoint.url(), account_id, limit)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -1181,19 +1508,15 @@ impl StellarRpcClient {
 
     async fn fetch_account_payments_internal(
         &self,
+        horizon_url: &str,
         account_id: &str,
         limit: u32,
     ) -> Result<Vec<Payment>, RpcError> {
         let url = format!(
             "{}/accounts/{}/payments?order=desc&limit={}",
-            self.horizon_url, account_id, limit
+            horizon_url, account_id, limit
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -1207,6 +1530,41 @@ impl StellarRpcClient {
             .unwrap_or_default())
     }
 
+    /// Fetch an account's current state, including balances and trustlines
+    pub async fn fetch_account(&self, account_id: &str) -> Result<HorizonAccount, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_account(account_id));
+        }
+
+        let result = self
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_account_internal(endpoint.url(), account_id)
+                    .await
+            })
+            .await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_account_internal(
+        &self,
+        horizon_url: &str,
+        account_id: &str,
+    ) -> Result<HorizonAccount, RpcError> {
+        let url = format!("{}/accounts/{}", horizon_url, account_id);
+        let response = self.send(self.client.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))
+    }
+
     // ============================================================================
     // Paginated Fetch Methods
     // ============================================================================
@@ -1392,17 +1750,17 @@ impl StellarRpcClient {
         while fetched < max_records {
             let limit = std::cmp::min(self.max_records_per_request, max_records - fetched);
 
-            let mut url = format!(
-                "{}/accounts/{}/payments?order=desc&limit={}",
-                self.horizon_url, account_id, limit
+            let mut path = format!(
+                "/accounts/{}/payments?order=desc&limit={}",
+                account_id, limit
             );
 
             if let Some(ref cursor_val) = cursor {
-                url.push_str(&format!("&cursor={}", cursor_val));
+                path.push_str(&format!("&cursor={}", cursor_val));
             }
 
             let response = self
-                .retry_request(|| async { self.client.get(&url).send().await })
+                .retry_request(&path)
                 .await
                 .context("Failed to fetch account payments page")?;
 
@@ -1482,84 +1840,72 @@ impl StellarRpcClient {
         }
     }
 
-    /// Retry a request with exponential backoff
-    async fn retry_request<F, Fut>(&self, request_fn: F) -> Result<reqwest::Response>
-    where
-        F: Fn() -> Fut,
-        Fut: std::future::Future<Output = Result<reqwest::Response, reqwest::Error>>,
-    {
+    /// GET a Horizon path with failover across endpoints and exponential backoff
+    async fn retry_request(&self, path: &str) -> Result<reqwest::Response> {
         let retry_config = RetryConfig {
             max_attempts: MAX_RETRIES + 1,
             base_delay_ms: INITIAL_BACKOFF_MS,
             max_delay_ms: INITIAL_BACKOFF_MS * BACKOFF_MULTIPLIER.pow(MAX_RETRIES),
         };
 
-        with_retry(
-            || async {
-                let queue_permit = self
-                    .rate_limiter
-                    .acquire()
-                    .await
-                    .map_err(|_| RpcError::RateLimitError { retry_after: None })?;
-
-                let start_time = Instant::now();
-                let response = request_fn()
-                    .await
-                    .map_err(|e| RpcError::categorize(&e.to_string()))?;
-                let elapsed = start_time.elapsed().as_millis();
-                let status = response.status();
-                let headers = response.headers().clone();
-
-                drop(queue_permit);
-                self.rate_limiter.observe_headers(&headers).await;
-
-                if status.is_success() {
-                    debug!("Request succeeded in {} ms", elapsed);
-                    return Ok(response);
-                }
-
-                if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                    self.rate_limiter.on_rate_limited(&headers).await;
-                }
+        self.horizon_pool
+            .execute(
+                |endpoint| async move {
+                    let url = format!("{}{}", endpoint.url(), path);
+                    let start_time = Instant::now();
+                    let response = self.send(self.client.get(&url)).await?;
+                    let elapsed = start_time.elapsed().as_millis();
+                    let status = response.status();
+                    let headers = response.headers().clone();
+
+                    endpoint.rate_limiter().observe_headers(&headers).await;
+
+                    if status.is_success() {
+                        debug!("Request succeeded in {} ms", elapsed);
+                        return Ok(response);
+                    }
 
-                let error_text = response
-                    .text()
-                    .await
-                    .unwrap_or_else(|_| "Unknown error".to_string());
-                warn!(
-                    "Request failed with status {} in {} ms: {}",
-                    status, elapsed, error_text
-                );
+                    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        endpoint.rate_limiter().on_rate_limited(&headers).await;
+                    }
 
-                let msg = format!("HTTP {}: {}", status, error_text);
-                if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                    let retry_after = headers
-                        .get("Retry-After")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse::<u64>().ok())
-                        .map(Duration::from_secs);
-                    Err(RpcError::RateLimitError { retry_after })
-                } else if status == reqwest::StatusCode::REQUEST_TIMEOUT
-                    || status == reqwest::StatusCode::GATEWAY_TIMEOUT
-                {
-                    Err(RpcError::TimeoutError(msg))
-                } else if status.as_u16() >= 500 {
-                    Err(RpcError::NetworkError(msg))
-                } else {
-                    Err(RpcError::ServerError {
-                        status: status.as_u16(),
-                        message: msg,
-                    })
-                }
-            },
-            retry_config,
-            self.circuit_breaker.clone(),
-        )
-        .await
-        .map_err(|e| {
-            info!("Request failed after retry/circuit-breaker checks: {}", e);
-            anyhow!("Request failed: {}", e)
-        })
+                    let error_text = response
+                        .text()
+                        .await
+                        .unwrap_or_else(|_| "Unknown error".to_string());
+                    warn!(
+                        "Request failed with status {} in {} ms: {}",
+                        status, elapsed, error_text
+                    );
+
+                    let msg = format!("HTTP {}: {}", status, error_text);
+                    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        let retry_after = headers
+                            .get("Retry-After")
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(|s| s.parse::<u64>().ok())
+                            .map(Duration::from_secs);
+                        Err(RpcError::RateLimitError { retry_after })
+                    } else if status == reqwest::StatusCode::REQUEST_TIMEOUT
+                        || status == reqwest::StatusCode::GATEWAY_TIMEOUT
+                    {
+                        Err(RpcError::TimeoutError(msg))
+                    } else if status.as_u16() >= 500 {
+                        Err(RpcError::NetworkError(msg))
+                    } else {
+                        Err(RpcError::ServerError {
+                            status: status.as_u16(),
+                            message: msg,
+                        })
+                    }
+                },
+                &retry_config,
+            )
+            .await
+            .map_err(|e| {
+                info!("Request failed after retry/circuit-breaker checks: {}", e);
+                anyhow!("Request failed: {}", e)
+            })
     }
 
     // ============================================================================
@@ -1621,7 +1967,7 @@ impl StellarRpcClient {
         }
     }
 
-    fn mock_payments(limit: u32) -> Vec<Payment> {
+    pub(crate) fn mock_payments(limit: u32) -> Vec<Payment> {
         (0..limit)
             .map(|i| {
                 let is_path_payment = i % 5 == 0;
@@ -1725,9 +2071,20 @@ impl StellarRpcClient {
                     } else {
                         None
                     },
+                    // Every third path payment routes through XLM
+                    path: if is_path_payment && i % 3 == 0 {
+                        vec![Asset {
+                            asset_type: "native".to_string(),
+                            asset_code: None,
+                            asset_issuer: None,
+                        }]
+                    } else {
+                        Vec::new()
+                    },
                     from: Some(src_account),
                     to: Some(dest_account.clone()),
                     // Populate the new Soroban-compatible field for even entries
+                    transaction_successful: Some(true),
                     asset_balance_changes: if use_new_format {
                         Some(vec![AssetBalanceChange {
                             asset_type: asset_type_str,
@@ -1749,12 +2106,14 @@ impl StellarRpcClient {
             .collect()
     }
 
-    fn mock_trades(limit: u32) -> Vec<Trade> {
+    pub(crate) fn mock_trades(limit: u32) -> Vec<Trade> {
         (0..limit)
             .map(|i| Trade {
                 id: format!("trade_{}", i),
+                paging_token: format!("trade_{}", i),
                 ledger_close_time: format!("2026-01-22T10:{:02}:00Z", i % 60),
                 base_account: format!("GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX{:03}", i),
+                base_liquidity_pool_id: None,
                 base_amount: format!("{}.0000000", 1000 + i * 100),
                 base_asset_type: "native".to_string(),
                 base_asset_code: None,
@@ -1763,6 +2122,7 @@ impl StellarRpcClient {
                     "GDYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYY{:03}",
                     i
                 ),
+                counter_liquidity_pool_id: None,
                 counter_amount: format!("{}.0000000", 500 + i * 50),
                 counter_asset_type: "credit_alphanum4".to_string(),
                 counter_asset_code: Some("USDC".to_string()),
@@ -1778,7 +2138,7 @@ impl StellarRpcClient {
             .collect()
     }
 
-    fn mock_order_book(selling_asset: &Asset, buying_asset: &Asset) -> OrderBook {
+    pub(crate) fn mock_order_book(selling_asset: &Asset, buying_asset: &Asset) -> OrderBook {
         let bids = vec![
             OrderBookEntry {
                 price: "0.9950".to_string(),
@@ -1823,7 +2183,7 @@ impl StellarRpcClient {
         }
     }
 
-    fn mock_transactions(limit: u32, ledger_sequence: u64) -> Vec<HorizonTransaction> {
+    pub(crate) fn mock_transactions(limit: u32, ledger_sequence: u64) -> Vec<HorizonTransaction> {
         (0..limit)
             .map(|i| {
                 let is_fee_bump = i % 2 == 0;
@@ -1878,6 +2238,7 @@ impl StellarRpcClient {
                 account: Some(source_a),
                 into: Some(dest_a),
                 amount: None,
+                ..Default::default()
             },
             HorizonOperation {
                 id: format!("op_{}_1", sequence),
@@ -1890,6 +2251,7 @@ impl StellarRpcClient {
                 account: None,
                 into: None,
                 amount: Some("25.0000000".to_string()),
+                ..Default::default()
             },
             HorizonOperation {
                 id: format!("op_{}_2", sequence),
@@ -1901,20 +2263,23 @@ impl StellarRpcClient {
                 account: Some(source_b),
                 into: Some(dest_b),
                 amount: None,
+                ..Default::default()
             },
         ]
     }
 
-    fn mock_effects_for_operation(operation_id: &str) -> Vec<HorizonEffect> {
+    pub(crate) fn mock_effects_for_operation(operation_id: &str) -> Vec<HorizonEffect> {
         if operation_id.ends_with("_0") {
             return vec![HorizonEffect {
                 id: format!("effect_{}_0", operation_id),
+                paging_token: format!("{}-1", operation_id),
                 effect_type: "account_credited".to_string(),
                 account: Some(
                     "GDESTAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string(),
                 ),
                 amount: Some("125.5000000".to_string()),
                 asset_type: Some("native".to_string()),
+                ..Default::default()
             }];
         }
 
@@ -1922,21 +2287,25 @@ impl StellarRpcClient {
             return vec![
                 HorizonEffect {
                     id: format!("effect_{}_0", operation_id),
+                    paging_token: format!("{}-1", operation_id),
                     effect_type: "account_credited".to_string(),
                     account: Some(
                         "GDESTBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB".to_string(),
                     ),
                     amount: Some("10.0000000".to_string()),
                     asset_type: Some("native".to_string()),
+                    ..Default::default()
                 },
                 HorizonEffect {
                     id: format!("effect_{}_1", operation_id),
+                    paging_token: format!("{}-2", operation_id),
                     effect_type: "account_credited".to_string(),
                     account: Some(
                         "GDESTBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBBB".to_string(),
                     ),
                     amount: Some("0.5000000".to_string()),
                     asset_type: Some("native".to_string()),
+                    ..Default::default()
                 },
             ];
         }
@@ -1959,7 +2328,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_liquidity_pools_internal(limit, cursor))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_liquidity_pools_internal(endpoint.url(), limit, cursor)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -1970,22 +2342,15 @@ impl StellarRpcClient {
 
     async fn fetch_liquidity_pools_internal(
         &self,
+        horizon_url: &str,
         limit: u32,
         cursor: Option<&str>,
     ) -> Result<Vec<HorizonLiquidityPool>, RpcError> {
-        let mut url = format!(
-            "{}/liquidity_pools?order=desc&limit={}",
-            self.horizon_url, limit
-        );
+        let mut url = format!("{}/liquidity_pools?order=desc&limit={}", horizon_url, limit);
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -2012,7 +2377,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_liquidity_pool_internal(pool_id))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_liquidity_pool_internal(endpoint.url(), pool_id)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -2023,15 +2391,11 @@ impl StellarRpcClient {
 
     async fn fetch_liquidity_pool_internal(
         &self,
+        horizon_url: &str,
         pool_id: &str,
     ) -> Result<HorizonLiquidityPool, RpcError> {
-        let url = format!("{}/liquidity_pools/{}", self.horizon_url, pool_id);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let url = format!("{}/liquidity_pools/{}", horizon_url, pool_id);
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -2052,7 +2416,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_pool_trades_internal(pool_id, limit))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_pool_trades_internal(endpoint.url(), pool_id, limit)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -2063,19 +2430,15 @@ impl StellarRpcClient {
 
     async fn fetch_pool_trades_internal(
         &self,
+        horizon_url: &str,
         pool_id: &str,
         limit: u32,
     ) -> Result<Vec<Trade>, RpcError> {
         let url = format!(
             "{}/liquidity_pools/{}/trades?order=desc&limit={}",
-            self.horizon_url, pool_id, limit
+            horizon_url, pool_id, limit
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -2100,7 +2463,10 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_assets_internal(limit, rating_sort))
+            .execute_with_retry(&self.horizon_pool, |endpoint| async move {
+                self.fetch_assets_internal(endpoint.url(), limit, rating_sort)
+                    .await
+            })
             .await;
 
         result.map_err(|e| {
@@ -2111,21 +2477,17 @@ impl StellarRpcClient {
 
     async fn fetch_assets_internal(
         &self,
+        horizon_url: &str,
         limit: u32,
         rating_sort: bool,
     ) -> Result<Vec<HorizonAsset>, RpcError> {
-        let mut url = format!("{}/assets?limit={}", self.horizon_url, limit);
+        let mut url = format!("{}/assets?limit={}", horizon_url, limit);
         if rating_sort {
             url.push_str("&order=desc&sort=rating");
         } else {
             url.push_str("&order=desc");
         }
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -2139,6 +2501,36 @@ impl StellarRpcClient {
             .unwrap_or_default())
     }
 
+    pub(crate) fn mock_account(account_id: &str) -> HorizonAccount {
+        HorizonAccount {
+            account_id: account_id.to_string(),
+            sequence: "123456789012".to_string(),
+            subentry_count: 2,
+            home_domain: None,
+            last_modified_time: Some("2026-01-22T10:30:00Z".to_string()),
+            balances: vec![
+                HorizonBalance {
+                    balance: "2500.0000000".to_string(),
+                    asset_type: "credit_alphanum4".to_string(),
+                    asset_code: Some("USDC".to_string()),
+                    asset_issuer: Some(
+                        "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string(),
+                    ),
+                    limit: Some("922337203685.4775807".to_string()),
+                    is_authorized: Some(true),
+                    is_clawback_enabled: Some(false),
+                    ..Default::default()
+                },
+                HorizonBalance {
+                    balance: "150.5000000".to_string(),
+                    asset_type: "native".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
     // ============================================================================
     // Liquidity Pool Mock Data
     // ============================================================================
@@ -2533,6 +2925,8 @@ mod tests {
             source_amount: None,
             from: Some("GSRC".into()),
             to: Some("GDEST".into()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -2563,6 +2957,8 @@ mod tests {
             source_amount: None,
             from: None,
             to: None,
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: Some(vec![AssetBalanceChange {
                 asset_type: "credit_alphanum4".into(),
                 asset_code: Some("USDC".into()),
@@ -2606,6 +3002,8 @@ mod tests {
             source_amount: None,
             from: Some("GSRC".into()),
             to: Some("GDEST_LEGACY".into()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: Some(vec![AssetBalanceChange {
                 asset_type: "credit_alphanum4".into(),
                 asset_code: Some("NEW_CODE".into()),
@@ -2644,6 +3042,8 @@ mod tests {
             source_amount: None,
             from: None,
             to: None,
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: Some(vec![AssetBalanceChange {
                 asset_type: "native".into(),
                 asset_code: None,
@@ -2682,6 +3082,8 @@ mod tests {
             source_amount: None,
             from: Some("GSRC".into()),
             to: Some("GTO_FIELD".into()),
+            path: Vec::new(),
+            transaction_successful: None,
             asset_balance_changes: None,
         };
 
@@ -2743,6 +3145,44 @@ mod tests {
         assert_eq!(payment.get_amount(), "100.0000000");
         assert_eq!(payment.get_asset_code(), Some("USDC".to_string()));
         assert_eq!(payment.get_asset_issuer(), Some("GISSUER".to_string()));
+        assert!(!payment.is_path_payment());
+        assert_eq!(payment.get_source_amount(), "100.0000000");
+        assert_eq!(payment.exchange_rate(), None);
+    }
+
+    #[test]
+    fn test_deserialization_path_payment() {
+        let json = r#"{
+            "id": "op_path",
+            "paging_token": "pt_path",
+            "transaction_hash": "txhash_path",
+            "source_account": "GSRC",
+            "type": "path_payment_strict_send",
+            "from": "GSRC",
+            "to": "GDEST",
+            "asset_type": "credit_alphanum4",
+            "asset_code": "EURT",
+            "asset_issuer": "GEURISSUER",
+            "amount": "92.5000000",
+            "source_asset_type": "credit_alphanum4",
+            "source_asset_code": "USDC",
+            "source_asset_issuer": "GUSDCISSUER",
+            "source_amount": "100.0000000",
+            "destination_min": "90.0000000",
+            "path": [
+                {"asset_type": "native"},
+                {"asset_type": "credit_alphanum4", "asset_code": "BRL", "asset_issuer": "GBRLISSUER"}
+            ],
+            "created_at": "2026-01-22T10:00:00Z"
+        }"#;
+
+        let payment: Payment = serde_json::from_str(json).unwrap();
+        assert!(payment.is_path_payment());
+        assert_eq!(payment.get_source_amount(), "100.0000000");
+        assert_eq!(payment.exchange_rate(), Some(0.925));
+        assert_eq!(payment.path.len(), 2);
+        assert_eq!(payment.path[0].asset_type, "native");
+        assert_eq!(payment.path[1].asset_code.as_deref(), Some("BRL"));
     }
 
     #[test]
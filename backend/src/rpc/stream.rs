@@ -0,0 +1,467 @@
+//! Horizon Server-Sent Events streaming.
+//!
+//! Horizon exposes `text/event-stream` variants of its collection endpoints.
+//! Each event carries the record's `paging_token` as its SSE `id`, so a stream
+//! can resume exactly where it stopped. Connections are opened on the
+//! healthiest endpoint of the client's Horizon pool and re-established with
+//! exponential backoff whenever they drop.
+
+use futures::stream::{self, BoxStream, StreamExt};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+use crate::database::Database;
+use crate::rpc::error::RpcError;
+use crate::rpc::metrics;
+use crate::rpc::stellar::map_response_error;
+use crate::rpc::{HorizonEffect, HorizonTransaction, Payment, StellarRpcClient, Trade};
+
+const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 60;
+const MOCK_STREAM_RECORDS: u32 = 10;
+
+/// Horizon collections that support streaming
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HorizonStreamKind {
+    Payments,
+    Trades,
+    Transactions,
+    Effects,
+}
+
+impl HorizonStreamKind {
+    fn path(self) -> &'static str {
+        match self {
+            Self::Payments => "payments",
+            Self::Trades => "trades",
+            Self::Transactions => "transactions",
+            Self::Effects => "effects",
+        }
+    }
+
+    /// Extra query parameters; payments of failed transactions are streamed
+    /// too so their outcome reaches the corridor rollup
+    fn query(self) -> &'static str {
+        match self {
+            Self::Payments => "&include_failed=true",
+            Self::Trades | Self::Transactions | Self::Effects => "",
+        }
+    }
+
+    /// Task name under which the cursor is stored in `ingestion_state`
+    pub fn cursor_task_name(self) -> String {
+        format!("horizon_stream_{}", self.path())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HorizonStreamConfig {
+    /// Reconnect when no bytes (including keep-alives) arrive within this window
+    pub idle_timeout: Duration,
+}
+
+impl HorizonStreamConfig {
+    pub fn from_env() -> Self {
+        let idle_secs = std::env::var("HORIZON_STREAM_IDLE_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_IDLE_TIMEOUT_SECS);
+        Self {
+            idle_timeout: Duration::from_secs(idle_secs),
+        }
+    }
+}
+
+impl Default for HorizonStreamConfig {
+    fn default() -> Self {
+        Self {
+            idle_timeout: Duration::from_secs(DEFAULT_IDLE_TIMEOUT_SECS),
+        }
+    }
+}
+
+/// Factory for typed Horizon event streams
+pub struct HorizonStream {
+    rpc_client: Arc<StellarRpcClient>,
+    db: Option<Arc<Database>>,
+    config: HorizonStreamConfig,
+}
+
+impl HorizonStream {
+    pub fn new(rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self {
+            rpc_client,
+            db: None,
+            config: HorizonStreamConfig::from_env(),
+        }
+    }
+
+    /// Resume from, and persist, the cursor stored via `Database::update_ingestion_cursor`
+    pub fn with_cursor_store(mut self, db: Arc<Database>) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    pub fn with_config(mut self, config: HorizonStreamConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn payments(&self) -> BoxStream<'static, Result<Payment, RpcError>> {
+        if self.rpc_client.is_mock_mode() {
+            return mock_stream(StellarRpcClient::mock_payments(MOCK_STREAM_RECORDS));
+        }
+        self.records(HorizonStreamKind::Payments)
+    }
+
+    pub fn trades(&self) -> BoxStream<'static, Result<Trade, RpcError>> {
+        if self.rpc_client.is_mock_mode() {
+            return mock_stream(StellarRpcClient::mock_trades(MOCK_STREAM_RECORDS));
+        }
+        self.records(HorizonStreamKind::Trades)
+    }
+
+    pub fn transactions(&self) -> BoxStream<'static, Result<HorizonTransaction, RpcError>> {
+        if self.rpc_client.is_mock_mode() {
+            return mock_stream(StellarRpcClient::mock_transactions(MOCK_STREAM_RECORDS, 0));
+        }
+        self.records(HorizonStreamKind::Transactions)
+    }
+
+    pub fn effects(&self) -> BoxStream<'static, Result<HorizonEffect, RpcError>> {
+        if self.rpc_client.is_mock_mode() {
+            return mock_stream(StellarRpcClient::mock_effects_for_operation("op_0"));
+        }
+        self.records(HorizonStreamKind::Effects)
+    }
+
+    /// Endless stream of records; transport errors are retried internally and
+    /// only records that fail to deserialize surface as errors.
+    fn records<T>(&self, kind: HorizonStreamKind) -> BoxStream<'static, Result<T, RpcError>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let (initial_backoff, max_backoff) = self.rpc_client.backoff_bounds();
+        let state = StreamState {
+            http: Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            rpc_client: Arc::clone(&self.rpc_client),
+            db: self.db.clone(),
+            kind,
+            idle_timeout: self.config.idle_timeout,
+            cursor: None,
+            cursor_loaded: false,
+            pending_cursor: None,
+            response: None,
+            decoder: SseDecoder::default(),
+            initial_backoff,
+            max_backoff,
+            backoff: initial_backoff,
+        };
+
+        stream::unfold(state, |mut state| async move {
+            let item = state.next_record::<T>().await;
+            Some((item, state))
+        })
+        .boxed()
+    }
+}
+
+fn mock_stream<T: Send + 'static>(records: Vec<T>) -> BoxStream<'static, Result<T, RpcError>> {
+    stream::iter(records.into_iter().map(Ok)).boxed()
+}
+
+struct StreamState {
+    http: Client,
+    rpc_client: Arc<StellarRpcClient>,
+    db: Option<Arc<Database>>,
+    kind: HorizonStreamKind,
+    idle_timeout: Duration,
+    cursor: Option<String>,
+    cursor_loaded: bool,
+    /// Cursor of the last yielded record; persisted once the consumer asks for
+    /// the next one so a crash never skips an unprocessed record
+    pending_cursor: Option<String>,
+    response: Option<reqwest::Response>,
+    decoder: SseDecoder,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    backoff: Duration,
+}
+
+impl StreamState {
+    async fn next_record<T: DeserializeOwned>(&mut self) -> Result<T, RpcError> {
+        self.flush_cursor().await;
+        if !self.cursor_loaded {
+            self.load_cursor().await;
+        }
+
+        loop {
+            if let Some(event) = self.decoder.next_event() {
+                if event.is_control() {
+                    continue;
+                }
+                if let Some(id) = event.id {
+                    self.cursor = Some(id.clone());
+                    self.pending_cursor = Some(id);
+                }
+                return serde_json::from_str(&event.data).map_err(|e| {
+                    RpcError::ParseError(format!(
+                        "Invalid {} stream record: {}",
+                        self.kind.path(),
+                        e
+                    ))
+                });
+            }
+
+            let Some(response) = self.response.as_mut() else {
+                match self.connect().await {
+                    Ok(response) => {
+                        self.response = Some(response);
+                        self.backoff = self.initial_backoff;
+                    }
+                    Err(e) => {
+                        metrics::record_rpc_error(e.error_type_label(), "horizon_stream");
+                        warn!(
+                            "Failed to open Horizon {} stream: {}; retrying in {:?}",
+                            self.kind.path(),
+                            e,
+                            self.backoff
+                        );
+                        self.wait_backoff().await;
+                    }
+                }
+                continue;
+            };
+
+            match tokio::time::timeout(self.idle_timeout, response.chunk()).await {
+                Ok(Ok(Some(bytes))) => self.decoder.push(&bytes),
+                Ok(Ok(None)) => {
+                    debug!("Horizon {} stream closed by server", self.kind.path());
+                    self.reset_connection();
+                }
+                Ok(Err(e)) => {
+                    warn!("Horizon {} stream read failed: {}", self.kind.path(), e);
+                    self.reset_connection();
+                    self.wait_backoff().await;
+                }
+                Err(_) => {
+                    debug!(
+                        "Horizon {} stream idle for {:?}, reconnecting",
+                        self.kind.path(),
+                        self.idle_timeout
+                    );
+                    self.reset_connection();
+                }
+            }
+        }
+    }
+
+    /// Open the SSE connection on the healthiest Horizon endpoint, failing
+    /// over to the others if it is down
+    async fn connect(&self) -> Result<reqwest::Response, RpcError> {
+        let path = self.kind.path();
+        let query = self.kind.query();
+        let cursor = self.cursor.as_deref().unwrap_or("now");
+
+        self.rpc_client
+            .horizon_pool()
+            .call(|endpoint| async move {
+                let url = format!("{}/{}?cursor={}{}", endpoint.url(), path, cursor, query);
+                let response = self
+                    .http
+                    .get(&url)
+                    .header("Accept", "text/event-stream")
+                    .send()
+                    .await
+                    .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+                if !response.status().is_success() {
+                    return Err(map_response_error(response).await);
+                }
+                info!(
+                    "Connected to Horizon {} stream on {}",
+                    path,
+                    endpoint.name()
+                );
+                Ok(response)
+            })
+            .await
+    }
+
+    async fn load_cursor(&mut self) {
+        if let Some(db) = &self.db {
+            match db.get_ingestion_cursor(&self.kind.cursor_task_name()).await {
+                Ok(cursor) => self.cursor = cursor,
+                Err(e) => warn!(
+                    "Failed to load {} stream cursor, starting from now: {}",
+                    self.kind.path(),
+                    e
+                ),
+            }
+        }
+        self.cursor_loaded = true;
+    }
+
+    async fn flush_cursor(&mut self) {
+        let (Some(db), Some(cursor)) = (&self.db, self.pending_cursor.take()) else {
+            return;
+        };
+        if let Err(e) = db
+            .update_ingestion_cursor(&self.kind.cursor_task_name(), &cursor)
+            .await
+        {
+            warn!(
+                "Failed to persist {} stream cursor {}: {}",
+                self.kind.path(),
+                cursor,
+                e
+            );
+        }
+    }
+
+    fn reset_connection(&mut self) {
+        self.response = None;
+        self.decoder = SseDecoder::default();
+    }
+
+    async fn wait_backoff(&mut self) {
+        tokio::time::sleep(self.backoff).await;
+        self.backoff = std::cmp::min(self.backoff.saturating_mul(2), self.max_backoff);
+    }
+}
+
+/// A single dispatched Server-Sent Event
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    pub id: Option<String>,
+    pub event: Option<String>,
+    pub data: String,
+}
+
+impl SseEvent {
+    /// Horizon opens every stream with `event: open` / `data: "hello"`
+    fn is_control(&self) -> bool {
+        self.event.as_deref() == Some("open") || self.data == "\"hello\"" || self.data.is_empty()
+    }
+}
+
+/// Incremental `text/event-stream` parser fed with raw response chunks.
+/// Bytes are buffered until a full line arrives, so multi-byte characters
+/// split across chunks decode intact.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buffer: Vec<u8>,
+    event: SseEvent,
+    data_lines: Vec<String>,
+}
+
+impl SseDecoder {
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Returns the next complete event, leaving partial input buffered
+    pub fn next_event(&mut self) -> Option<SseEvent> {
+        loop {
+            let end = self.buffer.iter().position(|&b| b == b'\n')?;
+            let mut line: Vec<u8> = self.buffer.drain(..=end).collect();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            let line = String::from_utf8_lossy(&line);
+
+            if line.is_empty() {
+                let mut event = std::mem::take(&mut self.event);
+                event.data = std::mem::take(&mut self.data_lines).join("\n");
+                if event.id.is_some() || event.event.is_some() || !event.data.is_empty() {
+                    return Some(event);
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = line.split_once(':').unwrap_or((&line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "id" => self.event.id = Some(value.to_string()),
+                "event" => self.event.event = Some(value.to_string()),
+                "data" => self.data_lines.push(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_parses_horizon_events() {
+        let mut decoder = SseDecoder::default();
+        decoder.push(b"retry: 1000\nevent: open\ndata: \"hello\"\n\n");
+        decoder.push(b"id: 123-1\ndata: {\"id\":\"1\"}\n\n");
+
+        let open = decoder.next_event().unwrap();
+        assert!(open.is_control());
+
+        let record = decoder.next_event().unwrap();
+        assert_eq!(record.id.as_deref(), Some("123-1"));
+        assert_eq!(record.data, "{\"id\":\"1\"}");
+        assert!(decoder.next_event().is_none());
+    }
+
+    #[test]
+    fn test_decoder_buffers_partial_chunks() {
+        let mut decoder = SseDecoder::default();
+        decoder.push(b"id: 42\r\ndata: {\"a\":");
+        assert!(decoder.next_event().is_none());
+
+        decoder.push(b"1}\r\n\r\n: keep-alive\n\n");
+        let event = decoder.next_event().unwrap();
+        assert_eq!(event.id.as_deref(), Some("42"));
+        assert_eq!(event.data, "{\"a\":1}");
+        assert!(decoder.next_event().is_none());
+    }
+
+    #[test]
+    fn test_decoder_keeps_multibyte_chars_split_across_chunks() {
+        let mut decoder = SseDecoder::default();
+        let chunk = "data: {\"memo\":\"caf\u{e9} \u{20ac}\"}\n\n".as_bytes();
+        let split = chunk.iter().position(|&b| b == 0xC3).unwrap() + 1;
+        decoder.push(&chunk[..split]);
+        assert!(decoder.next_event().is_none());
+
+        decoder.push(&chunk[split..]);
+        let event = decoder.next_event().unwrap();
+        assert_eq!(event.data, "{\"memo\":\"caf\u{e9} \u{20ac}\"}");
+    }
+
+    #[test]
+    fn test_cursor_task_names_are_per_kind() {
+        assert_eq!(
+            HorizonStreamKind::Payments.cursor_task_name(),
+            "horizon_stream_payments"
+        );
+        assert_ne!(
+            HorizonStreamKind::Trades.cursor_task_name(),
+            HorizonStreamKind::Effects.cursor_task_name()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_payment_stream_yields_typed_records() {
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let payments: Vec<_> = HorizonStream::new(client).payments().collect().await;
+
+        assert_eq!(payments.len(), MOCK_STREAM_RECORDS as usize);
+        assert!(payments.iter().all(Result::is_ok));
+    }
+}
@@ -7,7 +7,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::rpc::{Asset, StellarRpcClient};
+use crate::rpc::{Asset, EndpointStatus, HealthResponse, StellarRpcClient};
 
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
@@ -37,17 +37,42 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
-/// Health check for Stellar RPC
+/// Health of every configured RPC and Horizon endpoint
+#[derive(Debug, Serialize)]
+pub struct EndpointHealthReport {
+    pub rpc: Vec<EndpointStatus>,
+    pub horizon: Vec<EndpointStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcHealthResponse {
+    #[serde(flatten)]
+    pub health: HealthResponse,
+    pub endpoints: EndpointHealthReport,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RpcHealthErrorResponse {
+    pub error: String,
+    pub endpoints: EndpointHealthReport,
+}
+
+/// Health check for Stellar RPC, including per-endpoint failover status
 #[tracing::instrument(skip(client))]
 pub async fn rpc_health_check(
     State(client): State<Arc<StellarRpcClient>>,
-) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
-    match client.check_health().await {
-        Ok(health) => Ok(Json(health)),
+) -> Result<impl IntoResponse, (StatusCode, Json<RpcHealthErrorResponse>)> {
+    let result = client.check_health().await;
+    let (rpc, horizon) = client.endpoint_status().await;
+    let endpoints = EndpointHealthReport { rpc, horizon };
+
+    match result {
+        Ok(health) => Ok(Json(RpcHealthResponse { health, endpoints })),
         Err(e) => Err((
             StatusCode::SERVICE_UNAVAILABLE,
-            Json(ErrorResponse {
+            Json(RpcHealthErrorResponse {
                 error: format!("RPC health check failed: {}", e),
+                endpoints,
             }),
         )),
     }
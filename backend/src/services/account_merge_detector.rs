@@ -1,11 +1,13 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
 use sqlx::{Pool, Sqlite};
 use std::sync::Arc;
 use tracing::{info, warn};
 
-use crate::rpc::{HorizonOperation, StellarRpcClient};
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
+use crate::rpc::{HorizonEffect, HorizonOperation, StellarRpcClient};
 
 #[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct AccountMergeEvent {
@@ -50,6 +52,18 @@ impl AccountMergeDetector {
             .fetch_operations_for_ledger(ledger_sequence)
             .await?;
 
+        self.process_operations(ledger_sequence, &operations, None)
+            .await
+    }
+
+    /// Persists merges from already fetched operations. Merged balances come from
+    /// the bundle's effects when given, otherwise from one effects request per merge.
+    async fn process_operations(
+        &self,
+        ledger_sequence: u64,
+        operations: &[HorizonOperation],
+        bundle: Option<&LedgerBundle>,
+    ) -> Result<u64> {
         let mut inserted = 0_u64;
 
         for operation in operations
@@ -57,7 +71,7 @@ impl AccountMergeDetector {
             .filter(|op| op.operation_type == "account_merge")
         {
             if self
-                .persist_merge_from_operation(ledger_sequence, operation)
+                .persist_merge_from_operation(ledger_sequence, operation, bundle)
                 .await?
             {
                 inserted += 1;
@@ -78,6 +92,7 @@ impl AccountMergeDetector {
         &self,
         ledger_sequence: u64,
         operation: &HorizonOperation,
+        bundle: Option<&LedgerBundle>,
     ) -> Result<bool> {
         let destination_account = match operation.into.clone() {
             Some(account) => account,
@@ -95,9 +110,16 @@ impl AccountMergeDetector {
             .clone()
             .unwrap_or_else(|| operation.source_account.clone());
 
-        let merged_balance = self
-            .resolve_merged_balance(&operation.id, &destination_account)
-            .await;
+        let merged_balance = match bundle {
+            Some(bundle) => credited_amount(
+                bundle.effects_for_operation(&operation.id),
+                &destination_account,
+            ),
+            None => {
+                self.resolve_merged_balance(&operation.id, &destination_account)
+                    .await
+            }
+        };
 
         let created_at = DateTime::parse_from_rfc3339(&operation.created_at)
             .map(|dt| dt.with_timezone(&Utc))
@@ -118,27 +140,15 @@ impl AccountMergeDetector {
 
     async fn resolve_merged_balance(&self, operation_id: &str, destination: &str) -> f64 {
         match self.rpc_client.fetch_operation_effects(operation_id).await {
-            Ok(effects) => {
-                let credited_amount: f64 = effects
-                    .into_iter()
-                    .filter(|effect| effect.effect_type == "account_credited")
-                    .filter(|effect| effect.account.as_deref() == Some(destination))
-                    .filter_map(|effect| effect.amount.and_then(|value| value.parse::<f64>().ok()))
-                    .sum();
-
-                if credited_amount > 0.0 {
-                    return credited_amount;
-                }
-            }
+            Ok(effects) => credited_amount(&effects, destination),
             Err(error) => {
                 warn!(
                     "Failed to fetch effects for operation {} while resolving merge amount: {}",
                     operation_id, error
                 );
+                0.0
             }
         }
-
-        0.0
     }
 
     async fn persist_merge_event(&self, event: &AccountMergeEvent) -> Result<bool> {
@@ -186,6 +196,29 @@ impl AccountMergeDetector {
         Ok(rows)
     }
 
+    /// Merges where the account was merged away or received the balance
+    pub async fn get_merges_for_account(
+        &self,
+        account: &str,
+        limit: i64,
+    ) -> Result<Vec<AccountMergeEvent>> {
+        let rows = sqlx::query_as::<_, AccountMergeEvent>(
+            r#"
+            SELECT operation_id, transaction_hash, ledger_sequence, source_account, destination_account, merged_balance, created_at
+            FROM account_merges
+            WHERE source_account = $1 OR destination_account = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(account)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
     pub async fn get_merge_stats(&self) -> Result<AccountMergeStats> {
         let row: (i64, f64, i64, i64) = sqlx::query_as(
             r#"
@@ -231,3 +264,32 @@ impl AccountMergeDetector {
         Ok(rows)
     }
 }
+
+#[async_trait]
+impl LedgerProcessor for AccountMergeDetector {
+    fn name(&self) -> &'static str {
+        "account_merges"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Operations, BundlePart::Effects]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+        self.process_operations(bundle.sequence, &bundle.operations, Some(bundle))
+            .await
+    }
+}
+
+/// Amount credited to the destination account by a merge's effects
+fn credited_amount<'a>(
+    effects: impl IntoIterator<Item = &'a HorizonEffect>,
+    destination: &str,
+) -> f64 {
+    effects
+        .into_iter()
+        .filter(|effect| effect.effect_type == "account_credited")
+        .filter(|effect| effect.account.as_deref() == Some(destination))
+        .filter_map(|effect| effect.amount.as_deref()?.parse::<f64>().ok())
+        .sum()
+}
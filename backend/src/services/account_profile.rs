@@ -0,0 +1,419 @@
+//! Account-centric view combining Horizon account state, recent payments and
+//! the merge, fee-bump and sponsorship trackers.
+
+use chrono::DateTime;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::models::FeeBumpTransaction;
+use crate::muxed::{self, MuxedAccountInfo};
+use crate::rpc::error::RpcError;
+use crate::rpc::{HorizonBalance, Payment, StellarRpcClient};
+use crate::services::account_merge_detector::{AccountMergeDetector, AccountMergeEvent};
+use crate::services::fee_bump_tracker::FeeBumpTrackerService;
+use crate::services::sponsorship_tracker::{Sponsorship, SponsorshipTrackerService};
+use crate::utils::horizon_asset_key;
+
+/// History entries returned per tracker
+const HISTORY_LIMIT: i64 = 100;
+const MAX_COUNTERPARTIES: usize = 50;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccountProfileError {
+    #[error("Invalid account address: {0}")]
+    InvalidAccount(String),
+
+    #[error("Account not found: {0}")]
+    NotFound(String),
+
+    #[error("Horizon request failed: {0}")]
+    Upstream(#[from] RpcError),
+
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountBalance {
+    /// `XLM:native`, `CODE:ISSUER` or `pool:<id>` for pool shares
+    pub asset: String,
+    pub asset_type: String,
+    pub balance: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Trustline {
+    pub asset: String,
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub balance: f64,
+    pub limit: Option<f64>,
+    pub is_authorized: Option<bool>,
+    pub is_clawback_enabled: Option<bool>,
+    pub sponsor: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Counterparty {
+    pub account: String,
+    pub payments_in: u64,
+    pub payments_out: u64,
+    pub last_payment_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CorridorUsage {
+    /// `SOURCE->DESTINATION` asset keys
+    pub corridor_key: String,
+    pub payments_in: u64,
+    pub payments_out: u64,
+    /// Total in the destination asset
+    pub volume: f64,
+}
+
+/// Daily payment volume for one asset; outgoing volume is in the asset the
+/// account sent, incoming volume in the asset it received
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VolumeBucket {
+    pub date: String,
+    pub asset: String,
+    pub volume_in: f64,
+    pub volume_out: f64,
+    pub payments_in: u64,
+    pub payments_out: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PaymentActivity {
+    /// Most recent payments the activity is computed from
+    pub payments_analyzed: usize,
+    pub first_payment_at: Option<String>,
+    pub last_payment_at: Option<String>,
+    pub counterparties: Vec<Counterparty>,
+    pub corridors: Vec<CorridorUsage>,
+    pub volume: Vec<VolumeBucket>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountSponsorships {
+    /// Reserves other accounts sponsor for this account
+    pub sponsored_by: Vec<Sponsorship>,
+    /// Reserves this account sponsors for others
+    pub sponsoring: Vec<Sponsorship>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountProfile {
+    pub account_id: String,
+    /// Set when the profile was requested with an M-address
+    pub muxed: Option<MuxedAccountInfo>,
+    pub sequence: String,
+    pub subentry_count: u32,
+    pub num_sponsoring: u32,
+    pub num_sponsored: u32,
+    pub home_domain: Option<String>,
+    pub last_modified_time: Option<String>,
+    pub balances: Vec<AccountBalance>,
+    pub trustlines: Vec<Trustline>,
+    pub activity: PaymentActivity,
+    pub merges: Vec<AccountMergeEvent>,
+    pub fee_bumps: Vec<FeeBumpTransaction>,
+    pub sponsorships: AccountSponsorships,
+}
+
+pub struct AccountProfileService {
+    rpc_client: Arc<StellarRpcClient>,
+    merges: Arc<AccountMergeDetector>,
+    fee_bumps: Arc<FeeBumpTrackerService>,
+    sponsorships: Arc<SponsorshipTrackerService>,
+}
+
+impl AccountProfileService {
+    pub fn new(
+        rpc_client: Arc<StellarRpcClient>,
+        merges: Arc<AccountMergeDetector>,
+        fee_bumps: Arc<FeeBumpTrackerService>,
+        sponsorships: Arc<SponsorshipTrackerService>,
+    ) -> Self {
+        Self {
+            rpc_client,
+            merges,
+            fee_bumps,
+            sponsorships,
+        }
+    }
+
+    /// Build the profile for a G- or M-address, analyzing up to
+    /// `max_payments` of the account's most recent payments
+    pub async fn get_profile(
+        &self,
+        account_input: &str,
+        max_payments: u32,
+    ) -> Result<AccountProfile, AccountProfileError> {
+        let (account_id, muxed) = resolve_account(account_input)
+            .ok_or_else(|| AccountProfileError::InvalidAccount(account_input.to_string()))?;
+
+        let account = match self.rpc_client.fetch_account(&account_id).await {
+            Ok(account) => account,
+            Err(RpcError::ServerError { status: 404, .. }) => {
+                return Err(AccountProfileError::NotFound(account_id));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let (payments, merges, fee_bumps, sponsored_by, sponsoring) = tokio::join!(
+            self.rpc_client
+                .fetch_all_account_payments(&account_id, Some(max_payments)),
+            self.merges
+                .get_merges_for_account(&account_id, HISTORY_LIMIT),
+            self.fee_bumps
+                .get_fee_bumps_by_fee_source(&account_id, HISTORY_LIMIT),
+            self.sponsorships.get_sponsorships_for_account(&account_id),
+            self.sponsorships.get_sponsorships_by_sponsor(&account_id),
+        );
+
+        let (balances, trustlines) = split_balances(&account.balances);
+        Ok(AccountProfile {
+            activity: summarize_payments(&account_id, &payments?),
+            account_id,
+            muxed,
+            sequence: account.sequence,
+            subentry_count: account.subentry_count,
+            num_sponsoring: account.num_sponsoring,
+            num_sponsored: account.num_sponsored,
+            home_domain: account.home_domain,
+            last_modified_time: account.last_modified_time,
+            balances,
+            trustlines,
+            merges: merges?,
+            fee_bumps: fee_bumps?,
+            sponsorships: AccountSponsorships {
+                sponsored_by: sponsored_by?,
+                sponsoring: sponsoring?,
+            },
+        })
+    }
+}
+
+/// Base G-address for the input, with muxed details for M-addresses
+fn resolve_account(input: &str) -> Option<(String, Option<MuxedAccountInfo>)> {
+    let account = muxed::normalize_account_input(input)?;
+    if !muxed::is_muxed_address(account) {
+        return Some((account.to_string(), None));
+    }
+    let info = muxed::parse_muxed_address(account)?;
+    Some((info.base_account.clone()?, Some(info)))
+}
+
+fn split_balances(balances: &[HorizonBalance]) -> (Vec<AccountBalance>, Vec<Trustline>) {
+    let mut all = Vec::with_capacity(balances.len());
+    let mut trustlines = Vec::new();
+
+    for b in balances {
+        let amount = b.balance.parse().unwrap_or(0.0);
+        let asset = match (&b.liquidity_pool_id, b.asset_type.as_str()) {
+            (Some(pool_id), _) => format!("pool:{}", pool_id),
+            (None, asset_type) => horizon_asset_key(
+                asset_type,
+                b.asset_code.as_deref(),
+                b.asset_issuer.as_deref(),
+            ),
+        };
+        all.push(AccountBalance {
+            asset: asset.clone(),
+            asset_type: b.asset_type.clone(),
+            balance: amount,
+        });
+
+        if let (Some(code), Some(issuer)) = (&b.asset_code, &b.asset_issuer) {
+            trustlines.push(Trustline {
+                asset,
+                asset_code: code.clone(),
+                asset_issuer: issuer.clone(),
+                balance: amount,
+                limit: b.limit.as_deref().and_then(|l| l.parse().ok()),
+                is_authorized: b.is_authorized,
+                is_clawback_enabled: b.is_clawback_enabled,
+                sponsor: b.sponsor.clone(),
+            });
+        }
+    }
+
+    (all, trustlines)
+}
+
+/// Counterparties, corridors and daily volume from the account's point of
+/// view; payments the account neither sent nor received are ignored
+fn summarize_payments(account_id: &str, payments: &[Payment]) -> PaymentActivity {
+    let mut counterparties: HashMap<String, Counterparty> = HashMap::new();
+    let mut corridors: BTreeMap<String, CorridorUsage> = BTreeMap::new();
+    let mut volume: BTreeMap<(String, String), VolumeBucket> = BTreeMap::new();
+    let mut first_payment_at: Option<String> = None;
+    let mut last_payment_at: Option<String> = None;
+    let mut analyzed = 0;
+
+    for p in payments {
+        let from = p.from.as_deref().unwrap_or(&p.source_account);
+        let Some(to) = p.get_destination() else {
+            continue;
+        };
+        let incoming = to == account_id;
+        if incoming == (from == account_id) {
+            // Unrelated, or a payment to itself
+            continue;
+        }
+        let Ok(created_at) = DateTime::parse_from_rfc3339(&p.created_at) else {
+            continue;
+        };
+        analyzed += 1;
+
+        let destination_asset = horizon_asset_key(
+            &p.asset_type,
+            p.get_asset_code().as_deref(),
+            p.get_asset_issuer().as_deref(),
+        );
+        let source_asset = match &p.source_asset_type {
+            Some(asset_type) if p.is_path_payment() => horizon_asset_key(
+                asset_type,
+                p.source_asset_code.as_deref(),
+                p.source_asset_issuer.as_deref(),
+            ),
+            _ => destination_asset.clone(),
+        };
+        let received: f64 = p.get_amount().parse().unwrap_or(0.0);
+        let sent: f64 = p.get_source_amount().parse().unwrap_or(received);
+
+        let counterparty = if incoming {
+            from.to_string()
+        } else {
+            to.clone()
+        };
+        let entry = counterparties
+            .entry(counterparty.clone())
+            .or_insert_with(|| Counterparty {
+                account: counterparty,
+                ..Default::default()
+            });
+        let corridor_key = format!("{}->{}", source_asset, destination_asset);
+        let corridor = corridors
+            .entry(corridor_key.clone())
+            .or_insert_with(|| CorridorUsage {
+                corridor_key,
+                ..Default::default()
+            });
+        corridor.volume += received;
+
+        let date = created_at.format("%Y-%m-%d").to_string();
+        let bucket_asset = if incoming {
+            destination_asset
+        } else {
+            source_asset
+        };
+        let bucket = volume
+            .entry((date.clone(), bucket_asset.clone()))
+            .or_insert_with(|| VolumeBucket {
+                date,
+                asset: bucket_asset,
+                ..Default::default()
+            });
+
+        if incoming {
+            entry.payments_in += 1;
+            corridor.payments_in += 1;
+            bucket.payments_in += 1;
+            bucket.volume_in += received;
+        } else {
+            entry.payments_out += 1;
+            corridor.payments_out += 1;
+            bucket.payments_out += 1;
+            bucket.volume_out += sent;
+        }
+        if p.created_at > entry.last_payment_at {
+            entry.last_payment_at = p.created_at.clone();
+        }
+        if first_payment_at.as_ref().is_none_or(|t| p.created_at < *t) {
+            first_payment_at = Some(p.created_at.clone());
+        }
+        if last_payment_at.as_ref().is_none_or(|t| p.created_at > *t) {
+            last_payment_at = Some(p.created_at.clone());
+        }
+    }
+
+    let mut counterparties: Vec<Counterparty> = counterparties.into_values().collect();
+    counterparties.sort_by(|a, b| {
+        (b.payments_in + b.payments_out)
+            .cmp(&(a.payments_in + a.payments_out))
+            .then_with(|| a.account.cmp(&b.account))
+    });
+    counterparties.truncate(MAX_COUNTERPARTIES);
+
+    let mut corridors: Vec<CorridorUsage> = corridors.into_values().collect();
+    corridors.sort_by_key(|c| std::cmp::Reverse(c.payments_in + c.payments_out));
+
+    PaymentActivity {
+        payments_analyzed: analyzed,
+        first_payment_at,
+        last_payment_at,
+        counterparties,
+        corridors,
+        volume: volume.into_values().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GDYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYYY000";
+
+    #[test]
+    fn test_resolve_account() {
+        let g = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let (base, info) = resolve_account(g).unwrap();
+        assert_eq!(base, g);
+        assert!(info.is_none());
+        assert!(resolve_account("not-an-account").is_none());
+
+        let m = "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVAAAAAAAAAAAAAJLK";
+        let (base, info) = resolve_account(m).unwrap();
+        assert_eq!(
+            base,
+            "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ"
+        );
+        assert_eq!(info.unwrap().muxed_id, Some(9_223_372_036_854_775_808));
+    }
+
+    #[test]
+    fn test_split_balances() {
+        let account = StellarRpcClient::mock_account(ACCOUNT);
+        let (balances, trustlines) = split_balances(&account.balances);
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[1].asset, "XLM:native");
+        assert_eq!(trustlines.len(), 1);
+        assert_eq!(trustlines[0].asset_code, "USDC");
+        assert_eq!(trustlines[0].is_authorized, Some(true));
+    }
+
+    #[test]
+    fn test_summarize_payments() {
+        let mut payments = StellarRpcClient::mock_payments(3);
+        // payment_0 is received by ACCOUNT, payment_1 is sent by it,
+        // payment_2 does not involve it
+        payments[1].source_account = ACCOUNT.to_string();
+        payments[1].from = None;
+
+        let activity = summarize_payments(ACCOUNT, &payments);
+        assert_eq!(activity.payments_analyzed, 2);
+        assert_eq!(activity.counterparties.len(), 2);
+
+        let received = activity.volume.iter().map(|b| b.volume_in).sum::<f64>();
+        let sent_count = activity.volume.iter().map(|b| b.payments_out).sum::<u64>();
+        assert!((received - 100.0).abs() < f64::EPSILON);
+        assert_eq!(sent_count, 1);
+        assert!(activity
+            .corridors
+            .iter()
+            .all(|c| c.payments_in + c.payments_out == 1));
+    }
+}
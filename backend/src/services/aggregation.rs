@@ -1,12 +1,13 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Duration, Timelike, Utc};
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use tokio::time::{interval, Duration as TokioDuration};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::database::Database;
-use crate::models::corridor::CorridorMetrics;
+use crate::models::corridor::{CorridorMetrics, PaymentRecord};
 use crate::services::analytics::compute_metrics_from_payments;
 
 const MAX_RETRIES: i32 = 3;
@@ -103,10 +104,22 @@ impl AggregationService {
     }
 
     /// Execute the actual aggregation logic
+    ///
+    /// Covers the completed hours since the last completed run (at most
+    /// `lookback_hours` back), so each payment is counted once even though
+    /// the upsert adds to existing rows.
     async fn execute_aggregation(&self, job_id: &str, now: DateTime<Utc>) -> Result<usize> {
         // Calculate time window for aggregation
-        let end_time = now;
-        let start_time = end_time - Duration::hours(self.config.lookback_hours);
+        let end_time = self.truncate_to_hour(now);
+        let earliest = end_time - Duration::hours(self.config.lookback_hours);
+        let start_time = match self.db.last_processed_hour().await? {
+            Some(last_hour) => last_hour.max(earliest),
+            None => earliest,
+        };
+        if start_time >= end_time {
+            info!("No completed hours to aggregate");
+            return Ok(0);
+        }
 
         info!(
             "Aggregating corridor metrics from {} to {}",
@@ -123,16 +136,51 @@ impl AggregationService {
 
         if payments.is_empty() {
             info!("No payments found in time window");
+            self.update_last_processed_hour(job_id, end_time).await?;
             return Ok(0);
         }
 
         info!("Processing {} payments", payments.len());
 
-        // Compute metrics for each corridor
-        let corridor_metrics = compute_metrics_from_payments(&payments);
+        // A batch that hit the limit ends at its last payment's hour, which
+        // the next run picks up again
+        let end_time = if payments.len() as i64 >= self.config.batch_size {
+            let last_hour = self.truncate_to_hour(payments[payments.len() - 1].timestamp);
+            if last_hour <= start_time {
+                bail!(
+                    "More than {} payments in the hour starting {}",
+                    self.config.batch_size,
+                    start_time.to_rfc3339()
+                );
+            }
+            last_hour
+        } else {
+            end_time
+        };
+
+        // Compute metrics for each corridor and hour the payments fall in
+        let mut by_hour: BTreeMap<DateTime<Utc>, Vec<PaymentRecord>> = BTreeMap::new();
+        for payment in payments {
+            let hour = self.truncate_to_hour(payment.timestamp);
+            if hour < end_time {
+                by_hour.entry(hour).or_default().push(payment);
+            }
+        }
+        let corridor_metrics: Vec<CorridorMetrics> = by_hour
+            .into_iter()
+            .flat_map(|(hour, payments)| {
+                compute_metrics_from_payments(&payments)
+                    .into_iter()
+                    .map(move |metric| CorridorMetrics {
+                        date: hour,
+                        ..metric
+                    })
+            })
+            .collect();
 
         if corridor_metrics.is_empty() {
             info!("No corridor metrics computed");
+            self.update_last_processed_hour(job_id, end_time).await?;
             return Ok(0);
         }
 
@@ -143,8 +191,7 @@ impl AggregationService {
         let stored_count = self.store_hourly_metrics(hourly_metrics).await?;
 
         // Update last processed hour
-        let last_hour = self.truncate_to_hour(end_time);
-        self.update_last_processed_hour(job_id, last_hour).await?;
+        self.update_last_processed_hour(job_id, end_time).await?;
 
         Ok(stored_count)
     }
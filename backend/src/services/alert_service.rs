@@ -1,4 +1,4 @@
-//! Alert Service for Contract Event Monitoring
+//! Alert Service for Contract Event and Ledger Chain Monitoring
 //!
 //! Sends alerts when verification failures or anomalies are detected.
 
@@ -34,6 +34,27 @@ pub enum AlertType {
         epoch: u64,
         submitter: String,
     },
+    LedgerChainBreak {
+        sequence: u64,
+        expected_previous_hash: String,
+        actual_previous_hash: String,
+    },
+    LedgerFork {
+        sequence: u64,
+        stored_hash: String,
+        received_hash: String,
+    },
+    SponsorshipRevoked {
+        sequence: u64,
+        sponsor: String,
+        sponsored_account: String,
+    },
+    AssetSupplyJump {
+        asset: String,
+        previous_supply: f64,
+        current_supply: f64,
+        change_pct: f64,
+    },
 }
 
 /// Alert message
@@ -159,6 +180,104 @@ impl AlertService {
 
         self.send_alert(alert).await
     }
+
+    /// Send ledger hash-chain break alert
+    pub async fn alert_ledger_chain_break(
+        &self,
+        sequence: u64,
+        expected_previous_hash: String,
+        actual_previous_hash: String,
+    ) -> Result<()> {
+        let alert = Alert {
+            alert_type: AlertType::LedgerChainBreak {
+                sequence,
+                expected_previous_hash: expected_previous_hash.clone(),
+                actual_previous_hash: actual_previous_hash.clone(),
+            },
+            severity: AlertSeverity::Critical,
+            message: format!(
+                "Ledger {} does not link to its predecessor. Expected previous hash: {}, Actual: {}",
+                sequence, expected_previous_hash, actual_previous_hash
+            ),
+            timestamp: chrono::Utc::now(),
+        };
+
+        self.send_alert(alert).await
+    }
+
+    /// Send ledger fork alert
+    pub async fn alert_ledger_fork(
+        &self,
+        sequence: u64,
+        stored_hash: String,
+        received_hash: String,
+    ) -> Result<()> {
+        let alert = Alert {
+            alert_type: AlertType::LedgerFork {
+                sequence,
+                stored_hash: stored_hash.clone(),
+                received_hash: received_hash.clone(),
+            },
+            severity: AlertSeverity::Critical,
+            message: format!(
+                "Conflicting hash received for ledger {}. Stored: {}, Received: {}",
+                sequence, stored_hash, received_hash
+            ),
+            timestamp: chrono::Utc::now(),
+        };
+
+        self.send_alert(alert).await
+    }
+
+    /// Send sponsorship revocation alert
+    pub async fn alert_sponsorship_revoked(
+        &self,
+        sequence: u64,
+        sponsor: String,
+        sponsored_account: String,
+    ) -> Result<()> {
+        let alert = Alert {
+            alert_type: AlertType::SponsorshipRevoked {
+                sequence,
+                sponsor: sponsor.clone(),
+                sponsored_account: sponsored_account.clone(),
+            },
+            severity: AlertSeverity::Warning,
+            message: format!(
+                "Sponsor {} revoked a reserve sponsorship of {} in ledger {}",
+                sponsor, sponsored_account, sequence
+            ),
+            timestamp: chrono::Utc::now(),
+        };
+
+        self.send_alert(alert).await
+    }
+
+    /// Send asset supply jump alert
+    pub async fn alert_asset_supply_jump(
+        &self,
+        asset: String,
+        previous_supply: f64,
+        current_supply: f64,
+        change_pct: f64,
+    ) -> Result<()> {
+        let alert = Alert {
+            alert_type: AlertType::AssetSupplyJump {
+                asset: asset.clone(),
+                previous_supply,
+                current_supply,
+                change_pct,
+            },
+            severity: AlertSeverity::Warning,
+            message: format!(
+                "Circulating supply of {} changed by {:+.2}% ({} -> {})",
+                asset, change_pct, previous_supply, current_supply
+            ),
+            timestamp: chrono::Utc::now(),
+        };
+
+        self.send_alert(alert).await
+    }
 }
 
 impl Default for AlertService {
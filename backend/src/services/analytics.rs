@@ -250,7 +250,7 @@ mod tests {
         timestamp: chrono::DateTime<chrono::Utc>,
     ) -> PaymentRecord {
         PaymentRecord {
-            id: Uuid::new_v4(),
+            id: Uuid::new_v4().to_string(),
             source_asset_code: source_code.to_string(),
             source_asset_issuer: "issuer1".to_string(),
             destination_asset_code: dest_code.to_string(),
@@ -273,7 +273,7 @@ mod tests {
     ) -> PaymentRecord {
         let submission = timestamp - chrono::Duration::milliseconds(latency_ms);
         PaymentRecord {
-            id: Uuid::new_v4(),
+            id: Uuid::new_v4().to_string(),
             source_asset_code: source_code.to_string(),
             source_asset_issuer: "issuer1".to_string(),
             destination_asset_code: dest_code.to_string(),
@@ -0,0 +1,953 @@
+//! SEP-24 / SEP-31 transaction lifecycle tracking.
+//!
+//! Transactions initiated through the anchor proxies are recorded here and
+//! polled on the anchor's transfer server until they reach a final status.
+//! The first time each status is seen is stored. A transfer's settlement time
+//! runs from the first settlement start status, `pending_user_transfer_start`
+//! (SEP-24) or `pending_sender` (SEP-31), to `completed`. The anchor's
+//! timestamps are used where they mark those statuses: `completed_at`, and
+//! `started_at` for SEP-31 transactions, which start in `pending_sender`.
+//! SEP-24 `started_at` is when the transaction was created in `incomplete`,
+//! so it would count the user's interactive flow; the observed time of the
+//! start status is used instead.
+//! Settlement times are aggregated per anchor and per corridor, and each
+//! anchor's mean is fed into `Database::update_anchor_metrics`.
+//!
+//! SEP-10 tokens are kept for polling only when `ENCRYPTION_KEY` is a real
+//! key; they are never stored in plaintext.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::utils::{asset_key, format_time, parse_time, percentile};
+
+/// Statuses from which settlement time is measured
+const SETTLEMENT_START_STATUSES: &[&str] = &["pending_user_transfer_start", "pending_sender"];
+
+/// Final statuses other than `completed`
+const FAILED_STATUSES: &[&str] = &[
+    "refunded",
+    "expired",
+    "error",
+    "no_market",
+    "too_small",
+    "too_large",
+];
+
+/// Longest back-off between polls of a transfer server that keeps failing
+const MAX_POLL_BACKOFF_SECS: i64 = 3600;
+
+/// Issuer of the off-chain leg of a corridor key
+const OFFCHAIN_ISSUER: &str = "offchain";
+
+#[derive(Debug, Clone)]
+pub struct AnchorTransferConfig {
+    /// Transfers without a final status after this long stop being polled
+    pub max_tracking_hours: i64,
+    /// Transfers polled per run
+    pub poll_batch_size: i64,
+    /// Time between polls of a transfer that is still pending; read from the
+    /// polling job's interval
+    pub poll_interval_secs: i64,
+    /// Days of completed transfers behind the settlement statistics
+    pub metrics_window_days: i64,
+    pub request_timeout_secs: u64,
+}
+
+impl AnchorTransferConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            max_tracking_hours: std::env::var("ANCHOR_TRANSFER_MAX_TRACKING_HOURS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.max_tracking_hours),
+            poll_batch_size: std::env::var("ANCHOR_TRANSFER_POLL_BATCH_SIZE")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| (1..=1000).contains(v))
+                .unwrap_or(defaults.poll_batch_size),
+            poll_interval_secs: std::env::var("JOB_ANCHOR_TRANSFER_POLL_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| *v >= 0)
+                .unwrap_or(defaults.poll_interval_secs),
+            metrics_window_days: std::env::var("ANCHOR_TRANSFER_METRICS_WINDOW_DAYS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| (1..=365).contains(v))
+                .unwrap_or(defaults.metrics_window_days),
+            request_timeout_secs: defaults.request_timeout_secs,
+        }
+    }
+}
+
+impl Default for AnchorTransferConfig {
+    fn default() -> Self {
+        Self {
+            max_tracking_hours: 72,
+            poll_batch_size: 100,
+            poll_interval_secs: 60,
+            metrics_window_days: 30,
+            request_timeout_secs: 15,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferProtocol {
+    Sep24,
+    Sep31,
+}
+
+impl TransferProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sep24 => "sep24",
+            Self::Sep31 => "sep31",
+        }
+    }
+
+    /// Status a transaction has when the proxy creates it
+    fn initial_status(&self) -> &'static str {
+        match self {
+            Self::Sep24 => "incomplete",
+            Self::Sep31 => "pending_sender",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferOutcome {
+    Completed,
+    Failed,
+    /// Still pending when tracking gave up
+    Abandoned,
+}
+
+impl TransferOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Abandoned => "abandoned",
+        }
+    }
+
+    fn from_status(status: &str) -> Option<Self> {
+        if status == "completed" {
+            Some(Self::Completed)
+        } else if FAILED_STATUSES.contains(&status) {
+            Some(Self::Failed)
+        } else {
+            None
+        }
+    }
+}
+
+/// A transaction the proxy just created on an anchor
+#[derive(Debug, Clone)]
+pub struct NewAnchorTransfer {
+    pub protocol: TransferProtocol,
+    /// `deposit`, `withdrawal` or `send`
+    pub kind: String,
+    pub transfer_server: String,
+    pub transaction_id: String,
+    pub asset_code: Option<String>,
+    /// Looked up among the anchor's assets when not given
+    pub asset_issuer: Option<String>,
+    /// SEP-38 identifier of the other leg, e.g. `iso4217:BRL`
+    pub counter_asset: Option<String>,
+    pub amount: Option<String>,
+    pub jwt: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AnchorTransfer {
+    pub id: String,
+    pub protocol: String,
+    pub kind: String,
+    pub transfer_server: String,
+    pub transaction_id: String,
+    pub anchor_id: Option<String>,
+    pub asset_code: Option<String>,
+    pub amount: Option<String>,
+    pub corridor_key: Option<String>,
+    pub status: String,
+    pub outcome: Option<String>,
+    pub settlement_time_ms: Option<i64>,
+    pub poll_count: i64,
+    pub poll_error_count: i64,
+    pub last_error: Option<String>,
+    pub initiated_at: String,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TransferStatusEvent {
+    pub status: String,
+    pub observed_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnchorTransferDetail {
+    #[serde(flatten)]
+    pub transfer: AnchorTransfer,
+    pub status_history: Vec<TransferStatusEvent>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AnchorTransferFilter {
+    pub anchor_id: Option<String>,
+    pub corridor_key: Option<String>,
+    pub status: Option<String>,
+    pub limit: i64,
+}
+
+/// Settlement latency distribution of one anchor or corridor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettlementDistribution {
+    pub key: String,
+    pub completed_count: i64,
+    pub failed_count: i64,
+    pub abandoned_count: i64,
+    /// Completed transfers with a measured settlement time
+    pub sample_count: i64,
+    pub mean_ms: Option<f64>,
+    pub p50_ms: Option<i64>,
+    pub p90_ms: Option<i64>,
+    pub p99_ms: Option<i64>,
+    pub min_ms: Option<i64>,
+    pub max_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PollSummary {
+    pub polled: u64,
+    pub status_changes: u64,
+    pub finished: u64,
+    pub errors: u64,
+}
+
+/// Transaction fields read from `GET /transaction` (SEP-24) and
+/// `GET /transactions/:id` (SEP-31)
+#[derive(Debug, Clone, Deserialize)]
+struct AnchorTransaction {
+    status: String,
+    #[serde(default)]
+    started_at: Option<String>,
+    #[serde(default)]
+    completed_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnchorTransactionResponse {
+    transaction: AnchorTransaction,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct PendingTransfer {
+    id: String,
+    protocol: String,
+    transfer_server: String,
+    transaction_id: String,
+    jwt: Option<String>,
+    status: String,
+    poll_error_count: i64,
+    initiated_at: String,
+}
+
+pub struct AnchorTransferTracker {
+    db: Arc<Database>,
+    client: Client,
+    config: AnchorTransferConfig,
+    /// None unless `ENCRYPTION_KEY` is a non-zero 32-byte hex key
+    encryption_key: Option<String>,
+}
+
+impl AnchorTransferTracker {
+    pub fn new(db: Arc<Database>, config: AnchorTransferConfig) -> Self {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(config.request_timeout_secs))
+            .build()
+            .unwrap_or_else(|_| Client::new());
+        let encryption_key = std::env::var("ENCRYPTION_KEY")
+            .ok()
+            .filter(|key| is_usable_key(key));
+        Self {
+            db,
+            client,
+            config,
+            encryption_key,
+        }
+    }
+
+    pub fn with_encryption_key(mut self, key: &str) -> Self {
+        self.encryption_key = Some(key.to_string()).filter(|key| is_usable_key(key));
+        self
+    }
+
+    /// Start tracking a transaction created through a proxy; returns the
+    /// tracker's id for it
+    pub async fn record_initiated(&self, transfer: NewAnchorTransfer) -> Result<String> {
+        let transfer_server = base_url(&transfer.transfer_server);
+        let anchor_id = self.resolve_anchor(&transfer_server).await?;
+        let jwt = match &transfer.jwt {
+            Some(jwt) => {
+                let key = self
+                    .encryption_key
+                    .as_deref()
+                    .context("ENCRYPTION_KEY is not set; refusing to store the SEP-10 token")?;
+                Some(
+                    crate::crypto::encrypt_data(jwt, key)
+                        .context("Failed to encrypt the SEP-10 token")?,
+                )
+            }
+            None => None,
+        };
+        let corridor_key = self.corridor_key(&transfer, anchor_id.as_deref()).await?;
+        let status = transfer.protocol.initial_status();
+        let now = format_time(Utc::now(), SecondsFormat::Millis);
+
+        let mut tx = self.db.pool().begin().await?;
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO anchor_transfers (
+                id, protocol, kind, transfer_server, transaction_id, anchor_id, asset_code,
+                amount, corridor_key, jwt, status, next_poll_at, initiated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $12)
+            ON CONFLICT (transfer_server, transaction_id) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(transfer.protocol.as_str())
+        .bind(&transfer.kind)
+        .bind(&transfer_server)
+        .bind(&transfer.transaction_id)
+        .bind(&anchor_id)
+        .bind(&transfer.asset_code)
+        .bind(&transfer.amount)
+        .bind(&corridor_key)
+        .bind(&jwt)
+        .bind(status)
+        .bind(&now)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let (id,): (String,) = sqlx::query_as(
+            "SELECT id FROM anchor_transfers WHERE transfer_server = $1 AND transaction_id = $2",
+        )
+        .bind(&transfer_server)
+        .bind(&transfer.transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if inserted > 0 {
+            sqlx::query(
+                "INSERT INTO anchor_transfer_status_events (transfer_id, status, observed_at) VALUES ($1, $2, $3)",
+            )
+            .bind(&id)
+            .bind(status)
+            .bind(&now)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(id)
+    }
+
+    /// Poll every transfer that is due; transfers past the tracking limit are
+    /// marked abandoned
+    pub async fn poll_pending(&self) -> Result<PollSummary> {
+        let now = Utc::now();
+        let pending = sqlx::query_as::<_, PendingTransfer>(
+            r#"
+            SELECT id, protocol, transfer_server, transaction_id, jwt, status,
+                   poll_error_count, initiated_at
+            FROM anchor_transfers
+            WHERE outcome IS NULL AND next_poll_at <= $1
+            ORDER BY next_poll_at ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(format_time(now, SecondsFormat::Millis))
+        .bind(self.config.poll_batch_size)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let tracking_cutoff = now - Duration::hours(self.config.max_tracking_hours);
+        let mut summary = PollSummary::default();
+        for transfer in pending {
+            if parse_time(&transfer.initiated_at).is_some_and(|t| t < tracking_cutoff) {
+                self.finish(&transfer.id, TransferOutcome::Abandoned, None, now)
+                    .await?;
+                summary.finished += 1;
+                continue;
+            }
+
+            summary.polled += 1;
+            match self.fetch_transaction(&transfer).await {
+                Ok(transaction) => {
+                    let (changed, finished) = self.apply_status(&transfer, &transaction).await?;
+                    summary.status_changes += changed as u64;
+                    summary.finished += finished as u64;
+                }
+                Err(e) => {
+                    summary.errors += 1;
+                    warn!(
+                        "Failed to poll {} transaction {} on {}: {}",
+                        transfer.protocol, transfer.transaction_id, transfer.transfer_server, e
+                    );
+                    self.record_poll_error(&transfer, &e.to_string(), now)
+                        .await?;
+                }
+            }
+        }
+
+        if summary.polled > 0 || summary.finished > 0 {
+            info!(
+                "Polled {} anchor transfers: {} status changes, {} finished, {} errors",
+                summary.polled, summary.status_changes, summary.finished, summary.errors
+            );
+        }
+        Ok(summary)
+    }
+
+    async fn fetch_transaction(&self, transfer: &PendingTransfer) -> Result<AnchorTransaction> {
+        let id = urlencoding::encode(&transfer.transaction_id);
+        let url = if transfer.protocol == TransferProtocol::Sep31.as_str() {
+            format!("{}/transactions/{}", transfer.transfer_server, id)
+        } else {
+            format!("{}/transaction?id={}", transfer.transfer_server, id)
+        };
+
+        let mut req = self.client.get(&url);
+        if let Some(jwt) = &transfer.jwt {
+            let key = self
+                .encryption_key
+                .as_deref()
+                .context("ENCRYPTION_KEY is not set; cannot decrypt the SEP-10 token")?;
+            let jwt = crate::crypto::decrypt_data(jwt, key)
+                .context("Failed to decrypt the SEP-10 token")?;
+            req = req.header("Authorization", format!("Bearer {}", jwt));
+        }
+        let resp = req.send().await?.error_for_status()?;
+        let body = resp
+            .json::<AnchorTransactionResponse>()
+            .await
+            .context("Unexpected transaction response")?;
+        Ok(body.transaction)
+    }
+
+    /// Store a polled status; returns whether the status changed and whether
+    /// the transfer finished
+    async fn apply_status(
+        &self,
+        transfer: &PendingTransfer,
+        transaction: &AnchorTransaction,
+    ) -> Result<(bool, bool)> {
+        let now = Utc::now();
+        sqlx::query(
+            r#"
+            UPDATE anchor_transfers
+            SET status = $1,
+                poll_count = poll_count + 1,
+                poll_error_count = 0,
+                last_error = NULL,
+                next_poll_at = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(&transaction.status)
+        .bind(format_time(
+            now + Duration::seconds(self.config.poll_interval_secs),
+            SecondsFormat::Millis,
+        ))
+        .bind(&transfer.id)
+        .execute(self.db.pool())
+        .await?;
+
+        let changed = transaction.status != transfer.status;
+        if changed {
+            sqlx::query(
+                r#"
+                INSERT INTO anchor_transfer_status_events (transfer_id, status, observed_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (transfer_id, status) DO NOTHING
+                "#,
+            )
+            .bind(&transfer.id)
+            .bind(&transaction.status)
+            .bind(format_time(now, SecondsFormat::Millis))
+            .execute(self.db.pool())
+            .await?;
+        }
+
+        let Some(outcome) = TransferOutcome::from_status(&transaction.status) else {
+            return Ok((changed, false));
+        };
+        let settlement_time_ms = if outcome == TransferOutcome::Completed {
+            self.settlement_time_ms(transfer, transaction).await?
+        } else {
+            None
+        };
+        self.finish(&transfer.id, outcome, settlement_time_ms, now)
+            .await?;
+        Ok((changed, true))
+    }
+
+    /// First settlement start status to `completed`, preferring the anchor's
+    /// timestamps where they mark those statuses
+    async fn settlement_time_ms(
+        &self,
+        transfer: &PendingTransfer,
+        transaction: &AnchorTransaction,
+    ) -> Result<Option<i64>> {
+        let events = self.get_status_history(&transfer.id).await?;
+        // SEP-24 `started_at` marks `incomplete`, not the settlement start
+        let reported_start = if transfer.protocol == TransferProtocol::Sep31.as_str() {
+            transaction.started_at.as_deref().and_then(parse_time)
+        } else {
+            None
+        };
+        let started = reported_start.or_else(|| {
+            events
+                .iter()
+                .filter(|e| SETTLEMENT_START_STATUSES.contains(&e.status.as_str()))
+                .filter_map(|e| parse_time(&e.observed_at))
+                .min()
+        });
+        let completed = transaction
+            .completed_at
+            .as_deref()
+            .and_then(parse_time)
+            .or_else(|| {
+                events
+                    .iter()
+                    .find(|e| e.status == "completed")
+                    .and_then(|e| parse_time(&e.observed_at))
+            });
+        Ok(match (started, completed) {
+            (Some(started), Some(completed)) if completed >= started => {
+                Some((completed - started).num_milliseconds())
+            }
+            _ => None,
+        })
+    }
+
+    async fn finish(
+        &self,
+        transfer_id: &str,
+        outcome: TransferOutcome,
+        settlement_time_ms: Option<i64>,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE anchor_transfers
+            SET outcome = $1, settlement_time_ms = $2, finished_at = $3, jwt = NULL
+            WHERE id = $4
+            "#,
+        )
+        .bind(outcome.as_str())
+        .bind(settlement_time_ms)
+        .bind(format_time(now, SecondsFormat::Millis))
+        .bind(transfer_id)
+        .execute(self.db.pool())
+        .await?;
+        Ok(())
+    }
+
+    async fn record_poll_error(
+        &self,
+        transfer: &PendingTransfer,
+        error: &str,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let errors = transfer.poll_error_count + 1;
+        let backoff_secs = (60_i64 << errors.min(6)).min(MAX_POLL_BACKOFF_SECS);
+        sqlx::query(
+            r#"
+            UPDATE anchor_transfers
+            SET poll_count = poll_count + 1,
+                poll_error_count = $1,
+                last_error = $2,
+                next_poll_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(errors)
+        .bind(error)
+        .bind(format_time(
+            now + Duration::seconds(backoff_secs),
+            SecondsFormat::Millis,
+        ))
+        .bind(&transfer.id)
+        .execute(self.db.pool())
+        .await?;
+        Ok(())
+    }
+
+    /// `CODE:ISSUER->CODE:ISSUER` of a transfer, fixed when it is recorded:
+    /// the anchor's asset on one leg and the SEP-38 counter asset, or the
+    /// asset code at `offchain`, on the other, ordered by the transfer's
+    /// direction. None when the asset's issuer is unknown
+    async fn corridor_key(
+        &self,
+        transfer: &NewAnchorTransfer,
+        anchor_id: Option<&str>,
+    ) -> Result<Option<String>> {
+        let Some(code) = transfer.asset_code.as_deref() else {
+            return Ok(None);
+        };
+        let onchain = if code == "XLM" || code == "native" {
+            Some(asset_key("XLM", "native"))
+        } else if let Some(issuer) = &transfer.asset_issuer {
+            Some(asset_key(code, issuer))
+        } else if let Some(anchor_id) = anchor_id {
+            sqlx::query_scalar::<_, String>(
+                r#"
+                SELECT asset_issuer FROM assets
+                WHERE anchor_id = $1 AND asset_code = $2
+                ORDER BY asset_issuer
+                LIMIT 1
+                "#,
+            )
+            .bind(anchor_id)
+            .bind(code)
+            .fetch_optional(self.db.pool())
+            .await?
+            .map(|issuer| asset_key(code, &issuer))
+        } else {
+            None
+        };
+        let Some(onchain) = onchain else {
+            return Ok(None);
+        };
+        let counter = transfer
+            .counter_asset
+            .as_deref()
+            .and_then(sep38_asset_key)
+            .unwrap_or_else(|| asset_key(code, OFFCHAIN_ISSUER));
+        Ok(Some(if transfer.kind == "deposit" {
+            format!("{}->{}", counter, onchain)
+        } else {
+            format!("{}->{}", onchain, counter)
+        }))
+    }
+
+    /// Anchor whose home domain matches the transfer server host
+    async fn resolve_anchor(&self, transfer_server: &str) -> Result<Option<String>> {
+        let Some(host) = transfer_host(transfer_server) else {
+            return Ok(None);
+        };
+        let anchors: Vec<(String, String)> = sqlx::query_as(
+            "SELECT id, home_domain FROM anchors WHERE home_domain IS NOT NULL AND home_domain != ''",
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+        Ok(anchors
+            .into_iter()
+            .find(|(_, domain)| host_matches(&host, domain))
+            .map(|(id, _)| id))
+    }
+
+    /// Feed each anchor's mean settlement time over the metrics window into
+    /// its reliability metrics; returns the number of anchors updated
+    pub async fn refresh_anchor_metrics(&self) -> Result<u64> {
+        let since = format_time(
+            Utc::now() - Duration::days(self.config.metrics_window_days),
+            SecondsFormat::Millis,
+        );
+        let means: Vec<(String, f64)> = sqlx::query_as(
+            r#"
+            SELECT anchor_id, AVG(settlement_time_ms)
+            FROM anchor_transfers
+            WHERE anchor_id IS NOT NULL
+              AND outcome = 'completed'
+              AND settlement_time_ms IS NOT NULL
+              AND finished_at >= $1
+            GROUP BY anchor_id
+            "#,
+        )
+        .bind(since)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut updated = 0_u64;
+        for (anchor_id, mean_ms) in means {
+            let Ok(id) = Uuid::parse_str(&anchor_id) else {
+                continue;
+            };
+            let Some(anchor) = self.db.get_anchor_by_id(id).await? else {
+                continue;
+            };
+            self.db
+                .update_anchor_metrics(
+                    id,
+                    anchor.total_transactions,
+                    anchor.successful_transactions,
+                    anchor.failed_transactions,
+                    Some(mean_ms.round().min(i32::MAX as f64) as i32),
+                    Some(anchor.total_volume_usd),
+                )
+                .await?;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+
+    // ========================================================================
+    // Query Methods
+    // ========================================================================
+
+    /// Tracked transfers matching the filter, most recent first
+    pub async fn get_transfers(
+        &self,
+        filter: &AnchorTransferFilter,
+    ) -> Result<Vec<AnchorTransfer>> {
+        let transfers = sqlx::query_as::<_, AnchorTransfer>(
+            r#"
+            SELECT id, protocol, kind, transfer_server, transaction_id, anchor_id, asset_code,
+                   amount, corridor_key, status, outcome, settlement_time_ms, poll_count,
+                   poll_error_count, last_error, initiated_at, finished_at
+            FROM anchor_transfers
+            WHERE ($1 IS NULL OR anchor_id = $1)
+              AND ($2 IS NULL OR corridor_key = $2)
+              AND ($3 IS NULL OR status = $3)
+            ORDER BY initiated_at DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(&filter.anchor_id)
+        .bind(&filter.corridor_key)
+        .bind(&filter.status)
+        .bind(filter.limit)
+        .fetch_all(self.db.pool())
+        .await?;
+        Ok(transfers)
+    }
+
+    /// A transfer with the time each of its statuses was first seen
+    pub async fn get_transfer(&self, id: &str) -> Result<Option<AnchorTransferDetail>> {
+        let transfer = sqlx::query_as::<_, AnchorTransfer>(
+            r#"
+            SELECT id, protocol, kind, transfer_server, transaction_id, anchor_id, asset_code,
+                   amount, corridor_key, status, outcome, settlement_time_ms, poll_count,
+                   poll_error_count, last_error, initiated_at, finished_at
+            FROM anchor_transfers
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(self.db.pool())
+        .await?;
+        let Some(transfer) = transfer else {
+            return Ok(None);
+        };
+        let status_history = self.get_status_history(id).await?;
+        Ok(Some(AnchorTransferDetail {
+            transfer,
+            status_history,
+        }))
+    }
+
+    async fn get_status_history(&self, transfer_id: &str) -> Result<Vec<TransferStatusEvent>> {
+        let events = sqlx::query_as::<_, TransferStatusEvent>(
+            r#"
+            SELECT status, observed_at
+            FROM anchor_transfer_status_events
+            WHERE transfer_id = $1
+            ORDER BY observed_at ASC, rowid ASC
+            "#,
+        )
+        .bind(transfer_id)
+        .fetch_all(self.db.pool())
+        .await?;
+        Ok(events)
+    }
+
+    /// Settlement latency per anchor over the last `days`
+    pub async fn get_anchor_settlement(&self, days: i64) -> Result<Vec<SettlementDistribution>> {
+        self.settlement_distributions("anchor_id", days).await
+    }
+
+    /// Settlement latency per corridor over the last `days`
+    pub async fn get_corridor_settlement(&self, days: i64) -> Result<Vec<SettlementDistribution>> {
+        self.settlement_distributions("corridor_key", days).await
+    }
+
+    async fn settlement_distributions(
+        &self,
+        group_column: &'static str,
+        days: i64,
+    ) -> Result<Vec<SettlementDistribution>> {
+        let rows: Vec<(String, String, Option<i64>)> = sqlx::query_as(&format!(
+            r#"
+            SELECT {group_column}, outcome, settlement_time_ms
+            FROM anchor_transfers
+            WHERE {group_column} IS NOT NULL
+              AND outcome IS NOT NULL
+              AND finished_at >= $1
+            "#
+        ))
+        .bind(format_time(
+            Utc::now() - Duration::days(days),
+            SecondsFormat::Millis,
+        ))
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut groups: HashMap<String, (SettlementDistribution, Vec<i64>)> = HashMap::new();
+        for (key, outcome, settlement_time_ms) in rows {
+            let (dist, samples) = groups.entry(key.clone()).or_insert_with(|| {
+                (
+                    SettlementDistribution {
+                        key,
+                        completed_count: 0,
+                        failed_count: 0,
+                        abandoned_count: 0,
+                        sample_count: 0,
+                        mean_ms: None,
+                        p50_ms: None,
+                        p90_ms: None,
+                        p99_ms: None,
+                        min_ms: None,
+                        max_ms: None,
+                    },
+                    Vec::new(),
+                )
+            });
+            match outcome.as_str() {
+                "completed" => {
+                    dist.completed_count += 1;
+                    samples.extend(settlement_time_ms);
+                }
+                "failed" => dist.failed_count += 1,
+                _ => dist.abandoned_count += 1,
+            }
+        }
+
+        let mut distributions: Vec<SettlementDistribution> = groups
+            .into_values()
+            .map(|(mut dist, mut samples)| {
+                samples.sort_unstable();
+                dist.sample_count = samples.len() as i64;
+                if !samples.is_empty() {
+                    dist.mean_ms = Some(samples.iter().sum::<i64>() as f64 / samples.len() as f64);
+                    dist.p50_ms = percentile(&samples, 50.0);
+                    dist.p90_ms = percentile(&samples, 90.0);
+                    dist.p99_ms = percentile(&samples, 99.0);
+                    dist.min_ms = samples.first().copied();
+                    dist.max_ms = samples.last().copied();
+                }
+                dist
+            })
+            .collect();
+        distributions.sort_by(|a, b| {
+            b.completed_count
+                .cmp(&a.completed_count)
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        Ok(distributions)
+    }
+}
+
+/// Non-zero 32-byte hex key
+fn is_usable_key(key: &str) -> bool {
+    hex::decode(key).is_ok_and(|bytes| bytes.len() == 32 && bytes.iter().any(|b| *b != 0))
+}
+
+/// Corridor-style key of a SEP-38 asset identifier; fiat is `CODE:offchain`
+fn sep38_asset_key(asset: &str) -> Option<String> {
+    let mut parts = asset.splitn(3, ':');
+    match (parts.next()?, parts.next()?, parts.next()) {
+        ("stellar", "native", None) => Some(asset_key("XLM", "native")),
+        ("stellar", code, Some(issuer)) => Some(asset_key(code, issuer)),
+        ("iso4217", currency, None) => Some(asset_key(currency, OFFCHAIN_ISSUER)),
+        _ => None,
+    }
+}
+
+fn base_url(transfer_server: &str) -> String {
+    transfer_server.trim().trim_end_matches('/').to_string()
+}
+
+fn transfer_host(transfer_server: &str) -> Option<String> {
+    let url = reqwest::Url::parse(transfer_server.trim()).ok()?;
+    url.host_str().map(|h| h.to_ascii_lowercase())
+}
+
+/// Host is the home domain or one of its subdomains
+fn host_matches(host: &str, home_domain: &str) -> bool {
+    let domain = home_domain
+        .trim()
+        .trim_end_matches('/')
+        .to_ascii_lowercase();
+    !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome_from_status() {
+        assert_eq!(
+            TransferOutcome::from_status("completed"),
+            Some(TransferOutcome::Completed)
+        );
+        assert_eq!(
+            TransferOutcome::from_status("refunded"),
+            Some(TransferOutcome::Failed)
+        );
+        assert_eq!(TransferOutcome::from_status("pending_anchor"), None);
+    }
+
+    #[test]
+    fn test_sep38_asset_key() {
+        assert_eq!(
+            sep38_asset_key("stellar:USDC:GAISSUER").as_deref(),
+            Some("USDC:GAISSUER")
+        );
+        assert_eq!(
+            sep38_asset_key("stellar:native").as_deref(),
+            Some("XLM:native")
+        );
+        assert_eq!(
+            sep38_asset_key("iso4217:BRL").as_deref(),
+            Some("BRL:offchain")
+        );
+        assert_eq!(sep38_asset_key("BRL"), None);
+    }
+
+    #[test]
+    fn test_usable_encryption_key() {
+        assert!(is_usable_key(&"ab".repeat(32)));
+        assert!(!is_usable_key(&"0".repeat(64)));
+        assert!(!is_usable_key("not-hex"));
+        assert!(!is_usable_key(&"ab".repeat(16)));
+    }
+
+    #[test]
+    fn test_host_matches_home_domain() {
+        let host = transfer_host("https://api.anchor.example.com/sep24/").unwrap();
+        assert!(host_matches(&host, "anchor.example.com"));
+        assert!(host_matches(&host, "API.anchor.example.com"));
+        assert!(!host_matches(&host, "example.org"));
+        assert!(!host_matches("badanchor.example.com", "anchor.example.com"));
+        assert!(!host_matches(&host, ""));
+    }
+}
@@ -0,0 +1,1040 @@
+//! Streaming anomaly detection over hourly corridor and anchor metrics.
+//!
+//! Every metric series (a corridor's success rate, an anchor's settlement
+//! time, ...) is scored hour by hour against a baseline: the same hour of the
+//! week over the previous weeks when enough of them were recorded, otherwise
+//! an exponentially weighted moving average (EWMA) of the series itself. Only
+//! changes in the harmful direction count (a success rate falling, a latency
+//! rising), and two kinds of anomalies are raised:
+//!
+//! - `spike`: one hour more than `z_threshold` standard deviations off its
+//!   baseline;
+//! - `drift`: an EWMA control chart over the hourly z-scores crossing its
+//!   control limit, which catches slow degradations no single hour shows.
+//!
+//! The EWMA state of each series is stored, so a run only reads the hours
+//! completed since the previous one. Anomalies are stored, added to the alert
+//! history of users with an `anomaly` alert rule, pushed to the `anomalies`
+//! WebSocket channel and sent as an `anomaly.detected` webhook.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::webhooks::{WebhookEventType, WebhookService};
+use crate::websocket::{WsMessage, WsState};
+
+/// WebSocket channel receiving every anomaly; per-subject channels are
+/// `anomalies:{subject_id}`
+pub const ANOMALIES_CHANNEL: &str = "anomalies";
+
+/// `metric_type` of the alert rules that subscribe users to anomalies; the
+/// rule threshold is the minimum score
+pub const ANOMALY_ALERT_METRIC: &str = "anomaly";
+
+#[derive(Debug, Clone)]
+pub struct AnomalyConfig {
+    /// Hourly z-score that makes a spike
+    pub z_threshold: f64,
+    /// Score from which an anomaly is critical rather than a warning
+    pub critical_score: f64,
+    /// Smoothing factor of the per-series EWMA baseline
+    pub ewma_alpha: f64,
+    /// Smoothing factor of the drift control chart
+    pub drift_lambda: f64,
+    /// Control limit of the drift chart, in standard deviations of the chart
+    pub drift_threshold: f64,
+    /// Weeks of the same hour-of-week behind the seasonal baseline
+    pub seasonal_weeks: i64,
+    /// Same hour-of-week observations needed for the seasonal baseline
+    pub min_seasonal_points: usize,
+    /// Hours a series needs before its EWMA baseline is used
+    pub min_ewma_points: i64,
+    /// Transactions an hour needs before its success rate and latency count
+    pub min_transactions: i64,
+    /// Completed hours further back than this are not scored
+    pub catchup_hours: i64,
+    /// Anomalies in older hours only update the baselines, without alerting
+    pub max_alert_age_hours: i64,
+}
+
+impl AnomalyConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            z_threshold: std::env::var("ANOMALY_Z_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+                .unwrap_or(defaults.z_threshold),
+            critical_score: std::env::var("ANOMALY_CRITICAL_SCORE")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+                .unwrap_or(defaults.critical_score),
+            ewma_alpha: std::env::var("ANOMALY_EWMA_ALPHA")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > 0.0 && *v < 1.0)
+                .unwrap_or(defaults.ewma_alpha),
+            drift_threshold: std::env::var("ANOMALY_DRIFT_THRESHOLD")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+                .unwrap_or(defaults.drift_threshold),
+            seasonal_weeks: std::env::var("ANOMALY_SEASONAL_WEEKS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| (1..=12).contains(v))
+                .unwrap_or(defaults.seasonal_weeks),
+            ..defaults
+        }
+    }
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            z_threshold: 3.0,
+            critical_score: 5.0,
+            ewma_alpha: 0.05,
+            drift_lambda: 0.1,
+            drift_threshold: 3.0,
+            seasonal_weeks: 4,
+            min_seasonal_points: 3,
+            min_ewma_points: 24,
+            min_transactions: 10,
+            catchup_hours: 48,
+            max_alert_age_hours: 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalySubject {
+    Corridor,
+    Anchor,
+}
+
+impl AnomalySubject {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Corridor => "corridor",
+            Self::Anchor => "anchor",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "corridor" => Some(Self::Corridor),
+            "anchor" => Some(Self::Anchor),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMetric {
+    SuccessRate,
+    SettlementLatencyMs,
+    VolumeUsd,
+    LiquidityDepthUsd,
+}
+
+/// Direction in which a change of a metric is harmful
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Down,
+    Up,
+    Both,
+}
+
+impl Direction {
+    /// How far `z` lies in the harmful direction
+    fn badness(&self, z: f64) -> f64 {
+        match self {
+            Self::Down => -z,
+            Self::Up => z,
+            Self::Both => z.abs(),
+        }
+    }
+}
+
+impl AnomalyMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SuccessRate => "success_rate",
+            Self::SettlementLatencyMs => "settlement_latency_ms",
+            Self::VolumeUsd => "volume_usd",
+            Self::LiquidityDepthUsd => "liquidity_depth_usd",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "success_rate" => Some(Self::SuccessRate),
+            "settlement_latency_ms" => Some(Self::SettlementLatencyMs),
+            "volume_usd" => Some(Self::VolumeUsd),
+            "liquidity_depth_usd" => Some(Self::LiquidityDepthUsd),
+            _ => None,
+        }
+    }
+
+    fn direction(&self) -> Direction {
+        match self {
+            Self::SuccessRate | Self::LiquidityDepthUsd => Direction::Down,
+            Self::SettlementLatencyMs => Direction::Up,
+            Self::VolumeUsd => Direction::Both,
+        }
+    }
+
+    /// Smallest standard deviation a baseline is given, so that a flat
+    /// history does not turn noise into anomalies
+    fn min_std(&self, mean: f64) -> f64 {
+        let (relative, absolute) = match self {
+            Self::SuccessRate => (0.0, 1.0),
+            Self::SettlementLatencyMs => (0.1, 500.0),
+            Self::VolumeUsd => (0.1, 100.0),
+            Self::LiquidityDepthUsd => (0.05, 100.0),
+        };
+        (relative * mean.abs()).max(absolute)
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        match self {
+            Self::SuccessRate => value.clamp(0.0, 100.0),
+            _ => value.max(0.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    Spike,
+    Drift,
+}
+
+impl AnomalyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Spike => "spike",
+            Self::Drift => "drift",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BaselineKind {
+    Seasonal,
+    Ewma,
+}
+
+impl BaselineKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Seasonal => "seasonal",
+            Self::Ewma => "ewma",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Baseline {
+    kind: BaselineKind,
+    mean: f64,
+    std: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Anomaly {
+    pub id: String,
+    pub subject_type: String,
+    pub subject_id: String,
+    pub metric: String,
+    pub kind: String,
+    /// `seasonal` (same hour of the week) or `ewma`
+    pub baseline: String,
+    pub observed_value: f64,
+    pub expected_value: f64,
+    pub expected_low: f64,
+    pub expected_high: f64,
+    /// Standard deviations beyond the baseline in the harmful direction
+    pub score: f64,
+    pub severity: String,
+    pub observed_at: String,
+    pub detected_at: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AnomalyFilter {
+    pub subject_type: Option<String>,
+    pub subject_id: Option<String>,
+    pub metric: Option<String>,
+    pub limit: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct AnomalyRunSummary {
+    pub points_scored: u64,
+    pub anomalies: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct SeriesKey {
+    subject: AnomalySubject,
+    metric: AnomalyMetric,
+}
+
+type SeriesId = (SeriesKey, String);
+
+/// Stored EWMA state of one series
+#[derive(Debug, Clone, PartialEq)]
+struct SeriesState {
+    ewma_mean: f64,
+    ewma_variance: f64,
+    /// EWMA of the hourly z-scores
+    drift: f64,
+    in_drift: bool,
+    observations: i64,
+    last_observed_at: Option<DateTime<Utc>>,
+}
+
+impl SeriesState {
+    fn new() -> Self {
+        Self {
+            ewma_mean: 0.0,
+            ewma_variance: 0.0,
+            drift: 0.0,
+            in_drift: false,
+            observations: 0,
+            last_observed_at: None,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct SeriesStateRow {
+    subject_type: String,
+    subject_id: String,
+    metric: String,
+    ewma_mean: f64,
+    ewma_variance: f64,
+    drift: f64,
+    in_drift: bool,
+    observations: i64,
+    last_observed_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Detection {
+    kind: AnomalyKind,
+    baseline: Baseline,
+    score: f64,
+}
+
+#[derive(Debug, FromRow)]
+struct CorridorHourRow {
+    corridor_key: String,
+    hour_bucket: String,
+    total_transactions: Option<i64>,
+    success_rate: Option<f64>,
+    volume_usd: Option<f64>,
+    avg_settlement_latency_ms: Option<i64>,
+    liquidity_depth_usd: Option<f64>,
+}
+
+#[derive(Debug, FromRow)]
+struct AnchorMetricsRow {
+    anchor_id: String,
+    timestamp: DateTime<Utc>,
+    success_rate: f64,
+    total_transactions: i64,
+    avg_settlement_time_ms: Option<i64>,
+}
+
+pub struct AnomalyDetector {
+    db: Arc<Database>,
+    ws_state: Arc<WsState>,
+    config: AnomalyConfig,
+    webhooks: WebhookService,
+}
+
+impl AnomalyDetector {
+    pub fn new(db: Arc<Database>, ws_state: Arc<WsState>, config: AnomalyConfig) -> Self {
+        Self {
+            webhooks: WebhookService::new(db.pool().clone()),
+            db,
+            ws_state,
+            config,
+        }
+    }
+
+    /// Score every hour completed since the previous run and publish the
+    /// anomalies found
+    pub async fn run(&self) -> Result<AnomalyRunSummary> {
+        self.run_at(Utc::now()).await
+    }
+
+    /// `run` as of `now`; hours from `now`'s hour onwards are incomplete and
+    /// left for a later run
+    pub async fn run_at(&self, now: DateTime<Utc>) -> Result<AnomalyRunSummary> {
+        let current_hour = now.duration_trunc(Duration::hours(1))?;
+        let scoring_start = current_hour - Duration::hours(self.config.catchup_hours);
+        let history_start = scoring_start - Duration::weeks(self.config.seasonal_weeks);
+        let alert_start = current_hour - Duration::hours(self.config.max_alert_age_hours);
+
+        let mut series = self
+            .load_corridor_series(history_start, current_hour)
+            .await?;
+        series.extend(self.load_anchor_series(history_start, current_hour).await?);
+        let mut states = self.load_states().await?;
+
+        let mut summary = AnomalyRunSummary::default();
+        let mut rules = None;
+        for ((key, subject_id), points) in &series {
+            let (mut state, resume_from) = match states.remove(&(*key, subject_id.clone())) {
+                Some(state) => {
+                    let resume_from = state.last_observed_at.map(|at| at.max(scoring_start));
+                    (state, resume_from)
+                }
+                // A new series is primed with all the history loaded
+                None => (SeriesState::new(), None),
+            };
+            let before = state.clone();
+
+            let pending = points
+                .iter()
+                .filter(|(at, _)| resume_from.is_none_or(|from| **at > from));
+            for (&at, &value) in pending {
+                let seasonal = seasonal_baseline(points, at, key.metric, &self.config);
+                let detections = score_point(&mut state, value, seasonal, key.metric, &self.config);
+                state.last_observed_at = Some(at);
+                summary.points_scored += 1;
+                if at < alert_start {
+                    continue;
+                }
+                for detection in detections {
+                    let anomaly = self.build_anomaly(key, subject_id, at, value, &detection, now);
+                    if !self.insert_anomaly(&anomaly).await? {
+                        continue;
+                    }
+                    summary.anomalies += 1;
+                    if rules.is_none() {
+                        rules = Some(self.anomaly_rules().await?);
+                    }
+                    self.publish(&anomaly, rules.as_deref().unwrap_or_default())
+                        .await;
+                }
+            }
+
+            if state != before {
+                self.save_state(key, subject_id, &state).await?;
+            }
+        }
+
+        if summary.anomalies > 0 {
+            info!(
+                "Anomaly detection scored {} hours, found {} anomalies",
+                summary.points_scored, summary.anomalies
+            );
+        }
+        Ok(summary)
+    }
+
+    fn build_anomaly(
+        &self,
+        key: &SeriesKey,
+        subject_id: &str,
+        at: DateTime<Utc>,
+        value: f64,
+        detection: &Detection,
+        now: DateTime<Utc>,
+    ) -> Anomaly {
+        let baseline = detection.baseline;
+        let margin = self.config.z_threshold * baseline.std;
+        Anomaly {
+            id: Uuid::new_v4().to_string(),
+            subject_type: key.subject.as_str().to_string(),
+            subject_id: subject_id.to_string(),
+            metric: key.metric.as_str().to_string(),
+            kind: detection.kind.as_str().to_string(),
+            baseline: baseline.kind.as_str().to_string(),
+            observed_value: value,
+            expected_value: baseline.mean,
+            expected_low: key.metric.clamp(baseline.mean - margin),
+            expected_high: key.metric.clamp(baseline.mean + margin),
+            score: detection.score,
+            severity: if detection.score >= self.config.critical_score {
+                "critical"
+            } else {
+                "warning"
+            }
+            .to_string(),
+            observed_at: at.to_rfc3339(),
+            detected_at: now.to_rfc3339(),
+        }
+    }
+
+    // ========================================================================
+    // Series Loading
+    // ========================================================================
+
+    async fn load_corridor_series(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<HashMap<SeriesId, BTreeMap<DateTime<Utc>, f64>>> {
+        let rows = sqlx::query_as::<_, CorridorHourRow>(
+            r#"
+            SELECT corridor_key, hour_bucket, total_transactions, success_rate, volume_usd,
+                   avg_settlement_latency_ms, liquidity_depth_usd
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= $1 AND hour_bucket < $2
+            "#,
+        )
+        .bind(from.to_rfc3339())
+        .bind(to.to_rfc3339())
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut series: HashMap<SeriesId, BTreeMap<DateTime<Utc>, f64>> = HashMap::new();
+        for row in rows {
+            let Ok(at) = DateTime::parse_from_rfc3339(&row.hour_bucket) else {
+                continue;
+            };
+            let at = at.with_timezone(&Utc);
+            let busy = row.total_transactions.unwrap_or(0) >= self.config.min_transactions;
+            let values = [
+                (
+                    AnomalyMetric::SuccessRate,
+                    row.success_rate.filter(|_| busy),
+                ),
+                (
+                    AnomalyMetric::SettlementLatencyMs,
+                    row.avg_settlement_latency_ms
+                        .filter(|ms| busy && *ms > 0)
+                        .map(|ms| ms as f64),
+                ),
+                (AnomalyMetric::VolumeUsd, row.volume_usd),
+                (AnomalyMetric::LiquidityDepthUsd, row.liquidity_depth_usd),
+            ];
+            for (metric, value) in values {
+                if let Some(value) = value.filter(|v| v.is_finite()) {
+                    let key = SeriesKey {
+                        subject: AnomalySubject::Corridor,
+                        metric,
+                    };
+                    series
+                        .entry((key, row.corridor_key.clone()))
+                        .or_default()
+                        .insert(at, value);
+                }
+            }
+        }
+        Ok(series)
+    }
+
+    /// Anchor metrics are recorded whenever an anchor is refreshed; they are
+    /// averaged per hour
+    async fn load_anchor_series(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<HashMap<SeriesId, BTreeMap<DateTime<Utc>, f64>>> {
+        let rows = sqlx::query_as::<_, AnchorMetricsRow>(
+            r#"
+            SELECT anchor_id, timestamp, success_rate, total_transactions, avg_settlement_time_ms
+            FROM anchor_metrics_history
+            WHERE timestamp >= $1 AND timestamp < $2
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut sums: HashMap<SeriesId, BTreeMap<DateTime<Utc>, (f64, u32)>> = HashMap::new();
+        for row in rows {
+            if row.total_transactions < self.config.min_transactions {
+                continue;
+            }
+            let Ok(hour) = row.timestamp.duration_trunc(Duration::hours(1)) else {
+                continue;
+            };
+            let values = [
+                (AnomalyMetric::SuccessRate, Some(row.success_rate)),
+                (
+                    AnomalyMetric::SettlementLatencyMs,
+                    row.avg_settlement_time_ms
+                        .filter(|ms| *ms > 0)
+                        .map(|ms| ms as f64),
+                ),
+            ];
+            for (metric, value) in values {
+                if let Some(value) = value {
+                    let key = SeriesKey {
+                        subject: AnomalySubject::Anchor,
+                        metric,
+                    };
+                    let sum = sums
+                        .entry((key, row.anchor_id.clone()))
+                        .or_default()
+                        .entry(hour)
+                        .or_insert((0.0, 0));
+                    sum.0 += value;
+                    sum.1 += 1;
+                }
+            }
+        }
+
+        Ok(sums
+            .into_iter()
+            .map(|(id, hours)| {
+                let averages = hours
+                    .into_iter()
+                    .map(|(hour, (sum, count))| (hour, sum / count as f64))
+                    .collect();
+                (id, averages)
+            })
+            .collect())
+    }
+
+    async fn load_states(&self) -> Result<HashMap<SeriesId, SeriesState>> {
+        let rows = sqlx::query_as::<_, SeriesStateRow>(
+            r#"
+            SELECT subject_type, subject_id, metric, ewma_mean, ewma_variance, drift,
+                   in_drift, observations, last_observed_at
+            FROM anomaly_series_state
+            "#,
+        )
+        .fetch_all(self.db.pool())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let key = SeriesKey {
+                    subject: AnomalySubject::from_str(&row.subject_type)?,
+                    metric: AnomalyMetric::from_str(&row.metric)?,
+                };
+                let state = SeriesState {
+                    ewma_mean: row.ewma_mean,
+                    ewma_variance: row.ewma_variance,
+                    drift: row.drift,
+                    in_drift: row.in_drift,
+                    observations: row.observations,
+                    last_observed_at: DateTime::parse_from_rfc3339(&row.last_observed_at)
+                        .ok()
+                        .map(|at| at.with_timezone(&Utc)),
+                };
+                Some(((key, row.subject_id), state))
+            })
+            .collect())
+    }
+
+    async fn save_state(
+        &self,
+        key: &SeriesKey,
+        subject_id: &str,
+        state: &SeriesState,
+    ) -> Result<()> {
+        let Some(last_observed_at) = state.last_observed_at else {
+            return Ok(());
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO anomaly_series_state (
+                subject_type, subject_id, metric, ewma_mean, ewma_variance, drift,
+                in_drift, observations, last_observed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT(subject_type, subject_id, metric) DO UPDATE SET
+                ewma_mean = excluded.ewma_mean,
+                ewma_variance = excluded.ewma_variance,
+                drift = excluded.drift,
+                in_drift = excluded.in_drift,
+                observations = excluded.observations,
+                last_observed_at = excluded.last_observed_at
+            "#,
+        )
+        .bind(key.subject.as_str())
+        .bind(subject_id)
+        .bind(key.metric.as_str())
+        .bind(state.ewma_mean)
+        .bind(state.ewma_variance)
+        .bind(state.drift)
+        .bind(state.in_drift)
+        .bind(state.observations)
+        .bind(last_observed_at.to_rfc3339())
+        .execute(self.db.pool())
+        .await?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // Publishing
+    // ========================================================================
+
+    /// Store an anomaly; false when the same anomaly was already recorded
+    async fn insert_anomaly(&self, anomaly: &Anomaly) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO anomalies (
+                id, subject_type, subject_id, metric, kind, baseline, observed_value,
+                expected_value, expected_low, expected_high, score, severity,
+                observed_at, detected_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            "#,
+        )
+        .bind(&anomaly.id)
+        .bind(&anomaly.subject_type)
+        .bind(&anomaly.subject_id)
+        .bind(&anomaly.metric)
+        .bind(&anomaly.kind)
+        .bind(&anomaly.baseline)
+        .bind(anomaly.observed_value)
+        .bind(anomaly.expected_value)
+        .bind(anomaly.expected_low)
+        .bind(anomaly.expected_high)
+        .bind(anomaly.score)
+        .bind(&anomaly.severity)
+        .bind(&anomaly.observed_at)
+        .bind(&anomaly.detected_at)
+        .execute(self.db.pool())
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn anomaly_rules(&self) -> Result<Vec<crate::models::alerts::AlertRule>> {
+        let now = Utc::now();
+        Ok(self
+            .db
+            .get_all_active_alert_rules()
+            .await?
+            .into_iter()
+            .filter(|rule| rule.metric_type == ANOMALY_ALERT_METRIC)
+            .filter(|rule| rule.snoozed_until.is_none_or(|until| until <= now))
+            .collect())
+    }
+
+    async fn publish(&self, anomaly: &Anomaly, rules: &[crate::models::alerts::AlertRule]) {
+        let message = describe(anomaly);
+
+        for rule in rules {
+            if rule
+                .corridor_id
+                .as_ref()
+                .is_some_and(|id| *id != anomaly.subject_id)
+                || anomaly.score < rule.threshold
+            {
+                continue;
+            }
+            let corridor_id = (anomaly.subject_type == AnomalySubject::Corridor.as_str())
+                .then(|| anomaly.subject_id.clone());
+            if let Err(e) = self
+                .db
+                .insert_alert_history(
+                    &rule.id,
+                    &rule.user_id,
+                    corridor_id,
+                    ANOMALY_ALERT_METRIC,
+                    anomaly.score,
+                    rule.threshold,
+                    &rule.condition,
+                    &message,
+                )
+                .await
+            {
+                warn!("Failed to record anomaly alert for rule {}: {}", rule.id, e);
+            }
+        }
+
+        let ws_message = WsMessage::Anomaly {
+            anomaly_id: anomaly.id.clone(),
+            subject_type: anomaly.subject_type.clone(),
+            subject_id: anomaly.subject_id.clone(),
+            metric: anomaly.metric.clone(),
+            kind: anomaly.kind.clone(),
+            observed_value: anomaly.observed_value,
+            expected_low: anomaly.expected_low,
+            expected_high: anomaly.expected_high,
+            score: anomaly.score,
+            severity: anomaly.severity.clone(),
+            message,
+            timestamp: anomaly.observed_at.clone(),
+        };
+        self.ws_state
+            .broadcast_to_channel(ANOMALIES_CHANNEL, ws_message.clone())
+            .await;
+        self.ws_state
+            .broadcast_to_channel(
+                &format!("{}:{}", ANOMALIES_CHANNEL, anomaly.subject_id),
+                ws_message,
+            )
+            .await;
+
+        match serde_json::to_value(anomaly) {
+            Ok(payload) => {
+                if let Err(e) = self
+                    .webhooks
+                    .trigger_event(WebhookEventType::AnomalyDetected, payload)
+                    .await
+                {
+                    warn!("Failed to queue anomaly webhook: {}", e);
+                }
+            }
+            Err(e) => warn!("Failed to serialize anomaly {}: {}", anomaly.id, e),
+        }
+    }
+
+    // ========================================================================
+    // Query Methods
+    // ========================================================================
+
+    /// Anomalies matching the filter, most recent hour first
+    pub async fn get_anomalies(&self, filter: &AnomalyFilter) -> Result<Vec<Anomaly>> {
+        let anomalies = sqlx::query_as::<_, Anomaly>(
+            r#"
+            SELECT id, subject_type, subject_id, metric, kind, baseline, observed_value,
+                   expected_value, expected_low, expected_high, score, severity,
+                   observed_at, detected_at
+            FROM anomalies
+            WHERE ($1 IS NULL OR subject_type = $1)
+              AND ($2 IS NULL OR subject_id = $2)
+              AND ($3 IS NULL OR metric = $3)
+            ORDER BY observed_at DESC, score DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(&filter.subject_type)
+        .bind(&filter.subject_id)
+        .bind(&filter.metric)
+        .bind(filter.limit)
+        .fetch_all(self.db.pool())
+        .await?;
+        Ok(anomalies)
+    }
+}
+
+fn describe(anomaly: &Anomaly) -> String {
+    let what = match anomaly.kind.as_str() {
+        "drift" => "has been drifting",
+        _ => "spiked",
+    };
+    format!(
+        "{} {} {} {} to {:.2} (expected {:.2} to {:.2}, score {:.1})",
+        anomaly.subject_type,
+        anomaly.subject_id,
+        anomaly.metric,
+        what,
+        anomaly.observed_value,
+        anomaly.expected_low,
+        anomaly.expected_high,
+        anomaly.score
+    )
+}
+
+/// Mean and standard deviation of the same hour in previous weeks, when
+/// enough of them were recorded
+fn seasonal_baseline(
+    points: &BTreeMap<DateTime<Utc>, f64>,
+    at: DateTime<Utc>,
+    metric: AnomalyMetric,
+    config: &AnomalyConfig,
+) -> Option<Baseline> {
+    let values: Vec<f64> = (1..=config.seasonal_weeks)
+        .filter_map(|weeks| points.get(&(at - Duration::weeks(weeks))).copied())
+        .collect();
+    if values.len() < config.min_seasonal_points.max(2) {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Some(Baseline {
+        kind: BaselineKind::Seasonal,
+        mean,
+        std: variance.sqrt().max(metric.min_std(mean)),
+    })
+}
+
+/// Score one hourly value and fold it into the series state
+fn score_point(
+    state: &mut SeriesState,
+    value: f64,
+    seasonal: Option<Baseline>,
+    metric: AnomalyMetric,
+    config: &AnomalyConfig,
+) -> Vec<Detection> {
+    let ewma = (state.observations >= config.min_ewma_points).then(|| Baseline {
+        kind: BaselineKind::Ewma,
+        mean: state.ewma_mean,
+        std: state
+            .ewma_variance
+            .sqrt()
+            .max(metric.min_std(state.ewma_mean)),
+    });
+
+    let mut detections = Vec::new();
+    if let Some(baseline) = seasonal.or(ewma) {
+        let direction = metric.direction();
+        let z = (value - baseline.mean) / baseline.std;
+        let score = direction.badness(z);
+        if score >= config.z_threshold {
+            detections.push(Detection {
+                kind: AnomalyKind::Spike,
+                baseline,
+                score,
+            });
+        }
+
+        // Clipped so that one spike alone cannot trip the drift chart
+        let lambda = config.drift_lambda;
+        let clipped = z.clamp(-config.z_threshold, config.z_threshold);
+        state.drift = lambda * clipped + (1.0 - lambda) * state.drift;
+        let drift_score = direction.badness(state.drift / (lambda / (2.0 - lambda)).sqrt());
+        if drift_score >= config.drift_threshold {
+            if !state.in_drift {
+                state.in_drift = true;
+                detections.push(Detection {
+                    kind: AnomalyKind::Drift,
+                    baseline,
+                    score: drift_score,
+                });
+            }
+        } else if drift_score < config.drift_threshold / 2.0 {
+            state.in_drift = false;
+        }
+    }
+
+    if state.observations == 0 {
+        state.ewma_mean = value;
+        state.ewma_variance = 0.0;
+    } else {
+        let alpha = config.ewma_alpha;
+        let diff = value - state.ewma_mean;
+        state.ewma_mean += alpha * diff;
+        state.ewma_variance = (1.0 - alpha) * (state.ewma_variance + alpha * diff * diff);
+    }
+    state.observations += 1;
+    detections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn feed(
+        state: &mut SeriesState,
+        values: impl IntoIterator<Item = f64>,
+        metric: AnomalyMetric,
+    ) -> Vec<Detection> {
+        let config = AnomalyConfig::default();
+        values
+            .into_iter()
+            .flat_map(|v| score_point(state, v, None, metric, &config))
+            .collect()
+    }
+
+    fn noisy(mean: f64, amplitude: f64, n: usize) -> Vec<f64> {
+        (0..n)
+            .map(|i| mean + amplitude * [0.0, 1.0, -1.0, 0.5, -0.5][i % 5])
+            .collect()
+    }
+
+    #[test]
+    fn test_spike_only_in_harmful_direction() {
+        let mut state = SeriesState::new();
+        assert!(feed(&mut state, noisy(95.0, 1.0, 48), AnomalyMetric::SuccessRate).is_empty());
+
+        // A jump up in success rate is good news
+        let mut up = state.clone();
+        assert!(feed(&mut up, [100.0], AnomalyMetric::SuccessRate).is_empty());
+
+        let detections = feed(&mut state, [70.0], AnomalyMetric::SuccessRate);
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].kind, AnomalyKind::Spike);
+        assert_eq!(detections[0].baseline.kind, BaselineKind::Ewma);
+        assert!(detections[0].score > 10.0);
+    }
+
+    #[test]
+    fn test_no_baseline_before_warm_up() {
+        let mut state = SeriesState::new();
+        let detections = feed(
+            &mut state,
+            [1000.0, 1000.0, 5000.0],
+            AnomalyMetric::SettlementLatencyMs,
+        );
+        assert!(detections.is_empty());
+        assert_eq!(state.observations, 3);
+    }
+
+    #[test]
+    fn test_slow_degradation_raises_one_drift() {
+        let mut state = SeriesState::new();
+        feed(&mut state, noisy(95.0, 1.0, 48), AnomalyMetric::SuccessRate);
+
+        // Down 0.3 points an hour: never a spike against the adapting EWMA
+        let declining: Vec<f64> = (0..48).map(|i| 95.0 - 0.3 * i as f64).collect();
+        let detections = feed(&mut state, declining, AnomalyMetric::SuccessRate);
+        assert!(detections.iter().all(|d| d.kind == AnomalyKind::Drift));
+        assert_eq!(detections.len(), 1);
+        assert!(state.in_drift);
+
+        // Recovery re-arms the chart
+        feed(
+            &mut state,
+            noisy(95.0, 1.0, 200),
+            AnomalyMetric::SuccessRate,
+        );
+        assert!(!state.in_drift);
+    }
+
+    #[test]
+    fn test_seasonal_baseline_uses_same_hour_of_week() {
+        let config = AnomalyConfig::default();
+        let at = Utc.with_ymd_and_hms(2024, 3, 25, 3, 0, 0).unwrap();
+        let mut points = BTreeMap::new();
+        for week in 1..=4 {
+            // Nights are quiet, the hour before is busy
+            points.insert(at - Duration::weeks(week), 200.0 + week as f64 * 10.0);
+            points.insert(at - Duration::weeks(week) - Duration::hours(1), 20_000.0);
+        }
+        let baseline = seasonal_baseline(&points, at, AnomalyMetric::VolumeUsd, &config).unwrap();
+        assert_eq!(baseline.kind, BaselineKind::Seasonal);
+        assert!((baseline.mean - 225.0).abs() < 1e-9);
+        assert!(baseline.std >= 100.0);
+
+        points.retain(|t, _| *t > at - Duration::weeks(2));
+        assert!(seasonal_baseline(&points, at, AnomalyMetric::VolumeUsd, &config).is_none());
+
+        // A quiet night is normal against the seasonal baseline even though
+        // the EWMA of the busy series would call it a drop
+        let mut state = SeriesState::new();
+        feed(
+            &mut state,
+            noisy(20_000.0, 500.0, 48),
+            AnomalyMetric::VolumeUsd,
+        );
+        let detections = score_point(
+            &mut state,
+            230.0,
+            Some(baseline),
+            AnomalyMetric::VolumeUsd,
+            &config,
+        );
+        assert!(detections.is_empty());
+    }
+}
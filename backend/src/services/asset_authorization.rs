@@ -0,0 +1,573 @@
+//! Issuer control actions per asset.
+//!
+//! Records trustline authorization changes (`allow_trust`,
+//! `set_trust_line_flags`), clawbacks and issuer account flag changes
+//! (`set_options`) of accounts issuing a tracked asset. Nothing is fetched
+//! from Horizon: a clawed back claimable balance takes its amount from the
+//! stored balance. Freezes are counted but carry no amount, since the
+//! trustor's balance isn't in the ingested ledger data. Issuer flag changes
+//! trigger an `asset.flags_changed` webhook.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{FromRow, Pool, Sqlite};
+use tracing::{info, warn};
+
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
+use crate::rpc::HorizonOperation;
+use crate::webhooks::{WebhookEventType, WebhookService};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuthorizationEventType {
+    TrustlineAuthorized,
+    TrustlineMaintainLiabilities,
+    TrustlineDeauthorized,
+    TrustlineFlagsUpdated,
+    Clawback,
+    ClaimableBalanceClawback,
+    IssuerFlagsChanged,
+}
+
+impl AuthorizationEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TrustlineAuthorized => "trustline_authorized",
+            Self::TrustlineMaintainLiabilities => "trustline_maintain_liabilities",
+            Self::TrustlineDeauthorized => "trustline_deauthorized",
+            Self::TrustlineFlagsUpdated => "trustline_flags_updated",
+            Self::Clawback => "clawback",
+            Self::ClaimableBalanceClawback => "claimable_balance_clawback",
+            Self::IssuerFlagsChanged => "issuer_flags_changed",
+        }
+    }
+
+    /// Trustor can no longer send or receive the asset
+    pub fn is_freeze(&self) -> bool {
+        matches!(
+            self,
+            Self::TrustlineDeauthorized | Self::TrustlineMaintainLiabilities
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuthorizationEvent {
+    pub operation_id: String,
+    pub event_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: String,
+    pub account: Option<String>,
+    pub amount: Option<f64>,
+    pub set_flags: String,
+    pub clear_flags: String,
+    pub transaction_hash: String,
+    pub ledger_sequence: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuthorizationEventFilter {
+    pub issuer: Option<String>,
+    pub asset_code: Option<String>,
+    pub event_type: Option<AuthorizationEventType>,
+    pub limit: i64,
+}
+
+/// Freeze counts and clawed back totals per asset; `asset_code` is `None` for
+/// the issuer-wide row holding account flag changes
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IssuerAssetSummary {
+    pub asset_code: Option<String>,
+    pub asset_issuer: String,
+    pub freeze_count: i64,
+    pub authorize_count: i64,
+    pub clawback_count: i64,
+    pub clawed_back_amount: f64,
+    pub flag_change_count: i64,
+    pub last_event_at: String,
+}
+
+/// One day of an issuer's control actions for one asset
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IssuerActivityDay {
+    pub date: String,
+    pub asset_code: Option<String>,
+    pub freeze_count: i64,
+    pub authorize_count: i64,
+    pub clawback_count: i64,
+    pub clawed_back_amount: f64,
+    pub flag_change_count: i64,
+}
+
+/// An event extracted from an operation, before enrichment
+#[derive(Debug, Clone, PartialEq)]
+struct ExtractedEvent {
+    event_type: AuthorizationEventType,
+    asset_code: Option<String>,
+    asset_issuer: String,
+    account: Option<String>,
+    amount: Option<f64>,
+    set_flags: Vec<String>,
+    clear_flags: Vec<String>,
+}
+
+pub struct AssetAuthorizationTracker {
+    pool: Pool<Sqlite>,
+    webhooks: WebhookService,
+}
+
+impl AssetAuthorizationTracker {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self {
+            webhooks: WebhookService::new(pool.clone()),
+            pool,
+        }
+    }
+
+    /// Record the authorization, clawback and flag operations of a ledger;
+    /// returns the number of new events
+    pub async fn process_operations(
+        &self,
+        ledger_sequence: u64,
+        operations: &[HorizonOperation],
+    ) -> Result<u64> {
+        let mut recorded = 0_u64;
+        for op in operations {
+            let Some(mut event) = event_from_operation(op) else {
+                continue;
+            };
+            // Any account can set its own flags; only issuers are of interest
+            if event.event_type == AuthorizationEventType::IssuerFlagsChanged
+                && !self.is_tracked_issuer(&event.asset_issuer).await?
+            {
+                continue;
+            }
+            self.enrich(&mut event).await?;
+
+            let inserted = sqlx::query(
+                r#"
+                INSERT INTO asset_authorization_events (
+                    operation_id, event_type, asset_code, asset_issuer, account, amount,
+                    set_flags, clear_flags, transaction_hash, ledger_sequence, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (operation_id) DO NOTHING
+                "#,
+            )
+            .bind(&op.id)
+            .bind(event.event_type.as_str())
+            .bind(&event.asset_code)
+            .bind(&event.asset_issuer)
+            .bind(&event.account)
+            .bind(event.amount)
+            .bind(event.set_flags.join(","))
+            .bind(event.clear_flags.join(","))
+            .bind(&op.transaction_hash)
+            .bind(ledger_sequence as i64)
+            .bind(&op.created_at)
+            .execute(&self.pool)
+            .await?
+            .rows_affected();
+
+            if inserted == 0 {
+                continue;
+            }
+            recorded += 1;
+
+            if event.event_type == AuthorizationEventType::IssuerFlagsChanged {
+                self.notify_flags_changed(ledger_sequence, op, &event).await;
+            }
+        }
+
+        if recorded > 0 {
+            info!(
+                "Recorded {} asset authorization events for ledger {}",
+                recorded, ledger_sequence
+            );
+        }
+        Ok(recorded)
+    }
+
+    /// Fill in the clawed back amount of a claimable balance from the stored
+    /// balance; other events keep what the operation carries
+    async fn enrich(&self, event: &mut ExtractedEvent) -> Result<()> {
+        if event.event_type != AuthorizationEventType::ClaimableBalanceClawback {
+            return Ok(());
+        }
+        let Some(balance_id) = event.account.as_deref() else {
+            return Ok(());
+        };
+        let balance: Option<(Option<String>, f64)> = sqlx::query_as(
+            "SELECT asset_code, amount FROM claimable_balances WHERE balance_id = $1",
+        )
+        .bind(balance_id)
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some((asset_code, amount)) = balance {
+            event.asset_code = asset_code;
+            event.amount = Some(amount);
+        }
+        Ok(())
+    }
+
+    /// Issuer of an anchor asset or of an asset with supply snapshots
+    async fn is_tracked_issuer(&self, account: &str) -> Result<bool> {
+        let tracked: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (SELECT 1 FROM assets WHERE asset_issuer = $1)
+                OR EXISTS (SELECT 1 FROM asset_supply_snapshots WHERE asset_issuer = $1)
+            "#,
+        )
+        .bind(account)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(tracked)
+    }
+
+    async fn notify_flags_changed(
+        &self,
+        ledger_sequence: u64,
+        op: &HorizonOperation,
+        event: &ExtractedEvent,
+    ) {
+        let payload = json!({
+            "issuer": event.asset_issuer,
+            "set_flags": event.set_flags,
+            "clear_flags": event.clear_flags,
+            "operation_id": op.id,
+            "transaction_hash": op.transaction_hash,
+            "ledger_sequence": ledger_sequence,
+        });
+        if let Err(e) = self
+            .webhooks
+            .trigger_event(WebhookEventType::AssetFlagsChanged, payload)
+            .await
+        {
+            warn!("Failed to queue asset flags webhook: {}", e);
+        }
+    }
+
+    // ========================================================================
+    // Query Methods
+    // ========================================================================
+
+    /// Events matching the filter, most recent first
+    pub async fn get_events(
+        &self,
+        filter: &AuthorizationEventFilter,
+    ) -> Result<Vec<AuthorizationEvent>> {
+        let events = sqlx::query_as(
+            r#"
+            SELECT operation_id, event_type, asset_code, asset_issuer, account, amount,
+                   set_flags, clear_flags, transaction_hash, ledger_sequence, created_at
+            FROM asset_authorization_events
+            WHERE ($1 IS NULL OR asset_issuer = $1)
+              AND ($2 IS NULL OR asset_code = $2)
+              AND ($3 IS NULL OR event_type = $3)
+            ORDER BY ledger_sequence DESC, operation_id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(&filter.issuer)
+        .bind(&filter.asset_code)
+        .bind(filter.event_type.map(|t| t.as_str()))
+        .bind(filter.limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(events)
+    }
+
+    /// Totals per issued asset, most recently active first
+    pub async fn get_issuer_summaries(
+        &self,
+        issuer: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<IssuerAssetSummary>> {
+        let summaries = sqlx::query_as(
+            r#"
+            SELECT
+                asset_code,
+                asset_issuer,
+                COALESCE(SUM(event_type IN ('trustline_deauthorized', 'trustline_maintain_liabilities')), 0)
+                    AS freeze_count,
+                COALESCE(SUM(event_type = 'trustline_authorized'), 0) AS authorize_count,
+                COALESCE(SUM(event_type IN ('clawback', 'claimable_balance_clawback')), 0)
+                    AS clawback_count,
+                COALESCE(SUM(CASE WHEN event_type IN ('clawback', 'claimable_balance_clawback')
+                    THEN amount END), 0.0) AS clawed_back_amount,
+                COALESCE(SUM(event_type IN ('issuer_flags_changed', 'trustline_flags_updated')), 0)
+                    AS flag_change_count,
+                MAX(created_at) AS last_event_at
+            FROM asset_authorization_events
+            WHERE ($1 IS NULL OR asset_issuer = $1)
+            GROUP BY asset_issuer, asset_code
+            ORDER BY last_event_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(issuer)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(summaries)
+    }
+
+    /// Daily freeze counts and clawed back amounts for an issuer over the last
+    /// `days` days
+    pub async fn get_issuer_activity(
+        &self,
+        issuer: &str,
+        days: i64,
+    ) -> Result<Vec<IssuerActivityDay>> {
+        let since = (chrono::Utc::now() - chrono::Duration::days(days))
+            .date_naive()
+            .to_string();
+        let activity = sqlx::query_as(
+            r#"
+            SELECT
+                DATE(created_at) AS date,
+                asset_code,
+                COALESCE(SUM(event_type IN ('trustline_deauthorized', 'trustline_maintain_liabilities')), 0)
+                    AS freeze_count,
+                COALESCE(SUM(event_type = 'trustline_authorized'), 0) AS authorize_count,
+                COALESCE(SUM(event_type IN ('clawback', 'claimable_balance_clawback')), 0)
+                    AS clawback_count,
+                COALESCE(SUM(CASE WHEN event_type IN ('clawback', 'claimable_balance_clawback')
+                    THEN amount END), 0.0) AS clawed_back_amount,
+                COALESCE(SUM(event_type IN ('issuer_flags_changed', 'trustline_flags_updated')), 0)
+                    AS flag_change_count
+            FROM asset_authorization_events
+            WHERE asset_issuer = $1 AND DATE(created_at) >= $2
+            GROUP BY DATE(created_at), asset_code
+            ORDER BY date, asset_code
+            "#,
+        )
+        .bind(issuer)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(activity)
+    }
+}
+
+#[async_trait]
+impl LedgerProcessor for AssetAuthorizationTracker {
+    fn name(&self) -> &'static str {
+        "asset_authorizations"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Operations]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+        self.process_operations(bundle.sequence, &bundle.operations)
+            .await
+    }
+}
+
+fn has_flag(flags: &[String], name: &str) -> bool {
+    flags.iter().any(|f| f == name)
+}
+
+fn event_from_operation(op: &HorizonOperation) -> Option<ExtractedEvent> {
+    let trustline_asset = || {
+        let issuer = op
+            .asset_issuer
+            .clone()
+            .unwrap_or_else(|| op.source_account.clone());
+        (op.asset_code.clone(), issuer)
+    };
+
+    let event = match op.operation_type.as_str() {
+        "allow_trust" => {
+            let (asset_code, asset_issuer) = trustline_asset();
+            let (event_type, set_flags, clear_flags) = if op.authorize == Some(true) {
+                (
+                    AuthorizationEventType::TrustlineAuthorized,
+                    vec!["authorized"],
+                    vec![],
+                )
+            } else if op.authorize_to_maintain_liabilities == Some(true) {
+                (
+                    AuthorizationEventType::TrustlineMaintainLiabilities,
+                    vec!["authorized_to_maintain_liabilities"],
+                    vec!["authorized"],
+                )
+            } else {
+                (
+                    AuthorizationEventType::TrustlineDeauthorized,
+                    vec![],
+                    vec!["authorized", "authorized_to_maintain_liabilities"],
+                )
+            };
+            ExtractedEvent {
+                event_type,
+                asset_code,
+                asset_issuer,
+                account: op.trustor.clone(),
+                amount: None,
+                set_flags: set_flags.into_iter().map(String::from).collect(),
+                clear_flags: clear_flags.into_iter().map(String::from).collect(),
+            }
+        }
+        "set_trust_line_flags" => {
+            let (asset_code, asset_issuer) = trustline_asset();
+            let (set, clear) = (&op.set_flags_s, &op.clear_flags_s);
+            let event_type = if has_flag(set, "authorized") {
+                AuthorizationEventType::TrustlineAuthorized
+            } else if has_flag(set, "authorized_to_maintain_liabilities") {
+                AuthorizationEventType::TrustlineMaintainLiabilities
+            } else if has_flag(clear, "authorized")
+                || has_flag(clear, "authorized_to_maintain_liabilities")
+            {
+                AuthorizationEventType::TrustlineDeauthorized
+            } else {
+                AuthorizationEventType::TrustlineFlagsUpdated
+            };
+            ExtractedEvent {
+                event_type,
+                asset_code,
+                asset_issuer,
+                account: op.trustor.clone(),
+                amount: None,
+                set_flags: set.clone(),
+                clear_flags: clear.clone(),
+            }
+        }
+        "clawback" => {
+            let (asset_code, asset_issuer) = trustline_asset();
+            ExtractedEvent {
+                event_type: AuthorizationEventType::Clawback,
+                asset_code,
+                asset_issuer,
+                account: op.from.clone(),
+                amount: op.amount.as_deref().and_then(|a| a.parse().ok()),
+                set_flags: vec![],
+                clear_flags: vec![],
+            }
+        }
+        // Only the issuer can claw back, so the source account is the issuer
+        "clawback_claimable_balance" => ExtractedEvent {
+            event_type: AuthorizationEventType::ClaimableBalanceClawback,
+            asset_code: None,
+            asset_issuer: op.source_account.clone(),
+            account: op.balance_id.clone(),
+            amount: None,
+            set_flags: vec![],
+            clear_flags: vec![],
+        },
+        "set_options" if !op.set_flags_s.is_empty() || !op.clear_flags_s.is_empty() => {
+            ExtractedEvent {
+                event_type: AuthorizationEventType::IssuerFlagsChanged,
+                asset_code: None,
+                asset_issuer: op.source_account.clone(),
+                account: None,
+                amount: None,
+                set_flags: op.set_flags_s.clone(),
+                clear_flags: op.clear_flags_s.clone(),
+            }
+        }
+        _ => return None,
+    };
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(operation_type: &str) -> HorizonOperation {
+        HorizonOperation {
+            id: "op1".to_string(),
+            operation_type: operation_type.to_string(),
+            source_account: "GISSUER".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some("GISSUER".to_string()),
+            trustor: Some("GHOLDER".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn event_type(op: &HorizonOperation) -> Option<AuthorizationEventType> {
+        event_from_operation(op).map(|e| e.event_type)
+    }
+
+    #[test]
+    fn test_allow_trust_levels() {
+        let authorize = HorizonOperation {
+            authorize: Some(true),
+            ..op("allow_trust")
+        };
+        let maintain = HorizonOperation {
+            authorize: Some(false),
+            authorize_to_maintain_liabilities: Some(true),
+            ..op("allow_trust")
+        };
+        let revoke = HorizonOperation {
+            authorize: Some(false),
+            ..op("allow_trust")
+        };
+        assert_eq!(
+            event_type(&authorize),
+            Some(AuthorizationEventType::TrustlineAuthorized)
+        );
+        assert_eq!(
+            event_type(&maintain),
+            Some(AuthorizationEventType::TrustlineMaintainLiabilities)
+        );
+        let revoked = event_from_operation(&revoke).unwrap();
+        assert_eq!(
+            revoked.event_type,
+            AuthorizationEventType::TrustlineDeauthorized
+        );
+        assert_eq!(revoked.account.as_deref(), Some("GHOLDER"));
+    }
+
+    #[test]
+    fn test_set_trust_line_flags() {
+        let freeze = HorizonOperation {
+            clear_flags_s: vec!["authorized".to_string()],
+            ..op("set_trust_line_flags")
+        };
+        let clawback_off = HorizonOperation {
+            clear_flags_s: vec!["clawback_enabled".to_string()],
+            ..op("set_trust_line_flags")
+        };
+        assert_eq!(
+            event_type(&freeze),
+            Some(AuthorizationEventType::TrustlineDeauthorized)
+        );
+        assert_eq!(
+            event_type(&clawback_off),
+            Some(AuthorizationEventType::TrustlineFlagsUpdated)
+        );
+    }
+
+    #[test]
+    fn test_clawback_and_set_options() {
+        let clawback = HorizonOperation {
+            from: Some("GHOLDER".to_string()),
+            amount: Some("12.5000000".to_string()),
+            ..op("clawback")
+        };
+        let event = event_from_operation(&clawback).unwrap();
+        assert_eq!(event.amount, Some(12.5));
+        assert_eq!(event.account.as_deref(), Some("GHOLDER"));
+
+        let set_options = HorizonOperation {
+            set_flags_s: vec!["auth_revocable".to_string()],
+            ..op("set_options")
+        };
+        let event = event_from_operation(&set_options).unwrap();
+        assert_eq!(event.event_type, AuthorizationEventType::IssuerFlagsChanged);
+        assert_eq!(event.asset_code, None);
+
+        // set_options without flag changes (e.g. a new signer) is ignored
+        assert!(event_from_operation(&op("set_options")).is_none());
+        assert!(event_from_operation(&op("payment")).is_none());
+    }
+}
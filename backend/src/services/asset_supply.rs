@@ -0,0 +1,614 @@
+//! Circulating supply tracking for issued assets.
+//!
+//! Supply snapshots come from Horizon's `/assets` totals; payments sent by an
+//! asset's issuer are recorded as mints and payments received by it as burns.
+//! Snapshots whose supply moved by at least the configured percentage raise
+//! an alert and an `asset.supply_jump` webhook.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{FromRow, Pool, Sqlite};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
+use crate::rpc::{HorizonAsset, Payment, StellarRpcClient};
+use crate::services::alert_service::AlertService;
+use crate::webhooks::{WebhookEventType, WebhookService};
+
+#[derive(Debug, Clone)]
+pub struct AssetSupplyConfig {
+    /// Absolute percent change between snapshots that counts as a jump
+    pub jump_threshold_pct: f64,
+    /// Assets fetched from Horizon per snapshot, by rating
+    pub asset_limit: u32,
+}
+
+impl AssetSupplyConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            jump_threshold_pct: std::env::var("ASSET_SUPPLY_JUMP_THRESHOLD_PCT")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+                .unwrap_or(defaults.jump_threshold_pct),
+            asset_limit: std::env::var("ASSET_SUPPLY_ASSET_LIMIT")
+                .ok()
+                .and_then(|v| v.parse::<u32>().ok())
+                .filter(|v| (1..=200).contains(v))
+                .unwrap_or(defaults.asset_limit),
+        }
+    }
+}
+
+impl Default for AssetSupplyConfig {
+    fn default() -> Self {
+        Self {
+            jump_threshold_pct: 10.0,
+            asset_limit: 200,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssuanceEventType {
+    Mint,
+    Burn,
+}
+
+impl IssuanceEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Mint => "mint",
+            Self::Burn => "burn",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SupplySnapshot {
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub circulating_supply: f64,
+    pub authorized_supply: f64,
+    pub claimable_balances_amount: f64,
+    pub liquidity_pools_amount: f64,
+    pub contracts_amount: f64,
+    pub holders: i64,
+    pub change_pct: Option<f64>,
+    pub supply_jump: bool,
+    pub snapshot_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IssuanceEvent {
+    pub operation_id: String,
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub event_type: String,
+    pub amount: f64,
+    pub counterparty: String,
+    pub transaction_hash: String,
+    pub ledger_sequence: i64,
+    pub created_at: String,
+}
+
+/// Minted and burned amounts for one day
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IssuanceDay {
+    pub date: String,
+    pub minted: f64,
+    pub burned: f64,
+    pub net_issuance: f64,
+    pub mint_count: i64,
+    pub burn_count: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SnapshotSummary {
+    pub assets: u64,
+    pub jumps: u64,
+}
+
+/// A mint or burn extracted from a payment
+#[derive(Debug, Clone, PartialEq)]
+struct Issuance {
+    asset_code: String,
+    asset_issuer: String,
+    event_type: IssuanceEventType,
+    amount: f64,
+    counterparty: String,
+}
+
+pub struct AssetSupplyTracker {
+    pool: Pool<Sqlite>,
+    rpc_client: Arc<StellarRpcClient>,
+    config: AssetSupplyConfig,
+    alert_service: Arc<AlertService>,
+    webhooks: WebhookService,
+}
+
+impl AssetSupplyTracker {
+    pub fn new(
+        pool: Pool<Sqlite>,
+        rpc_client: Arc<StellarRpcClient>,
+        config: AssetSupplyConfig,
+    ) -> Self {
+        Self {
+            webhooks: WebhookService::new(pool.clone()),
+            alert_service: Arc::new(AlertService::new()),
+            pool,
+            rpc_client,
+            config,
+        }
+    }
+
+    pub fn with_alert_service(mut self, alert_service: Arc<AlertService>) -> Self {
+        self.alert_service = alert_service;
+        self
+    }
+
+    // ========================================================================
+    // Supply snapshots
+    // ========================================================================
+
+    /// Snapshot the circulating supply of the top assets on Horizon
+    pub async fn snapshot_supply(&self) -> Result<SnapshotSummary> {
+        let assets = self
+            .rpc_client
+            .fetch_assets(self.config.asset_limit, true)
+            .await?;
+        self.record_snapshots(&assets).await
+    }
+
+    /// Store one snapshot per issued asset and flag supply jumps against the
+    /// previous snapshot
+    pub async fn record_snapshots(&self, assets: &[HorizonAsset]) -> Result<SnapshotSummary> {
+        let snapshot_at = chrono::Utc::now().to_rfc3339();
+        let mut summary = SnapshotSummary::default();
+
+        for asset in assets.iter().filter(|a| a.asset_type != "native") {
+            let supply = SupplyTotals::from_asset(asset);
+            let previous: Option<f64> = sqlx::query_scalar(
+                r#"
+                SELECT circulating_supply FROM asset_supply_snapshots
+                WHERE asset_code = $1 AND asset_issuer = $2
+                ORDER BY snapshot_at DESC, id DESC
+                LIMIT 1
+                "#,
+            )
+            .bind(&asset.asset_code)
+            .bind(&asset.asset_issuer)
+            .fetch_optional(&self.pool)
+            .await?;
+
+            let change_pct = previous
+                .filter(|p| *p > 0.0)
+                .map(|p| (supply.circulating - p) / p * 100.0);
+            let supply_jump = change_pct.is_some_and(|c| c.abs() >= self.config.jump_threshold_pct);
+
+            sqlx::query(
+                r#"
+                INSERT INTO asset_supply_snapshots (
+                    asset_code, asset_issuer, circulating_supply, authorized_supply,
+                    claimable_balances_amount, liquidity_pools_amount, contracts_amount,
+                    holders, change_pct, supply_jump, snapshot_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                "#,
+            )
+            .bind(&asset.asset_code)
+            .bind(&asset.asset_issuer)
+            .bind(supply.circulating)
+            .bind(supply.authorized)
+            .bind(supply.claimable_balances)
+            .bind(supply.liquidity_pools)
+            .bind(supply.contracts)
+            .bind(supply.holders)
+            .bind(change_pct)
+            .bind(supply_jump)
+            .bind(&snapshot_at)
+            .execute(&self.pool)
+            .await?;
+            summary.assets += 1;
+
+            if let (true, Some(previous), Some(change_pct)) = (supply_jump, previous, change_pct) {
+                summary.jumps += 1;
+                self.notify_jump(asset, previous, supply.circulating, change_pct)
+                    .await;
+            }
+        }
+
+        info!(
+            "Recorded supply for {} assets ({} jumps)",
+            summary.assets, summary.jumps
+        );
+        Ok(summary)
+    }
+
+    async fn notify_jump(
+        &self,
+        asset: &HorizonAsset,
+        previous: f64,
+        current: f64,
+        change_pct: f64,
+    ) {
+        let asset_key = format!("{}:{}", asset.asset_code, asset.asset_issuer);
+        if let Err(e) = self
+            .alert_service
+            .alert_asset_supply_jump(asset_key.clone(), previous, current, change_pct)
+            .await
+        {
+            warn!("Failed to send supply jump alert: {}", e);
+        }
+
+        let payload = json!({
+            "asset": asset_key,
+            "asset_code": asset.asset_code,
+            "asset_issuer": asset.asset_issuer,
+            "previous_supply": previous,
+            "current_supply": current,
+            "change_pct": change_pct,
+            "threshold_pct": self.config.jump_threshold_pct,
+        });
+        if let Err(e) = self
+            .webhooks
+            .trigger_event(WebhookEventType::AssetSupplyJump, payload)
+            .await
+        {
+            warn!("Failed to queue supply jump webhook: {}", e);
+        }
+    }
+
+    // ========================================================================
+    // Mint and burn detection
+    // ========================================================================
+
+    /// Record the mints and burns among a ledger's payments; returns the
+    /// number of new events
+    pub async fn process_payments(
+        &self,
+        ledger_sequence: u64,
+        payments: &[Payment],
+    ) -> Result<u64> {
+        let mut recorded = 0_u64;
+        for payment in payments {
+            for issuance in issuances_from_payment(payment) {
+                recorded += sqlx::query(
+                    r#"
+                    INSERT INTO asset_issuance_events (
+                        operation_id, asset_code, asset_issuer, event_type, amount,
+                        counterparty, transaction_hash, ledger_sequence, created_at
+                    )
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    ON CONFLICT DO NOTHING
+                    "#,
+                )
+                .bind(&payment.id)
+                .bind(&issuance.asset_code)
+                .bind(&issuance.asset_issuer)
+                .bind(issuance.event_type.as_str())
+                .bind(issuance.amount)
+                .bind(&issuance.counterparty)
+                .bind(&payment.transaction_hash)
+                .bind(ledger_sequence as i64)
+                .bind(&payment.created_at)
+                .execute(&self.pool)
+                .await?
+                .rows_affected();
+            }
+        }
+
+        if recorded > 0 {
+            info!(
+                "Recorded {} mint/burn events for ledger {}",
+                recorded, ledger_sequence
+            );
+        }
+        Ok(recorded)
+    }
+
+    // ========================================================================
+    // Query Methods
+    // ========================================================================
+
+    /// Latest snapshot of every tracked asset, largest supply first
+    pub async fn get_latest_supplies(&self, limit: i64) -> Result<Vec<SupplySnapshot>> {
+        let supplies = sqlx::query_as(
+            r#"
+            SELECT s.asset_code, s.asset_issuer, s.circulating_supply, s.authorized_supply,
+                   s.claimable_balances_amount, s.liquidity_pools_amount, s.contracts_amount,
+                   s.holders, s.change_pct, s.supply_jump, s.snapshot_at
+            FROM asset_supply_snapshots s
+            WHERE s.id = (
+                SELECT id FROM asset_supply_snapshots
+                WHERE asset_code = s.asset_code AND asset_issuer = s.asset_issuer
+                ORDER BY snapshot_at DESC, id DESC
+                LIMIT 1
+            )
+            ORDER BY s.circulating_supply DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(supplies)
+    }
+
+    /// Supply time series for an asset, oldest first
+    pub async fn get_supply_history(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        limit: i64,
+    ) -> Result<Vec<SupplySnapshot>> {
+        let mut history: Vec<SupplySnapshot> = sqlx::query_as(
+            r#"
+            SELECT asset_code, asset_issuer, circulating_supply, authorized_supply,
+                   claimable_balances_amount, liquidity_pools_amount, contracts_amount,
+                   holders, change_pct, supply_jump, snapshot_at
+            FROM asset_supply_snapshots
+            WHERE asset_code = $1 AND asset_issuer = $2
+            ORDER BY snapshot_at DESC, id DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        history.reverse();
+        Ok(history)
+    }
+
+    /// Snapshots flagged as supply jumps, most recent first
+    pub async fn get_supply_jumps(&self, limit: i64) -> Result<Vec<SupplySnapshot>> {
+        let jumps = sqlx::query_as(
+            r#"
+            SELECT asset_code, asset_issuer, circulating_supply, authorized_supply,
+                   claimable_balances_amount, liquidity_pools_amount, contracts_amount,
+                   holders, change_pct, supply_jump, snapshot_at
+            FROM asset_supply_snapshots
+            WHERE supply_jump = 1
+            ORDER BY snapshot_at DESC, id DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(jumps)
+    }
+
+    /// Mints and burns of an asset, most recent first
+    pub async fn get_issuance_events(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        limit: i64,
+    ) -> Result<Vec<IssuanceEvent>> {
+        let events = sqlx::query_as(
+            r#"
+            SELECT operation_id, asset_code, asset_issuer, event_type, amount,
+                   counterparty, transaction_hash, ledger_sequence, created_at
+            FROM asset_issuance_events
+            WHERE asset_code = $1 AND asset_issuer = $2
+            ORDER BY ledger_sequence DESC, operation_id DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(events)
+    }
+
+    /// Daily minted, burned and net issued amounts over the last `days` days
+    pub async fn get_issuance_trend(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        days: i64,
+    ) -> Result<Vec<IssuanceDay>> {
+        let since = (chrono::Utc::now() - chrono::Duration::days(days))
+            .date_naive()
+            .to_string();
+        let trend = sqlx::query_as(
+            r#"
+            SELECT
+                DATE(created_at) AS date,
+                COALESCE(SUM(CASE WHEN event_type = 'mint' THEN amount END), 0.0) AS minted,
+                COALESCE(SUM(CASE WHEN event_type = 'burn' THEN amount END), 0.0) AS burned,
+                COALESCE(SUM(CASE WHEN event_type = 'mint' THEN amount ELSE -amount END), 0.0)
+                    AS net_issuance,
+                SUM(event_type = 'mint') AS mint_count,
+                SUM(event_type = 'burn') AS burn_count
+            FROM asset_issuance_events
+            WHERE asset_code = $1 AND asset_issuer = $2 AND DATE(created_at) >= $3
+            GROUP BY DATE(created_at)
+            ORDER BY date
+            "#,
+        )
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(trend)
+    }
+}
+
+#[async_trait]
+impl LedgerProcessor for AssetSupplyTracker {
+    fn name(&self) -> &'static str {
+        "asset_supply"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Payments]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+        self.process_payments(bundle.sequence, &bundle.payments)
+            .await
+    }
+}
+
+struct SupplyTotals {
+    circulating: f64,
+    authorized: f64,
+    claimable_balances: f64,
+    liquidity_pools: f64,
+    contracts: f64,
+    holders: i64,
+}
+
+impl SupplyTotals {
+    fn from_asset(asset: &HorizonAsset) -> Self {
+        let amount = |value: &str| value.parse::<f64>().unwrap_or(0.0);
+        let balances = &asset.balances;
+        let authorized = amount(&balances.authorized);
+        let claimable_balances = amount(&asset.claimable_balances_amount);
+        let liquidity_pools = amount(&asset.liquidity_pools_amount);
+        let contracts = amount(&asset.contracts_amount);
+        Self {
+            circulating: authorized
+                + amount(&balances.authorized_to_maintain_liabilities)
+                + amount(&balances.unauthorized)
+                + claimable_balances
+                + liquidity_pools
+                + contracts,
+            authorized,
+            claimable_balances,
+            liquidity_pools,
+            contracts,
+            holders: i64::from(asset.accounts.authorized)
+                + i64::from(asset.accounts.authorized_to_maintain_liabilities)
+                + i64::from(asset.accounts.unauthorized),
+        }
+    }
+}
+
+/// Issuers create an asset by sending it and destroy it by receiving it.
+/// A path payment sent by the issuer of its source asset also mints that
+/// asset, even though a different asset is delivered.
+fn issuances_from_payment(payment: &Payment) -> Vec<Issuance> {
+    let sender = payment.from.as_deref().unwrap_or(&payment.source_account);
+    let mut issuances = Vec::new();
+
+    if let (Some(code), Some(issuer), Some(destination)) = (
+        payment.get_asset_code(),
+        payment.get_asset_issuer(),
+        payment.get_destination(),
+    ) {
+        let amount = payment.get_amount().parse::<f64>().unwrap_or(0.0);
+        // Issuer-to-issuer payments neither create nor destroy supply
+        if sender == issuer && destination != issuer {
+            issuances.push(Issuance {
+                asset_code: code.clone(),
+                asset_issuer: issuer.clone(),
+                event_type: IssuanceEventType::Mint,
+                amount,
+                counterparty: destination.clone(),
+            });
+        } else if destination == issuer && sender != issuer {
+            issuances.push(Issuance {
+                asset_code: code.clone(),
+                asset_issuer: issuer.clone(),
+                event_type: IssuanceEventType::Burn,
+                amount,
+                counterparty: sender.to_string(),
+            });
+        }
+
+        if payment.is_path_payment() {
+            if let (Some(source_code), Some(source_issuer)) = (
+                payment.source_asset_code.as_ref(),
+                payment.source_asset_issuer.as_ref(),
+            ) {
+                let same_asset = *source_code == code && *source_issuer == issuer;
+                if !same_asset && sender == source_issuer {
+                    issuances.push(Issuance {
+                        asset_code: source_code.clone(),
+                        asset_issuer: source_issuer.clone(),
+                        event_type: IssuanceEventType::Mint,
+                        amount: payment.get_source_amount().parse().unwrap_or(0.0),
+                        counterparty: destination,
+                    });
+                }
+            }
+        }
+    }
+
+    issuances.retain(|i| i.amount > 0.0);
+    issuances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "GISSUER";
+
+    fn payment(from: &str, to: &str, code: &str, issuer: &str, amount: &str) -> Payment {
+        Payment {
+            id: "op1".to_string(),
+            paging_token: "op1".to_string(),
+            transaction_hash: "tx1".to_string(),
+            source_account: from.to_string(),
+            destination: to.to_string(),
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some(code.to_string()),
+            asset_issuer: Some(issuer.to_string()),
+            amount: amount.to_string(),
+            created_at: "2026-01-22T10:30:00Z".to_string(),
+            operation_type: Some("payment".to_string()),
+            source_asset_type: None,
+            source_asset_code: None,
+            source_asset_issuer: None,
+            source_amount: None,
+            path: vec![],
+            from: Some(from.to_string()),
+            to: Some(to.to_string()),
+            transaction_successful: None,
+            asset_balance_changes: None,
+        }
+    }
+
+    #[test]
+    fn test_mint_and_burn_classification() {
+        let mint = issuances_from_payment(&payment(ISSUER, "GHOLDER", "USDC", ISSUER, "50"));
+        assert_eq!(mint.len(), 1);
+        assert_eq!(mint[0].event_type, IssuanceEventType::Mint);
+        assert_eq!(mint[0].counterparty, "GHOLDER");
+
+        let burn = issuances_from_payment(&payment("GHOLDER", ISSUER, "USDC", ISSUER, "20"));
+        assert_eq!(burn[0].event_type, IssuanceEventType::Burn);
+        assert_eq!(burn[0].amount, 20.0);
+
+        assert!(issuances_from_payment(&payment("GA", "GB", "USDC", ISSUER, "5")).is_empty());
+        assert!(issuances_from_payment(&payment(ISSUER, ISSUER, "USDC", ISSUER, "5")).is_empty());
+    }
+
+    #[test]
+    fn test_path_payment_mints_source_asset() {
+        let mut p = payment(ISSUER, "GHOLDER", "XLM", "GOTHER", "10");
+        p.operation_type = Some("path_payment_strict_send".to_string());
+        p.source_asset_code = Some("USDC".to_string());
+        p.source_asset_issuer = Some(ISSUER.to_string());
+        p.source_amount = Some("12.5".to_string());
+
+        let issuances = issuances_from_payment(&p);
+        assert_eq!(issuances.len(), 1);
+        assert_eq!(issuances[0].asset_code, "USDC");
+        assert_eq!(issuances[0].amount, 12.5);
+    }
+}
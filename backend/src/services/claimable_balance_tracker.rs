@@ -0,0 +1,567 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite, SqlitePool};
+use tracing::{info, warn};
+
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
+use crate::rpc::HorizonEffect;
+
+/// Upper bounds (seconds) and labels of the time-to-claim histogram
+const TIME_TO_CLAIM_BUCKETS: &[(i64, &str)] = &[
+    (3_600, "<1h"),
+    (86_400, "1h-24h"),
+    (604_800, "1d-7d"),
+    (2_592_000, "7d-30d"),
+    (i64::MAX, ">30d"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimableBalance {
+    pub balance_id: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub amount: f64,
+    pub sponsor: Option<String>,
+    pub claimants: Vec<String>,
+    pub status: String,
+    pub created_ledger: Option<i64>,
+    pub created_at: Option<String>,
+    pub claimed_by: Option<String>,
+    pub resolved_ledger: Option<i64>,
+    pub resolved_at: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct ClaimableBalanceRow {
+    balance_id: String,
+    asset_code: Option<String>,
+    asset_issuer: Option<String>,
+    amount: f64,
+    sponsor: Option<String>,
+    claimants: String,
+    status: String,
+    created_ledger: Option<i64>,
+    created_at: Option<String>,
+    claimed_by: Option<String>,
+    resolved_ledger: Option<i64>,
+    resolved_at: Option<String>,
+}
+
+impl From<ClaimableBalanceRow> for ClaimableBalance {
+    fn from(row: ClaimableBalanceRow) -> Self {
+        Self {
+            balance_id: row.balance_id,
+            asset_code: row.asset_code,
+            asset_issuer: row.asset_issuer,
+            amount: row.amount,
+            sponsor: row.sponsor,
+            claimants: serde_json::from_str(&row.claimants).unwrap_or_default(),
+            status: row.status,
+            created_ledger: row.created_ledger,
+            created_at: row.created_at,
+            claimed_by: row.claimed_by,
+            resolved_ledger: row.resolved_ledger,
+            resolved_at: row.resolved_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ClaimableBalanceFilter {
+    pub status: Option<String>,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub claimant: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct UnclaimedAssetValue {
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub unclaimed_count: i64,
+    pub unclaimed_amount: f64,
+}
+
+#[derive(sqlx::FromRow)]
+struct AnchorAssetRow {
+    anchor_id: String,
+    anchor_name: String,
+    #[sqlx(flatten)]
+    asset: UnclaimedAssetValue,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AnchorUnclaimedValue {
+    pub anchor_id: String,
+    pub anchor_name: String,
+    pub unclaimed_count: i64,
+    pub assets: Vec<UnclaimedAssetValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeToClaimBucket {
+    pub label: &'static str,
+    pub count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimeToClaimDistribution {
+    /// Claimed balances whose creation was ingested
+    pub sample_size: i64,
+    pub average_seconds: Option<f64>,
+    pub median_seconds: Option<f64>,
+    pub buckets: Vec<TimeToClaimBucket>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimableBalanceStats {
+    pub total: i64,
+    pub unclaimed: i64,
+    pub claimed: i64,
+    pub clawed_back: i64,
+    pub unclaimed_by_asset: Vec<UnclaimedAssetValue>,
+    pub time_to_claim: TimeToClaimDistribution,
+}
+
+pub struct ClaimableBalanceTracker {
+    pool: Pool<Sqlite>,
+}
+
+impl ClaimableBalanceTracker {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    /// Records create, claimant, claim, clawback and sponsorship effects; returns the
+    /// number of balances changed
+    pub async fn process_effects(
+        &self,
+        ledger_sequence: u64,
+        effects: &[HorizonEffect],
+    ) -> Result<u64> {
+        let mut changed = 0_u64;
+
+        for effect in effects {
+            let Some(balance_id) = effect.balance_id.as_deref() else {
+                continue;
+            };
+
+            let rows = match effect.effect_type.as_str() {
+                "claimable_balance_created" => {
+                    self.record_created(ledger_sequence, balance_id, effect)
+                        .await?
+                }
+                "claimable_balance_claimant_created" => match effect.account.as_deref() {
+                    Some(claimant) => self.add_claimant(balance_id, claimant).await?,
+                    None => 0,
+                },
+                "claimable_balance_claimed" => {
+                    self.record_resolved(ledger_sequence, balance_id, "claimed", effect)
+                        .await?
+                }
+                "claimable_balance_clawed_back" => {
+                    self.record_resolved(ledger_sequence, balance_id, "clawed_back", effect)
+                        .await?
+                }
+                "claimable_balance_sponsorship_created" => {
+                    self.set_sponsor(balance_id, effect.sponsor.as_deref())
+                        .await?
+                }
+                "claimable_balance_sponsorship_updated" => {
+                    self.set_sponsor(balance_id, effect.new_sponsor.as_deref())
+                        .await?
+                }
+                "claimable_balance_sponsorship_removed" => {
+                    self.set_sponsor(balance_id, None).await?
+                }
+                _ => 0,
+            };
+            changed += rows;
+        }
+
+        if changed > 0 {
+            info!(
+                "Recorded {} claimable balance changes for ledger {}",
+                changed, ledger_sequence
+            );
+        }
+
+        Ok(changed)
+    }
+
+    async fn record_created(
+        &self,
+        ledger_sequence: u64,
+        balance_id: &str,
+        effect: &HorizonEffect,
+    ) -> Result<u64> {
+        let (asset_code, asset_issuer) = parse_asset(effect.asset.as_deref());
+        let result = sqlx::query(
+            r#"
+            INSERT INTO claimable_balances (
+                balance_id, asset_code, asset_issuer, amount, created_ledger, created_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (balance_id) DO UPDATE SET
+                asset_code = COALESCE(claimable_balances.asset_code, excluded.asset_code),
+                asset_issuer = COALESCE(claimable_balances.asset_issuer, excluded.asset_issuer),
+                amount = excluded.amount,
+                created_ledger = excluded.created_ledger,
+                created_at = excluded.created_at,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE claimable_balances.created_ledger IS NULL
+            "#,
+        )
+        .bind(balance_id)
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(parse_amount(effect.amount.as_deref()))
+        .bind(ledger_sequence as i64)
+        .bind(&effect.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Sponsor from the balance's sponsorship effects; NULL once sponsorship is removed
+    async fn set_sponsor(&self, balance_id: &str, sponsor: Option<&str>) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE claimable_balances
+            SET sponsor = $2, updated_at = CURRENT_TIMESTAMP
+            WHERE balance_id = $1 AND sponsor IS NOT $2
+            "#,
+        )
+        .bind(balance_id)
+        .bind(sponsor)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn add_claimant(&self, balance_id: &str, claimant: &str) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE claimable_balances
+            SET claimants = json_insert(claimants, '$[#]', $2), updated_at = CURRENT_TIMESTAMP
+            WHERE balance_id = $1
+              AND NOT EXISTS (SELECT 1 FROM json_each(claimants) WHERE value = $2)
+            "#,
+        )
+        .bind(balance_id)
+        .bind(claimant)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Balances created before ingestion started are recorded without creation details
+    async fn record_resolved(
+        &self,
+        ledger_sequence: u64,
+        balance_id: &str,
+        status: &str,
+        effect: &HorizonEffect,
+    ) -> Result<u64> {
+        let (asset_code, asset_issuer) = parse_asset(effect.asset.as_deref());
+        let claimed_by = if status == "claimed" {
+            effect.account.as_deref()
+        } else {
+            None
+        };
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO claimable_balances (
+                balance_id, asset_code, asset_issuer, amount, status, claimed_by,
+                resolved_ledger, resolved_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (balance_id) DO UPDATE SET
+                status = excluded.status,
+                claimed_by = excluded.claimed_by,
+                resolved_ledger = excluded.resolved_ledger,
+                resolved_at = excluded.resolved_at,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE claimable_balances.status = 'unclaimed'
+            "#,
+        )
+        .bind(balance_id)
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .bind(parse_amount(effect.amount.as_deref()))
+        .bind(status)
+        .bind(claimed_by)
+        .bind(ledger_sequence as i64)
+        .bind(&effect.created_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn list_balances(
+        &self,
+        filter: &ClaimableBalanceFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<ClaimableBalance>> {
+        let rows: Vec<ClaimableBalanceRow> = sqlx::query_as(
+            r#"
+            SELECT balance_id, asset_code, asset_issuer, amount, sponsor, claimants, status,
+                   created_ledger, created_at, claimed_by, resolved_ledger, resolved_at
+            FROM claimable_balances
+            WHERE ($1 IS NULL OR status = $1)
+              AND ($2 IS NULL OR asset_code = $2)
+              AND ($3 IS NULL OR asset_issuer = $3)
+              AND ($4 IS NULL OR EXISTS (SELECT 1 FROM json_each(claimants) WHERE value = $4))
+            ORDER BY COALESCE(resolved_ledger, created_ledger) DESC, balance_id
+            LIMIT $5 OFFSET $6
+            "#,
+        )
+        .bind(&filter.status)
+        .bind(&filter.asset_code)
+        .bind(&filter.asset_issuer)
+        .bind(&filter.claimant)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(ClaimableBalance::from).collect())
+    }
+
+    pub async fn get_balance(&self, balance_id: &str) -> Result<Option<ClaimableBalance>> {
+        let row: Option<ClaimableBalanceRow> = sqlx::query_as(
+            r#"
+            SELECT balance_id, asset_code, asset_issuer, amount, sponsor, claimants, status,
+                   created_ledger, created_at, claimed_by, resolved_ledger, resolved_at
+            FROM claimable_balances
+            WHERE balance_id = $1
+            "#,
+        )
+        .bind(balance_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(ClaimableBalance::from))
+    }
+
+    pub async fn get_stats(&self) -> Result<ClaimableBalanceStats> {
+        let (total, unclaimed, claimed, clawed_back): (i64, i64, i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*),
+                COALESCE(SUM(status = 'unclaimed'), 0),
+                COALESCE(SUM(status = 'claimed'), 0),
+                COALESCE(SUM(status = 'clawed_back'), 0)
+            FROM claimable_balances
+            "#,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let unclaimed_by_asset: Vec<UnclaimedAssetValue> = sqlx::query_as(
+            r#"
+            SELECT asset_code, asset_issuer,
+                   COUNT(*) AS unclaimed_count,
+                   COALESCE(SUM(amount), 0.0) AS unclaimed_amount
+            FROM claimable_balances
+            WHERE status = 'unclaimed'
+            GROUP BY asset_code, asset_issuer
+            ORDER BY unclaimed_amount DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let claims: Vec<(String, String)> = sqlx::query_as(
+            r#"
+            SELECT created_at, resolved_at
+            FROM claimable_balances
+            WHERE status = 'claimed' AND created_at IS NOT NULL AND resolved_at IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let durations: Vec<i64> = claims
+            .iter()
+            .filter_map(|(created, resolved)| {
+                let created = DateTime::parse_from_rfc3339(created).ok()?;
+                let resolved = DateTime::parse_from_rfc3339(resolved).ok()?;
+                Some((resolved - created).num_seconds().max(0))
+            })
+            .collect();
+
+        Ok(ClaimableBalanceStats {
+            total,
+            unclaimed,
+            claimed,
+            clawed_back,
+            unclaimed_by_asset,
+            time_to_claim: time_to_claim_distribution(durations),
+        })
+    }
+
+    pub async fn get_unclaimed_by_anchor(&self) -> Result<Vec<AnchorUnclaimedValue>> {
+        unclaimed_by_anchor(&self.pool, None).await
+    }
+}
+
+#[async_trait]
+impl LedgerProcessor for ClaimableBalanceTracker {
+    fn name(&self) -> &'static str {
+        "claimable_balances"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Effects]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+        self.process_effects(bundle.sequence, &bundle.effects).await
+    }
+}
+
+/// Unclaimed balances of assets issued by each anchor, optionally for one anchor.
+/// An asset belongs to an anchor when the anchor's account issues it or it is
+/// listed among the anchor's assets.
+pub async fn unclaimed_by_anchor(
+    pool: &SqlitePool,
+    anchor_id: Option<&str>,
+) -> Result<Vec<AnchorUnclaimedValue>> {
+    let rows: Vec<AnchorAssetRow> = sqlx::query_as(
+        r#"
+        SELECT a.id AS anchor_id, a.name AS anchor_name, cb.asset_code, cb.asset_issuer,
+               COUNT(*) AS unclaimed_count,
+               COALESCE(SUM(cb.amount), 0.0) AS unclaimed_amount
+        FROM claimable_balances cb
+        JOIN anchors a
+          ON a.stellar_account = cb.asset_issuer
+          OR EXISTS (
+              SELECT 1 FROM assets s
+              WHERE s.anchor_id = a.id
+                AND s.asset_code = cb.asset_code
+                AND s.asset_issuer = cb.asset_issuer
+          )
+        WHERE cb.status = 'unclaimed' AND ($1 IS NULL OR a.id = $1)
+        GROUP BY a.id, a.name, cb.asset_code, cb.asset_issuer
+        ORDER BY a.name, unclaimed_amount DESC
+        "#,
+    )
+    .bind(anchor_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut anchors: Vec<AnchorUnclaimedValue> = Vec::new();
+    for AnchorAssetRow {
+        anchor_id,
+        anchor_name,
+        asset,
+    } in rows
+    {
+        let count = asset.unclaimed_count;
+        match anchors.last_mut().filter(|a| a.anchor_id == anchor_id) {
+            Some(anchor) => {
+                anchor.unclaimed_count += count;
+                anchor.assets.push(asset);
+            }
+            None => anchors.push(AnchorUnclaimedValue {
+                anchor_id,
+                anchor_name,
+                unclaimed_count: count,
+                assets: vec![asset],
+            }),
+        }
+    }
+
+    Ok(anchors)
+}
+
+/// `native` or `CODE:ISSUER`
+fn parse_asset(asset: Option<&str>) -> (Option<String>, Option<String>) {
+    match asset.and_then(|a| a.split_once(':')) {
+        Some((code, issuer)) => (Some(code.to_string()), Some(issuer.to_string())),
+        None => (None, None),
+    }
+}
+
+fn parse_amount(amount: Option<&str>) -> f64 {
+    match amount.map(str::parse::<f64>) {
+        Some(Ok(value)) => value,
+        Some(Err(e)) => {
+            warn!("Unparseable claimable balance amount: {}", e);
+            0.0
+        }
+        None => 0.0,
+    }
+}
+
+fn time_to_claim_distribution(mut durations: Vec<i64>) -> TimeToClaimDistribution {
+    durations.sort_unstable();
+    let sample_size = durations.len() as i64;
+
+    let average_seconds = (!durations.is_empty())
+        .then(|| durations.iter().sum::<i64>() as f64 / durations.len() as f64);
+    let median_seconds = (!durations.is_empty()).then(|| {
+        let mid = durations.len() / 2;
+        if durations.len().is_multiple_of(2) {
+            (durations[mid - 1] + durations[mid]) as f64 / 2.0
+        } else {
+            durations[mid] as f64
+        }
+    });
+
+    let mut lower = 0;
+    let buckets = TIME_TO_CLAIM_BUCKETS
+        .iter()
+        .map(|(upper, label)| {
+            let count = durations
+                .iter()
+                .filter(|d| **d >= lower && **d < *upper)
+                .count() as i64;
+            lower = *upper;
+            TimeToClaimBucket { label, count }
+        })
+        .collect();
+
+    TimeToClaimDistribution {
+        sample_size,
+        average_seconds,
+        median_seconds,
+        buckets,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_canonical_assets() {
+        assert_eq!(parse_asset(Some("native")), (None, None));
+        assert_eq!(
+            parse_asset(Some("USDC:GISSUER")),
+            (Some("USDC".to_string()), Some("GISSUER".to_string()))
+        );
+        assert_eq!(parse_asset(None), (None, None));
+    }
+
+    #[test]
+    fn time_to_claim_buckets_and_median() {
+        let distribution = time_to_claim_distribution(vec![90_000, 60, 7_200, 3_000_000]);
+        assert_eq!(distribution.sample_size, 4);
+        assert_eq!(distribution.median_seconds, Some(48_600.0));
+        let counts: Vec<i64> = distribution.buckets.iter().map(|b| b.count).collect();
+        assert_eq!(counts, vec![1, 1, 1, 0, 1]);
+
+        let empty = time_to_claim_distribution(Vec::new());
+        assert_eq!(empty.average_seconds, None);
+        assert!(empty.buckets.iter().all(|b| b.count == 0));
+    }
+}
@@ -0,0 +1,653 @@
+//! Corridor forecasts from additive Holt-Winters models.
+//!
+//! For every corridor with enough hourly history in `corridor_metrics_hourly`,
+//! one model per metric (volume, transaction count, success rate) is fitted
+//! with additive trend and seasonality: the level, trend and seasonal
+//! smoothing factors are picked by a grid search minimising the one-step-ahead
+//! squared error. Forecasts are stored hourly up to `max_horizon_hours` ahead,
+//! with prediction intervals from the one-step error and the error
+//! propagation of the additive model, and refreshed by the
+//! `corridor-forecast` job. Series run up to the last complete hour, with
+//! hours missing from the end filled like gaps, so the first forecast point
+//! is always the current hour. Forecasts older than the job's interval are
+//! refitted when they are read.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::database::Database;
+
+const ALPHA_GRID: [f64; 7] = [0.05, 0.1, 0.2, 0.3, 0.5, 0.7, 0.9];
+const BETA_GRID: [f64; 5] = [0.0, 0.01, 0.05, 0.1, 0.2];
+const GAMMA_GRID: [f64; 5] = [0.05, 0.1, 0.2, 0.3, 0.5];
+
+#[derive(Debug, Clone)]
+pub struct ForecastConfig {
+    /// Days of hourly history each model is fitted on
+    pub history_days: i64,
+    /// Hours in one seasonal cycle: 24 (daily) or 168 (weekly)
+    pub season_hours: usize,
+    /// Hours ahead forecasts are stored for, and the longest horizon served
+    pub max_horizon_hours: usize,
+    /// Seasonal cycles of history a corridor needs before it is forecast
+    pub min_seasons: usize,
+    /// Standard normal quantile of the prediction intervals (1.96 for 95%)
+    pub interval_z: f64,
+    /// Age after which a stored forecast is refitted when read; the
+    /// `corridor-forecast` job's interval
+    pub refresh_interval_secs: i64,
+}
+
+impl ForecastConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            history_days: std::env::var("FORECAST_HISTORY_DAYS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| (2..=90).contains(v))
+                .unwrap_or(defaults.history_days),
+            season_hours: std::env::var("FORECAST_SEASON_HOURS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| *v == 24 || *v == 168)
+                .unwrap_or(defaults.season_hours),
+            max_horizon_hours: std::env::var("FORECAST_MAX_HORIZON_HOURS")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|v| (1..=336).contains(v))
+                .unwrap_or(defaults.max_horizon_hours),
+            refresh_interval_secs: std::env::var("JOB_CORRIDOR_FORECAST_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(defaults.refresh_interval_secs),
+            ..defaults
+        }
+    }
+}
+
+impl Default for ForecastConfig {
+    fn default() -> Self {
+        Self {
+            history_days: 28,
+            season_hours: 24,
+            max_horizon_hours: 168,
+            min_seasons: 2,
+            interval_z: 1.96,
+            refresh_interval_secs: 3600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForecastMetric {
+    VolumeUsd,
+    TransactionCount,
+    SuccessRate,
+}
+
+impl ForecastMetric {
+    pub const ALL: [Self; 3] = [Self::VolumeUsd, Self::TransactionCount, Self::SuccessRate];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::VolumeUsd => "volume_usd",
+            Self::TransactionCount => "transaction_count",
+            Self::SuccessRate => "success_rate",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "volume_usd" => Some(Self::VolumeUsd),
+            "transaction_count" => Some(Self::TransactionCount),
+            "success_rate" => Some(Self::SuccessRate),
+            _ => None,
+        }
+    }
+
+    fn clamp(&self, value: f64) -> f64 {
+        match self {
+            Self::SuccessRate => value.clamp(0.0, 100.0),
+            _ => value.max(0.0),
+        }
+    }
+}
+
+/// Forecast of one hour
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForecastPoint {
+    /// Start of the hour, RFC 3339
+    pub timestamp: String,
+    pub value: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricForecast {
+    pub metric: String,
+    pub alpha: f64,
+    pub beta: f64,
+    pub gamma: f64,
+    pub season_hours: i64,
+    /// One-step-ahead error on the training hours
+    pub rmse: f64,
+    pub training_hours: i64,
+    pub points: Vec<ForecastPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorridorForecast {
+    pub corridor_key: String,
+    pub generated_at: String,
+    pub horizon_hours: usize,
+    pub metrics: Vec<MetricForecast>,
+}
+
+/// Additive Holt-Winters model fitted on an hourly series
+#[derive(Debug, Clone, PartialEq)]
+struct HoltWinters {
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    level: f64,
+    trend: f64,
+    /// Seasonal components indexed by position in the series modulo the season
+    seasonals: Vec<f64>,
+    observations: usize,
+    rmse: f64,
+}
+
+impl HoltWinters {
+    /// Fit the smoothing factors by grid search; `None` unless the series
+    /// covers two seasons
+    fn fit(series: &[f64], season: usize) -> Option<Self> {
+        if season == 0 || series.len() < 2 * season {
+            return None;
+        }
+        let mut best: Option<Self> = None;
+        for alpha in ALPHA_GRID {
+            for beta in BETA_GRID {
+                for gamma in GAMMA_GRID {
+                    let model = Self::smooth(series, season, alpha, beta, gamma);
+                    if best.as_ref().is_none_or(|b| model.rmse < b.rmse) {
+                        best = Some(model);
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    /// Run the smoothing recursions from the first season onwards; the level,
+    /// trend and seasonal components start from the first two seasons
+    fn smooth(series: &[f64], season: usize, alpha: f64, beta: f64, gamma: f64) -> Self {
+        let first = mean(&series[..season]);
+        let second = mean(&series[season..2 * season]);
+        let mut level = first;
+        let mut trend = (second - first) / season as f64;
+        let mut seasonals: Vec<f64> = series[..season].iter().map(|y| y - first).collect();
+
+        let mut sse = 0.0;
+        for (t, &y) in series.iter().enumerate().skip(season) {
+            let s = seasonals[t % season];
+            let error = y - (level + trend + s);
+            sse += error * error;
+
+            let previous_level = level;
+            level = alpha * (y - s) + (1.0 - alpha) * (level + trend);
+            trend = beta * (level - previous_level) + (1.0 - beta) * trend;
+            seasonals[t % season] = gamma * (y - level) + (1.0 - gamma) * s;
+        }
+
+        Self {
+            alpha,
+            beta,
+            gamma,
+            level,
+            trend,
+            seasonals,
+            observations: series.len(),
+            rmse: (sse / (series.len() - season) as f64).sqrt(),
+        }
+    }
+
+    /// Point forecasts and interval half-widths for the next `horizon` hours
+    fn forecast(&self, horizon: usize, z: f64) -> Vec<(f64, f64)> {
+        let season = self.seasonals.len();
+        let mut variance_factor = 1.0;
+        (1..=horizon)
+            .map(|h| {
+                if h > 1 {
+                    let j = h - 1;
+                    let seasonal = if j % season == 0 { self.gamma } else { 0.0 };
+                    let c =
+                        self.alpha * (1.0 + j as f64 * self.beta) + seasonal * (1.0 - self.alpha);
+                    variance_factor += c * c;
+                }
+                let value = self.level
+                    + h as f64 * self.trend
+                    + self.seasonals[(self.observations + h - 1) % season];
+                (value, z * self.rmse * variance_factor.sqrt())
+            })
+            .collect()
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[derive(Debug, FromRow)]
+struct CorridorHourRow {
+    corridor_key: String,
+    hour_bucket: String,
+    total_transactions: Option<i64>,
+    success_rate: Option<f64>,
+    volume_usd: Option<f64>,
+}
+
+/// Hourly values of one corridor
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct HourValues {
+    volume_usd: f64,
+    transaction_count: f64,
+    success_rate: Option<f64>,
+}
+
+#[derive(Debug, FromRow)]
+struct ForecastRow {
+    corridor_key: String,
+    metric: String,
+    alpha: f64,
+    beta: f64,
+    gamma: f64,
+    season_hours: i64,
+    rmse: f64,
+    training_hours: i64,
+    points: String,
+    generated_at: String,
+}
+
+pub struct CorridorForecaster {
+    db: Arc<Database>,
+    config: ForecastConfig,
+}
+
+impl CorridorForecaster {
+    pub fn new(db: Arc<Database>, config: ForecastConfig) -> Self {
+        Self { db, config }
+    }
+
+    pub fn config(&self) -> &ForecastConfig {
+        &self.config
+    }
+
+    /// Refit and store the forecasts of every corridor with enough history,
+    /// dropping those of corridors that no longer have it
+    pub async fn refresh_all(&self) -> Result<usize> {
+        self.refresh_all_at(Utc::now()).await
+    }
+
+    /// `refresh_all` as of `now`; `now`'s hour is incomplete and not fitted
+    pub async fn refresh_all_at(&self, now: DateTime<Utc>) -> Result<usize> {
+        let current_hour = now.duration_trunc(Duration::hours(1))?;
+        let series = self.load_series(current_hour, None).await?;
+        let generated_at = Utc::now().to_rfc3339();
+
+        let mut refreshed = 0;
+        for (corridor_key, hours) in &series {
+            let Some(forecast) =
+                self.build_forecast(corridor_key, hours, current_hour, &generated_at)
+            else {
+                continue;
+            };
+            if let Err(e) = self.store(&forecast).await {
+                warn!("Failed to store forecast for {}: {}", corridor_key, e);
+                continue;
+            }
+            refreshed += 1;
+        }
+
+        sqlx::query("DELETE FROM corridor_forecasts WHERE generated_at < $1")
+            .bind(&generated_at)
+            .execute(self.db.pool())
+            .await?;
+
+        info!("Refreshed forecasts of {} corridors", refreshed);
+        Ok(refreshed)
+    }
+
+    /// Refit and store the forecasts of one corridor; `None` when it lacks
+    /// the history
+    pub async fn refresh_corridor(&self, corridor_key: &str) -> Result<Option<CorridorForecast>> {
+        let now = Utc::now();
+        let current_hour = now.duration_trunc(Duration::hours(1))?;
+        let series = self.load_series(current_hour, Some(corridor_key)).await?;
+        let generated_at = now.to_rfc3339();
+        let Some(forecast) = series.get(corridor_key).and_then(|hours| {
+            self.build_forecast(corridor_key, hours, current_hour, &generated_at)
+        }) else {
+            return Ok(None);
+        };
+        self.store(&forecast).await?;
+        Ok(Some(forecast))
+    }
+
+    /// Stored forecasts of a corridor cut to `horizon_hours`, fitted on
+    /// demand for corridors the job has not covered yet and refitted when
+    /// older than the refresh interval
+    pub async fn get_forecast(
+        &self,
+        corridor_key: &str,
+        horizon_hours: usize,
+    ) -> Result<Option<CorridorForecast>> {
+        let rows = sqlx::query_as::<_, ForecastRow>(
+            r#"
+            SELECT corridor_key, metric, alpha, beta, gamma, season_hours, rmse,
+                   training_hours, points, generated_at
+            FROM corridor_forecasts
+            WHERE corridor_key = $1
+            "#,
+        )
+        .bind(corridor_key)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let refresh_cutoff = Utc::now() - Duration::seconds(self.config.refresh_interval_secs);
+        let stale = rows.iter().any(|row| {
+            DateTime::parse_from_rfc3339(&row.generated_at)
+                .map_or(true, |generated_at| generated_at < refresh_cutoff)
+        });
+        let forecast = if rows.is_empty() || stale {
+            self.refresh_corridor(corridor_key).await?
+        } else {
+            Some(from_rows(rows)?)
+        };
+
+        Ok(forecast.map(|mut forecast| {
+            for metric in &mut forecast.metrics {
+                metric.points.truncate(horizon_hours);
+            }
+            forecast.horizon_hours = forecast.metrics.first().map_or(0, |m| m.points.len());
+            forecast
+        }))
+    }
+
+    fn build_forecast(
+        &self,
+        corridor_key: &str,
+        hours: &BTreeMap<DateTime<Utc>, HourValues>,
+        current_hour: DateTime<Utc>,
+        generated_at: &str,
+    ) -> Option<CorridorForecast> {
+        let (&first_hour, _) = hours.first_key_value()?;
+        let filled = fill_hours(hours, first_hour, current_hour - Duration::hours(1));
+        let season = self.config.season_hours;
+        if filled.len() < self.config.min_seasons.max(2) * season {
+            return None;
+        }
+
+        let mut metrics = Vec::with_capacity(ForecastMetric::ALL.len());
+        for metric in ForecastMetric::ALL {
+            let series: Vec<f64> = filled
+                .iter()
+                .map(|values| match metric {
+                    ForecastMetric::VolumeUsd => values.volume_usd,
+                    ForecastMetric::TransactionCount => values.transaction_count,
+                    ForecastMetric::SuccessRate => values.success_rate.unwrap_or(0.0),
+                })
+                .collect();
+            let model = HoltWinters::fit(&series, season)?;
+
+            let points = model
+                .forecast(self.config.max_horizon_hours, self.config.interval_z)
+                .into_iter()
+                .enumerate()
+                .map(|(i, (value, half_width))| ForecastPoint {
+                    timestamp: (current_hour + Duration::hours(i as i64)).to_rfc3339(),
+                    value: metric.clamp(value),
+                    lower: metric.clamp(value - half_width),
+                    upper: metric.clamp(value + half_width),
+                })
+                .collect();
+
+            metrics.push(MetricForecast {
+                metric: metric.as_str().to_string(),
+                alpha: model.alpha,
+                beta: model.beta,
+                gamma: model.gamma,
+                season_hours: season as i64,
+                rmse: model.rmse,
+                training_hours: series.len() as i64,
+                points,
+            });
+        }
+
+        Some(CorridorForecast {
+            corridor_key: corridor_key.to_string(),
+            generated_at: generated_at.to_string(),
+            horizon_hours: self.config.max_horizon_hours,
+            metrics,
+        })
+    }
+
+    // ========================================================================
+    // Storage
+    // ========================================================================
+
+    /// Hourly values per corridor over the history window before `current_hour`
+    async fn load_series(
+        &self,
+        current_hour: DateTime<Utc>,
+        corridor_key: Option<&str>,
+    ) -> Result<HashMap<String, BTreeMap<DateTime<Utc>, HourValues>>> {
+        let history_start = current_hour - Duration::days(self.config.history_days);
+
+        let rows = sqlx::query_as::<_, CorridorHourRow>(
+            r#"
+            SELECT corridor_key, hour_bucket, total_transactions, success_rate, volume_usd
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= $1 AND hour_bucket < $2
+              AND ($3 IS NULL OR corridor_key = $3)
+            "#,
+        )
+        .bind(history_start.to_rfc3339())
+        .bind(current_hour.to_rfc3339())
+        .bind(corridor_key)
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut series: HashMap<String, BTreeMap<DateTime<Utc>, HourValues>> = HashMap::new();
+        for row in rows {
+            let Ok(at) = DateTime::parse_from_rfc3339(&row.hour_bucket) else {
+                continue;
+            };
+            let values = HourValues {
+                volume_usd: row.volume_usd.filter(|v| v.is_finite()).unwrap_or(0.0),
+                transaction_count: row.total_transactions.unwrap_or(0) as f64,
+                success_rate: row.success_rate.filter(|v| v.is_finite()),
+            };
+            series
+                .entry(row.corridor_key)
+                .or_default()
+                .insert(at.with_timezone(&Utc), values);
+        }
+        Ok(series)
+    }
+
+    async fn store(&self, forecast: &CorridorForecast) -> Result<()> {
+        let mut tx = self.db.pool().begin().await?;
+        for metric in &forecast.metrics {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_forecasts (
+                    corridor_key, metric, alpha, beta, gamma, season_hours, rmse,
+                    training_hours, points, generated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT(corridor_key, metric) DO UPDATE SET
+                    alpha = excluded.alpha,
+                    beta = excluded.beta,
+                    gamma = excluded.gamma,
+                    season_hours = excluded.season_hours,
+                    rmse = excluded.rmse,
+                    training_hours = excluded.training_hours,
+                    points = excluded.points,
+                    generated_at = excluded.generated_at
+                "#,
+            )
+            .bind(&forecast.corridor_key)
+            .bind(&metric.metric)
+            .bind(metric.alpha)
+            .bind(metric.beta)
+            .bind(metric.gamma)
+            .bind(metric.season_hours)
+            .bind(metric.rmse)
+            .bind(metric.training_hours)
+            .bind(serde_json::to_string(&metric.points)?)
+            .bind(&forecast.generated_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+/// A regular hourly series from `first` to `last`: hours without a row had
+/// no volume or transactions and keep the previous success rate
+fn fill_hours(
+    hours: &BTreeMap<DateTime<Utc>, HourValues>,
+    first: DateTime<Utc>,
+    last: DateTime<Utc>,
+) -> Vec<HourValues> {
+    let mut filled = Vec::new();
+    let mut success_rate = hours.values().find_map(|v| v.success_rate);
+    let mut at = first;
+    while at <= last {
+        let values = hours.get(&at).copied().unwrap_or_default();
+        success_rate = values.success_rate.or(success_rate);
+        filled.push(HourValues {
+            success_rate,
+            ..values
+        });
+        at += Duration::hours(1);
+    }
+    filled
+}
+
+/// Assemble the stored rows of one corridor, in `ForecastMetric::ALL` order
+fn from_rows(rows: Vec<ForecastRow>) -> Result<CorridorForecast> {
+    let corridor_key = rows[0].corridor_key.clone();
+    let generated_at = rows[0].generated_at.clone();
+    let mut metrics = Vec::with_capacity(rows.len());
+    for row in rows {
+        metrics.push(MetricForecast {
+            points: serde_json::from_str(&row.points)?,
+            metric: row.metric,
+            alpha: row.alpha,
+            beta: row.beta,
+            gamma: row.gamma,
+            season_hours: row.season_hours,
+            rmse: row.rmse,
+            training_hours: row.training_hours,
+        });
+    }
+    metrics.sort_by_key(|m| {
+        ForecastMetric::from_str(&m.metric)
+            .and_then(|metric| ForecastMetric::ALL.iter().position(|m| *m == metric))
+            .unwrap_or(usize::MAX)
+    });
+    Ok(CorridorForecast {
+        corridor_key,
+        generated_at,
+        horizon_hours: metrics.first().map_or(0, |m| m.points.len()),
+        metrics,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    /// `days` of a daily cycle peaking at noon, on a slow upward trend
+    fn seasonal_series(days: usize) -> Vec<f64> {
+        (0..days * 24)
+            .map(|t| {
+                let hour = (t % 24) as f64;
+                let daily = 50.0 * (std::f64::consts::PI * (hour - 6.0) / 12.0).sin();
+                let noise = [0.0, 2.0, -2.0, 1.0, -1.0][t % 5];
+                100.0 + 0.1 * t as f64 + daily + noise
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_recovers_daily_seasonality() {
+        let series = seasonal_series(14);
+        let model = HoltWinters::fit(&series, 24).unwrap();
+        assert!(model.rmse < 5.0, "rmse {}", model.rmse);
+
+        // The series ends at midnight: the next noon is 13 hours ahead
+        let forecast = model.forecast(24, 1.96);
+        let (noon, _) = forecast[12];
+        let (night, _) = forecast[0];
+        let expected_noon = 100.0 + 0.1 * (14 * 24 + 12) as f64 + 50.0;
+        assert!((noon - expected_noon).abs() < 10.0, "noon {}", noon);
+        assert!(noon - night > 60.0);
+    }
+
+    #[test]
+    fn test_intervals_widen_with_horizon() {
+        let model = HoltWinters::fit(&seasonal_series(14), 24).unwrap();
+        let widths: Vec<f64> = model.forecast(48, 1.96).iter().map(|(_, w)| *w).collect();
+        assert!(widths[0] > 0.0);
+        assert!(widths.windows(2).all(|w| w[1] >= w[0]));
+        assert!(widths[47] > widths[0]);
+    }
+
+    #[test]
+    fn test_needs_two_seasons() {
+        assert!(HoltWinters::fit(&seasonal_series(1), 24).is_none());
+        assert!(HoltWinters::fit(&seasonal_series(2), 24).is_some());
+    }
+
+    #[test]
+    fn test_fill_hours_carries_success_rate() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let mut hours = BTreeMap::new();
+        hours.insert(
+            start,
+            HourValues {
+                volume_usd: 10.0,
+                transaction_count: 2.0,
+                success_rate: Some(90.0),
+            },
+        );
+        hours.insert(
+            start + Duration::hours(3),
+            HourValues {
+                volume_usd: 5.0,
+                transaction_count: 1.0,
+                success_rate: Some(100.0),
+            },
+        );
+
+        let filled = fill_hours(&hours, start, start + Duration::hours(3));
+        assert_eq!(filled.len(), 4);
+        assert_eq!(filled[1].volume_usd, 0.0);
+        assert_eq!(filled[2].transaction_count, 0.0);
+        assert_eq!(filled[2].success_rate, Some(90.0));
+        assert_eq!(filled[3].success_rate, Some(100.0));
+    }
+}
@@ -0,0 +1,738 @@
+//! Corridor health scores with a per-component breakdown.
+//!
+//! A corridor's health score (0-100) is the weighted sum of component scores,
+//! each 0-100:
+//!
+//! - `success_rate`: linear from `success_rate_floor` (0) to 100% (100);
+//! - `latency_p50` / `latency_p95`: percentiles of the hourly settlement
+//!   latency, linear from `latency_target_ms` (100) to `latency_max_ms` (0);
+//! - `liquidity_depth`: log scale from `liquidity_floor_usd` (0) to
+//!   `liquidity_target_usd` (100);
+//! - `volume_stability`: coefficient of variation of the daily volume, from 0
+//!   (100) to `volume_cv_max` (0).
+//!
+//! Components whose input is unavailable are left out and their weight is
+//! shared among the others. Anomalies detected on the corridor recently are
+//! then deducted as penalty points. Scores carry the scoring version, which
+//! changes with the algorithm and with any weight or scaling parameter, so two
+//! scores can only be compared when their versions match.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::database::Database;
+
+/// Version of the scoring algorithm; the hard-coded score this replaced was 1
+pub const SCORING_ALGORITHM_VERSION: u32 = 2;
+
+#[derive(Debug, Clone)]
+pub struct HealthScoreConfig {
+    pub success_rate_weight: f64,
+    pub latency_p50_weight: f64,
+    pub latency_p95_weight: f64,
+    pub liquidity_depth_weight: f64,
+    pub volume_stability_weight: f64,
+    /// Success rate (percent) scoring 0
+    pub success_rate_floor: f64,
+    /// Settlement latency scoring 100
+    pub latency_target_ms: f64,
+    /// Settlement latency scoring 0
+    pub latency_max_ms: f64,
+    /// Liquidity depth scoring 0
+    pub liquidity_floor_usd: f64,
+    /// Liquidity depth scoring 100
+    pub liquidity_target_usd: f64,
+    /// Coefficient of variation of the daily volume scoring 0
+    pub volume_cv_max: f64,
+    /// Points deducted per warning anomaly
+    pub warning_anomaly_penalty: f64,
+    /// Points deducted per critical anomaly
+    pub critical_anomaly_penalty: f64,
+    /// Most points anomalies can deduct
+    pub max_anomaly_penalty: f64,
+    /// Days of hourly history behind latency, liquidity and volume stability
+    pub history_days: i64,
+    /// Hours of anomalies that count toward the penalty
+    pub anomaly_window_hours: i64,
+    /// Relative change of liquidity depth, last day against the days before,
+    /// that makes it increasing or decreasing
+    pub liquidity_trend_threshold: f64,
+}
+
+impl HealthScoreConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let weight = |name: &str, default: f64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v >= 0.0)
+                .unwrap_or(default)
+        };
+        Self {
+            success_rate_weight: weight("HEALTH_WEIGHT_SUCCESS_RATE", defaults.success_rate_weight),
+            latency_p50_weight: weight("HEALTH_WEIGHT_LATENCY_P50", defaults.latency_p50_weight),
+            latency_p95_weight: weight("HEALTH_WEIGHT_LATENCY_P95", defaults.latency_p95_weight),
+            liquidity_depth_weight: weight(
+                "HEALTH_WEIGHT_LIQUIDITY_DEPTH",
+                defaults.liquidity_depth_weight,
+            ),
+            volume_stability_weight: weight(
+                "HEALTH_WEIGHT_VOLUME_STABILITY",
+                defaults.volume_stability_weight,
+            ),
+            success_rate_floor: std::env::var("HEALTH_SUCCESS_RATE_FLOOR")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| (0.0..100.0).contains(v))
+                .unwrap_or(defaults.success_rate_floor),
+            latency_target_ms: std::env::var("HEALTH_LATENCY_TARGET_MS")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+                .unwrap_or(defaults.latency_target_ms),
+            latency_max_ms: std::env::var("HEALTH_LATENCY_MAX_MS")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+                .unwrap_or(defaults.latency_max_ms),
+            liquidity_target_usd: std::env::var("HEALTH_LIQUIDITY_TARGET_USD")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > defaults.liquidity_floor_usd)
+                .unwrap_or(defaults.liquidity_target_usd),
+            warning_anomaly_penalty: weight(
+                "HEALTH_ANOMALY_WARNING_PENALTY",
+                defaults.warning_anomaly_penalty,
+            ),
+            critical_anomaly_penalty: weight(
+                "HEALTH_ANOMALY_CRITICAL_PENALTY",
+                defaults.critical_anomaly_penalty,
+            ),
+            max_anomaly_penalty: weight("HEALTH_ANOMALY_MAX_PENALTY", defaults.max_anomaly_penalty),
+            ..defaults
+        }
+    }
+
+    /// `v{algorithm}-{digest of the parameters}`
+    pub fn version(&self) -> String {
+        let digest = Sha256::digest(format!("{:?}", self).as_bytes());
+        format!(
+            "v{}-{}",
+            SCORING_ALGORITHM_VERSION,
+            &hex::encode(digest)[..8]
+        )
+    }
+}
+
+impl Default for HealthScoreConfig {
+    fn default() -> Self {
+        Self {
+            success_rate_weight: 0.4,
+            latency_p50_weight: 0.1,
+            latency_p95_weight: 0.1,
+            liquidity_depth_weight: 0.2,
+            volume_stability_weight: 0.2,
+            success_rate_floor: 80.0,
+            latency_target_ms: 5_000.0,
+            latency_max_ms: 60_000.0,
+            liquidity_floor_usd: 10_000.0,
+            liquidity_target_usd: 10_000_000.0,
+            volume_cv_max: 1.0,
+            warning_anomaly_penalty: 5.0,
+            critical_anomaly_penalty: 15.0,
+            max_anomaly_penalty: 30.0,
+            history_days: 7,
+            anomaly_window_hours: 24,
+            liquidity_trend_threshold: 0.1,
+        }
+    }
+}
+
+/// One component of a health score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct HealthComponent {
+    /// `success_rate`, `latency_p50`, `latency_p95`, `liquidity_depth`,
+    /// `volume_stability` or `anomaly_penalty`
+    #[schema(example = "success_rate")]
+    pub component: String,
+    /// Input the component is scored on (percent, milliseconds, USD,
+    /// coefficient of variation or anomaly count); null when unavailable
+    #[schema(example = 98.5)]
+    pub value: Option<f64>,
+    /// Component score (0-100); null when its input is unavailable
+    #[schema(example = 92.5)]
+    pub score: Option<f64>,
+    /// Share of the health score, after the weight of unavailable components
+    /// is redistributed
+    #[schema(example = 0.4)]
+    pub weight: f64,
+    /// Points the component adds to the health score; negative for anomalies
+    #[schema(example = 37.0)]
+    pub contribution: f64,
+    #[schema(example = "80% scores 0 and 100% scores 100")]
+    pub detail: String,
+}
+
+/// How a health score was computed; contributions add up to the score
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct HealthBreakdown {
+    #[schema(example = "v2-1a2b3c4d")]
+    pub scoring_version: String,
+    pub components: Vec<HealthComponent>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthScore {
+    pub score: f64,
+    pub breakdown: HealthBreakdown,
+}
+
+/// Health score, breakdown and liquidity trend of a corridor
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorridorHealth {
+    pub score: f64,
+    pub breakdown: HealthBreakdown,
+    pub liquidity_trend: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HealthInputs {
+    /// Percent
+    pub success_rate: Option<f64>,
+    pub latency_p50_ms: Option<f64>,
+    pub latency_p95_ms: Option<f64>,
+    pub liquidity_depth_usd: Option<f64>,
+    /// Oldest day first
+    pub daily_volumes_usd: Vec<f64>,
+    pub warning_anomalies: u32,
+    pub critical_anomalies: u32,
+}
+
+/// Recent history of a corridor from its hourly metrics and anomalies
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CorridorHistory {
+    pub latency_p50_ms: Option<f64>,
+    pub latency_p95_ms: Option<f64>,
+    /// Latest hour
+    pub liquidity_depth_usd: Option<f64>,
+    /// `increasing`, `stable` or `decreasing`; `None` without a day of history
+    /// before the last one
+    pub liquidity_trend: Option<String>,
+    pub daily_volumes_usd: Vec<f64>,
+    pub warning_anomalies: u32,
+    pub critical_anomalies: u32,
+}
+
+impl CorridorHistory {
+    /// Scoring inputs with the corridor's current success rate
+    pub fn inputs(&self, success_rate: Option<f64>) -> HealthInputs {
+        HealthInputs {
+            success_rate,
+            latency_p50_ms: self.latency_p50_ms,
+            latency_p95_ms: self.latency_p95_ms,
+            liquidity_depth_usd: self.liquidity_depth_usd,
+            daily_volumes_usd: self.daily_volumes_usd.clone(),
+            warning_anomalies: self.warning_anomalies,
+            critical_anomalies: self.critical_anomalies,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct HourRow {
+    corridor_key: String,
+    hour_bucket: String,
+    total_transactions: Option<i64>,
+    volume_usd: Option<f64>,
+    avg_settlement_latency_ms: Option<i64>,
+    liquidity_depth_usd: Option<f64>,
+}
+
+pub struct CorridorHealthScorer {
+    db: Arc<Database>,
+    config: HealthScoreConfig,
+}
+
+impl CorridorHealthScorer {
+    pub fn new(db: Arc<Database>, config: HealthScoreConfig) -> Self {
+        Self { db, config }
+    }
+
+    pub fn config(&self) -> &HealthScoreConfig {
+        &self.config
+    }
+
+    pub fn version(&self) -> String {
+        self.config.version()
+    }
+
+    pub fn score(&self, inputs: &HealthInputs) -> HealthScore {
+        score_inputs(&self.config, inputs)
+    }
+
+    /// Health of a corridor by key, with its history when it has any; the
+    /// liquidity trend is stable until a day of hourly history exists
+    pub fn assess(
+        &self,
+        corridor_key: &str,
+        success_rate: f64,
+        history: &HashMap<String, CorridorHistory>,
+    ) -> CorridorHealth {
+        let history = lookup(history, corridor_key);
+        let inputs = match history {
+            Some(history) => history.inputs(Some(success_rate)),
+            None => HealthInputs {
+                success_rate: Some(success_rate),
+                ..Default::default()
+            },
+        };
+        let health = self.score(&inputs);
+        CorridorHealth {
+            score: health.score,
+            breakdown: health.breakdown,
+            liquidity_trend: history
+                .and_then(|h| h.liquidity_trend.clone())
+                .unwrap_or_else(|| "stable".to_string()),
+        }
+    }
+
+    /// History of every corridor with hourly metrics in the window
+    pub async fn load_history(&self) -> Result<HashMap<String, CorridorHistory>> {
+        self.load_history_at(Utc::now()).await
+    }
+
+    pub async fn load_history_at(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<HashMap<String, CorridorHistory>> {
+        let current_hour = now.duration_trunc(Duration::hours(1))?;
+        let history_start = current_hour - Duration::days(self.config.history_days);
+
+        let rows = sqlx::query_as::<_, HourRow>(
+            r#"
+            SELECT corridor_key, hour_bucket, total_transactions, volume_usd,
+                   avg_settlement_latency_ms, liquidity_depth_usd
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= $1 AND hour_bucket < $2
+            "#,
+        )
+        .bind(history_start.to_rfc3339())
+        .bind(current_hour.to_rfc3339())
+        .fetch_all(self.db.pool())
+        .await?;
+
+        let mut by_corridor: HashMap<String, BTreeMap<DateTime<Utc>, HourRow>> = HashMap::new();
+        for row in rows {
+            let Ok(at) = DateTime::parse_from_rfc3339(&row.hour_bucket) else {
+                continue;
+            };
+            by_corridor
+                .entry(row.corridor_key.clone())
+                .or_default()
+                .insert(at.with_timezone(&Utc), row);
+        }
+
+        let mut history: HashMap<String, CorridorHistory> = by_corridor
+            .into_iter()
+            .map(|(key, hours)| (key, self.summarize(&hours, current_hour)))
+            .collect();
+
+        let anomaly_start = current_hour - Duration::hours(self.config.anomaly_window_hours);
+        let anomalies: Vec<(String, String, i64)> = sqlx::query_as(
+            r#"
+            SELECT subject_id, severity, COUNT(*)
+            FROM anomalies
+            WHERE subject_type = 'corridor' AND observed_at >= $1
+            GROUP BY subject_id, severity
+            "#,
+        )
+        .bind(anomaly_start.to_rfc3339())
+        .fetch_all(self.db.pool())
+        .await?;
+        for (corridor_key, severity, count) in anomalies {
+            let entry = history.entry(corridor_key).or_default();
+            match severity.as_str() {
+                "critical" => entry.critical_anomalies += count as u32,
+                _ => entry.warning_anomalies += count as u32,
+            }
+        }
+
+        Ok(history)
+    }
+
+    fn summarize(
+        &self,
+        hours: &BTreeMap<DateTime<Utc>, HourRow>,
+        current_hour: DateTime<Utc>,
+    ) -> CorridorHistory {
+        // Hourly latencies weighted by the transactions behind them
+        let mut latencies: Vec<(f64, f64)> = hours
+            .values()
+            .filter_map(|row| {
+                let ms = row.avg_settlement_latency_ms.filter(|ms| *ms > 0)?;
+                let weight = row.total_transactions.unwrap_or(0).max(1) as f64;
+                Some((ms as f64, weight))
+            })
+            .collect();
+        latencies.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // Days counted back from the current hour, so the last one is complete
+        let day_of = |at: &DateTime<Utc>| ((current_hour - *at).num_hours() - 1) / 24;
+        let mut daily_volumes: BTreeMap<i64, f64> = BTreeMap::new();
+        let mut recent_depths = Vec::new();
+        let mut earlier_depths = Vec::new();
+        for (at, row) in hours {
+            let day = day_of(at);
+            *daily_volumes.entry(day).or_default() += row.volume_usd.unwrap_or(0.0);
+            if let Some(depth) = row.liquidity_depth_usd.filter(|d| *d > 0.0) {
+                if day == 0 {
+                    recent_depths.push(depth);
+                } else {
+                    earlier_depths.push(depth);
+                }
+            }
+        }
+        // Days without any hour had no volume
+        let oldest_day = daily_volumes.keys().next_back().copied().unwrap_or(0);
+        let daily_volumes_usd = (0..=oldest_day)
+            .rev()
+            .map(|day| daily_volumes.get(&day).copied().unwrap_or(0.0))
+            .collect();
+
+        CorridorHistory {
+            latency_p50_ms: weighted_percentile(&latencies, 0.5),
+            latency_p95_ms: weighted_percentile(&latencies, 0.95),
+            liquidity_depth_usd: hours
+                .values()
+                .next_back()
+                .and_then(|row| row.liquidity_depth_usd)
+                .filter(|d| *d > 0.0),
+            liquidity_trend: liquidity_trend(
+                &recent_depths,
+                &earlier_depths,
+                self.config.liquidity_trend_threshold,
+            )
+            .map(str::to_string),
+            daily_volumes_usd,
+            warning_anomalies: 0,
+            critical_anomalies: 0,
+        }
+    }
+}
+
+/// History of a corridor under any spelling of its key
+pub fn lookup<'a>(
+    history: &'a HashMap<String, CorridorHistory>,
+    corridor_key: &str,
+) -> Option<&'a CorridorHistory> {
+    history.get(corridor_key).or_else(|| {
+        let normalized = crate::ml::parse_corridor(corridor_key)?.to_string_key();
+        history.get(&normalized)
+    })
+}
+
+/// Component name, configured weight, measured value, 0-100 score and how
+/// the value is scored
+type RatedComponent = (&'static str, f64, Option<f64>, Option<f64>, String);
+
+/// Score `inputs` under `config`
+pub fn score_inputs(config: &HealthScoreConfig, inputs: &HealthInputs) -> HealthScore {
+    let cv = coefficient_of_variation(&inputs.daily_volumes_usd);
+    let rated: [RatedComponent; 5] = [
+        (
+            "success_rate",
+            config.success_rate_weight,
+            inputs.success_rate,
+            inputs
+                .success_rate
+                .map(|rate| linear(rate, config.success_rate_floor, 100.0)),
+            format!(
+                "{}% scores 0 and 100% scores 100",
+                config.success_rate_floor
+            ),
+        ),
+        (
+            "latency_p50",
+            config.latency_p50_weight,
+            inputs.latency_p50_ms,
+            inputs
+                .latency_p50_ms
+                .map(|ms| linear(ms, config.latency_max_ms, config.latency_target_ms)),
+            format!(
+                "median hourly settlement latency; {}ms scores 100 and {}ms scores 0",
+                config.latency_target_ms, config.latency_max_ms
+            ),
+        ),
+        (
+            "latency_p95",
+            config.latency_p95_weight,
+            inputs.latency_p95_ms,
+            inputs
+                .latency_p95_ms
+                .map(|ms| linear(ms, config.latency_max_ms, config.latency_target_ms)),
+            format!(
+                "95th percentile hourly settlement latency; {}ms scores 100 and {}ms scores 0",
+                config.latency_target_ms, config.latency_max_ms
+            ),
+        ),
+        (
+            "liquidity_depth",
+            config.liquidity_depth_weight,
+            inputs.liquidity_depth_usd,
+            inputs.liquidity_depth_usd.map(|depth| {
+                linear(
+                    depth.max(1.0).ln(),
+                    config.liquidity_floor_usd.ln(),
+                    config.liquidity_target_usd.ln(),
+                )
+            }),
+            format!(
+                "USD on a log scale; ${} scores 0 and ${} scores 100",
+                config.liquidity_floor_usd, config.liquidity_target_usd
+            ),
+        ),
+        (
+            "volume_stability",
+            config.volume_stability_weight,
+            cv,
+            cv.map(|cv| linear(cv, config.volume_cv_max, 0.0)),
+            format!(
+                "coefficient of variation of daily volume; 0 scores 100 and {} scores 0",
+                config.volume_cv_max
+            ),
+        ),
+    ];
+
+    let available_weight: f64 = rated
+        .iter()
+        .filter(|(_, _, _, score, _)| score.is_some())
+        .map(|(_, weight, _, _, _)| weight)
+        .sum();
+
+    let mut components = Vec::with_capacity(rated.len() + 1);
+    let mut total = 0.0;
+    for (name, weight, value, score, detail) in rated {
+        let weight = match score {
+            Some(_) if available_weight > 0.0 => weight / available_weight,
+            _ => 0.0,
+        };
+        let contribution = score.unwrap_or(0.0) * weight;
+        total += contribution;
+        components.push(HealthComponent {
+            component: name.to_string(),
+            value: value.map(round2),
+            score: score.map(round2),
+            weight: round4(weight),
+            contribution: round2(contribution),
+            detail: match value {
+                Some(_) => detail,
+                None => "no data; weight shared among the other components".to_string(),
+            },
+        });
+    }
+
+    let anomalies = inputs.warning_anomalies + inputs.critical_anomalies;
+    let penalty = (inputs.warning_anomalies as f64 * config.warning_anomaly_penalty
+        + inputs.critical_anomalies as f64 * config.critical_anomaly_penalty)
+        .min(config.max_anomaly_penalty)
+        .min(total);
+    total -= penalty;
+    components.push(HealthComponent {
+        component: "anomaly_penalty".to_string(),
+        value: Some(anomalies as f64),
+        score: None,
+        weight: 0.0,
+        contribution: round2(-penalty),
+        detail: format!(
+            "{} warning and {} critical anomalies in the last {}h; {} and {} points each, at most {}",
+            inputs.warning_anomalies,
+            inputs.critical_anomalies,
+            config.anomaly_window_hours,
+            config.warning_anomaly_penalty,
+            config.critical_anomaly_penalty,
+            config.max_anomaly_penalty
+        ),
+    });
+
+    HealthScore {
+        score: (total.clamp(0.0, 100.0) * 10.0).round() / 10.0,
+        breakdown: HealthBreakdown {
+            scoring_version: config.version(),
+            components,
+        },
+    }
+}
+
+/// `increasing`, `stable` or `decreasing` liquidity depth over the last day
+/// against the days before it
+pub fn liquidity_trend(recent: &[f64], earlier: &[f64], threshold: f64) -> Option<&'static str> {
+    if recent.is_empty() || earlier.is_empty() {
+        return None;
+    }
+    let earlier = mean(earlier);
+    if earlier <= 0.0 {
+        return None;
+    }
+    let change = (mean(recent) - earlier) / earlier;
+    Some(if change > threshold {
+        "increasing"
+    } else if change < -threshold {
+        "decreasing"
+    } else {
+        "stable"
+    })
+}
+
+/// 0 at `zero`, 100 at `hundred`, linear in between and clamped
+fn linear(value: f64, zero: f64, hundred: f64) -> f64 {
+    if hundred == zero {
+        return if value >= hundred { 100.0 } else { 0.0 };
+    }
+    ((value - zero) / (hundred - zero) * 100.0).clamp(0.0, 100.0)
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Standard deviation over mean; needs three days
+fn coefficient_of_variation(values: &[f64]) -> Option<f64> {
+    if values.len() < 3 {
+        return None;
+    }
+    let mean = mean(values);
+    if mean <= 0.0 {
+        return None;
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+    Some(variance.sqrt() / mean)
+}
+
+/// Percentile of values sorted ascending, each with a weight
+fn weighted_percentile(sorted: &[(f64, f64)], percentile: f64) -> Option<f64> {
+    let total: f64 = sorted.iter().map(|(_, w)| w).sum();
+    let target = total * percentile;
+    let mut cumulative = 0.0;
+    for (value, weight) in sorted {
+        cumulative += weight;
+        if cumulative >= target {
+            return Some(*value);
+        }
+    }
+    sorted.last().map(|(value, _)| *value)
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn round4(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_inputs() -> HealthInputs {
+        HealthInputs {
+            success_rate: Some(98.0),
+            latency_p50_ms: Some(5_000.0),
+            latency_p95_ms: Some(32_500.0),
+            liquidity_depth_usd: Some(10_000_000.0),
+            daily_volumes_usd: vec![100.0, 100.0, 100.0],
+            warning_anomalies: 0,
+            critical_anomalies: 0,
+        }
+    }
+
+    #[test]
+    fn test_contributions_add_up_to_score() {
+        let score = score_inputs(&HealthScoreConfig::default(), &full_inputs());
+        // 0.4 * 90 + 0.1 * 100 + 0.1 * 50 + 0.2 * 100 + 0.2 * 100
+        assert_eq!(score.score, 91.0);
+        let sum: f64 = score
+            .breakdown
+            .components
+            .iter()
+            .map(|c| c.contribution)
+            .sum();
+        assert!((sum - score.score).abs() < 0.05);
+        assert_eq!(score.breakdown.components.len(), 6);
+    }
+
+    #[test]
+    fn test_missing_components_share_their_weight() {
+        let inputs = HealthInputs {
+            success_rate: Some(100.0),
+            ..Default::default()
+        };
+        let score = score_inputs(&HealthScoreConfig::default(), &inputs);
+        assert_eq!(score.score, 100.0);
+
+        let success = &score.breakdown.components[0];
+        assert_eq!(success.weight, 1.0);
+        let latency = &score.breakdown.components[1];
+        assert_eq!(latency.score, None);
+        assert_eq!(latency.weight, 0.0);
+    }
+
+    #[test]
+    fn test_anomalies_deduct_capped_penalty() {
+        let config = HealthScoreConfig::default();
+        let mut inputs = full_inputs();
+        inputs.warning_anomalies = 1;
+        assert_eq!(score_inputs(&config, &inputs).score, 86.0);
+
+        inputs.critical_anomalies = 5;
+        let score = score_inputs(&config, &inputs);
+        assert_eq!(score.score, 61.0);
+        let penalty = score.breakdown.components.last().unwrap();
+        assert_eq!(penalty.component, "anomaly_penalty");
+        assert_eq!(penalty.value, Some(6.0));
+        assert_eq!(penalty.contribution, -30.0);
+    }
+
+    #[test]
+    fn test_version_changes_with_weights() {
+        let config = HealthScoreConfig::default();
+        assert!(config.version().starts_with("v2-"));
+        assert_eq!(config.version(), HealthScoreConfig::default().version());
+
+        let reweighted = HealthScoreConfig {
+            success_rate_weight: 0.5,
+            ..HealthScoreConfig::default()
+        };
+        assert_ne!(config.version(), reweighted.version());
+    }
+
+    #[test]
+    fn test_liquidity_trend_compares_with_history() {
+        // A large but shrinking pool is decreasing, whatever its size
+        assert_eq!(
+            liquidity_trend(&[20_000_000.0], &[30_000_000.0, 30_000_000.0], 0.1),
+            Some("decreasing")
+        );
+        assert_eq!(
+            liquidity_trend(&[120_000.0], &[100_000.0], 0.1),
+            Some("increasing")
+        );
+        assert_eq!(liquidity_trend(&[105.0], &[100.0], 0.1), Some("stable"));
+        assert_eq!(liquidity_trend(&[105.0], &[], 0.1), None);
+    }
+
+    #[test]
+    fn test_weighted_percentile() {
+        let sorted = [(1_000.0, 1.0), (2_000.0, 8.0), (30_000.0, 1.0)];
+        assert_eq!(weighted_percentile(&sorted, 0.5), Some(2_000.0));
+        assert_eq!(weighted_percentile(&sorted, 0.95), Some(30_000.0));
+        assert_eq!(weighted_percentile(&[], 0.5), None);
+    }
+}
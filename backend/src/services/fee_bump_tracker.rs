@@ -1,8 +1,10 @@
 use anyhow::Result;
+use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{Pool, Sqlite};
 use tracing::{info, warn};
 
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
 use crate::models::{FeeBumpStats, FeeBumpTransaction};
 use crate::rpc::HorizonTransaction; // Changed from StellarRpcClient as we process data structs
 
@@ -120,6 +122,28 @@ impl FeeBumpTrackerService {
         Ok(transactions)
     }
 
+    /// Get fee bump transactions paid for by an account
+    pub async fn get_fee_bumps_by_fee_source(
+        &self,
+        fee_source: &str,
+        limit: i64,
+    ) -> Result<Vec<FeeBumpTransaction>> {
+        let transactions = sqlx::query_as::<_, FeeBumpTransaction>(
+            r#"
+            SELECT * FROM fee_bump_transactions
+            WHERE fee_source = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(fee_source)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(transactions)
+    }
+
     /// Get fee bump statistics
     pub async fn get_fee_bump_stats(&self) -> Result<FeeBumpStats> {
         let row: (i64, f64, i64, i64, i64) = sqlx::query_as(
@@ -145,3 +169,18 @@ impl FeeBumpTrackerService {
         })
     }
 }
+
+#[async_trait]
+impl LedgerProcessor for FeeBumpTrackerService {
+    fn name(&self) -> &'static str {
+        "fee_bumps"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Transactions]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+        self.process_transactions(&bundle.transactions).await
+    }
+}
@@ -0,0 +1,525 @@
+//! Network fee market analytics.
+//!
+//! Every ingested ledger's transactions are reduced to per-operation fee
+//! percentiles, capacity usage and a surge-pricing flag. Recent ledgers drive
+//! the recommended fee.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, Pool, Sqlite};
+
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
+use crate::rpc::HorizonTransaction;
+use crate::utils::percentile;
+
+pub const STROOPS_PER_XLM: f64 = 10_000_000.0;
+
+#[derive(Debug, Clone)]
+pub struct FeeMarketConfig {
+    /// Network base fee per operation in stroops; ledgers use their header's
+    /// `base_fee` when it was decoded
+    pub base_fee_stroops: i64,
+    /// Operations a ledger can hold; ledgers use their header's
+    /// `max_tx_set_size` when it was decoded
+    pub ledger_capacity_ops: i64,
+    /// Recent ledgers the recommended fee is computed from
+    pub recommendation_window: i64,
+}
+
+impl FeeMarketConfig {
+    pub fn from_env() -> Self {
+        let parse = |key: &str, default: i64| {
+            std::env::var(key)
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            base_fee_stroops: parse("FEE_MARKET_BASE_FEE_STROOPS", 100),
+            ledger_capacity_ops: parse("FEE_MARKET_LEDGER_CAPACITY_OPS", 1000),
+            recommendation_window: parse("FEE_MARKET_RECOMMENDATION_WINDOW", 20),
+        }
+    }
+}
+
+impl Default for FeeMarketConfig {
+    fn default() -> Self {
+        Self {
+            base_fee_stroops: 100,
+            ledger_capacity_ops: 1000,
+            recommendation_window: 20,
+        }
+    }
+}
+
+/// Fee statistics for one ledger; fees are stroops per operation
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct LedgerFeeStats {
+    pub ledger_sequence: i64,
+    pub closed_at: String,
+    pub transaction_count: i64,
+    pub operation_count: i64,
+    pub capacity_usage: f64,
+    pub base_fee: i64,
+    pub min_fee: i64,
+    pub p10_fee: i64,
+    pub p50_fee: i64,
+    pub p90_fee: i64,
+    pub p99_fee: i64,
+    pub max_fee: i64,
+    pub p50_max_fee_bid: i64,
+    pub total_fee_charged: i64,
+    pub surge_pricing: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FeeMarketStats {
+    pub since: String,
+    pub ledgers: i64,
+    pub transactions: i64,
+    pub operations: i64,
+    pub avg_capacity_usage: f64,
+    pub surge_ledgers: i64,
+    pub surge_ratio: f64,
+    pub avg_base_fee: f64,
+    pub avg_p50_fee: f64,
+    pub max_p99_fee: i64,
+    /// Average median fee charged relative to the base fee
+    pub fee_premium_ratio: f64,
+    pub total_fee_charged_xlm: f64,
+}
+
+/// Consecutive ledgers under surge pricing
+#[derive(Debug, Clone, Serialize)]
+pub struct SurgePeriod {
+    pub start_ledger: i64,
+    pub end_ledger: i64,
+    pub started_at: String,
+    pub ended_at: String,
+    pub ledger_count: i64,
+    pub peak_p50_fee: i64,
+    pub peak_capacity_usage: f64,
+}
+
+/// Suggested fee per operation in stroops, at three inclusion priorities
+#[derive(Debug, Clone, Serialize)]
+pub struct RecommendedFee {
+    pub based_on_ledgers: usize,
+    pub latest_ledger: Option<i64>,
+    pub base_fee: i64,
+    pub surge_pricing_active: bool,
+    pub low: i64,
+    pub medium: i64,
+    pub high: i64,
+    pub operations: i64,
+    /// `medium` for the requested number of operations, in XLM
+    pub estimated_fee_xlm: f64,
+}
+
+impl RecommendedFee {
+    /// Fee in XLM for a transaction with `operations` operations at the medium level
+    pub fn medium_fee_xlm(&self, operations: i64) -> f64 {
+        (self.medium * operations.max(1)) as f64 / STROOPS_PER_XLM
+    }
+}
+
+pub struct FeeMarketService {
+    pool: Pool<Sqlite>,
+    config: FeeMarketConfig,
+}
+
+impl FeeMarketService {
+    pub fn new(pool: Pool<Sqlite>, config: FeeMarketConfig) -> Self {
+        Self { pool, config }
+    }
+
+    /// Store the fee statistics of a ledger's transactions; returns `false`
+    /// for ledgers without transactions
+    pub async fn process_transactions(
+        &self,
+        ledger_sequence: u64,
+        transactions: &[HorizonTransaction],
+    ) -> Result<bool> {
+        let header = self.ledger_header(ledger_sequence).await?;
+        let Some(stats) = compute_ledger_stats(ledger_sequence, transactions, &header) else {
+            return Ok(false);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO fee_market_ledgers (
+                ledger_sequence, closed_at, transaction_count, operation_count,
+                capacity_usage, base_fee, min_fee, p10_fee, p50_fee, p90_fee, p99_fee,
+                max_fee, p50_max_fee_bid, total_fee_charged, surge_pricing
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            ON CONFLICT (ledger_sequence) DO UPDATE SET
+                closed_at = excluded.closed_at,
+                transaction_count = excluded.transaction_count,
+                operation_count = excluded.operation_count,
+                capacity_usage = excluded.capacity_usage,
+                base_fee = excluded.base_fee,
+                min_fee = excluded.min_fee,
+                p10_fee = excluded.p10_fee,
+                p50_fee = excluded.p50_fee,
+                p90_fee = excluded.p90_fee,
+                p99_fee = excluded.p99_fee,
+                max_fee = excluded.max_fee,
+                p50_max_fee_bid = excluded.p50_max_fee_bid,
+                total_fee_charged = excluded.total_fee_charged,
+                surge_pricing = excluded.surge_pricing
+            "#,
+        )
+        .bind(stats.ledger_sequence)
+        .bind(&stats.closed_at)
+        .bind(stats.transaction_count)
+        .bind(stats.operation_count)
+        .bind(stats.capacity_usage)
+        .bind(stats.base_fee)
+        .bind(stats.min_fee)
+        .bind(stats.p10_fee)
+        .bind(stats.p50_fee)
+        .bind(stats.p90_fee)
+        .bind(stats.p99_fee)
+        .bind(stats.max_fee)
+        .bind(stats.p50_max_fee_bid)
+        .bind(stats.total_fee_charged)
+        .bind(stats.surge_pricing)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(true)
+    }
+
+    /// Base fee and capacity from the stored ledger header, falling back to the
+    /// configured values for ledgers whose header was not decoded
+    async fn ledger_header(&self, ledger_sequence: u64) -> Result<LedgerHeaderFees> {
+        let row: Option<(Option<i64>, Option<i64>)> =
+            sqlx::query_as("SELECT base_fee, max_tx_set_size FROM ledgers WHERE sequence = $1")
+                .bind(ledger_sequence as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        let (base_fee, capacity_ops) = row.unwrap_or_default();
+        Ok(LedgerHeaderFees {
+            base_fee: base_fee
+                .filter(|f| *f > 0)
+                .unwrap_or(self.config.base_fee_stroops),
+            capacity_ops: capacity_ops
+                .filter(|c| *c > 0)
+                .unwrap_or(self.config.ledger_capacity_ops),
+        })
+    }
+
+    /// Most recent ledgers first
+    pub async fn get_recent_ledgers(&self, limit: i64) -> Result<Vec<LedgerFeeStats>> {
+        let ledgers = sqlx::query_as(
+            r#"
+            SELECT ledger_sequence, closed_at, transaction_count, operation_count,
+                   capacity_usage, base_fee, min_fee, p10_fee, p50_fee, p90_fee, p99_fee,
+                   max_fee, p50_max_fee_bid, total_fee_charged, surge_pricing
+            FROM fee_market_ledgers
+            ORDER BY ledger_sequence DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(ledgers)
+    }
+
+    pub async fn get_stats(&self, since: DateTime<Utc>) -> Result<FeeMarketStats> {
+        let since = since.to_rfc3339();
+        let row: (i64, i64, i64, f64, i64, f64, f64, i64, f64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*),
+                COALESCE(SUM(transaction_count), 0),
+                COALESCE(SUM(operation_count), 0),
+                COALESCE(AVG(capacity_usage), 0.0),
+                COALESCE(SUM(surge_pricing), 0),
+                COALESCE(AVG(base_fee), 0.0),
+                COALESCE(AVG(p50_fee), 0.0),
+                COALESCE(MAX(p99_fee), 0),
+                COALESCE(AVG(CAST(p50_fee AS REAL) / base_fee), 0.0),
+                COALESCE(SUM(total_fee_charged), 0)
+            FROM fee_market_ledgers
+            WHERE closed_at >= $1
+            "#,
+        )
+        .bind(&since)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(FeeMarketStats {
+            since,
+            ledgers: row.0,
+            transactions: row.1,
+            operations: row.2,
+            avg_capacity_usage: row.3,
+            surge_ledgers: row.4,
+            surge_ratio: if row.0 > 0 {
+                row.4 as f64 / row.0 as f64
+            } else {
+                0.0
+            },
+            avg_base_fee: row.5,
+            avg_p50_fee: row.6,
+            max_p99_fee: row.7,
+            fee_premium_ratio: row.8,
+            total_fee_charged_xlm: row.9 as f64 / STROOPS_PER_XLM,
+        })
+    }
+
+    /// Surge-pricing periods since `since`, most recent first
+    pub async fn get_surge_periods(&self, since: DateTime<Utc>) -> Result<Vec<SurgePeriod>> {
+        let ledgers: Vec<LedgerFeeStats> = sqlx::query_as(
+            r#"
+            SELECT ledger_sequence, closed_at, transaction_count, operation_count,
+                   capacity_usage, base_fee, min_fee, p10_fee, p50_fee, p90_fee, p99_fee,
+                   max_fee, p50_max_fee_bid, total_fee_charged, surge_pricing
+            FROM fee_market_ledgers
+            WHERE closed_at >= $1 AND surge_pricing = 1
+            ORDER BY ledger_sequence
+            "#,
+        )
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut periods = group_surge_periods(&ledgers);
+        periods.reverse();
+        Ok(periods)
+    }
+
+    /// Recommended per-operation fee from the last `window` ledgers
+    /// (defaults to the configured window)
+    pub async fn recommended_fee(
+        &self,
+        window: Option<i64>,
+        operations: i64,
+    ) -> Result<RecommendedFee> {
+        let window = window.unwrap_or(self.config.recommendation_window);
+        let ledgers = self.get_recent_ledgers(window).await?;
+        let base_fee = ledgers
+            .first()
+            .map_or(self.config.base_fee_stroops, |l| l.base_fee);
+        Ok(recommend(&ledgers, base_fee, operations))
+    }
+}
+
+#[async_trait]
+impl LedgerProcessor for FeeMarketService {
+    fn name(&self) -> &'static str {
+        "fee_market"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Transactions]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+        let stored = self
+            .process_transactions(bundle.sequence, &bundle.transactions)
+            .await?;
+        Ok(stored as u64)
+    }
+}
+
+/// Fee parameters of one ledger
+#[derive(Debug, Clone, Copy)]
+struct LedgerHeaderFees {
+    base_fee: i64,
+    capacity_ops: i64,
+}
+
+impl From<&FeeMarketConfig> for LedgerHeaderFees {
+    fn from(config: &FeeMarketConfig) -> Self {
+        Self {
+            base_fee: config.base_fee_stroops,
+            capacity_ops: config.ledger_capacity_ops,
+        }
+    }
+}
+
+fn compute_ledger_stats(
+    ledger_sequence: u64,
+    transactions: &[HorizonTransaction],
+    header: &LedgerHeaderFees,
+) -> Option<LedgerFeeStats> {
+    let parse = |fee: &Option<String>| fee.as_deref().and_then(|f| f.parse::<i64>().ok());
+
+    let mut fees = Vec::with_capacity(transactions.len());
+    let mut bids = Vec::with_capacity(transactions.len());
+    let mut operation_count = 0_i64;
+    let mut total_fee_charged = 0_i64;
+
+    for tx in transactions {
+        // A fee bump pays for its inner operations plus the outer envelope
+        let fee_ops =
+            tx.operation_count.max(1) as i64 + i64::from(tx.fee_bump_transaction.is_some());
+        operation_count += tx.operation_count as i64;
+
+        if let Some(charged) = parse(&tx.fee_charged) {
+            total_fee_charged += charged;
+            fees.push(charged / fee_ops);
+        }
+        if let Some(bid) = parse(&tx.max_fee) {
+            bids.push(bid / fee_ops);
+        }
+    }
+    if fees.is_empty() {
+        return None;
+    }
+    fees.sort_unstable();
+    bids.sort_unstable();
+
+    let capacity_usage = operation_count as f64 / header.capacity_ops as f64;
+    let min_fee = fees[0];
+    Some(LedgerFeeStats {
+        ledger_sequence: ledger_sequence as i64,
+        closed_at: transactions[0].created_at.clone(),
+        transaction_count: transactions.len() as i64,
+        operation_count,
+        capacity_usage,
+        base_fee: header.base_fee,
+        min_fee,
+        p10_fee: percentile(&fees, 10.0).unwrap_or_default(),
+        p50_fee: percentile(&fees, 50.0).unwrap_or_default(),
+        p90_fee: percentile(&fees, 90.0).unwrap_or_default(),
+        p99_fee: percentile(&fees, 99.0).unwrap_or_default(),
+        max_fee: fees[fees.len() - 1],
+        p50_max_fee_bid: percentile(&bids, 50.0).unwrap_or_default(),
+        total_fee_charged,
+        // Under surge pricing every transaction pays the clearing price
+        surge_pricing: min_fee > header.base_fee || capacity_usage >= 1.0,
+    })
+}
+
+/// Group ascending surge ledgers into runs of consecutive sequences
+fn group_surge_periods(ledgers: &[LedgerFeeStats]) -> Vec<SurgePeriod> {
+    let mut periods: Vec<SurgePeriod> = Vec::new();
+    for ledger in ledgers.iter().filter(|l| l.surge_pricing) {
+        match periods.last_mut() {
+            Some(period) if period.end_ledger + 1 == ledger.ledger_sequence => {
+                period.end_ledger = ledger.ledger_sequence;
+                period.ended_at = ledger.closed_at.clone();
+                period.ledger_count += 1;
+                period.peak_p50_fee = period.peak_p50_fee.max(ledger.p50_fee);
+                period.peak_capacity_usage = period.peak_capacity_usage.max(ledger.capacity_usage);
+            }
+            _ => periods.push(SurgePeriod {
+                start_ledger: ledger.ledger_sequence,
+                end_ledger: ledger.ledger_sequence,
+                started_at: ledger.closed_at.clone(),
+                ended_at: ledger.closed_at.clone(),
+                ledger_count: 1,
+                peak_p50_fee: ledger.p50_fee,
+                peak_capacity_usage: ledger.capacity_usage,
+            }),
+        }
+    }
+    periods
+}
+
+/// Low, medium and high track the typical p10, p50 and p90 of recent
+/// ledgers; none is below the base fee
+fn recommend(ledgers: &[LedgerFeeStats], base_fee: i64, operations: i64) -> RecommendedFee {
+    let level = |select: fn(&LedgerFeeStats) -> i64, pct: f64| {
+        let mut values: Vec<i64> = ledgers.iter().map(select).collect();
+        values.sort_unstable();
+        percentile(&values, pct).unwrap_or_default().max(base_fee)
+    };
+
+    let low = level(|l| l.p10_fee, 50.0);
+    let medium = level(|l| l.p50_fee, 50.0).max(low);
+    let high = level(|l| l.p90_fee, 90.0).max(medium);
+    let operations = operations.max(1);
+
+    RecommendedFee {
+        based_on_ledgers: ledgers.len(),
+        latest_ledger: ledgers.first().map(|l| l.ledger_sequence),
+        base_fee,
+        surge_pricing_active: ledgers.first().is_some_and(|l| l.surge_pricing),
+        low,
+        medium,
+        high,
+        operations,
+        estimated_fee_xlm: (medium * operations) as f64 / STROOPS_PER_XLM,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::StellarRpcClient;
+
+    fn transactions(fees: &[(i64, u32)]) -> Vec<HorizonTransaction> {
+        let template = StellarRpcClient::mock_transactions(1, 100).remove(0);
+        fees.iter()
+            .map(|(fee, ops)| HorizonTransaction {
+                fee_charged: Some(fee.to_string()),
+                max_fee: Some((fee * 10).to_string()),
+                operation_count: *ops,
+                fee_bump_transaction: None,
+                inner_transaction: None,
+                ..template.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_ledger_stats() {
+        let config = LedgerHeaderFees::from(&FeeMarketConfig::default());
+        let quiet = compute_ledger_stats(1, &transactions(&[(100, 1), (200, 2)]), &config).unwrap();
+        assert_eq!(quiet.p50_fee, 100);
+        assert_eq!(quiet.operation_count, 3);
+        assert!(!quiet.surge_pricing);
+
+        let surge =
+            compute_ledger_stats(2, &transactions(&[(500, 1), (1200, 2)]), &config).unwrap();
+        assert_eq!(surge.min_fee, 500);
+        assert_eq!(surge.max_fee, 600);
+        assert!(surge.surge_pricing);
+
+        assert!(compute_ledger_stats(3, &[], &config).is_none());
+    }
+
+    #[test]
+    fn test_fee_bump_counts_outer_envelope() {
+        let mut txs = StellarRpcClient::mock_transactions(1, 100);
+        txs[0].fee_charged = Some("200".to_string());
+        let header = LedgerHeaderFees::from(&FeeMarketConfig::default());
+        let stats = compute_ledger_stats(1, &txs, &header).unwrap();
+        assert_eq!(stats.p50_fee, 100);
+    }
+
+    #[test]
+    fn test_group_surge_periods_and_recommend() {
+        let config = LedgerHeaderFees::from(&FeeMarketConfig::default());
+        let ledgers: Vec<LedgerFeeStats> = [(10, 100), (11, 400), (12, 800), (14, 300)]
+            .iter()
+            .map(|(seq, fee)| {
+                compute_ledger_stats(*seq, &transactions(&[(*fee, 1)]), &config).unwrap()
+            })
+            .collect();
+
+        let periods = group_surge_periods(&ledgers);
+        assert_eq!(periods.len(), 2);
+        assert_eq!((periods[0].start_ledger, periods[0].end_ledger), (11, 12));
+        assert_eq!(periods[0].peak_p50_fee, 800);
+        assert_eq!(periods[1].ledger_count, 1);
+
+        let recommended = recommend(&ledgers, 100, 2);
+        assert!(recommended.low >= 100);
+        assert!(recommended.low <= recommended.medium && recommended.medium <= recommended.high);
+        assert_eq!(recommended.high, 800);
+        assert!((recommended.estimated_fee_xlm - recommended.medium_fee_xlm(2)).abs() < 1e-12);
+
+        let empty = recommend(&[], 100, 1);
+        assert_eq!((empty.low, empty.medium, empty.high), (100, 100, 100));
+    }
+}
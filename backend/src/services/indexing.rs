@@ -1,20 +1,42 @@
 use anyhow::{Context, Result};
 use chrono::DateTime;
+use futures::StreamExt;
 use std::sync::Arc;
-use tracing::info;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
+use crate::cache_invalidation::CacheInvalidationService;
 use crate::database::Database;
 use crate::models::PaymentRecord;
-use crate::rpc::StellarRpcClient;
+use crate::network::StellarNetwork;
+use crate::rpc::{HorizonStream, Payment, StellarRpcClient};
+
+/// Shortest gap between overview invalidations while streaming, so a burst
+/// of payments clears the cached overview once rather than per payment
+const STREAM_INVALIDATION_INTERVAL: Duration = Duration::from_secs(10);
 
 pub struct IndexingService {
     rpc_client: Arc<StellarRpcClient>,
     db: Arc<Database>,
+    cache_invalidation: Option<Arc<CacheInvalidationService>>,
 }
 
 impl IndexingService {
     pub fn new(rpc_client: Arc<StellarRpcClient>, db: Arc<Database>) -> Self {
-        Self { rpc_client, db }
+        Self {
+            rpc_client,
+            db,
+            cache_invalidation: None,
+        }
+    }
+
+    /// Clear the cached metrics overview whenever new payments are saved
+    pub fn with_cache_invalidation(
+        mut self,
+        cache_invalidation: Arc<CacheInvalidationService>,
+    ) -> Self {
+        self.cache_invalidation = Some(cache_invalidation);
+        self
     }
 
     /// Run payment ingestion starting from the last saved cursor
@@ -30,7 +52,7 @@ impl IndexingService {
         // Fetch payments from Horizon
         let payments = self
             .rpc_client
-            .fetch_payments(100, last_cursor.as_deref())
+            .fetch_payments_including_failed(100, last_cursor.as_deref())
             .await
             .context("Failed to fetch payments from RPC")?;
 
@@ -42,34 +64,10 @@ impl IndexingService {
         let last_paging_token = payments.last().map(|p| p.paging_token.clone());
 
         // Normalize payments
+        let network = self.rpc_client.network_config().network;
         let records: Vec<PaymentRecord> = payments
             .into_iter()
-            .filter_map(|p| {
-                let amount = p.amount.parse::<f64>().ok()?;
-                let created_at = DateTime::parse_from_rfc3339(&p.created_at)
-                    .ok()?
-                    .with_timezone(&chrono::Utc);
-
-                Some(PaymentRecord {
-                    id: p.id,
-                    transaction_hash: p.transaction_hash,
-                    source_account: p.source_account,
-                    destination_account: p.destination,
-                    asset_type: p.asset_type.clone(),
-                    asset_code: p.asset_code.clone(),
-                    asset_issuer: p.asset_issuer.clone(),
-                    source_asset_code: p.asset_code.clone().unwrap_or_default(),
-                    source_asset_issuer: p.asset_issuer.clone().unwrap_or_default(),
-                    destination_asset_code: p.asset_code.unwrap_or_default(),
-                    destination_asset_issuer: p.asset_issuer.unwrap_or_default(),
-                    amount,
-                    successful: true,
-                    timestamp: Some(created_at),
-                    submission_time: None,
-                    confirmation_time: None,
-                    created_at,
-                })
-            })
+            .filter_map(|p| to_payment_record(p, network))
             .collect();
 
         let count = records.len();
@@ -79,6 +77,9 @@ impl IndexingService {
             .save_payments(records)
             .await
             .context("Failed to save payments to database")?;
+        if count > 0 {
+            self.invalidate_overview().await;
+        }
 
         // Update cursor
         if let Some(cursor) = last_paging_token {
@@ -91,4 +92,110 @@ impl IndexingService {
 
         Ok(())
     }
+
+    /// Consume the Horizon payments stream, persisting each payment as it arrives.
+    ///
+    /// Runs until the stream ends (only in mock mode); the stream cursor is kept
+    /// under its own task name so it can resume after restarts.
+    pub async fn run_payment_stream(&self) -> Result<()> {
+        info!("Starting Horizon payment stream ingestion");
+
+        let network = self.rpc_client.network_config().network;
+        let mut payments = HorizonStream::new(Arc::clone(&self.rpc_client))
+            .with_cursor_store(Arc::clone(&self.db))
+            .payments();
+
+        let mut last_invalidation: Option<Instant> = None;
+        while let Some(item) = payments.next().await {
+            let payment = match item {
+                Ok(payment) => payment,
+                Err(e) => {
+                    debug!("Skipping undecodable stream record: {}", e);
+                    continue;
+                }
+            };
+
+            let Some(record) = to_payment_record(payment, network) else {
+                continue;
+            };
+            if let Err(e) = self.db.save_payments(vec![record]).await {
+                warn!("Failed to save streamed payment: {}", e);
+                continue;
+            }
+            if last_invalidation.is_none_or(|at| at.elapsed() >= STREAM_INVALIDATION_INTERVAL) {
+                self.invalidate_overview().await;
+                last_invalidation = Some(Instant::now());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The overview counts payments and active accounts straight from
+    /// `payments`, so it goes stale as soon as new ones land
+    async fn invalidate_overview(&self) {
+        if let Some(cache_invalidation) = &self.cache_invalidation {
+            if let Err(e) = cache_invalidation.invalidate_metrics_overview().await {
+                warn!("Failed to invalidate metrics overview cache: {}", e);
+            }
+        }
+    }
+}
+
+/// Normalize a Horizon payment from `network` into a `PaymentRecord`, skipping
+/// unparsable ones. Path payments keep the asset they were sent in as the
+/// source leg; plain payments send and receive the same asset.
+fn to_payment_record(p: Payment, network: StellarNetwork) -> Option<PaymentRecord> {
+    let amount = p.get_amount().parse::<f64>().ok()?;
+    let created_at = DateTime::parse_from_rfc3339(&p.created_at)
+        .ok()?
+        .with_timezone(&chrono::Utc);
+
+    let asset_code = p.get_asset_code();
+    let asset_issuer = p.get_asset_issuer();
+    let destination_leg = asset_leg(
+        &p.asset_type,
+        asset_code.as_deref(),
+        asset_issuer.as_deref(),
+    );
+    let source_leg = match &p.source_asset_type {
+        Some(asset_type) if p.is_path_payment() => asset_leg(
+            asset_type,
+            p.source_asset_code.as_deref(),
+            p.source_asset_issuer.as_deref(),
+        ),
+        _ => destination_leg.clone(),
+    };
+
+    Some(PaymentRecord {
+        destination_account: p.get_destination().unwrap_or_default(),
+        id: p.id,
+        transaction_hash: p.transaction_hash,
+        source_account: p.source_account,
+        asset_type: p.asset_type,
+        asset_code,
+        asset_issuer,
+        source_asset_code: source_leg.0,
+        source_asset_issuer: source_leg.1,
+        destination_asset_code: destination_leg.0,
+        destination_asset_issuer: destination_leg.1,
+        amount,
+        successful: p.transaction_successful.unwrap_or(true),
+        timestamp: Some(created_at),
+        submission_time: None,
+        confirmation_time: None,
+        network: Some(network.to_string()),
+        created_at,
+    })
+}
+
+/// Code and issuer of one payment leg; lumens are `XLM` / `native`
+fn asset_leg(asset_type: &str, code: Option<&str>, issuer: Option<&str>) -> (String, String) {
+    if asset_type == "native" {
+        return ("XLM".to_string(), "native".to_string());
+    }
+    (
+        code.unwrap_or_default().to_string(),
+        issuer.unwrap_or_default().to_string(),
+    )
 }
@@ -0,0 +1,685 @@
+//! Large payment ("whale flow") detection.
+//!
+//! Payments are grouped into directional corridors (`SOURCE_ASSET->DESTINATION_ASSET`)
+//! and compared against the corridor's payment size distribution over the
+//! preceding hours. A payment is flagged when it exceeds the absolute threshold
+//! configured for its asset, or when it lies more than the configured number of
+//! standard deviations above the corridor mean. Flagged payments are valued in
+//! USD, stored, pushed to the `large_payments` WebSocket channel and sent as a
+//! `large_payment` webhook.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{FromRow, Pool, Sqlite};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
+use crate::rpc::Payment;
+use crate::services::price_feed::PriceFeedClient;
+use crate::utils::{format_time, horizon_asset_key};
+use crate::webhooks::{WebhookEventType, WebhookService};
+use crate::websocket::{WsMessage, WsState};
+
+/// WebSocket channel receiving every large payment; per-corridor channels are
+/// `large_payments:{corridor_key}`
+pub const LARGE_PAYMENTS_CHANNEL: &str = "large_payments";
+
+#[derive(Debug, Clone)]
+pub struct LargePaymentConfig {
+    /// Absolute thresholds in asset units, keyed by `CODE:ISSUER` (`XLM:native` for lumens)
+    pub asset_thresholds: HashMap<String, f64>,
+    /// Standard deviations above the corridor mean that count as large
+    pub stddev_multiplier: f64,
+    /// Hours of corridor history the distribution is built from
+    pub window_hours: i64,
+    /// Payments a corridor needs in the window before the deviation rule applies
+    pub min_samples: i64,
+}
+
+impl LargePaymentConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            asset_thresholds: std::env::var("LARGE_PAYMENT_THRESHOLDS")
+                .ok()
+                .map(|v| parse_thresholds(&v))
+                .filter(|thresholds| !thresholds.is_empty())
+                .unwrap_or(defaults.asset_thresholds),
+            stddev_multiplier: std::env::var("LARGE_PAYMENT_STDDEV_MULTIPLIER")
+                .ok()
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|v| *v > 0.0)
+                .unwrap_or(defaults.stddev_multiplier),
+            window_hours: std::env::var("LARGE_PAYMENT_WINDOW_HOURS")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| (1..=24 * 30).contains(v))
+                .unwrap_or(defaults.window_hours),
+            min_samples: std::env::var("LARGE_PAYMENT_MIN_SAMPLES")
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|v| *v >= 2)
+                .unwrap_or(defaults.min_samples),
+        }
+    }
+}
+
+impl Default for LargePaymentConfig {
+    fn default() -> Self {
+        Self {
+            asset_thresholds: HashMap::from([
+                ("XLM:native".to_string(), 1_000_000.0),
+                (
+                    "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string(),
+                    250_000.0,
+                ),
+            ]),
+            stddev_multiplier: 3.0,
+            window_hours: 24,
+            min_samples: 30,
+        }
+    }
+}
+
+/// Parse `ASSET=AMOUNT` pairs separated by commas, e.g.
+/// `XLM:native=1000000,USDC:GA5Z...=250000`
+fn parse_thresholds(raw: &str) -> HashMap<String, f64> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (asset, amount) = entry.trim().rsplit_once('=')?;
+            let amount = amount.trim().parse::<f64>().ok().filter(|a| *a > 0.0)?;
+            Some((asset.trim().to_string(), amount))
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LargePaymentReason {
+    AbsoluteThreshold,
+    Deviation,
+}
+
+impl LargePaymentReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AbsoluteThreshold => "absolute_threshold",
+            Self::Deviation => "deviation",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LargePayment {
+    pub payment_id: String,
+    pub corridor_key: String,
+    pub asset: String,
+    pub amount: f64,
+    pub amount_usd: Option<f64>,
+    pub source_account: String,
+    pub destination: String,
+    pub z_score: Option<f64>,
+    pub corridor_mean: Option<f64>,
+    pub corridor_stddev: Option<f64>,
+    pub reasons: String,
+    pub transaction_hash: String,
+    pub ledger_sequence: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LargePaymentFilter {
+    pub corridor_key: Option<String>,
+    pub asset: Option<String>,
+    pub min_amount_usd: Option<f64>,
+    pub limit: i64,
+}
+
+/// Payment size distribution of a corridor for one hour
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorridorPaymentHour {
+    pub hour: String,
+    pub payment_count: i64,
+    pub mean_amount: f64,
+    pub stddev_amount: f64,
+    pub large_payment_count: i64,
+}
+
+/// Running totals from which a mean and standard deviation are derived
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Distribution {
+    count: i64,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Distribution {
+    fn add(&mut self, amount: f64) {
+        self.count += 1;
+        self.sum += amount;
+        self.sum_sq += amount * amount;
+    }
+
+    fn mean(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        self.sum / self.count as f64
+    }
+
+    /// Population standard deviation
+    fn stddev(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+        let mean = self.mean();
+        (self.sum_sq / self.count as f64 - mean * mean)
+            .max(0.0)
+            .sqrt()
+    }
+}
+
+/// A payment reduced to what the detector needs
+#[derive(Debug, Clone, PartialEq)]
+struct Observation {
+    corridor_key: String,
+    asset: String,
+    amount: f64,
+    hour: DateTime<Utc>,
+}
+
+/// Outcome of checking one payment against its thresholds
+#[derive(Debug, Clone, PartialEq)]
+struct Assessment {
+    reasons: Vec<LargePaymentReason>,
+    z_score: Option<f64>,
+    mean: Option<f64>,
+    stddev: Option<f64>,
+}
+
+pub struct LargePaymentDetector {
+    pool: Pool<Sqlite>,
+    price_feed: Arc<PriceFeedClient>,
+    ws_state: Arc<WsState>,
+    config: LargePaymentConfig,
+    webhooks: WebhookService,
+}
+
+impl LargePaymentDetector {
+    pub fn new(
+        pool: Pool<Sqlite>,
+        price_feed: Arc<PriceFeedClient>,
+        ws_state: Arc<WsState>,
+        config: LargePaymentConfig,
+    ) -> Self {
+        Self {
+            webhooks: WebhookService::new(pool.clone()),
+            pool,
+            price_feed,
+            ws_state,
+            config,
+        }
+    }
+
+    /// Check the payments of a ledger against their corridor norms, store the
+    /// large ones and fold the ledger into the hourly totals; returns the
+    /// number of new large payments
+    pub async fn process_payments(
+        &self,
+        ledger_sequence: u64,
+        payments: &[Payment],
+    ) -> Result<u64> {
+        let observed: Vec<(&Payment, Observation)> = payments
+            .iter()
+            .filter_map(|p| observe(p).map(|o| (p, o)))
+            .collect();
+        if observed.is_empty() {
+            return Ok(0);
+        }
+
+        // Every payment is judged against the history before this ledger
+        let mut baselines: HashMap<(String, DateTime<Utc>), Distribution> = HashMap::new();
+        let mut flagged = Vec::new();
+        for (payment, obs) in &observed {
+            let baseline_key = (obs.corridor_key.clone(), obs.hour);
+            let baseline = match baselines.get(&baseline_key) {
+                Some(baseline) => *baseline,
+                None => {
+                    let baseline = self.load_distribution(&obs.corridor_key, obs.hour).await?;
+                    baselines.insert(baseline_key, baseline);
+                    baseline
+                }
+            };
+            let assessment = assess(&self.config, obs, &baseline);
+            if assessment.reasons.is_empty() {
+                continue;
+            }
+            let amount_usd = match self.price_feed.convert_to_usd(&obs.asset, obs.amount).await {
+                Ok(usd) => Some(usd),
+                Err(e) => {
+                    warn!("Could not value large payment {} in USD: {}", payment.id, e);
+                    None
+                }
+            };
+            flagged.push(LargePayment {
+                payment_id: payment.id.clone(),
+                corridor_key: obs.corridor_key.clone(),
+                asset: obs.asset.clone(),
+                amount: obs.amount,
+                amount_usd,
+                source_account: payment
+                    .from
+                    .clone()
+                    .unwrap_or_else(|| payment.source_account.clone()),
+                destination: payment.get_destination().unwrap_or_default(),
+                z_score: assessment.z_score,
+                corridor_mean: assessment.mean,
+                corridor_stddev: assessment.stddev,
+                reasons: assessment
+                    .reasons
+                    .iter()
+                    .map(|r| r.as_str())
+                    .collect::<Vec<_>>()
+                    .join(","),
+                transaction_hash: payment.transaction_hash.clone(),
+                ledger_sequence: ledger_sequence as i64,
+                created_at: payment.created_at.clone(),
+            });
+        }
+
+        let mut tx = self.pool.begin().await?;
+        let first_pass = sqlx::query(
+            "INSERT INTO large_payment_ledgers (ledger_sequence) VALUES ($1) ON CONFLICT (ledger_sequence) DO NOTHING",
+        )
+        .bind(ledger_sequence as i64)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            > 0;
+        if !first_pass {
+            return Ok(0);
+        }
+
+        let mut hourly: HashMap<(String, DateTime<Utc>), Distribution> = HashMap::new();
+        for (_, obs) in &observed {
+            hourly
+                .entry((obs.corridor_key.clone(), obs.hour))
+                .or_default()
+                .add(obs.amount);
+        }
+        for ((corridor_key, hour), totals) in &hourly {
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_payment_hourly_stats (
+                    corridor_key, hour, payment_count, amount_sum, amount_sum_sq
+                )
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (corridor_key, hour) DO UPDATE SET
+                    payment_count = payment_count + excluded.payment_count,
+                    amount_sum = amount_sum + excluded.amount_sum,
+                    amount_sum_sq = amount_sum_sq + excluded.amount_sum_sq
+                "#,
+            )
+            .bind(corridor_key)
+            .bind(format_time(*hour, SecondsFormat::Secs))
+            .bind(totals.count)
+            .bind(totals.sum)
+            .bind(totals.sum_sq)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let mut recorded = Vec::with_capacity(flagged.len());
+        for payment in flagged {
+            let inserted = sqlx::query(
+                r#"
+                INSERT INTO large_payments (
+                    payment_id, corridor_key, asset, amount, amount_usd, source_account,
+                    destination, z_score, corridor_mean, corridor_stddev, reasons,
+                    transaction_hash, ledger_sequence, created_at
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                ON CONFLICT (payment_id) DO NOTHING
+                "#,
+            )
+            .bind(&payment.payment_id)
+            .bind(&payment.corridor_key)
+            .bind(&payment.asset)
+            .bind(payment.amount)
+            .bind(payment.amount_usd)
+            .bind(&payment.source_account)
+            .bind(&payment.destination)
+            .bind(payment.z_score)
+            .bind(payment.corridor_mean)
+            .bind(payment.corridor_stddev)
+            .bind(&payment.reasons)
+            .bind(&payment.transaction_hash)
+            .bind(payment.ledger_sequence)
+            .bind(&payment.created_at)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+            if inserted > 0 {
+                recorded.push(payment);
+            }
+        }
+        tx.commit().await?;
+
+        for payment in &recorded {
+            self.publish(payment).await;
+        }
+        if !recorded.is_empty() {
+            info!(
+                "Flagged {} large payments in ledger {}",
+                recorded.len(),
+                ledger_sequence
+            );
+        }
+        Ok(recorded.len() as u64)
+    }
+
+    /// Totals of the corridor over the window ending with `hour`
+    async fn load_distribution(
+        &self,
+        corridor_key: &str,
+        hour: DateTime<Utc>,
+    ) -> Result<Distribution> {
+        let (count, sum, sum_sq): (i64, f64, f64) = sqlx::query_as(
+            r#"
+            SELECT COALESCE(SUM(payment_count), 0),
+                   COALESCE(SUM(amount_sum), 0.0),
+                   COALESCE(SUM(amount_sum_sq), 0.0)
+            FROM corridor_payment_hourly_stats
+            WHERE corridor_key = $1 AND hour >= $2 AND hour <= $3
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(format_time(
+            hour - Duration::hours(self.config.window_hours),
+            SecondsFormat::Secs,
+        ))
+        .bind(format_time(hour, SecondsFormat::Secs))
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(Distribution { count, sum, sum_sq })
+    }
+
+    async fn publish(&self, payment: &LargePayment) {
+        let message = WsMessage::LargePayment {
+            payment_id: payment.payment_id.clone(),
+            corridor_key: payment.corridor_key.clone(),
+            asset: payment.asset.clone(),
+            amount: payment.amount,
+            amount_usd: payment.amount_usd,
+            z_score: payment.z_score,
+            reasons: payment.reasons.split(',').map(str::to_string).collect(),
+            timestamp: payment.created_at.clone(),
+        };
+        self.ws_state
+            .broadcast_to_channel(LARGE_PAYMENTS_CHANNEL, message.clone())
+            .await;
+        self.ws_state
+            .broadcast_to_channel(
+                &format!("{}:{}", LARGE_PAYMENTS_CHANNEL, payment.corridor_key),
+                message,
+            )
+            .await;
+
+        let payload = json!({
+            "payment_id": payment.payment_id,
+            "corridor_key": payment.corridor_key,
+            "asset": payment.asset,
+            "amount": payment.amount,
+            "amount_usd": payment.amount_usd,
+            "source_account": payment.source_account,
+            "destination": payment.destination,
+            "z_score": payment.z_score,
+            "reasons": payment.reasons,
+            "transaction_hash": payment.transaction_hash,
+            "ledger_sequence": payment.ledger_sequence,
+        });
+        if let Err(e) = self
+            .webhooks
+            .trigger_event(WebhookEventType::LargePayment, payload)
+            .await
+        {
+            warn!("Failed to queue large payment webhook: {}", e);
+        }
+    }
+
+    // ========================================================================
+    // Query Methods
+    // ========================================================================
+
+    /// Large payments matching the filter, most recent first
+    pub async fn get_large_payments(
+        &self,
+        filter: &LargePaymentFilter,
+    ) -> Result<Vec<LargePayment>> {
+        let payments = sqlx::query_as::<_, LargePayment>(
+            r#"
+            SELECT payment_id, corridor_key, asset, amount, amount_usd, source_account,
+                   destination, z_score, corridor_mean, corridor_stddev, reasons,
+                   transaction_hash, ledger_sequence, created_at
+            FROM large_payments
+            WHERE ($1 IS NULL OR corridor_key = $1)
+              AND ($2 IS NULL OR asset = $2)
+              AND ($3 IS NULL OR amount_usd >= $3)
+            ORDER BY created_at DESC, payment_id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(&filter.corridor_key)
+        .bind(&filter.asset)
+        .bind(filter.min_amount_usd)
+        .bind(filter.limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(payments)
+    }
+
+    /// Hourly payment size distribution of a corridor over the last `hours`
+    pub async fn get_corridor_distribution(
+        &self,
+        corridor_key: &str,
+        hours: i64,
+    ) -> Result<Vec<CorridorPaymentHour>> {
+        let since = format_time(Utc::now() - Duration::hours(hours), SecondsFormat::Secs);
+        let rows: Vec<(String, i64, f64, f64, i64)> = sqlx::query_as(
+            r#"
+            SELECT s.hour, s.payment_count, s.amount_sum, s.amount_sum_sq,
+                   (SELECT COUNT(*) FROM large_payments l
+                    WHERE l.corridor_key = s.corridor_key
+                      AND substr(l.created_at, 1, 13) = substr(s.hour, 1, 13))
+            FROM corridor_payment_hourly_stats s
+            WHERE s.corridor_key = $1 AND s.hour >= $2
+            ORDER BY s.hour ASC
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(hour, count, sum, sum_sq, large_payment_count)| {
+                let totals = Distribution { count, sum, sum_sq };
+                CorridorPaymentHour {
+                    hour,
+                    payment_count: count,
+                    mean_amount: totals.mean(),
+                    stddev_amount: totals.stddev(),
+                    large_payment_count,
+                }
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl LedgerProcessor for LargePaymentDetector {
+    fn name(&self) -> &'static str {
+        "large_payments"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Payments]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+        self.process_payments(bundle.sequence, &bundle.payments)
+            .await
+    }
+}
+
+/// Corridor, destination asset, amount and hour of a payment; `None` for
+/// payments without a positive amount or a parseable timestamp
+fn observe(p: &Payment) -> Option<Observation> {
+    let amount = p.get_amount().parse::<f64>().ok().filter(|a| *a > 0.0)?;
+    let time = DateTime::parse_from_rfc3339(&p.created_at)
+        .ok()?
+        .with_timezone(&Utc);
+    let hour = time
+        .with_minute(0)
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))?;
+
+    let asset = horizon_asset_key(
+        &p.asset_type,
+        p.get_asset_code().as_deref(),
+        p.get_asset_issuer().as_deref(),
+    );
+    let source_asset = match &p.source_asset_type {
+        Some(asset_type) if p.is_path_payment() => horizon_asset_key(
+            asset_type,
+            p.source_asset_code.as_deref(),
+            p.source_asset_issuer.as_deref(),
+        ),
+        _ => asset.clone(),
+    };
+
+    Some(Observation {
+        corridor_key: format!("{}->{}", source_asset, asset),
+        asset,
+        amount,
+        hour,
+    })
+}
+
+fn assess(config: &LargePaymentConfig, obs: &Observation, baseline: &Distribution) -> Assessment {
+    let mut reasons = Vec::new();
+    if config
+        .asset_thresholds
+        .get(&obs.asset)
+        .is_some_and(|threshold| obs.amount >= *threshold)
+    {
+        reasons.push(LargePaymentReason::AbsoluteThreshold);
+    }
+
+    let (mut z_score, mut mean, mut stddev) = (None, None, None);
+    if baseline.count >= config.min_samples {
+        let (m, s) = (baseline.mean(), baseline.stddev());
+        mean = Some(m);
+        stddev = Some(s);
+        if s > 0.0 {
+            let z = (obs.amount - m) / s;
+            z_score = Some(z);
+            if z >= config.stddev_multiplier {
+                reasons.push(LargePaymentReason::Deviation);
+            }
+        }
+    }
+
+    Assessment {
+        reasons,
+        z_score,
+        mean,
+        stddev,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observation(asset: &str, amount: f64) -> Observation {
+        Observation {
+            corridor_key: format!("{}->{}", asset, asset),
+            asset: asset.to_string(),
+            amount,
+            hour: Utc::now(),
+        }
+    }
+
+    fn distribution(amounts: &[f64]) -> Distribution {
+        let mut d = Distribution::default();
+        for a in amounts {
+            d.add(*a);
+        }
+        d
+    }
+
+    #[test]
+    fn test_parse_thresholds() {
+        let thresholds = parse_thresholds("XLM:native=1000000, USDC:GISSUER=250000,bad,EURC:G=-1");
+        assert_eq!(thresholds.len(), 2);
+        assert_eq!(thresholds["XLM:native"], 1_000_000.0);
+        assert_eq!(thresholds["USDC:GISSUER"], 250_000.0);
+    }
+
+    #[test]
+    fn test_distribution_mean_and_stddev() {
+        let d = distribution(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        assert_eq!(d.mean(), 5.0);
+        assert!((d.stddev() - 2.0).abs() < 1e-9);
+        assert_eq!(Distribution::default().stddev(), 0.0);
+    }
+
+    #[test]
+    fn test_assess_absolute_threshold() {
+        let config = LargePaymentConfig::default();
+        let big = assess(
+            &config,
+            &observation("XLM:native", 2_000_000.0),
+            &Distribution::default(),
+        );
+        assert_eq!(big.reasons, vec![LargePaymentReason::AbsoluteThreshold]);
+        assert_eq!(big.z_score, None);
+
+        let small = assess(
+            &config,
+            &observation("XLM:native", 10.0),
+            &Distribution::default(),
+        );
+        assert!(small.reasons.is_empty());
+    }
+
+    #[test]
+    fn test_assess_deviation_needs_enough_samples() {
+        let config = LargePaymentConfig {
+            min_samples: 4,
+            ..Default::default()
+        };
+        let obs = observation("EURC:GISSUER", 100.0);
+
+        let sparse = assess(&config, &obs, &distribution(&[10.0, 12.0]));
+        assert!(sparse.reasons.is_empty());
+        assert_eq!(sparse.mean, None);
+
+        let baseline = distribution(&[10.0, 12.0, 8.0, 10.0]);
+        let flagged = assess(&config, &obs, &baseline);
+        assert_eq!(flagged.reasons, vec![LargePaymentReason::Deviation]);
+        assert_eq!(flagged.mean, Some(10.0));
+        assert!(flagged.z_score.unwrap() > 3.0);
+
+        let normal = assess(&config, &observation("EURC:GISSUER", 11.0), &baseline);
+        assert!(normal.reasons.is_empty());
+        assert!(normal.z_score.is_some());
+    }
+}
@@ -0,0 +1,352 @@
+//! Network overview for the dashboard's first panel.
+//!
+//! Every KPI is computed for the requested window and for the window before
+//! it, so each one carries its period-over-period change:
+//!
+//! - volume, transactions, success rate and active corridors from
+//!   `corridor_metrics_hourly`
+//! - active accounts from `payments` on the configured network
+//! - active anchors from `anchor_metrics_history`
+//! - pool TVL from the latest `liquidity_pool_snapshots` row of each pool
+//!
+//! Payments are also broken down by the network they were ingested from;
+//! rows stored before payments were tagged count under the configured one.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::database::Database;
+use crate::network::StellarNetwork;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OverviewWindow {
+    #[serde(rename = "24h")]
+    Day,
+    #[serde(rename = "7d")]
+    Week,
+    #[serde(rename = "30d")]
+    Month,
+}
+
+impl OverviewWindow {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OverviewWindow::Day => "24h",
+            OverviewWindow::Week => "7d",
+            OverviewWindow::Month => "30d",
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            OverviewWindow::Day => Duration::hours(24),
+            OverviewWindow::Week => Duration::days(7),
+            OverviewWindow::Month => Duration::days(30),
+        }
+    }
+}
+
+impl std::str::FromStr for OverviewWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "24h" | "1d" => Ok(OverviewWindow::Day),
+            "7d" => Ok(OverviewWindow::Week),
+            "30d" => Ok(OverviewWindow::Month),
+            _ => Err(format!(
+                "Invalid window: {}. Must be '24h', '7d' or '30d'",
+                s
+            )),
+        }
+    }
+}
+
+/// A KPI over the window and over the window before it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricDelta {
+    pub current: f64,
+    pub previous: f64,
+    pub change: f64,
+    /// None when the previous window was zero
+    pub change_pct: Option<f64>,
+}
+
+impl MetricDelta {
+    pub fn new(current: f64, previous: f64) -> Self {
+        let change_pct = if previous != 0.0 {
+            Some(round2((current - previous) / previous.abs() * 100.0))
+        } else {
+            None
+        };
+        Self {
+            current: round2(current),
+            previous: round2(previous),
+            change: round2(current - previous),
+            change_pct,
+        }
+    }
+}
+
+/// Payment activity ingested from one network
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkBreakdown {
+    pub network: String,
+    pub payments: MetricDelta,
+    pub active_users: MetricDelta,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsOverview {
+    /// Network the backend ingests; the top-level KPIs cover this one
+    pub network: StellarNetwork,
+    pub window: OverviewWindow,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// USD
+    pub total_volume: MetricDelta,
+    pub total_transactions: MetricDelta,
+    /// Percent; `change` is in percentage points
+    pub success_rate: MetricDelta,
+    /// USD per transaction
+    pub average_transaction_value: MetricDelta,
+    /// Distinct payment sources and destinations
+    pub active_users: MetricDelta,
+    /// Corridors with at least one transaction
+    pub corridor_count: MetricDelta,
+    /// Anchors with transactions recorded in their metrics history
+    pub active_anchors: MetricDelta,
+    /// USD in liquidity pools at the latest snapshot of the window
+    pub liquidity_pool_tvl: MetricDelta,
+    pub networks: Vec<NetworkBreakdown>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Raw totals of one window
+#[derive(Debug, Default, Clone)]
+struct PeriodTotals {
+    volume_usd: f64,
+    transactions: i64,
+    successful_transactions: i64,
+    corridors: i64,
+    anchors: i64,
+    pool_tvl_usd: f64,
+    /// Payments and distinct accounts per network
+    networks: BTreeMap<String, (i64, i64)>,
+}
+
+impl PeriodTotals {
+    fn success_rate(&self) -> f64 {
+        if self.transactions > 0 {
+            self.successful_transactions as f64 / self.transactions as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    fn average_transaction_value(&self) -> f64 {
+        if self.transactions > 0 {
+            self.volume_usd / self.transactions as f64
+        } else {
+            0.0
+        }
+    }
+
+    fn network(&self, network: &str) -> (i64, i64) {
+        self.networks.get(network).copied().unwrap_or_default()
+    }
+}
+
+pub struct MetricsOverviewService {
+    db: Arc<Database>,
+    network: StellarNetwork,
+}
+
+impl MetricsOverviewService {
+    pub fn new(db: Arc<Database>, network: StellarNetwork) -> Self {
+        Self { db, network }
+    }
+
+    pub async fn overview(&self, window: OverviewWindow) -> Result<MetricsOverview> {
+        self.overview_at(window, Utc::now()).await
+    }
+
+    pub async fn overview_at(
+        &self,
+        window: OverviewWindow,
+        now: DateTime<Utc>,
+    ) -> Result<MetricsOverview> {
+        let period_start = now - window.duration();
+        let previous_start = period_start - window.duration();
+        let current = self.period_totals(period_start, now).await?;
+        let previous = self.period_totals(previous_start, period_start).await?;
+
+        let mut networks: Vec<String> = current
+            .networks
+            .keys()
+            .chain(previous.networks.keys())
+            .cloned()
+            .collect();
+        networks.sort();
+        networks.dedup();
+        let configured = self.network.to_string();
+
+        Ok(MetricsOverview {
+            network: self.network,
+            window,
+            period_start,
+            period_end: now,
+            total_volume: MetricDelta::new(current.volume_usd, previous.volume_usd),
+            total_transactions: MetricDelta::new(
+                current.transactions as f64,
+                previous.transactions as f64,
+            ),
+            success_rate: MetricDelta::new(current.success_rate(), previous.success_rate()),
+            average_transaction_value: MetricDelta::new(
+                current.average_transaction_value(),
+                previous.average_transaction_value(),
+            ),
+            active_users: MetricDelta::new(
+                current.network(&configured).1 as f64,
+                previous.network(&configured).1 as f64,
+            ),
+            corridor_count: MetricDelta::new(current.corridors as f64, previous.corridors as f64),
+            active_anchors: MetricDelta::new(current.anchors as f64, previous.anchors as f64),
+            liquidity_pool_tvl: MetricDelta::new(current.pool_tvl_usd, previous.pool_tvl_usd),
+            networks: networks
+                .into_iter()
+                .map(|network| {
+                    let (payments, accounts) = current.network(&network);
+                    let (previous_payments, previous_accounts) = previous.network(&network);
+                    NetworkBreakdown {
+                        payments: MetricDelta::new(payments as f64, previous_payments as f64),
+                        active_users: MetricDelta::new(accounts as f64, previous_accounts as f64),
+                        network,
+                    }
+                })
+                .collect(),
+            generated_at: Utc::now(),
+        })
+    }
+
+    async fn period_totals(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<PeriodTotals> {
+        let pool = self.db.pool();
+        let start = start.to_rfc3339();
+        let end = end.to_rfc3339();
+        let configured = self.network.to_string();
+
+        let (volume_usd, transactions, successful_transactions, corridors): (f64, i64, i64, i64) =
+            sqlx::query_as(
+                r#"
+            SELECT COALESCE(SUM(volume_usd), 0.0),
+                   COALESCE(SUM(total_transactions), 0),
+                   COALESCE(SUM(successful_transactions), 0),
+                   COUNT(DISTINCT CASE WHEN total_transactions > 0 THEN corridor_key END)
+            FROM corridor_metrics_hourly
+            WHERE hour_bucket >= $1 AND hour_bucket < $2
+            "#,
+            )
+            .bind(&start)
+            .bind(&end)
+            .fetch_one(pool)
+            .await?;
+
+        let payment_rows: Vec<(String, i64, i64)> = sqlx::query_as(
+            r#"
+            SELECT network, COUNT(DISTINCT id), COUNT(DISTINCT account)
+            FROM (
+                SELECT COALESCE(network, $3) AS network, id, source_account AS account
+                FROM payments
+                WHERE created_at >= $1 AND created_at < $2
+                UNION ALL
+                SELECT COALESCE(network, $3) AS network, id, destination_account AS account
+                FROM payments
+                WHERE created_at >= $1 AND created_at < $2
+            )
+            GROUP BY network
+            "#,
+        )
+        .bind(&start)
+        .bind(&end)
+        .bind(&configured)
+        .fetch_all(pool)
+        .await?;
+
+        let anchors: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(DISTINCT anchor_id)
+            FROM anchor_metrics_history
+            WHERE timestamp >= $1 AND timestamp < $2 AND total_transactions > 0
+            "#,
+        )
+        .bind(&start)
+        .bind(&end)
+        .fetch_one(pool)
+        .await?;
+
+        let pool_tvl_usd: f64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(s.total_value_usd), 0.0)
+            FROM liquidity_pool_snapshots s
+            WHERE s.snapshot_at = (
+                SELECT MAX(snapshot_at)
+                FROM liquidity_pool_snapshots
+                WHERE pool_id = s.pool_id AND snapshot_at >= $1 AND snapshot_at < $2
+            )
+            "#,
+        )
+        .bind(&start)
+        .bind(&end)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(PeriodTotals {
+            volume_usd,
+            transactions,
+            successful_transactions,
+            corridors,
+            anchors,
+            pool_tvl_usd,
+            networks: payment_rows
+                .into_iter()
+                .map(|(network, payments, accounts)| (network, (payments, accounts)))
+                .collect(),
+        })
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_delta() {
+        let delta = MetricDelta::new(150.0, 100.0);
+        assert_eq!(delta.change, 50.0);
+        assert_eq!(delta.change_pct, Some(50.0));
+
+        let delta = MetricDelta::new(25.0, 0.0);
+        assert_eq!(delta.change, 25.0);
+        assert_eq!(delta.change_pct, None);
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!("24h".parse::<OverviewWindow>(), Ok(OverviewWindow::Day));
+        assert_eq!("7D".parse::<OverviewWindow>(), Ok(OverviewWindow::Week));
+        assert_eq!("30d".parse::<OverviewWindow>(), Ok(OverviewWindow::Month));
+        assert!("1y".parse::<OverviewWindow>().is_err());
+        assert_eq!(OverviewWindow::Week.duration(), Duration::days(7));
+    }
+}
@@ -1,22 +1,36 @@
 pub mod account_merge_detector;
+pub mod account_profile;
 pub mod aggregation;
 pub mod alert_manager;
 pub mod alert_service;
 pub mod analytics;
 pub mod anchor_monitor;
+pub mod anchor_transfer_tracker;
+pub mod anomaly_detector;
+pub mod asset_authorization;
+pub mod asset_supply;
 pub mod asset_verifier;
+pub mod claimable_balance_tracker;
 pub mod contract;
 pub mod contract_listener;
+pub mod corridor_forecast;
+pub mod corridor_health;
 pub mod event_indexer;
 pub mod fee_bump_tracker;
+pub mod fee_market;
 pub mod governance;
 pub mod indexing;
+pub mod large_payment_detector;
 pub mod liquidity_pool_analyzer;
+pub mod metrics_overview;
+pub mod order_book_snapshot;
 pub mod price_feed;
 pub mod realtime_broadcaster;
 pub mod slack_bot;
 pub mod snapshot;
+pub mod sponsorship_tracker;
 pub mod stellar_toml;
+pub mod trade_aggregator;
 pub mod trustline_analyzer;
 pub mod verification_rewards;
 pub mod webhook_dispatcher;
@@ -0,0 +1,604 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Pool, Sqlite};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use crate::rpc::{Asset, OrderBook, StellarRpcClient};
+
+/// Relative change between the first and second half of a depth series that
+/// counts as a trend
+const DEPTH_TREND_THRESHOLD: f64 = 0.10;
+
+#[derive(Debug, Clone)]
+pub struct OrderBookSnapshotConfig {
+    /// Slippage bands (percent from mid price) at which depth is recorded
+    pub slippage_bands_pct: Vec<f64>,
+    /// Price levels requested per side
+    pub depth_limit: u32,
+    pub retention_days: i64,
+}
+
+impl OrderBookSnapshotConfig {
+    pub fn from_env() -> Self {
+        let slippage_bands_pct = std::env::var("ORDER_BOOK_SLIPPAGE_BANDS")
+            .ok()
+            .map(|bands| {
+                bands
+                    .split(',')
+                    .filter_map(|b| b.trim().parse::<f64>().ok())
+                    .filter(|b| *b > 0.0)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|bands| !bands.is_empty())
+            .unwrap_or_else(|| vec![0.5, 1.0, 2.0, 5.0]);
+
+        Self {
+            slippage_bands_pct,
+            depth_limit: std::env::var("ORDER_BOOK_DEPTH_LIMIT")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(50),
+            retention_days: std::env::var("ORDER_BOOK_RETENTION_DAYS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(30),
+        }
+    }
+}
+
+impl Default for OrderBookSnapshotConfig {
+    fn default() -> Self {
+        Self {
+            slippage_bands_pct: vec![0.5, 1.0, 2.0, 5.0],
+            depth_limit: 50,
+            retention_days: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceLevel {
+    pub price: f64,
+    /// Amount in selling (base) asset units
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct DepthBand {
+    pub slippage_pct: f64,
+    pub bid_depth: f64,
+    pub ask_depth: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderBookSnapshotRecord {
+    pub pair_key: String,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub mid_price: Option<f64>,
+    pub spread: Option<f64>,
+    pub spread_bps: Option<f64>,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+    pub depth: Vec<DepthBand>,
+    pub snapshot_at: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct SnapshotRow {
+    id: i64,
+    pair_key: String,
+    best_bid: Option<f64>,
+    best_ask: Option<f64>,
+    mid_price: Option<f64>,
+    spread: Option<f64>,
+    spread_bps: Option<f64>,
+    bids: String,
+    asks: String,
+    snapshot_at: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct DepthSeriesRow {
+    id: i64,
+    snapshot_at: String,
+    mid_price: Option<f64>,
+    spread_bps: Option<f64>,
+    #[sqlx(flatten)]
+    band: DepthBand,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DepthSeriesPoint {
+    pub snapshot_at: String,
+    pub mid_price: Option<f64>,
+    pub spread_bps: Option<f64>,
+    pub depth: Vec<DepthBand>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DepthSeries {
+    pub pair_key: String,
+    pub points: Vec<DepthSeriesPoint>,
+    /// Band used for `depth_trend`
+    pub trend_slippage_pct: Option<f64>,
+    /// "increasing" | "stable" | "decreasing", `None` with fewer than two points
+    pub depth_trend: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct TrackedPair {
+    pub pair_key: String,
+    pub snapshot_count: i64,
+    pub latest_snapshot_at: String,
+    pub latest_spread_bps: Option<f64>,
+}
+
+/// Periodically snapshots the order books of tracked corridor pairs
+pub struct OrderBookSnapshotService {
+    pool: Pool<Sqlite>,
+    rpc_client: Arc<StellarRpcClient>,
+    config: OrderBookSnapshotConfig,
+}
+
+impl OrderBookSnapshotService {
+    pub fn new(
+        pool: Pool<Sqlite>,
+        rpc_client: Arc<StellarRpcClient>,
+        config: OrderBookSnapshotConfig,
+    ) -> Self {
+        Self {
+            pool,
+            rpc_client,
+            config,
+        }
+    }
+
+    /// Snapshot every tracked corridor pair and prune expired snapshots;
+    /// returns the number of snapshots stored
+    pub async fn snapshot_all(&self) -> Result<u64> {
+        let pairs: Vec<(String, String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT source_asset_code, source_asset_issuer,
+                   destination_asset_code, destination_asset_issuer
+            FROM corridors
+            WHERE status = 'active'
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let now = Utc::now();
+        let mut stored = 0_u64;
+        for (source_code, source_issuer, dest_code, dest_issuer) in pairs {
+            // Same-asset corridors have no order book
+            if source_code == dest_code && source_issuer == dest_issuer {
+                continue;
+            }
+
+            let selling = to_asset(&source_code, &source_issuer);
+            let buying = to_asset(&dest_code, &dest_issuer);
+            let pair_key = format!(
+                "{}:{}->{}:{}",
+                source_code, source_issuer, dest_code, dest_issuer
+            );
+
+            match self
+                .rpc_client
+                .fetch_order_book(&selling, &buying, self.config.depth_limit)
+                .await
+            {
+                Ok(order_book) => {
+                    self.record_snapshot(&pair_key, &order_book, now).await?;
+                    stored += 1;
+                }
+                Err(e) => warn!("Failed to fetch order book for {}: {}", pair_key, e),
+            }
+        }
+
+        let pruned = self.prune(now).await?;
+        info!(
+            "Stored {} order book snapshots, pruned {} expired",
+            stored, pruned
+        );
+        Ok(stored)
+    }
+
+    pub async fn record_snapshot(
+        &self,
+        pair_key: &str,
+        order_book: &OrderBook,
+        snapshot_at: DateTime<Utc>,
+    ) -> Result<OrderBookSnapshotRecord> {
+        let record = build_snapshot(
+            pair_key,
+            order_book,
+            &self.config.slippage_bands_pct,
+            snapshot_at,
+        );
+
+        let mut tx = self.pool.begin().await?;
+        let snapshot_id = sqlx::query(
+            r#"
+            INSERT INTO order_book_snapshots (
+                pair_key, best_bid, best_ask, mid_price, spread, spread_bps,
+                bid_levels, ask_levels, bids, asks, snapshot_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(&record.pair_key)
+        .bind(record.best_bid)
+        .bind(record.best_ask)
+        .bind(record.mid_price)
+        .bind(record.spread)
+        .bind(record.spread_bps)
+        .bind(record.bids.len() as i64)
+        .bind(record.asks.len() as i64)
+        .bind(serde_json::to_string(&record.bids)?)
+        .bind(serde_json::to_string(&record.asks)?)
+        .bind(&record.snapshot_at)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+
+        for band in &record.depth {
+            sqlx::query(
+                r#"
+                INSERT INTO order_book_depth (snapshot_id, slippage_pct, bid_depth, ask_depth)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(snapshot_id)
+            .bind(band.slippage_pct)
+            .bind(band.bid_depth)
+            .bind(band.ask_depth)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(record)
+    }
+
+    async fn prune(&self, now: DateTime<Utc>) -> Result<u64> {
+        let cutoff = (now - Duration::days(self.config.retention_days)).to_rfc3339();
+        sqlx::query(
+            r#"
+            DELETE FROM order_book_depth
+            WHERE snapshot_id IN (SELECT id FROM order_book_snapshots WHERE snapshot_at < $1)
+            "#,
+        )
+        .bind(&cutoff)
+        .execute(&self.pool)
+        .await?;
+        let result = sqlx::query("DELETE FROM order_book_snapshots WHERE snapshot_at < $1")
+            .bind(&cutoff)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Pairs with stored snapshots and their latest spread
+    pub async fn get_tracked_pairs(&self) -> Result<Vec<TrackedPair>> {
+        let pairs = sqlx::query_as(
+            r#"
+            SELECT s.pair_key, counts.snapshot_count,
+                   s.snapshot_at AS latest_snapshot_at, s.spread_bps AS latest_spread_bps
+            FROM order_book_snapshots s
+            JOIN (
+                SELECT pair_key, COUNT(*) AS snapshot_count, MAX(id) AS latest_id
+                FROM order_book_snapshots
+                GROUP BY pair_key
+            ) counts ON counts.latest_id = s.id
+            ORDER BY s.pair_key
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(pairs)
+    }
+
+    pub async fn get_latest_snapshot(
+        &self,
+        pair_key: &str,
+    ) -> Result<Option<OrderBookSnapshotRecord>> {
+        let row: Option<SnapshotRow> = sqlx::query_as(
+            r#"
+            SELECT id, pair_key, best_bid, best_ask, mid_price, spread, spread_bps,
+                   bids, asks, snapshot_at
+            FROM order_book_snapshots
+            WHERE pair_key = $1
+            ORDER BY snapshot_at DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(pair_key)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let depth = self.depth_for_snapshot(row.id).await?;
+        Ok(Some(OrderBookSnapshotRecord {
+            pair_key: row.pair_key,
+            best_bid: row.best_bid,
+            best_ask: row.best_ask,
+            mid_price: row.mid_price,
+            spread: row.spread,
+            spread_bps: row.spread_bps,
+            bids: serde_json::from_str(&row.bids).unwrap_or_default(),
+            asks: serde_json::from_str(&row.asks).unwrap_or_default(),
+            depth,
+            snapshot_at: row.snapshot_at,
+        }))
+    }
+
+    async fn depth_for_snapshot(&self, snapshot_id: i64) -> Result<Vec<DepthBand>> {
+        let depth = sqlx::query_as(
+            r#"
+            SELECT slippage_pct, bid_depth, ask_depth
+            FROM order_book_depth
+            WHERE snapshot_id = $1
+            ORDER BY slippage_pct
+            "#,
+        )
+        .bind(snapshot_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(depth)
+    }
+
+    /// Depth and spread time series for a pair since `since`, oldest first.
+    /// The trend is computed on `trend_slippage_pct`, defaulting to the
+    /// narrowest configured band.
+    pub async fn get_depth_series(
+        &self,
+        pair_key: &str,
+        since: DateTime<Utc>,
+        trend_slippage_pct: Option<f64>,
+    ) -> Result<DepthSeries> {
+        let rows: Vec<DepthSeriesRow> = sqlx::query_as(
+            r#"
+            SELECT s.id, s.snapshot_at, s.mid_price, s.spread_bps,
+                   d.slippage_pct, d.bid_depth, d.ask_depth
+            FROM order_book_snapshots s
+            JOIN order_book_depth d ON d.snapshot_id = s.id
+            WHERE s.pair_key = $1 AND s.snapshot_at >= $2
+            ORDER BY s.snapshot_at, s.id, d.slippage_pct
+            "#,
+        )
+        .bind(pair_key)
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut points: Vec<DepthSeriesPoint> = Vec::new();
+        let mut last_id = None;
+        for row in rows {
+            match points.last_mut().filter(|_| last_id == Some(row.id)) {
+                Some(point) => point.depth.push(row.band),
+                None => points.push(DepthSeriesPoint {
+                    snapshot_at: row.snapshot_at,
+                    mid_price: row.mid_price,
+                    spread_bps: row.spread_bps,
+                    depth: vec![row.band],
+                }),
+            }
+            last_id = Some(row.id);
+        }
+
+        let trend_band = trend_slippage_pct.or_else(|| {
+            self.config
+                .slippage_bands_pct
+                .iter()
+                .copied()
+                .reduce(f64::min)
+        });
+        let depth_trend = trend_band.and_then(|band| {
+            let totals: Vec<f64> = points
+                .iter()
+                .filter_map(|p| {
+                    p.depth
+                        .iter()
+                        .find(|d| (d.slippage_pct - band).abs() < f64::EPSILON)
+                        .map(|d| d.bid_depth + d.ask_depth)
+                })
+                .collect();
+            depth_trend(&totals)
+        });
+
+        Ok(DepthSeries {
+            pair_key: pair_key.to_string(),
+            points,
+            trend_slippage_pct: trend_band,
+            depth_trend,
+        })
+    }
+}
+
+/// Corridor assets use `XLM:native` for lumens
+fn to_asset(code: &str, issuer: &str) -> Asset {
+    if issuer == "native" || (code == "XLM" && issuer.is_empty()) {
+        return Asset {
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+        };
+    }
+
+    let asset_type = if code.len() <= 4 {
+        "credit_alphanum4"
+    } else {
+        "credit_alphanum12"
+    };
+    Asset {
+        asset_type: asset_type.to_string(),
+        asset_code: Some(code.to_string()),
+        asset_issuer: Some(issuer.to_string()),
+    }
+}
+
+/// Horizon quotes bid amounts in the buying (counter) asset; they are
+/// converted to base units so both sides are comparable
+fn build_snapshot(
+    pair_key: &str,
+    order_book: &OrderBook,
+    slippage_bands_pct: &[f64],
+    snapshot_at: DateTime<Utc>,
+) -> OrderBookSnapshotRecord {
+    let parse = |price: &str, amount: &str| -> Option<(f64, f64)> {
+        let price = price.parse::<f64>().ok().filter(|p| *p > 0.0)?;
+        Some((price, amount.parse::<f64>().ok()?))
+    };
+    let bids: Vec<PriceLevel> = order_book
+        .bids
+        .iter()
+        .filter_map(|b| parse(&b.price, &b.amount))
+        .map(|(price, amount)| PriceLevel {
+            price,
+            amount: amount / price,
+        })
+        .collect();
+    let asks: Vec<PriceLevel> = order_book
+        .asks
+        .iter()
+        .filter_map(|a| parse(&a.price, &a.amount))
+        .map(|(price, amount)| PriceLevel { price, amount })
+        .collect();
+
+    let best_bid = bids.first().map(|b| b.price);
+    let best_ask = asks.first().map(|a| a.price);
+    let (mid_price, spread, spread_bps) = match (best_bid, best_ask) {
+        (Some(bid), Some(ask)) => {
+            let mid = (bid + ask) / 2.0;
+            let spread = ask - bid;
+            (Some(mid), Some(spread), Some(spread / mid * 10_000.0))
+        }
+        _ => (None, None, None),
+    };
+
+    let depth = match mid_price {
+        Some(mid) => slippage_bands_pct
+            .iter()
+            .map(|pct| depth_within(&bids, &asks, mid, *pct))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    OrderBookSnapshotRecord {
+        pair_key: pair_key.to_string(),
+        best_bid,
+        best_ask,
+        mid_price,
+        spread,
+        spread_bps,
+        bids,
+        asks,
+        depth,
+        snapshot_at: snapshot_at.to_rfc3339(),
+    }
+}
+
+fn depth_within(
+    bids: &[PriceLevel],
+    asks: &[PriceLevel],
+    mid: f64,
+    slippage_pct: f64,
+) -> DepthBand {
+    let max_ask = mid * (1.0 + slippage_pct / 100.0);
+    let min_bid = mid * (1.0 - slippage_pct / 100.0);
+
+    DepthBand {
+        slippage_pct,
+        bid_depth: bids
+            .iter()
+            .take_while(|b| b.price >= min_bid)
+            .map(|b| b.amount)
+            .sum(),
+        ask_depth: asks
+            .iter()
+            .take_while(|a| a.price <= max_ask)
+            .map(|a| a.amount)
+            .sum(),
+    }
+}
+
+/// Compares mean depth of the first and second half of the series
+fn depth_trend(totals: &[f64]) -> Option<String> {
+    if totals.len() < 2 {
+        return None;
+    }
+    let (first, second) = totals.split_at(totals.len() / 2);
+    let mean = |values: &[f64]| values.iter().sum::<f64>() / values.len() as f64;
+    let (before, after) = (mean(first), mean(second));
+
+    let trend = if before == 0.0 {
+        if after > 0.0 {
+            "increasing"
+        } else {
+            "stable"
+        }
+    } else {
+        let change = (after - before) / before;
+        if change > DEPTH_TREND_THRESHOLD {
+            "increasing"
+        } else if change < -DEPTH_TREND_THRESHOLD {
+            "decreasing"
+        } else {
+            "stable"
+        }
+    };
+    Some(trend.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_book() -> OrderBook {
+        let asset = to_asset("XLM", "native");
+        StellarRpcClient::mock_order_book(&asset, &asset)
+    }
+
+    #[test]
+    fn test_build_snapshot_spread_and_depth() {
+        let snapshot = build_snapshot("A->B", &mock_book(), &[0.6, 1.1], Utc::now());
+
+        assert_eq!(snapshot.best_bid, Some(0.995));
+        assert_eq!(snapshot.best_ask, Some(1.005));
+        assert!((snapshot.spread_bps.unwrap() - 100.0).abs() < 1e-6);
+
+        // 0.6% band reaches the best level on each side, 1.1% the second too
+        assert!((snapshot.depth[0].bid_depth - 1000.0 / 0.995).abs() < 1e-6);
+        assert!((snapshot.depth[0].ask_depth - 1200.0).abs() < 1e-6);
+        assert!((snapshot.depth[1].ask_depth - 4200.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_depth_trend() {
+        assert_eq!(depth_trend(&[100.0]), None);
+        assert_eq!(
+            depth_trend(&[100.0, 100.0, 70.0, 60.0]).as_deref(),
+            Some("decreasing")
+        );
+        assert_eq!(
+            depth_trend(&[100.0, 104.0, 98.0]).as_deref(),
+            Some("stable")
+        );
+        assert_eq!(depth_trend(&[0.0, 10.0]).as_deref(), Some("increasing"));
+    }
+
+    #[test]
+    fn test_to_asset() {
+        assert_eq!(to_asset("XLM", "native").asset_type, "native");
+        assert_eq!(to_asset("USDC", "GISSUER").asset_type, "credit_alphanum4");
+        assert_eq!(
+            to_asset("LONGASSET", "GISSUER").asset_type,
+            "credit_alphanum12"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::database::Database;
+use crate::services::corridor_health::CorridorHealthScorer;
 use crate::snapshot::schema::{
     AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
 };
@@ -43,19 +44,23 @@ pub struct SnapshotService {
     db: Arc<Database>,
     contract_service: Option<Arc<ContractService>>,
     event_indexer: Option<Arc<EventIndexer>>,
+    health_scorer: Arc<CorridorHealthScorer>,
 }
 
 impl SnapshotService {
-    /// Create a new snapshot service
+    /// Create a new snapshot service; corridor health scores come from the
+    /// same scorer the API serves them with
     pub fn new(
-        db: Arc<Database>, 
+        db: Arc<Database>,
         contract_service: Option<Arc<ContractService>>,
         event_indexer: Option<Arc<EventIndexer>>,
+        health_scorer: Arc<CorridorHealthScorer>,
     ) -> Self {
         Self {
             db,
             contract_service,
             event_indexer,
+            health_scorer,
         }
     }
 
@@ -147,6 +152,7 @@ impl SnapshotService {
     pub async fn aggregate_all_metrics(&self, epoch: u64) -> Result<AnalyticsSnapshot> {
         let timestamp = Utc::now();
         let mut snapshot = AnalyticsSnapshot::new(epoch, timestamp);
+        snapshot.scoring_version = self.health_scorer.version();
 
         // Aggregate anchor metrics
         let anchor_metrics = self
@@ -266,13 +272,24 @@ impl SnapshotService {
             .await
             .context("Failed to fetch corridor metrics")?;
 
+        let history = self.health_scorer.load_history().await.unwrap_or_else(|e| {
+            warn!("Failed to load corridor history for health scores: {}", e);
+            Default::default()
+        });
+
         let mut metrics = Vec::new();
 
         for row in rows {
+            let corridor_key: String = row.get("corridor_key");
+            let success_rate: f64 = row.get("success_rate");
+            let health = self
+                .health_scorer
+                .assess(&corridor_key, success_rate, &history);
+
             let corridor_metrics = SnapshotCorridorMetrics {
                 id: Uuid::parse_str(&row.get::<String, _>("id"))
                     .context("Invalid corridor metrics ID format")?,
-                corridor_key: row.get("corridor_key"),
+                corridor_key,
                 asset_a_code: row.get("asset_a_code"),
                 asset_a_issuer: row.get("asset_a_issuer"),
                 asset_b_code: row.get("asset_b_code"),
@@ -280,10 +297,11 @@ impl SnapshotService {
                 total_transactions: row.get("total_transactions"),
                 successful_transactions: row.get("successful_transactions"),
                 failed_transactions: row.get("failed_transactions"),
-                success_rate: row.get("success_rate"),
+                success_rate,
                 volume_usd: row.get("volume_usd"),
                 avg_settlement_latency_ms: row.get("avg_settlement_latency_ms"),
                 liquidity_depth_usd: row.get("liquidity_depth_usd"),
+                health_score: Some(health.score),
             };
 
             metrics.push(corridor_metrics);
@@ -393,6 +411,10 @@ impl SnapshotService {
             "schema_version".to_string(),
             Value::Number(snapshot.schema_version.into()),
         );
+        map.insert(
+            "scoring_version".to_string(),
+            Value::String(snapshot.scoring_version),
+        );
         map.insert("epoch".to_string(), Value::Number(snapshot.epoch.into()));
 
         // Serialize timestamp as ISO 8601 string (deterministic format)
@@ -550,6 +572,12 @@ impl SnapshotService {
             "liquidity_depth_usd".to_string(),
             Self::serialize_f64(metrics.liquidity_depth_usd),
         );
+        map.insert(
+            "health_score".to_string(),
+            metrics
+                .health_score
+                .map_or(Value::Null, Self::serialize_f64),
+        );
 
         // serde_json::Map preserves insertion order (uses IndexMap internally)
         // Since BTreeMap iteration is sorted, insertion order is sorted
@@ -944,6 +972,7 @@ mod tests {
             volume_usd: 50000.0,
             avg_settlement_latency_ms: Some(250),
             liquidity_depth_usd: 100000.0,
+            health_score: Some(92.5),
         }
     }
 
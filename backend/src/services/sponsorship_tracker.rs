@@ -0,0 +1,534 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{Pool, Sqlite};
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
+use crate::rpc::{HorizonEffect, HorizonOperation};
+use crate::services::alert_service::AlertService;
+use crate::webhooks::{WebhookEventType, WebhookService};
+
+/// Base reserve in XLM paid per sponsored subentry
+pub const BASE_RESERVE_XLM: f64 = 0.5;
+
+/// Ledger entry types whose sponsorship changes are reported as
+/// `<entry>_sponsorship_{created,updated,removed}` effects
+const SPONSORED_ENTRY_TYPES: &[&str] = &[
+    "claimable_balance",
+    "account",
+    "trustline",
+    "signer",
+    "data",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Sponsorship {
+    pub id: String,
+    pub sponsor: String,
+    pub sponsored_account: String,
+    pub sponsored_reserves: i64,
+    pub reserve_exposure_xlm: f64,
+    pub status: String,
+    pub first_ledger: i64,
+    pub last_ledger: i64,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SponsorshipHistory {
+    pub id: String,
+    pub sponsorship_id: String,
+    pub source_id: String,
+    pub change_type: String,
+    pub entry_type: Option<String>,
+    pub reserves_delta: i64,
+    pub ledger_sequence: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SponsorLeaderboard {
+    pub sponsor: String,
+    pub sponsored_accounts_count: i64,
+    pub total_sponsored_reserves: i64,
+    pub reserve_exposure_xlm: f64,
+    pub rank: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SponsorshipAnalytics {
+    pub total_sponsorships: i64,
+    pub active_sponsorships: i64,
+    pub unique_sponsors: i64,
+    pub unique_sponsored_accounts: i64,
+    pub total_sponsored_reserves: i64,
+    pub total_reserve_exposure_xlm: f64,
+    pub average_sponsored_reserves: f64,
+    pub largest_sponsored_reserves: i64,
+}
+
+/// A single sponsorship change extracted from a ledger
+#[derive(Debug, Clone, PartialEq)]
+struct SponsorshipChange {
+    sponsor: String,
+    sponsored_account: String,
+    source_id: String,
+    change_type: &'static str,
+    entry_type: Option<&'static str>,
+    reserves_delta: i64,
+}
+
+pub struct SponsorshipTrackerService {
+    pool: Pool<Sqlite>,
+    alert_service: Arc<AlertService>,
+    webhooks: WebhookService,
+}
+
+impl SponsorshipTrackerService {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self {
+            webhooks: WebhookService::new(pool.clone()),
+            alert_service: Arc::new(AlertService::new()),
+            pool,
+        }
+    }
+
+    pub fn with_alert_service(mut self, alert_service: Arc<AlertService>) -> Self {
+        self.alert_service = alert_service;
+        self
+    }
+
+    /// Apply the sponsorship operations and entry sponsorship effects of a ledger;
+    /// returns the number of changes recorded
+    pub async fn process_ledger(
+        &self,
+        ledger_sequence: u64,
+        operations: &[HorizonOperation],
+        effects: &[HorizonEffect],
+    ) -> Result<u64> {
+        let changes: Vec<SponsorshipChange> = operations
+            .iter()
+            .filter_map(change_from_operation)
+            .chain(effects.iter().flat_map(changes_from_effect))
+            .collect();
+
+        let mut recorded = 0_u64;
+        for change in changes {
+            let Some(sponsored_reserves) = self.apply_change(ledger_sequence, &change).await?
+            else {
+                continue;
+            };
+            recorded += 1;
+
+            if matches!(change.change_type, "BEGIN" | "REVOKED") {
+                self.notify(ledger_sequence, &change, sponsored_reserves)
+                    .await;
+            }
+        }
+
+        if recorded > 0 {
+            info!(
+                "Recorded {} sponsorship changes for ledger {}",
+                recorded, ledger_sequence
+            );
+        }
+
+        Ok(recorded)
+    }
+
+    /// Returns the resulting reserve count, or `None` if the change was already applied
+    async fn apply_change(
+        &self,
+        ledger_sequence: u64,
+        change: &SponsorshipChange,
+    ) -> Result<Option<i64>> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO sponsorships (
+                id, sponsor, sponsored_account, sponsored_reserves, status, first_ledger, last_ledger
+            )
+            VALUES ($1, $2, $3, 0, 'inactive', $4, $4)
+            ON CONFLICT (sponsor, sponsored_account) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&change.sponsor)
+        .bind(&change.sponsored_account)
+        .bind(ledger_sequence as i64)
+        .execute(&mut *tx)
+        .await?;
+
+        let sponsorship_id: String = sqlx::query_scalar(
+            "SELECT id FROM sponsorships WHERE sponsor = $1 AND sponsored_account = $2",
+        )
+        .bind(&change.sponsor)
+        .bind(&change.sponsored_account)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO sponsorship_history (
+                id, sponsorship_id, source_id, change_type, entry_type, reserves_delta, ledger_sequence
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (source_id) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&sponsorship_id)
+        .bind(&change.source_id)
+        .bind(change.change_type)
+        .bind(change.entry_type)
+        .bind(change.reserves_delta)
+        .bind(ledger_sequence as i64)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        if inserted == 0 {
+            tx.rollback().await?;
+            return Ok(None);
+        }
+
+        // Reserves are clamped at zero since sponsorships that predate ingestion
+        // can be removed without ever having been seen
+        let sponsored_reserves: i64 = sqlx::query_scalar(
+            r#"
+            UPDATE sponsorships SET
+                sponsored_reserves = MAX(sponsored_reserves + $2, 0),
+                status = CASE
+                    WHEN $3 = 'BEGIN' THEN 'sponsoring'
+                    WHEN $3 = 'REVOKED' THEN status
+                    WHEN $3 <> 'END' AND status = 'sponsoring' THEN status
+                    WHEN sponsored_reserves + $2 > 0 THEN 'active'
+                    ELSE 'inactive'
+                END,
+                last_ledger = MAX(last_ledger, $4),
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            RETURNING sponsored_reserves
+            "#,
+        )
+        .bind(&sponsorship_id)
+        .bind(change.reserves_delta)
+        .bind(change.change_type)
+        .bind(ledger_sequence as i64)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(Some(sponsored_reserves))
+    }
+
+    async fn notify(
+        &self,
+        ledger_sequence: u64,
+        change: &SponsorshipChange,
+        sponsored_reserves: i64,
+    ) {
+        if change.change_type == "REVOKED" {
+            if let Err(e) = self
+                .alert_service
+                .alert_sponsorship_revoked(
+                    ledger_sequence,
+                    change.sponsor.clone(),
+                    change.sponsored_account.clone(),
+                )
+                .await
+            {
+                warn!("Failed to send sponsorship alert: {}", e);
+            }
+        }
+
+        let payload = json!({
+            "sponsor": change.sponsor,
+            "sponsored_account": change.sponsored_account,
+            "change_type": change.change_type,
+            "sponsored_reserves": sponsored_reserves,
+            "ledger_sequence": ledger_sequence,
+        });
+        if let Err(e) = self
+            .webhooks
+            .trigger_event(WebhookEventType::SponsorshipChanged, payload)
+            .await
+        {
+            warn!("Failed to queue sponsorship webhook: {}", e);
+        }
+    }
+
+    /// Get all sponsorships with pagination
+    pub async fn get_all_sponsorships(&self, limit: i64, offset: i64) -> Result<Vec<Sponsorship>> {
+        let sponsorships = sqlx::query_as(&sponsorship_query(
+            "ORDER BY sponsored_reserves DESC, last_ledger DESC LIMIT $1 OFFSET $2",
+        ))
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(sponsorships)
+    }
+
+    pub async fn get_sponsorship(&self, id: &str) -> Result<Option<Sponsorship>> {
+        let sponsorship = sqlx::query_as(&sponsorship_query("WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(sponsorship)
+    }
+
+    /// Get sponsorships by a specific sponsor
+    pub async fn get_sponsorships_by_sponsor(&self, sponsor: &str) -> Result<Vec<Sponsorship>> {
+        let sponsorships = sqlx::query_as(&sponsorship_query(
+            "WHERE sponsor = $1 ORDER BY sponsored_reserves DESC",
+        ))
+        .bind(sponsor)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(sponsorships)
+    }
+
+    /// Get sponsorships for a specific account
+    pub async fn get_sponsorships_for_account(&self, account: &str) -> Result<Vec<Sponsorship>> {
+        let sponsorships = sqlx::query_as(&sponsorship_query(
+            "WHERE sponsored_account = $1 ORDER BY sponsored_reserves DESC",
+        ))
+        .bind(account)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(sponsorships)
+    }
+
+    /// Get sponsorship history for tracking changes
+    pub async fn get_sponsorship_history(
+        &self,
+        sponsorship_id: &str,
+    ) -> Result<Vec<SponsorshipHistory>> {
+        let history = sqlx::query_as(
+            r#"
+            SELECT id, sponsorship_id, source_id, change_type, entry_type, reserves_delta,
+                   ledger_sequence, created_at
+            FROM sponsorship_history
+            WHERE sponsorship_id = $1
+            ORDER BY ledger_sequence DESC, created_at DESC
+            "#,
+        )
+        .bind(sponsorship_id)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(history)
+    }
+
+    /// Get sponsor leaderboard (top sponsors by sponsored reserves)
+    pub async fn get_sponsor_leaderboard(&self, limit: i64) -> Result<Vec<SponsorLeaderboard>> {
+        let leaderboard = sqlx::query_as(
+            r#"
+            SELECT
+                sponsor,
+                COUNT(*) AS sponsored_accounts_count,
+                SUM(sponsored_reserves) AS total_sponsored_reserves,
+                SUM(sponsored_reserves) * $1 AS reserve_exposure_xlm,
+                ROW_NUMBER() OVER (ORDER BY SUM(sponsored_reserves) DESC, sponsor) AS rank
+            FROM sponsorships
+            WHERE sponsored_reserves > 0
+            GROUP BY sponsor
+            ORDER BY rank
+            LIMIT $2
+            "#,
+        )
+        .bind(BASE_RESERVE_XLM)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(leaderboard)
+    }
+
+    /// Calculate analytics summary
+    pub async fn get_analytics(&self) -> Result<SponsorshipAnalytics> {
+        let analytics = sqlx::query_as(
+            r#"
+            SELECT
+                COUNT(*) AS total_sponsorships,
+                COALESCE(SUM(status <> 'inactive'), 0) AS active_sponsorships,
+                COUNT(DISTINCT sponsor) AS unique_sponsors,
+                COUNT(DISTINCT sponsored_account) AS unique_sponsored_accounts,
+                COALESCE(SUM(sponsored_reserves), 0) AS total_sponsored_reserves,
+                COALESCE(SUM(sponsored_reserves), 0) * $1 AS total_reserve_exposure_xlm,
+                COALESCE(AVG(sponsored_reserves), 0.0) AS average_sponsored_reserves,
+                COALESCE(MAX(sponsored_reserves), 0) AS largest_sponsored_reserves
+            FROM sponsorships
+            "#,
+        )
+        .bind(BASE_RESERVE_XLM)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(analytics)
+    }
+}
+
+fn sponsorship_query(clause: &str) -> String {
+    format!(
+        r#"
+        SELECT id, sponsor, sponsored_account, sponsored_reserves,
+               sponsored_reserves * {} AS reserve_exposure_xlm,
+               status, first_ledger, last_ledger, created_at, updated_at
+        FROM sponsorships
+        {}
+        "#,
+        BASE_RESERVE_XLM, clause
+    )
+}
+
+#[async_trait]
+impl LedgerProcessor for SponsorshipTrackerService {
+    fn name(&self) -> &'static str {
+        "sponsorships"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Operations, BundlePart::Effects]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> Result<u64> {
+        self.process_ledger(bundle.sequence, &bundle.operations, &bundle.effects)
+            .await
+    }
+}
+
+fn change_from_operation(op: &HorizonOperation) -> Option<SponsorshipChange> {
+    let (sponsor, sponsored_account, change_type) = match op.operation_type.as_str() {
+        "begin_sponsoring_future_reserves" => {
+            (op.source_account.clone(), op.sponsored_id.clone()?, "BEGIN")
+        }
+        "end_sponsoring_future_reserves" => {
+            (op.begin_sponsor.clone()?, op.source_account.clone(), "END")
+        }
+        "revoke_sponsorship" => (
+            op.source_account.clone(),
+            op.revoked_entry_owner()?.to_string(),
+            "REVOKED",
+        ),
+        _ => return None,
+    };
+
+    Some(SponsorshipChange {
+        sponsor,
+        sponsored_account,
+        source_id: op.id.clone(),
+        change_type,
+        entry_type: None,
+        reserves_delta: 0,
+    })
+}
+
+/// An updated sponsorship moves the entry's reserves between two sponsors
+fn changes_from_effect(effect: &HorizonEffect) -> Vec<SponsorshipChange> {
+    let Some((entry_type, action)) = parse_sponsorship_effect(&effect.effect_type) else {
+        return Vec::new();
+    };
+    let Some(owner) = effect.account.clone() else {
+        return Vec::new();
+    };
+    let reserves = entry_reserves(entry_type);
+
+    let change = |sponsor: &Option<String>, suffix: &str, change_type, reserves_delta| {
+        sponsor.clone().map(|sponsor| SponsorshipChange {
+            sponsor,
+            sponsored_account: owner.clone(),
+            source_id: format!("{}{}", effect.id, suffix),
+            change_type,
+            entry_type: Some(entry_type),
+            reserves_delta,
+        })
+    };
+
+    match action {
+        "created" => change(&effect.sponsor, "", "ENTRY_SPONSORED", reserves)
+            .into_iter()
+            .collect(),
+        "removed" => change(&effect.former_sponsor, "", "ENTRY_RELEASED", -reserves)
+            .into_iter()
+            .collect(),
+        "updated" => [
+            change(
+                &effect.former_sponsor,
+                ":from",
+                "ENTRY_TRANSFERRED_OUT",
+                -reserves,
+            ),
+            change(&effect.new_sponsor, ":to", "ENTRY_TRANSFERRED_IN", reserves),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_sponsorship_effect(effect_type: &str) -> Option<(&'static str, &str)> {
+    let (entry, action) = effect_type.split_once("_sponsorship_")?;
+    let entry_type = SPONSORED_ENTRY_TYPES.iter().find(|t| **t == entry)?;
+    Some((entry_type, action))
+}
+
+/// An account entry needs two base reserves, every subentry one
+fn entry_reserves(entry_type: &str) -> i64 {
+    if entry_type == "account" {
+        2
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(effect_type: &str) -> HorizonEffect {
+        HorizonEffect {
+            id: "effect-1".to_string(),
+            effect_type: effect_type.to_string(),
+            account: Some("GOWNER".to_string()),
+            sponsor: Some("GNEW".to_string()),
+            former_sponsor: Some("GOLD".to_string()),
+            new_sponsor: Some("GNEW".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_sponsorship_effect() {
+        assert_eq!(
+            parse_sponsorship_effect("claimable_balance_sponsorship_created"),
+            Some(("claimable_balance", "created"))
+        );
+        assert_eq!(
+            parse_sponsorship_effect("trustline_sponsorship_removed"),
+            Some(("trustline", "removed"))
+        );
+        assert_eq!(parse_sponsorship_effect("account_credited"), None);
+    }
+
+    #[test]
+    fn test_changes_from_effect() {
+        let created = changes_from_effect(&effect("account_sponsorship_created"));
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].sponsor, "GNEW");
+        assert_eq!(created[0].reserves_delta, 2);
+
+        let updated = changes_from_effect(&effect("signer_sponsorship_updated"));
+        let deltas: Vec<(&str, i64)> = updated
+            .iter()
+            .map(|c| (c.sponsor.as_str(), c.reserves_delta))
+            .collect();
+        assert_eq!(deltas, vec![("GOLD", -1), ("GNEW", 1)]);
+        assert_ne!(updated[0].source_id, updated[1].source_id);
+    }
+}
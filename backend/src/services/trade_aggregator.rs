@@ -0,0 +1,509 @@
+//! OHLCV candles built incrementally from ingested trades.
+//!
+//! Every trade updates one candle per resolution for its asset pair and, for
+//! liquidity pool trades, for the pool. Pairs keep Horizon's base/counter
+//! order; prices are counter units per base unit.
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, Pool, Sqlite, Transaction};
+use std::fmt;
+use tracing::{debug, info, warn};
+
+use crate::rpc::error::RpcError;
+use crate::rpc::Trade;
+use crate::utils::{format_time, horizon_asset_key};
+
+/// Monday 1970-01-05, so weekly candles start on Mondays
+const WEEK_ANCHOR_SECS: i64 = 4 * 86_400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Resolution {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "1d")]
+    OneDay,
+    #[serde(rename = "1w")]
+    OneWeek,
+}
+
+impl Resolution {
+    pub const ALL: [Resolution; 5] = [
+        Resolution::OneMinute,
+        Resolution::FiveMinutes,
+        Resolution::OneHour,
+        Resolution::OneDay,
+        Resolution::OneWeek,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::OneMinute => "1m",
+            Self::FiveMinutes => "5m",
+            Self::OneHour => "1h",
+            Self::OneDay => "1d",
+            Self::OneWeek => "1w",
+        }
+    }
+
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Self::OneMinute => 60,
+            Self::FiveMinutes => 300,
+            Self::OneHour => 3_600,
+            Self::OneDay => 86_400,
+            Self::OneWeek => 604_800,
+        }
+    }
+
+    /// Start of the candle containing `time`
+    pub fn bucket_start(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let secs = time.timestamp();
+        let anchor = if *self == Self::OneWeek {
+            WEEK_ANCHOR_SECS
+        } else {
+            0
+        };
+        let start = secs - (secs - anchor).rem_euclid(self.seconds());
+        Utc.timestamp_opt(start, 0).single().unwrap_or(time)
+    }
+}
+
+impl fmt::Display for Resolution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for Resolution {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|r| r.as_str() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid resolution: {}. Must be one of 1m, 5m, 1h, 1d, 1w",
+                    s
+                )
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketType {
+    Pair,
+    LiquidityPool,
+}
+
+impl MarketType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pair => "pair",
+            Self::LiquidityPool => "liquidity_pool",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Candle {
+    pub market_key: String,
+    pub market_type: String,
+    pub resolution: String,
+    pub bucket_start: String,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub base_volume: f64,
+    pub counter_volume: f64,
+    pub trade_count: i64,
+}
+
+impl Candle {
+    /// Same candle quoted from the other side of the pair
+    fn inverted(self, market_key: &str) -> Self {
+        Self {
+            market_key: market_key.to_string(),
+            open: 1.0 / self.open,
+            high: 1.0 / self.low,
+            low: 1.0 / self.high,
+            close: 1.0 / self.close,
+            base_volume: self.counter_volume,
+            counter_volume: self.base_volume,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Market {
+    pub market_key: String,
+    pub market_type: String,
+    pub trade_count: i64,
+    pub last_price: f64,
+    pub last_trade_at: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CandleQuery {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub limit: i64,
+}
+
+/// A trade reduced to what candles need
+#[derive(Debug, Clone, PartialEq)]
+struct NormalizedTrade {
+    pair_key: String,
+    liquidity_pool_id: Option<String>,
+    base_amount: f64,
+    counter_amount: f64,
+    price: f64,
+    time: DateTime<Utc>,
+}
+
+pub struct TradeAggregator {
+    pool: Pool<Sqlite>,
+}
+
+impl TradeAggregator {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    /// Consume a trade stream, folding each trade into candles as it arrives.
+    /// Runs until the stream ends.
+    pub async fn run(&self, mut trades: BoxStream<'static, Result<Trade, RpcError>>) -> Result<()> {
+        info!("Starting trade aggregation");
+
+        while let Some(item) = trades.next().await {
+            let trade = match item {
+                Ok(trade) => trade,
+                Err(e) => {
+                    debug!("Skipping undecodable trade record: {}", e);
+                    continue;
+                }
+            };
+            if let Err(e) = self.ingest_trade(&trade).await {
+                warn!("Failed to aggregate trade {}: {}", trade.id, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of trades that were new
+    pub async fn ingest_trades(&self, trades: &[Trade]) -> Result<u64> {
+        let mut ingested = 0;
+        for trade in trades {
+            if self.ingest_trade(trade).await? {
+                ingested += 1;
+            }
+        }
+        Ok(ingested)
+    }
+
+    /// Store a trade and update its candles; already seen and unparsable
+    /// trades are ignored and return `false`
+    pub async fn ingest_trade(&self, trade: &Trade) -> Result<bool> {
+        let Some(normalized) = normalize_trade(trade) else {
+            debug!("Skipping unparsable trade {}", trade.id);
+            return Ok(false);
+        };
+        let time = format_time(normalized.time, SecondsFormat::Secs);
+
+        let mut tx = self.pool.begin().await?;
+        let inserted = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO trades (
+                id, paging_token, pair_key, liquidity_pool_id, trade_type,
+                base_amount, counter_amount, price, ledger_close_time
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(&trade.id)
+        .bind(&trade.paging_token)
+        .bind(&normalized.pair_key)
+        .bind(&normalized.liquidity_pool_id)
+        .bind(&trade.trade_type)
+        .bind(normalized.base_amount)
+        .bind(normalized.counter_amount)
+        .bind(normalized.price)
+        .bind(&time)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if inserted == 0 {
+            return Ok(false);
+        }
+
+        for resolution in Resolution::ALL {
+            upsert_candle(
+                &mut tx,
+                &normalized.pair_key,
+                MarketType::Pair,
+                resolution,
+                &normalized,
+            )
+            .await?;
+            if let Some(pool_id) = &normalized.liquidity_pool_id {
+                upsert_candle(
+                    &mut tx,
+                    pool_id,
+                    MarketType::LiquidityPool,
+                    resolution,
+                    &normalized,
+                )
+                .await?;
+            }
+        }
+        tx.commit().await?;
+
+        Ok(true)
+    }
+
+    /// Candles for a market, oldest first. When `query.end` is unset the
+    /// latest `query.limit` candles are returned.
+    pub async fn get_candles(
+        &self,
+        market_type: MarketType,
+        market_key: &str,
+        resolution: Resolution,
+        query: &CandleQuery,
+    ) -> Result<Vec<Candle>> {
+        let start = query
+            .start
+            .map(|t| format_time(t, SecondsFormat::Secs))
+            .unwrap_or_else(|| "0000".to_string());
+        let end = query
+            .end
+            .map(|t| format_time(t, SecondsFormat::Secs))
+            .unwrap_or_else(|| "9999".to_string());
+
+        let mut candles: Vec<Candle> = sqlx::query_as(
+            r#"
+            SELECT market_key, market_type, resolution, bucket_start,
+                   open, high, low, close, base_volume, counter_volume, trade_count
+            FROM trade_candles
+            WHERE market_key = $1 AND market_type = $2 AND resolution = $3
+              AND bucket_start >= $4 AND bucket_start <= $5
+            ORDER BY bucket_start DESC
+            LIMIT $6
+            "#,
+        )
+        .bind(market_key)
+        .bind(market_type.as_str())
+        .bind(resolution.as_str())
+        .bind(start)
+        .bind(end)
+        .bind(query.limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        candles.reverse();
+        Ok(candles)
+    }
+
+    /// Pair candles in the requested direction; pairs stored the other way
+    /// round are inverted
+    pub async fn get_pair_candles(
+        &self,
+        pair_key: &str,
+        resolution: Resolution,
+        query: &CandleQuery,
+    ) -> Result<Vec<Candle>> {
+        let candles = self
+            .get_candles(MarketType::Pair, pair_key, resolution, query)
+            .await?;
+        if !candles.is_empty() {
+            return Ok(candles);
+        }
+
+        let Some((base, counter)) = pair_key.split_once("->") else {
+            return Ok(candles);
+        };
+        let reversed = format!("{}->{}", counter, base);
+        let candles = self
+            .get_candles(MarketType::Pair, &reversed, resolution, query)
+            .await?;
+        Ok(candles
+            .into_iter()
+            .map(|candle| candle.inverted(pair_key))
+            .collect())
+    }
+
+    /// Markets with candles, most traded first
+    pub async fn get_markets(&self, market_type: Option<MarketType>) -> Result<Vec<Market>> {
+        let markets = sqlx::query_as(
+            r#"
+            SELECT c.market_key, c.market_type, totals.trade_count,
+                   c.close AS last_price, c.close_at AS last_trade_at
+            FROM trade_candles c
+            JOIN (
+                SELECT market_key, market_type, SUM(trade_count) AS trade_count,
+                       MAX(bucket_start) AS latest_bucket
+                FROM trade_candles
+                WHERE resolution = '1w'
+                GROUP BY market_key, market_type
+            ) totals ON totals.market_key = c.market_key
+                AND totals.market_type = c.market_type
+                AND totals.latest_bucket = c.bucket_start
+            WHERE c.resolution = '1w' AND ($1 IS NULL OR c.market_type = $1)
+            ORDER BY totals.trade_count DESC, c.market_key
+            "#,
+        )
+        .bind(market_type.map(|t| t.as_str()))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(markets)
+    }
+}
+
+/// Merge a trade into its candle. Open and close follow trade time, so
+/// trades arriving out of order still produce the right candle.
+async fn upsert_candle(
+    tx: &mut Transaction<'_, Sqlite>,
+    market_key: &str,
+    market_type: MarketType,
+    resolution: Resolution,
+    trade: &NormalizedTrade,
+) -> Result<()> {
+    let time = format_time(trade.time, SecondsFormat::Secs);
+    sqlx::query(
+        r#"
+        INSERT INTO trade_candles (
+            market_key, market_type, resolution, bucket_start,
+            open, high, low, close, base_volume, counter_volume, trade_count,
+            open_at, close_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $5, $5, $5, $6, $7, 1, $8, $8)
+        ON CONFLICT (market_key, resolution, bucket_start) DO UPDATE SET
+            open = CASE WHEN excluded.open_at < trade_candles.open_at
+                        THEN excluded.open ELSE trade_candles.open END,
+            open_at = MIN(trade_candles.open_at, excluded.open_at),
+            close = CASE WHEN excluded.close_at >= trade_candles.close_at
+                         THEN excluded.close ELSE trade_candles.close END,
+            close_at = MAX(trade_candles.close_at, excluded.close_at),
+            high = MAX(trade_candles.high, excluded.high),
+            low = MIN(trade_candles.low, excluded.low),
+            base_volume = trade_candles.base_volume + excluded.base_volume,
+            counter_volume = trade_candles.counter_volume + excluded.counter_volume,
+            trade_count = trade_candles.trade_count + 1,
+            updated_at = CURRENT_TIMESTAMP
+        "#,
+    )
+    .bind(market_key)
+    .bind(market_type.as_str())
+    .bind(resolution.as_str())
+    .bind(format_time(
+        resolution.bucket_start(trade.time),
+        SecondsFormat::Secs,
+    ))
+    .bind(trade.price)
+    .bind(trade.base_amount)
+    .bind(trade.counter_amount)
+    .bind(&time)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+fn normalize_trade(trade: &Trade) -> Option<NormalizedTrade> {
+    if trade.price.n <= 0 || trade.price.d <= 0 {
+        return None;
+    }
+    let time = DateTime::parse_from_rfc3339(&trade.ledger_close_time)
+        .ok()?
+        .with_timezone(&Utc);
+
+    let base = horizon_asset_key(
+        &trade.base_asset_type,
+        trade.base_asset_code.as_deref(),
+        trade.base_asset_issuer.as_deref(),
+    );
+    let counter = horizon_asset_key(
+        &trade.counter_asset_type,
+        trade.counter_asset_code.as_deref(),
+        trade.counter_asset_issuer.as_deref(),
+    );
+
+    Some(NormalizedTrade {
+        pair_key: format!("{}->{}", base, counter),
+        liquidity_pool_id: trade
+            .base_liquidity_pool_id
+            .clone()
+            .or_else(|| trade.counter_liquidity_pool_id.clone()),
+        base_amount: trade.base_amount.parse().ok()?,
+        counter_amount: trade.counter_amount.parse().ok()?,
+        price: trade.price.n as f64 / trade.price.d as f64,
+        time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_bucket_start() {
+        let time = at("2026-01-22T10:37:42Z");
+        assert_eq!(
+            Resolution::OneMinute.bucket_start(time),
+            at("2026-01-22T10:37:00Z")
+        );
+        assert_eq!(
+            Resolution::FiveMinutes.bucket_start(time),
+            at("2026-01-22T10:35:00Z")
+        );
+        assert_eq!(
+            Resolution::OneHour.bucket_start(time),
+            at("2026-01-22T10:00:00Z")
+        );
+        assert_eq!(
+            Resolution::OneDay.bucket_start(time),
+            at("2026-01-22T00:00:00Z")
+        );
+        // 2026-01-22 is a Thursday
+        assert_eq!(
+            Resolution::OneWeek.bucket_start(time),
+            at("2026-01-19T00:00:00Z")
+        );
+    }
+
+    #[test]
+    fn test_resolution_from_str() {
+        assert_eq!("1h".parse::<Resolution>(), Ok(Resolution::OneHour));
+        assert!("2h".parse::<Resolution>().is_err());
+    }
+
+    #[test]
+    fn test_normalize_pool_trade() {
+        let mut trade = crate::rpc::StellarRpcClient::mock_trades(1).remove(0);
+        trade.base_account = String::new();
+        trade.base_liquidity_pool_id = Some("pool_1".to_string());
+
+        let normalized = normalize_trade(&trade).unwrap();
+        assert_eq!(
+            normalized.pair_key,
+            "XLM:native->USDC:GBXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX"
+        );
+        assert_eq!(normalized.liquidity_pool_id.as_deref(), Some("pool_1"));
+        assert!((normalized.price - 2.0).abs() < f64::EPSILON);
+        assert!((normalized.base_amount - 1000.0).abs() < f64::EPSILON);
+    }
+}
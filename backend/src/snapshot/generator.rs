@@ -85,6 +85,7 @@ mod tests {
             volume_usd: 50000.0,
             avg_settlement_latency_ms: Some(250),
             liquidity_depth_usd: 100000.0,
+            health_score: Some(92.5),
         }
     }
 
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Snapshot schema version for backward compatibility
-pub const SCHEMA_VERSION: u32 = 1;
+pub const SCHEMA_VERSION: u32 = 2;
 
 /// Individual anchor metrics within a snapshot
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -38,6 +38,9 @@ pub struct SnapshotCorridorMetrics {
     pub volume_usd: f64,
     pub avg_settlement_latency_ms: Option<i32>,
     pub liquidity_depth_usd: f64,
+    /// Health score under the snapshot's `scoring_version`
+    #[serde(default)]
+    pub health_score: Option<f64>,
 }
 
 /// Complete snapshot containing all metrics at a specific epoch
@@ -45,6 +48,9 @@ pub struct SnapshotCorridorMetrics {
 pub struct AnalyticsSnapshot {
     /// Schema version for compatibility checking
     pub schema_version: u32,
+    /// Version of the corridor health scoring behind the health scores
+    #[serde(default)]
+    pub scoring_version: String,
     /// Epoch number for this snapshot
     pub epoch: u64,
     /// Timestamp when snapshot was created
@@ -60,6 +66,7 @@ impl AnalyticsSnapshot {
     pub fn new(epoch: u64, timestamp: DateTime<Utc>) -> Self {
         Self {
             schema_version: SCHEMA_VERSION,
+            scoring_version: String::new(),
             epoch,
             timestamp,
             anchor_metrics: Vec::new(),
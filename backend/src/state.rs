@@ -1,5 +1,6 @@
 use crate::database::Database;
 use crate::ingestion::DataIngestionService;
+use crate::services::corridor_health::CorridorHealthScorer;
 use crate::websocket::WsState;
 use std::sync::Arc;
 
@@ -9,6 +10,7 @@ pub struct AppState {
     pub db: Arc<Database>,
     pub ws_state: Arc<WsState>,
     pub ingestion: Arc<DataIngestionService>,
+    pub health_scorer: Arc<CorridorHealthScorer>,
 }
 
 impl AppState {
@@ -16,11 +18,13 @@ impl AppState {
         db: Arc<Database>,
         ws_state: Arc<WsState>,
         ingestion: Arc<DataIngestionService>,
+        health_scorer: Arc<CorridorHealthScorer>,
     ) -> Self {
         Self {
             db,
             ws_state,
             ingestion,
+            health_scorer,
         }
     }
 }
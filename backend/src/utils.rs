@@ -0,0 +1,83 @@
+//! Small helpers shared by the ingestion services, ML and API handlers.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// `CODE:ISSUER` key of an asset; the native asset is `XLM:native`
+pub fn asset_key(code: &str, issuer: &str) -> String {
+    format!("{}:{}", code, issuer)
+}
+
+/// Corridor-style asset key from Horizon's `asset_type` / `asset_code` /
+/// `asset_issuer` fields; lumens are `XLM:native`
+pub fn horizon_asset_key(asset_type: &str, code: Option<&str>, issuer: Option<&str>) -> String {
+    if asset_type == "native" {
+        return asset_key("XLM", "native");
+    }
+    asset_key(code.unwrap_or_default(), issuer.unwrap_or_default())
+}
+
+/// `Z`-suffixed RFC 3339 at a fixed precision, so stored timestamps compare as text
+pub fn format_time(time: DateTime<Utc>, precision: SecondsFormat) -> String {
+    time.to_rfc3339_opts(precision, true)
+}
+
+/// Same format as the rollup tables' `hour_bucket` and `created_at`
+/// (`DateTime::to_rfc3339`), so text comparisons against them hold
+pub fn format_rollup_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339()
+}
+
+pub fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Nearest-rank percentile of an ascending slice
+pub fn percentile(sorted: &[i64], pct: f64) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.clamp(1, sorted.len()) - 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_keys() {
+        assert_eq!(horizon_asset_key("native", None, None), "XLM:native");
+        assert_eq!(
+            horizon_asset_key("credit_alphanum4", Some("USDC"), Some("GA")),
+            "USDC:GA"
+        );
+        assert_eq!(asset_key("EURC", "GB"), "EURC:GB");
+    }
+
+    #[test]
+    fn test_time_formats_round_trip() {
+        let time = parse_time("2026-01-22T10:37:42.123Z").unwrap();
+        assert_eq!(
+            format_time(time, SecondsFormat::Secs),
+            "2026-01-22T10:37:42Z"
+        );
+        assert_eq!(
+            format_time(time, SecondsFormat::Millis),
+            "2026-01-22T10:37:42.123Z"
+        );
+        assert_eq!(format_rollup_time(time), "2026-01-22T10:37:42.123+00:00");
+        assert!(parse_time("not a time").is_none());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values: Vec<i64> = (1..=100).collect();
+        assert_eq!(percentile(&values, 10.0), Some(10));
+        assert_eq!(percentile(&values, 50.0), Some(50));
+        assert_eq!(percentile(&values, 99.0), Some(99));
+        assert_eq!(percentile(&[7], 90.0), Some(7));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+}
@@ -78,6 +78,11 @@ pub enum WebhookEventType {
     AnchorStatusChanged,
     PaymentCreated,
     CorridorLiquidityDropped,
+    SponsorshipChanged,
+    AssetSupplyJump,
+    AssetFlagsChanged,
+    LargePayment,
+    AnomalyDetected,
 }
 
 impl WebhookEventType {
@@ -87,6 +92,11 @@ impl WebhookEventType {
             Self::AnchorStatusChanged => "anchor.status_changed",
             Self::PaymentCreated => "payment.created",
             Self::CorridorLiquidityDropped => "corridor.liquidity_dropped",
+            Self::SponsorshipChanged => "sponsorship.changed",
+            Self::AssetSupplyJump => "asset.supply_jump",
+            Self::AssetFlagsChanged => "asset.flags_changed",
+            Self::LargePayment => "large_payment",
+            Self::AnomalyDetected => "anomaly.detected",
         }
     }
 
@@ -96,6 +106,11 @@ impl WebhookEventType {
             "anchor.status_changed" => Some(Self::AnchorStatusChanged),
             "payment.created" => Some(Self::PaymentCreated),
             "corridor.liquidity_dropped" => Some(Self::CorridorLiquidityDropped),
+            "sponsorship.changed" => Some(Self::SponsorshipChanged),
+            "asset.supply_jump" => Some(Self::AssetSupplyJump),
+            "asset.flags_changed" => Some(Self::AssetFlagsChanged),
+            "large_payment" => Some(Self::LargePayment),
+            "anomaly.detected" => Some(Self::AnomalyDetected),
             _ => None,
         }
     }
@@ -230,6 +245,44 @@ impl WebhookService {
         Ok(id)
     }
 
+    /// Queue an event for every active webhook subscribed to it whose filters
+    /// match the payload; returns the number of deliveries queued
+    pub async fn trigger_event(
+        &self,
+        event_type: WebhookEventType,
+        payload: serde_json::Value,
+    ) -> anyhow::Result<usize> {
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            "SELECT id, user_id, url, event_types, filters, secret, is_active, created_at, last_fired_at FROM webhooks WHERE is_active = 1"
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut queued = 0;
+        for webhook in webhooks {
+            if !webhook
+                .event_types
+                .split(',')
+                .any(|et| et.trim() == event_type.as_str())
+            {
+                continue;
+            }
+            let filters = webhook
+                .filters
+                .as_deref()
+                .and_then(|f| serde_json::from_str::<serde_json::Value>(f).ok());
+            if !filters.is_none_or(|f| filters_match(&payload, &f)) {
+                continue;
+            }
+
+            self.create_webhook_event(&webhook.id, event_type.as_str(), payload.clone())
+                .await?;
+            queued += 1;
+        }
+
+        Ok(queued)
+    }
+
     /// Get pending webhook events
     pub async fn get_pending_events(
         &self,
@@ -298,10 +351,33 @@ impl WebhookService {
     }
 }
 
+/// Every filter key must be present in the payload with an equal value
+fn filters_match(payload: &serde_json::Value, filters: &serde_json::Value) -> bool {
+    filters.as_object().is_none_or(|filters| {
+        filters
+            .iter()
+            .all(|(key, expected)| payload.get(key) == Some(expected))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_filters_match() {
+        let payload = serde_json::json!({"sponsor": "GA", "change_type": "REVOKED"});
+        assert!(filters_match(
+            &payload,
+            &serde_json::json!({"sponsor": "GA"})
+        ));
+        assert!(!filters_match(
+            &payload,
+            &serde_json::json!({"sponsor": "GB"})
+        ));
+        assert!(!filters_match(&payload, &serde_json::json!({"missing": 1})));
+    }
+
     #[test]
     fn test_webhook_signature() {
         let payload = r#"{"event":"test"}"#;
@@ -172,6 +172,34 @@ pub enum WsMessage {
         message: String,
         timestamp: String,
     },
+    /// Payment flagged as large for its asset or corridor
+    LargePayment {
+        payment_id: String,
+        corridor_key: String,
+        asset: String,
+        amount: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        amount_usd: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        z_score: Option<f64>,
+        reasons: Vec<String>,
+        timestamp: String,
+    },
+    /// Corridor or anchor metric outside its expected range
+    Anomaly {
+        anomaly_id: String,
+        subject_type: String,
+        subject_id: String,
+        metric: String,
+        kind: String,
+        observed_value: f64,
+        expected_low: f64,
+        expected_high: f64,
+        score: f64,
+        severity: String,
+        message: String,
+        timestamp: String,
+    },
     /// Subscription management
     Subscribe {
         channels: Vec<String>,
@@ -0,0 +1,84 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use stellar_insights_backend::services::account_profile::{
+    AccountProfileError, AccountProfileService,
+};
+use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+use stellar_insights_backend::services::sponsorship_tracker::SponsorshipTrackerService;
+
+const ACCOUNT: &str = "GACCOUNTAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const OTHER: &str = "GOTHERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+fn service(pool: SqlitePool) -> AccountProfileService {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    AccountProfileService::new(
+        Arc::clone(&rpc_client),
+        Arc::new(AccountMergeDetector::new(pool.clone(), rpc_client)),
+        Arc::new(FeeBumpTrackerService::new(pool.clone())),
+        Arc::new(SponsorshipTrackerService::new(pool)),
+    )
+}
+
+#[sqlx::test]
+async fn test_profile_combines_tracker_history(pool: SqlitePool) {
+    sqlx::query("INSERT INTO ledgers (sequence, hash, close_time) VALUES (100, 'h', '2026-01-22T10:30:00Z')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO account_merges (
+            operation_id, transaction_hash, ledger_sequence,
+            source_account, destination_account, merged_balance
+        )
+        VALUES ('op_1', 'tx_1', 100, $1, $2, 12.5),
+               ('op_2', 'tx_2', 100, $2, 'GUNRELATED', 1.0)
+        "#,
+    )
+    .bind(OTHER)
+    .bind(ACCOUNT)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO fee_bump_transactions (
+            transaction_hash, ledger_sequence, fee_source, fee_charged, max_fee,
+            inner_transaction_hash, inner_max_fee, signatures_count
+        )
+        VALUES ('fb_1', 100, $1, 200, 1000, 'inner_1', 100, 1),
+               ('fb_2', 100, $2, 200, 1000, 'inner_2', 100, 1)
+        "#,
+    )
+    .bind(ACCOUNT)
+    .bind(OTHER)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let profile = service(pool).get_profile(ACCOUNT, 10).await.unwrap();
+
+    assert_eq!(profile.account_id, ACCOUNT);
+    assert!(profile.muxed.is_none());
+    assert_eq!(profile.balances.len(), 2);
+    assert_eq!(profile.trustlines.len(), 1);
+    assert_eq!(profile.merges.len(), 2);
+    assert_eq!(profile.fee_bumps.len(), 1);
+    assert_eq!(profile.fee_bumps[0].transaction_hash, "fb_1");
+    assert!(profile.sponsorships.sponsored_by.is_empty());
+
+    // Mock payments are between unrelated accounts
+    assert_eq!(profile.activity.payments_analyzed, 0);
+}
+
+#[sqlx::test]
+async fn test_profile_rejects_invalid_account(pool: SqlitePool) {
+    let result = service(pool).get_profile("not-an-account", 10).await;
+    assert!(matches!(
+        result,
+        Err(AccountProfileError::InvalidAccount(_))
+    ));
+}
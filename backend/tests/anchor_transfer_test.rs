@@ -0,0 +1,381 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    routing::get,
+    Json, Router,
+};
+use chrono::{Duration, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::services::anchor_transfer_tracker::{
+    AnchorTransferConfig, AnchorTransferFilter, AnchorTransferTracker, NewAnchorTransfer,
+    TransferProtocol,
+};
+
+const JWT: &str = "test-jwt";
+const ENCRYPTION_KEY: &str = "6b1f0d2e9c3a4b5d8e7f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5";
+const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+/// Stand-in transfer server: each poll of a transaction moves it to the next
+/// scripted status, staying on the last one
+#[derive(Clone, Default)]
+struct TransferServer {
+    scripts: Arc<Mutex<HashMap<String, Vec<&'static str>>>>,
+}
+
+impl TransferServer {
+    fn script(&self, id: &str, statuses: Vec<&'static str>) {
+        self.scripts
+            .lock()
+            .unwrap()
+            .insert(id.to_string(), statuses);
+    }
+
+    fn next_status(&self, id: &str) -> Option<&'static str> {
+        let mut scripts = self.scripts.lock().unwrap();
+        let statuses = scripts.get_mut(id)?;
+        if statuses.len() > 1 {
+            Some(statuses.remove(0))
+        } else {
+            statuses.first().copied()
+        }
+    }
+}
+
+fn authorized(headers: &HeaderMap) -> bool {
+    headers.get("authorization").and_then(|v| v.to_str().ok()) == Some("Bearer test-jwt")
+}
+
+async fn sep24_transaction(
+    State(server): State<TransferServer>,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> (StatusCode, Json<Value>) {
+    if !authorized(&headers) {
+        return (StatusCode::FORBIDDEN, Json(json!({ "error": "forbidden" })));
+    }
+    let id = params.get("id").cloned().unwrap_or_default();
+    match server.next_status(&id) {
+        // `started_at` is when the deposit was created, before the user's
+        // interactive flow; the anchor completes it an hour from now
+        Some("completed") => {
+            let now = Utc::now();
+            let started_at = (now - Duration::hours(2)).to_rfc3339_opts(SecondsFormat::Secs, true);
+            let completed_at =
+                (now + Duration::hours(1)).to_rfc3339_opts(SecondsFormat::Secs, true);
+            (
+                StatusCode::OK,
+                Json(json!({
+                    "transaction": {
+                        "id": id,
+                        "kind": "deposit",
+                        "status": "completed",
+                        "started_at": started_at,
+                        "completed_at": completed_at
+                    }
+                })),
+            )
+        }
+        Some(status) => (
+            StatusCode::OK,
+            Json(json!({ "transaction": { "id": id, "kind": "deposit", "status": status } })),
+        ),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "error": "not found" }))),
+    }
+}
+
+async fn sep31_transaction(
+    State(server): State<TransferServer>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<Value>) {
+    if !authorized(&headers) {
+        return (StatusCode::FORBIDDEN, Json(json!({ "error": "forbidden" })));
+    }
+    match server.next_status(&id) {
+        // The anchor reports a 90 minute settlement from `pending_sender`
+        Some("completed") => (
+            StatusCode::OK,
+            Json(json!({
+                "transaction": {
+                    "id": id,
+                    "status": "completed",
+                    "started_at": "2026-01-22T10:00:00Z",
+                    "completed_at": "2026-01-22T11:30:00Z"
+                }
+            })),
+        ),
+        Some(status) => (
+            StatusCode::OK,
+            Json(json!({ "transaction": { "id": id, "status": status } })),
+        ),
+        None => (StatusCode::NOT_FOUND, Json(json!({ "error": "not found" }))),
+    }
+}
+
+/// Serve the stand-in on a random local port; returns its base URL
+async fn spawn_transfer_server(server: TransferServer) -> String {
+    let app = Router::new()
+        .route("/transaction", get(sep24_transaction))
+        .route("/transactions/:id", get(sep31_transaction))
+        .with_state(server);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    format!("http://{}", addr)
+}
+
+fn new_tracker(db: Arc<Database>, config: AnchorTransferConfig) -> AnchorTransferTracker {
+    AnchorTransferTracker::new(db, config).with_encryption_key(ENCRYPTION_KEY)
+}
+
+fn transfer(protocol: TransferProtocol, kind: &str, server: &str, id: &str) -> NewAnchorTransfer {
+    NewAnchorTransfer {
+        protocol,
+        kind: kind.to_string(),
+        transfer_server: format!("{}/", server),
+        transaction_id: id.to_string(),
+        asset_code: Some("USDC".to_string()),
+        asset_issuer: None,
+        counter_asset: None,
+        amount: Some("100".to_string()),
+        jwt: Some(JWT.to_string()),
+    }
+}
+
+#[sqlx::test]
+async fn test_tracks_transfers_to_completion(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Local Anchor".to_string(),
+            stellar_account: "GLOCALANCHOR".to_string(),
+            home_domain: Some("127.0.0.1".to_string()),
+        })
+        .await
+        .unwrap();
+    let anchor_uuid = Uuid::parse_str(&anchor.id).unwrap();
+    db.create_asset(anchor_uuid, "USDC".to_string(), ISSUER.to_string())
+        .await
+        .unwrap();
+    let tracker = new_tracker(
+        Arc::clone(&db),
+        AnchorTransferConfig {
+            poll_interval_secs: 0,
+            ..Default::default()
+        },
+    );
+
+    let server = TransferServer::default();
+    server.script(
+        "dep1",
+        vec!["pending_user_transfer_start", "pending_anchor", "completed"],
+    );
+    server.script("send1", vec!["pending_receiver", "error"]);
+    server.script("send2", vec!["pending_receiver", "completed"]);
+    let base = spawn_transfer_server(server).await;
+
+    let deposit_id = tracker
+        .record_initiated(transfer(TransferProtocol::Sep24, "deposit", &base, "dep1"))
+        .await
+        .unwrap();
+    tracker
+        .record_initiated(NewAnchorTransfer {
+            counter_asset: Some("iso4217:BRL".to_string()),
+            ..transfer(TransferProtocol::Sep31, "send", &base, "send1")
+        })
+        .await
+        .unwrap();
+    let send_id = tracker
+        .record_initiated(NewAnchorTransfer {
+            counter_asset: Some("iso4217:BRL".to_string()),
+            ..transfer(TransferProtocol::Sep31, "send", &base, "send2")
+        })
+        .await
+        .unwrap();
+    // Recording the same transaction again keeps the original
+    assert_eq!(
+        tracker
+            .record_initiated(transfer(TransferProtocol::Sep24, "deposit", &base, "dep1"))
+            .await
+            .unwrap(),
+        deposit_id
+    );
+
+    let mut finished = 0;
+    for _ in 0..3 {
+        let summary = tracker.poll_pending().await.unwrap();
+        assert_eq!(summary.errors, 0);
+        finished += summary.finished;
+    }
+    assert_eq!(finished, 3);
+    assert_eq!(tracker.poll_pending().await.unwrap().polled, 0);
+
+    let deposit = tracker.get_transfer(&deposit_id).await.unwrap().unwrap();
+    assert_eq!(
+        deposit.transfer.anchor_id.as_deref(),
+        Some(anchor.id.as_str())
+    );
+    assert_eq!(deposit.transfer.outcome.as_deref(), Some("completed"));
+    let deposit_corridor = format!("USDC:offchain->USDC:{}", ISSUER);
+    assert_eq!(
+        deposit.transfer.corridor_key.as_deref(),
+        Some(deposit_corridor.as_str())
+    );
+    // Measured from the observed `pending_user_transfer_start`, not the
+    // anchor's `started_at`
+    let deposit_settlement = deposit.transfer.settlement_time_ms.unwrap();
+    assert!(
+        (3_500_000..=3_600_000).contains(&deposit_settlement),
+        "{}",
+        deposit_settlement
+    );
+    let statuses: Vec<&str> = deposit
+        .status_history
+        .iter()
+        .map(|e| e.status.as_str())
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            "incomplete",
+            "pending_user_transfer_start",
+            "pending_anchor",
+            "completed"
+        ]
+    );
+
+    let sends = tracker
+        .get_transfers(&AnchorTransferFilter {
+            status: Some("error".to_string()),
+            limit: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(sends.len(), 1);
+    assert_eq!(sends[0].outcome.as_deref(), Some("failed"));
+    assert_eq!(sends[0].settlement_time_ms, None);
+    assert_eq!(
+        sends[0].corridor_key,
+        Some(format!("USDC:{}->BRL:offchain", ISSUER))
+    );
+
+    let send = tracker.get_transfer(&send_id).await.unwrap().unwrap();
+    assert_eq!(send.transfer.outcome.as_deref(), Some("completed"));
+    assert_eq!(send.transfer.settlement_time_ms, Some(5_400_000));
+
+    let by_anchor = tracker.get_anchor_settlement(30).await.unwrap();
+    assert_eq!(by_anchor.len(), 1);
+    assert_eq!(by_anchor[0].key, anchor.id);
+    assert_eq!(
+        (by_anchor[0].completed_count, by_anchor[0].failed_count),
+        (2, 1)
+    );
+    assert_eq!(by_anchor[0].sample_count, 2);
+    assert_eq!(by_anchor[0].min_ms, Some(deposit_settlement));
+    assert_eq!(by_anchor[0].max_ms, Some(5_400_000));
+
+    let by_corridor = tracker.get_corridor_settlement(30).await.unwrap();
+    assert_eq!(by_corridor.len(), 2);
+    let deposits = by_corridor
+        .iter()
+        .find(|d| d.key == deposit_corridor)
+        .unwrap();
+    assert_eq!(deposits.p50_ms, Some(deposit_settlement));
+
+    assert_eq!(tracker.refresh_anchor_metrics().await.unwrap(), 1);
+    let updated = db.get_anchor_by_id(anchor_uuid).await.unwrap().unwrap();
+    assert_eq!(
+        updated.avg_settlement_time_ms as i64,
+        ((deposit_settlement + 5_400_000) as f64 / 2.0).round() as i64
+    );
+}
+
+#[sqlx::test]
+async fn test_backs_off_when_transfer_server_fails(pool: SqlitePool) {
+    let tracker = new_tracker(
+        Arc::new(Database::new(pool)),
+        AnchorTransferConfig::default(),
+    );
+    let base = spawn_transfer_server(TransferServer::default()).await;
+
+    let id = tracker
+        .record_initiated(transfer(
+            TransferProtocol::Sep24,
+            "withdrawal",
+            &base,
+            "missing",
+        ))
+        .await
+        .unwrap();
+
+    let summary = tracker.poll_pending().await.unwrap();
+    assert_eq!((summary.polled, summary.errors), (1, 1));
+    // Not due again until the back-off passes
+    assert_eq!(tracker.poll_pending().await.unwrap().polled, 0);
+
+    let detail = tracker.get_transfer(&id).await.unwrap().unwrap();
+    assert_eq!(detail.transfer.anchor_id, None);
+    assert_eq!(detail.transfer.corridor_key, None);
+    assert_eq!(detail.transfer.outcome, None);
+    assert_eq!(detail.transfer.poll_error_count, 1);
+    assert!(detail.transfer.last_error.is_some());
+}
+
+#[sqlx::test]
+async fn test_waits_poll_interval_after_successful_poll(pool: SqlitePool) {
+    let tracker = new_tracker(
+        Arc::new(Database::new(pool)),
+        AnchorTransferConfig::default(),
+    );
+    let server = TransferServer::default();
+    server.script(
+        "dep1",
+        vec!["pending_user_transfer_start", "pending_anchor"],
+    );
+    let base = spawn_transfer_server(server).await;
+
+    tracker
+        .record_initiated(transfer(TransferProtocol::Sep24, "deposit", &base, "dep1"))
+        .await
+        .unwrap();
+    let summary = tracker.poll_pending().await.unwrap();
+    assert_eq!((summary.polled, summary.status_changes), (1, 1));
+    assert_eq!(tracker.poll_pending().await.unwrap().polled, 0);
+}
+
+#[sqlx::test]
+async fn test_refuses_to_store_token_without_encryption_key(pool: SqlitePool) {
+    let tracker = AnchorTransferTracker::new(
+        Arc::new(Database::new(pool)),
+        AnchorTransferConfig::default(),
+    )
+    .with_encryption_key(&"0".repeat(64));
+
+    let result = tracker
+        .record_initiated(transfer(
+            TransferProtocol::Sep24,
+            "deposit",
+            "http://127.0.0.1:1",
+            "dep1",
+        ))
+        .await;
+    assert!(result.is_err());
+    let stored = tracker
+        .get_transfers(&AnchorTransferFilter {
+            limit: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(stored.is_empty());
+}
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::anomaly_detector::{
+    AnomalyConfig, AnomalyDetector, AnomalyFilter,
+};
+use stellar_insights_backend::websocket::WsState;
+
+mod common;
+use common::daily_volume;
+
+const CORRIDOR: &str = "USDC:GAISSUER->XLM:native";
+const ANCHOR_ID: &str = "anchor-1";
+
+async fn insert_corridor_hour(pool: &SqlitePool, hour: DateTime<Utc>, success_rate: f64) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridor_metrics_hourly (
+            id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+            hour_bucket, total_transactions, successful_transactions, failed_transactions,
+            success_rate, volume_usd, avg_settlement_latency_ms, liquidity_depth_usd
+        )
+        VALUES ($1, $2, 'USDC', 'GAISSUER', 'XLM', 'native', $3, 100, $4, $5, $6, $7, 4000, 250000.0)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(CORRIDOR)
+    .bind(hour.to_rfc3339())
+    .bind(success_rate as i64)
+    .bind(100 - success_rate as i64)
+    .bind(success_rate)
+    .bind(daily_volume(hour))
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn insert_anchor_metrics(pool: &SqlitePool, at: DateTime<Utc>, settlement_ms: i64) {
+    sqlx::query(
+        r#"
+        INSERT INTO anchor_metrics_history (
+            id, anchor_id, timestamp, success_rate, failure_rate, reliability_score,
+            total_transactions, successful_transactions, failed_transactions,
+            avg_settlement_time_ms, volume_usd
+        )
+        VALUES ($1, $2, $3, 98.0, 2.0, 95.0, 5000, 4900, 100, $4, 0.0)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(ANCHOR_ID)
+    .bind(at)
+    .bind(settlement_ms)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Five weeks of steady history up to the hour before `now`'s, whose
+/// success rate collapses while the anchor slows down tenfold
+async fn seed(pool: &SqlitePool, now: DateTime<Utc>) {
+    sqlx::query(
+        "INSERT INTO anchors (id, name, stellar_account) VALUES ($1, 'Anchor One', 'GANCHOR')",
+    )
+    .bind(ANCHOR_ID)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    let current_hour = now.duration_trunc(Duration::hours(1)).unwrap();
+    for h in 2..=24 * 35 {
+        let hour = current_hour - Duration::hours(h);
+        insert_corridor_hour(pool, hour, 96.0 + (h % 3) as f64).await;
+        insert_anchor_metrics(pool, hour + Duration::minutes(20), 2000 + (h % 4) * 50).await;
+    }
+    let last_hour = current_hour - Duration::hours(1);
+    insert_corridor_hour(pool, last_hour, 60.0).await;
+    insert_anchor_metrics(pool, last_hour + Duration::minutes(20), 20_000).await;
+
+    sqlx::query("INSERT INTO users (id, username) VALUES ('user-1', 'ops')")
+        .execute(pool)
+        .await
+        .unwrap();
+    for (id, threshold) in [("rule-any", 3.0), ("rule-extreme", 1000.0)] {
+        sqlx::query(
+            r#"
+            INSERT INTO alert_rules (id, user_id, metric_type, condition, threshold)
+            VALUES ($1, 'user-1', 'anomaly', 'above', $2)
+            "#,
+        )
+        .bind(id)
+        .bind(threshold)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+    sqlx::query(
+        r#"
+        INSERT INTO webhooks (id, user_id, url, event_types, secret)
+        VALUES ('hook-1', 'user-1', 'https://example.com/hook', 'anomaly.detected', 'secret')
+        "#,
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn new_detector(pool: SqlitePool) -> AnomalyDetector {
+    AnomalyDetector::new(
+        Arc::new(Database::new(pool)),
+        Arc::new(WsState::new()),
+        AnomalyConfig::default(),
+    )
+}
+
+#[sqlx::test]
+async fn test_detects_spikes_against_seasonal_baseline(pool: SqlitePool) {
+    let now = Utc::now();
+    seed(&pool, now).await;
+    let detector = new_detector(pool.clone());
+
+    let summary = detector.run_at(now).await.unwrap();
+    assert!(summary.points_scored > 0);
+    assert_eq!(summary.anomalies, 2);
+
+    let anomalies = detector
+        .get_anomalies(&AnomalyFilter {
+            limit: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(anomalies.len(), 2);
+
+    let corridor = anomalies
+        .iter()
+        .find(|a| a.subject_type == "corridor")
+        .unwrap();
+    assert_eq!(corridor.subject_id, CORRIDOR);
+    assert_eq!(corridor.metric, "success_rate");
+    assert_eq!(corridor.kind, "spike");
+    assert_eq!(corridor.baseline, "seasonal");
+    assert_eq!(corridor.severity, "critical");
+    assert_eq!(corridor.observed_value, 60.0);
+    assert!(corridor.expected_low > 80.0 && corridor.expected_high <= 100.0);
+
+    let anchor = anomalies
+        .iter()
+        .find(|a| a.subject_type == "anchor")
+        .unwrap();
+    assert_eq!(anchor.subject_id, ANCHOR_ID);
+    assert_eq!(anchor.metric, "settlement_latency_ms");
+    assert!(anchor.expected_high < 20_000.0);
+
+    // Quiet nights are normal for the hour of the week: no volume anomalies
+    assert!(anomalies.iter().all(|a| a.metric != "volume_usd"));
+
+    let history: Vec<(String, String, f64)> = sqlx::query_as(
+        "SELECT rule_id, metric_type, trigger_value FROM alert_history ORDER BY trigger_value",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(history.len(), 2);
+    assert!(history
+        .iter()
+        .all(|(rule, metric, _)| rule == "rule-any" && metric == "anomaly"));
+
+    let webhook_events: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM webhook_events WHERE event_type = 'anomaly.detected'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(webhook_events, 2);
+}
+
+#[sqlx::test]
+async fn test_runs_only_score_new_hours(pool: SqlitePool) {
+    let now = Utc::now();
+    seed(&pool, now).await;
+    let detector = new_detector(pool.clone());
+    detector.run_at(now).await.unwrap();
+
+    // Nothing completed since the previous run
+    let summary = detector.run_at(now).await.unwrap();
+    assert_eq!(summary.points_scored, 0);
+    assert_eq!(summary.anomalies, 0);
+
+    // A restarted detector resumes from the stored state
+    let next_hour = now.duration_trunc(Duration::hours(1)).unwrap();
+    insert_corridor_hour(&pool, next_hour, 97.0).await;
+    let restarted = new_detector(pool.clone());
+    let summary = restarted
+        .run_at(next_hour + Duration::hours(1))
+        .await
+        .unwrap();
+    // Success rate, volume, latency and liquidity of the corridor
+    assert_eq!(summary.points_scored, 4);
+    assert_eq!(summary.anomalies, 0);
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM anomalies")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 2);
+}
@@ -13,6 +13,9 @@ use stellar_insights_backend::api::corridors::{get_corridor_detail, list_corrido
 use stellar_insights_backend::database::Database;
 use stellar_insights_backend::ingestion::DataIngestionService;
 use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::corridor_health::{
+    CorridorHealthScorer, HealthScoreConfig,
+};
 use stellar_insights_backend::state::AppState;
 use stellar_insights_backend::websocket::WsState;
 
@@ -29,10 +32,15 @@ fn create_test_router(db: Arc<Database>) -> Router {
     let ws_state = Arc::new(WsState::new());
     let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
     let ingestion = Arc::new(DataIngestionService::new(rpc_client, Arc::clone(&db)));
+    let health_scorer = Arc::new(CorridorHealthScorer::new(
+        Arc::clone(&db),
+        HealthScoreConfig::default(),
+    ));
     let state = AppState {
         db,
         ws_state,
         ingestion,
+        health_scorer,
     };
     Router::new()
         .route("/api/corridors", axum::routing::get(list_corridors))
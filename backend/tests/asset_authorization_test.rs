@@ -0,0 +1,163 @@
+use sqlx::SqlitePool;
+
+use stellar_insights_backend::ingestion::processor::{LedgerBundle, LedgerProcessor};
+use stellar_insights_backend::rpc::HorizonOperation;
+use stellar_insights_backend::services::asset_authorization::{
+    AssetAuthorizationTracker, AuthorizationEventFilter, AuthorizationEventType,
+};
+
+const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+const HOLDER: &str = "GHOLDERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+fn tracker(pool: SqlitePool) -> AssetAuthorizationTracker {
+    AssetAuthorizationTracker::new(pool)
+}
+
+fn operation(id: &str, operation_type: &str, created_at: &str) -> HorizonOperation {
+    HorizonOperation {
+        id: id.to_string(),
+        paging_token: id.to_string(),
+        transaction_hash: format!("tx_{}", id),
+        source_account: ISSUER.to_string(),
+        operation_type: operation_type.to_string(),
+        created_at: created_at.to_string(),
+        asset_code: Some("USDC".to_string()),
+        asset_issuer: Some(ISSUER.to_string()),
+        ..Default::default()
+    }
+}
+
+fn ledger(operations: Vec<HorizonOperation>) -> LedgerBundle {
+    let mut bundle = LedgerBundle::new(700);
+    bundle.operations = operations;
+    bundle
+}
+
+#[sqlx::test]
+async fn test_records_freeze_and_clawbacks(pool: SqlitePool) {
+    sqlx::query(
+        r#"
+        INSERT INTO claimable_balances (balance_id, asset_code, asset_issuer, amount)
+        VALUES ('cb1', 'USDC', $1, 40.0)
+        "#,
+    )
+    .bind(ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let tracker = tracker(pool);
+
+    let freeze = HorizonOperation {
+        trustor: Some(HOLDER.to_string()),
+        clear_flags_s: vec!["authorized".to_string()],
+        ..operation("op1", "set_trust_line_flags", "2026-04-01T10:00:00Z")
+    };
+    let clawback = HorizonOperation {
+        from: Some(HOLDER.to_string()),
+        amount: Some("100.0000000".to_string()),
+        ..operation("op2", "clawback", "2026-04-01T11:00:00Z")
+    };
+    let balance_clawback = HorizonOperation {
+        balance_id: Some("cb1".to_string()),
+        asset_code: None,
+        asset_issuer: None,
+        ..operation("op3", "clawback_claimable_balance", "2026-04-02T09:00:00Z")
+    };
+    let payment = operation("op4", "payment", "2026-04-02T10:00:00Z");
+    let bundle = ledger(vec![freeze, clawback, balance_clawback, payment]);
+
+    assert_eq!(tracker.process(&bundle).await.unwrap(), 3);
+    assert_eq!(tracker.process(&bundle).await.unwrap(), 0);
+
+    let frozen = tracker
+        .get_events(&AuthorizationEventFilter {
+            event_type: Some(AuthorizationEventType::TrustlineDeauthorized),
+            limit: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(frozen.len(), 1);
+    assert_eq!(frozen[0].account.as_deref(), Some(HOLDER));
+    // The trustor's balance at the time of the freeze is not in the ledger data
+    assert_eq!(frozen[0].amount, None);
+    assert_eq!(frozen[0].clear_flags, "authorized");
+
+    let summaries = tracker
+        .get_issuer_summaries(Some(ISSUER), 10)
+        .await
+        .unwrap();
+    assert_eq!(summaries.len(), 1);
+    let usdc = &summaries[0];
+    assert_eq!(usdc.asset_code.as_deref(), Some("USDC"));
+    assert_eq!(usdc.freeze_count, 1);
+    assert_eq!(usdc.clawback_count, 2);
+    assert_eq!(usdc.clawed_back_amount, 140.0);
+
+    let activity = tracker.get_issuer_activity(ISSUER, 3650).await.unwrap();
+    assert_eq!(activity.len(), 2);
+    assert_eq!(activity[0].date, "2026-04-01");
+    assert_eq!(activity[0].clawed_back_amount, 100.0);
+    assert_eq!(activity[1].clawed_back_amount, 40.0);
+}
+
+#[sqlx::test]
+async fn test_records_issuer_flag_changes(pool: SqlitePool) {
+    sqlx::query(
+        r#"
+        INSERT INTO asset_supply_snapshots (asset_code, asset_issuer, circulating_supply, authorized_supply, snapshot_at)
+        VALUES ('USDC', $1, 1000.0, 1000.0, '2026-04-01T00:00:00Z')
+        "#,
+    )
+    .bind(ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+    let tracker = tracker(pool);
+
+    let flags = HorizonOperation {
+        set_flags_s: vec![
+            "auth_revocable".to_string(),
+            "auth_clawback_enabled".to_string(),
+        ],
+        asset_code: None,
+        asset_issuer: None,
+        ..operation("op1", "set_options", "2026-04-01T10:00:00Z")
+    };
+    let signer_only = HorizonOperation {
+        asset_code: None,
+        asset_issuer: None,
+        ..operation("op2", "set_options", "2026-04-01T10:01:00Z")
+    };
+    // Flag changes on an account that issues nothing tracked are ignored
+    let non_issuer = HorizonOperation {
+        source_account: HOLDER.to_string(),
+        set_flags_s: vec!["auth_required".to_string()],
+        asset_code: None,
+        asset_issuer: None,
+        ..operation("op3", "set_options", "2026-04-01T10:02:00Z")
+    };
+    assert_eq!(
+        tracker
+            .process(&ledger(vec![flags, signer_only, non_issuer]))
+            .await
+            .unwrap(),
+        1
+    );
+
+    let events = tracker
+        .get_events(&AuthorizationEventFilter {
+            issuer: Some(ISSUER.to_string()),
+            limit: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type, "issuer_flags_changed");
+    assert_eq!(events[0].asset_code, None);
+    assert_eq!(events[0].set_flags, "auth_revocable,auth_clawback_enabled");
+
+    let summaries = tracker.get_issuer_summaries(None, 10).await.unwrap();
+    assert_eq!(summaries[0].flag_change_count, 1);
+}
@@ -0,0 +1,190 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use stellar_insights_backend::ingestion::processor::{LedgerBundle, LedgerProcessor};
+use stellar_insights_backend::rpc::{
+    AssetAccounts, AssetBalances, AssetFlags, HorizonAsset, Payment, StellarRpcClient,
+};
+use stellar_insights_backend::services::asset_supply::{AssetSupplyConfig, AssetSupplyTracker};
+
+const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+fn tracker(pool: SqlitePool) -> AssetSupplyTracker {
+    AssetSupplyTracker::new(
+        pool,
+        Arc::new(StellarRpcClient::new_with_defaults(true)),
+        AssetSupplyConfig {
+            jump_threshold_pct: 10.0,
+            ..Default::default()
+        },
+    )
+}
+
+fn asset(code: &str, authorized: f64, pools: f64) -> HorizonAsset {
+    HorizonAsset {
+        asset_type: "credit_alphanum4".to_string(),
+        asset_code: code.to_string(),
+        asset_issuer: ISSUER.to_string(),
+        num_claimable_balances: 0,
+        num_liquidity_pools: 1,
+        num_contracts: 0,
+        accounts: AssetAccounts {
+            authorized: 100,
+            authorized_to_maintain_liabilities: 0,
+            unauthorized: 5,
+        },
+        claimable_balances_amount: "0.0000000".to_string(),
+        liquidity_pools_amount: format!("{pools:.7}"),
+        contracts_amount: "0.0000000".to_string(),
+        balances: AssetBalances {
+            authorized: format!("{authorized:.7}"),
+            authorized_to_maintain_liabilities: "0.0000000".to_string(),
+            unauthorized: "0.0000000".to_string(),
+        },
+        flags: AssetFlags {
+            auth_required: false,
+            auth_revocable: false,
+            auth_immutable: false,
+            auth_clawback_enabled: false,
+        },
+    }
+}
+
+fn payment(id: &str, from: &str, to: &str, amount: &str, created_at: &str) -> Payment {
+    Payment {
+        id: id.to_string(),
+        paging_token: id.to_string(),
+        transaction_hash: format!("tx_{id}"),
+        source_account: from.to_string(),
+        destination: to.to_string(),
+        asset_type: "credit_alphanum4".to_string(),
+        asset_code: Some("USDC".to_string()),
+        asset_issuer: Some(ISSUER.to_string()),
+        amount: amount.to_string(),
+        created_at: created_at.to_string(),
+        operation_type: Some("payment".to_string()),
+        source_asset_type: None,
+        source_asset_code: None,
+        source_asset_issuer: None,
+        source_amount: None,
+        path: vec![],
+        from: Some(from.to_string()),
+        to: Some(to.to_string()),
+        transaction_successful: None,
+        asset_balance_changes: None,
+    }
+}
+
+#[sqlx::test]
+async fn test_supply_snapshots_flag_jumps(pool: SqlitePool) {
+    let tracker = tracker(pool);
+
+    let first = tracker
+        .record_snapshots(&[asset("USDC", 900.0, 100.0), asset("EURC", 500.0, 0.0)])
+        .await
+        .unwrap();
+    assert_eq!(first.assets, 2);
+    assert_eq!(first.jumps, 0);
+
+    // USDC +50%, EURC +2%
+    let second = tracker
+        .record_snapshots(&[asset("USDC", 1400.0, 100.0), asset("EURC", 510.0, 0.0)])
+        .await
+        .unwrap();
+    assert_eq!(second.jumps, 1);
+
+    let history = tracker
+        .get_supply_history("USDC", ISSUER, 10)
+        .await
+        .unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].circulating_supply, 1000.0);
+    assert_eq!(history[0].change_pct, None);
+    assert_eq!(history[1].circulating_supply, 1500.0);
+    assert_eq!(history[1].holders, 105);
+    assert!((history[1].change_pct.unwrap() - 50.0).abs() < 1e-9);
+
+    let jumps = tracker.get_supply_jumps(10).await.unwrap();
+    assert_eq!(jumps.len(), 1);
+    assert_eq!(jumps[0].asset_code, "USDC");
+
+    let latest = tracker.get_latest_supplies(10).await.unwrap();
+    assert_eq!(latest.len(), 2);
+    assert_eq!(latest[0].asset_code, "USDC");
+    assert_eq!(latest[0].circulating_supply, 1500.0);
+    assert!(!latest[1].supply_jump);
+}
+
+#[sqlx::test]
+async fn test_snapshot_supply_from_horizon(pool: SqlitePool) {
+    let tracker = tracker(pool);
+    let summary = tracker.snapshot_supply().await.unwrap();
+    assert!(summary.assets > 0);
+    assert_eq!(
+        tracker.get_latest_supplies(100).await.unwrap().len() as u64,
+        summary.assets
+    );
+}
+
+#[sqlx::test]
+async fn test_processor_records_mints_and_burns(pool: SqlitePool) {
+    let tracker = tracker(pool);
+
+    let mut bundle = LedgerBundle::new(500);
+    bundle.payments = vec![
+        payment(
+            "op1",
+            ISSUER,
+            "GHOLDER1",
+            "1000.0000000",
+            "2026-03-01T10:00:00Z",
+        ),
+        payment(
+            "op2",
+            "GHOLDER1",
+            ISSUER,
+            "250.0000000",
+            "2026-03-01T12:00:00Z",
+        ),
+        payment(
+            "op3",
+            "GHOLDER1",
+            "GHOLDER2",
+            "50.0000000",
+            "2026-03-01T13:00:00Z",
+        ),
+        payment(
+            "op4",
+            ISSUER,
+            "GHOLDER2",
+            "300.0000000",
+            "2026-03-02T09:00:00Z",
+        ),
+    ];
+
+    assert_eq!(tracker.process(&bundle).await.unwrap(), 3);
+    // Replaying the ledger is idempotent
+    assert_eq!(tracker.process(&bundle).await.unwrap(), 0);
+
+    let events = tracker
+        .get_issuance_events("USDC", ISSUER, 10)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 3);
+    let burn = events.iter().find(|e| e.operation_id == "op2").unwrap();
+    assert_eq!(burn.event_type, "burn");
+    assert_eq!(burn.counterparty, "GHOLDER1");
+    assert_eq!(burn.amount, 250.0);
+
+    let trend = tracker
+        .get_issuance_trend("USDC", ISSUER, 3650)
+        .await
+        .unwrap();
+    assert_eq!(trend.len(), 2);
+    assert_eq!(trend[0].date, "2026-03-01");
+    assert_eq!(trend[0].minted, 1000.0);
+    assert_eq!(trend[0].burned, 250.0);
+    assert_eq!(trend[0].net_issuance, 750.0);
+    assert_eq!((trend[0].mint_count, trend[0].burn_count), (1, 1));
+    assert_eq!(trend[1].net_issuance, 300.0);
+}
@@ -0,0 +1,219 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use stellar_insights_backend::ingestion::backfill::{
+    BackfillConfig, BackfillError, BackfillRequest, BackfillService, GapFillRequest, LedgerGap,
+};
+use stellar_insights_backend::ingestion::ledger::LedgerIngestionService;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+
+// Bounds of the mock RPC retention window
+const MOCK_OLDEST: u64 = 51_565_760;
+const MOCK_LATEST: u64 = 51_565_820;
+
+fn backfill_service(pool: &SqlitePool) -> Arc<BackfillService> {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let ingestion = Arc::new(LedgerIngestionService::new(
+        Arc::clone(&rpc_client),
+        Arc::new(FeeBumpTrackerService::new(pool.clone())),
+        Arc::new(AccountMergeDetector::new(
+            pool.clone(),
+            Arc::clone(&rpc_client),
+        )),
+        pool.clone(),
+    ));
+    Arc::new(
+        BackfillService::new(ingestion, rpc_client, pool.clone()).with_config(BackfillConfig {
+            shard_size: 10,
+            concurrency: 3,
+            batch_size: 4,
+        }),
+    )
+}
+
+async fn insert_ledger(pool: &SqlitePool, sequence: u64) {
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count) VALUES ($1, $2, '2026-01-22T10:30:00Z', 0, 0)",
+    )
+    .bind(sequence as i64)
+    .bind(format!("hash_{}", sequence))
+    .execute(pool)
+    .await
+    .expect("failed to insert ledger row");
+}
+
+async fn stored_sequences(pool: &SqlitePool) -> Vec<i64> {
+    sqlx::query_scalar("SELECT sequence FROM ledgers ORDER BY sequence")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_range_backfill_ingests_every_shard(pool: SqlitePool) {
+    let service = backfill_service(&pool);
+    let start = MOCK_OLDEST;
+    let end = MOCK_OLDEST + 24;
+
+    let job = service
+        .create_range_job(BackfillRequest {
+            start_ledger: start,
+            end_ledger: end,
+            shard_size: None,
+            concurrency: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(job.status, "pending");
+
+    let job = service.run_job(&job.id).await.unwrap();
+    assert_eq!(job.status, "completed");
+    assert_eq!(job.ledgers_ingested, 25);
+
+    let detail = service.get_job(&job.id).await.unwrap().unwrap();
+    assert_eq!(detail.shards.len(), 3);
+    for shard in &detail.shards {
+        assert_eq!(shard.status, "completed");
+        assert_eq!(shard.checkpoint_ledger, Some(shard.end_ledger));
+    }
+
+    let expected: Vec<i64> = (start as i64..=end as i64).collect();
+    assert_eq!(stored_sequences(&pool).await, expected);
+}
+
+#[sqlx::test]
+async fn test_range_outside_retention_is_rejected(pool: SqlitePool) {
+    let service = backfill_service(&pool);
+
+    let err = service
+        .create_range_job(BackfillRequest {
+            start_ledger: MOCK_OLDEST - 10,
+            end_ledger: MOCK_OLDEST + 10,
+            shard_size: None,
+            concurrency: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BackfillError::InvalidRange(_)));
+
+    let err = service
+        .create_range_job(BackfillRequest {
+            start_ledger: MOCK_LATEST,
+            end_ledger: MOCK_OLDEST,
+            shard_size: None,
+            concurrency: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, BackfillError::InvalidRange(_)));
+}
+
+#[sqlx::test]
+async fn test_gap_scan_and_fill(pool: SqlitePool) {
+    let service = backfill_service(&pool);
+    for seq in [0, 1, 2, 6, 7, 12] {
+        insert_ledger(&pool, MOCK_OLDEST + seq).await;
+    }
+
+    let gaps = service.find_gaps(None, None).await.unwrap();
+    assert_eq!(
+        gaps,
+        vec![
+            LedgerGap {
+                start: MOCK_OLDEST + 3,
+                end: MOCK_OLDEST + 5,
+            },
+            LedgerGap {
+                start: MOCK_OLDEST + 8,
+                end: MOCK_OLDEST + 11,
+            },
+        ]
+    );
+
+    // Explicit bounds also report missing edges
+    let gaps = service
+        .find_gaps(Some(MOCK_OLDEST), Some(MOCK_OLDEST + 14))
+        .await
+        .unwrap();
+    assert_eq!(gaps.len(), 3);
+    assert_eq!(
+        gaps[2],
+        LedgerGap {
+            start: MOCK_OLDEST + 13,
+            end: MOCK_OLDEST + 14,
+        }
+    );
+
+    let job = service
+        .create_gap_job(GapFillRequest::default())
+        .await
+        .unwrap()
+        .expect("gaps should produce a job");
+    assert_eq!(job.kind, "gaps");
+
+    let job = service.run_job(&job.id).await.unwrap();
+    assert_eq!(job.status, "completed");
+    assert_eq!(job.ledgers_ingested, 7);
+
+    assert!(service.find_gaps(None, None).await.unwrap().is_empty());
+    assert!(service
+        .create_gap_job(GapFillRequest::default())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[sqlx::test]
+async fn test_backfill_skips_already_stored_ledgers(pool: SqlitePool) {
+    let service = backfill_service(&pool);
+    insert_ledger(&pool, MOCK_OLDEST + 2).await;
+
+    let job = service
+        .create_range_job(BackfillRequest {
+            start_ledger: MOCK_OLDEST,
+            end_ledger: MOCK_OLDEST + 4,
+            shard_size: None,
+            concurrency: None,
+        })
+        .await
+        .unwrap();
+    let job = service.run_job(&job.id).await.unwrap();
+
+    assert_eq!(job.status, "completed");
+    assert_eq!(job.ledgers_ingested, 4);
+    assert_eq!(stored_sequences(&pool).await.len(), 5);
+}
+
+#[sqlx::test]
+async fn test_shard_checkpoint_stops_at_failed_ledger(pool: SqlitePool) {
+    let service = backfill_service(&pool);
+    // A stored successor that does not link back quarantines MOCK_OLDEST + 2
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time, previous_hash) VALUES ($1, 'hash_x', '2026-01-22T10:30:00Z', 'unrelated')",
+    )
+    .bind((MOCK_OLDEST + 3) as i64)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let job = service
+        .create_range_job(BackfillRequest {
+            start_ledger: MOCK_OLDEST,
+            end_ledger: MOCK_OLDEST + 4,
+            shard_size: None,
+            concurrency: None,
+        })
+        .await
+        .unwrap();
+    let job = service.run_job(&job.id).await.unwrap();
+    assert_eq!(job.status, "failed");
+
+    let detail = service.get_job(&job.id).await.unwrap().unwrap();
+    assert_eq!(detail.shards[0].status, "failed");
+    assert_eq!(
+        detail.shards[0].checkpoint_ledger,
+        Some(MOCK_OLDEST as i64 + 1)
+    );
+}
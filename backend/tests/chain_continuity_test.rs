@@ -0,0 +1,125 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use stellar_insights_backend::ingestion::chain::{check_incoming, verify_chain, ChainViolation};
+use stellar_insights_backend::ingestion::ledger::LedgerIngestionService;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+
+async fn insert_ledger(pool: &SqlitePool, sequence: i64, hash: &str, previous_hash: Option<&str>) {
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count, previous_hash) VALUES ($1, $2, '2026-01-22T10:30:00Z', 0, 0, $3)",
+    )
+    .bind(sequence)
+    .bind(hash)
+    .bind(previous_hash)
+    .execute(pool)
+    .await
+    .expect("failed to insert ledger row");
+}
+
+#[sqlx::test]
+async fn test_verify_chain_reports_breaks(pool: SqlitePool) {
+    insert_ledger(&pool, 100, "aa", None).await;
+    insert_ledger(&pool, 101, "bb", Some("AA")).await;
+    insert_ledger(&pool, 102, "cc", Some("ff")).await;
+    insert_ledger(&pool, 103, "dd", None).await;
+    insert_ledger(&pool, 105, "ee", Some("zz")).await;
+
+    let report = verify_chain(&pool, None, None).await.unwrap();
+    assert_eq!(report.ledgers_checked, 5);
+    assert_eq!(report.links_verified, 1);
+    assert_eq!(report.links_unverified, 1);
+    assert!(!report.is_intact());
+    assert_eq!(report.breaks.len(), 1);
+    assert_eq!(report.breaks[0].sequence, 102);
+    assert_eq!(report.breaks[0].predecessor_hash, "bb");
+
+    let report = verify_chain(&pool, Some(100), Some(101)).await.unwrap();
+    assert!(report.is_intact());
+}
+
+#[sqlx::test]
+async fn test_check_incoming_detects_each_violation(pool: SqlitePool) {
+    insert_ledger(&pool, 200, "aa", None).await;
+    insert_ledger(&pool, 202, "cc", Some("bb")).await;
+
+    assert_eq!(
+        check_incoming(&pool, 201, "bb", Some("aa")).await.unwrap(),
+        None
+    );
+    assert_eq!(
+        check_incoming(&pool, 201, "bb", Some("99")).await.unwrap(),
+        Some(ChainViolation::PreviousHashMismatch {
+            expected: "aa".to_string(),
+            actual: "99".to_string(),
+        })
+    );
+    assert_eq!(
+        check_incoming(&pool, 201, "b2", Some("aa")).await.unwrap(),
+        Some(ChainViolation::SuccessorMismatch {
+            successor_sequence: 202,
+            expected: "b2".to_string(),
+            actual: "bb".to_string(),
+        })
+    );
+    assert_eq!(
+        check_incoming(&pool, 200, "a2", None).await.unwrap(),
+        Some(ChainViolation::HashConflict {
+            stored_hash: "aa".to_string(),
+        })
+    );
+    // Re-ingesting the stored ledger is not a violation
+    assert_eq!(check_incoming(&pool, 200, "aa", None).await.unwrap(), None);
+}
+
+#[sqlx::test]
+async fn test_ingestion_quarantines_forked_ledger(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let service = LedgerIngestionService::new(
+        Arc::clone(&rpc_client),
+        Arc::new(FeeBumpTrackerService::new(pool.clone())),
+        Arc::new(AccountMergeDetector::new(
+            pool.clone(),
+            Arc::clone(&rpc_client),
+        )),
+        pool.clone(),
+    );
+
+    let ledgers = rpc_client
+        .fetch_ledgers(Some(51_565_760), 3, None)
+        .await
+        .unwrap()
+        .ledgers;
+    insert_ledger(&pool, 51_565_761, "some_other_hash", None).await;
+
+    assert_eq!(service.process_ledgers(&ledgers).await.unwrap(), (2, None));
+
+    let quarantined: Vec<(i64, String, String)> =
+        sqlx::query_as("SELECT sequence, reason, expected_hash FROM quarantined_ledgers")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        quarantined,
+        vec![(
+            51_565_761,
+            "hash_conflict".to_string(),
+            "some_other_hash".to_string()
+        )]
+    );
+
+    let stored: String = sqlx::query_scalar("SELECT hash FROM ledgers WHERE sequence = 51565761")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, "some_other_hash");
+
+    let payments: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM ledger_payments WHERE ledger_sequence = 51565761")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(payments, 0);
+}
@@ -0,0 +1,185 @@
+use sqlx::SqlitePool;
+
+use stellar_insights_backend::ingestion::processor::{LedgerBundle, LedgerProcessor};
+use stellar_insights_backend::rpc::HorizonEffect;
+use stellar_insights_backend::services::claimable_balance_tracker::{
+    ClaimableBalanceFilter, ClaimableBalanceTracker,
+};
+
+const ISSUER: &str = "GDUKMGUGDZQK6YHYA5Z6AY2G4XDSZPSZ3SW5UN3ARVMO6QSRDWP5YLEX";
+
+fn effect(
+    effect_type: &str,
+    balance_id: &str,
+    account: &str,
+    amount: Option<&str>,
+    created_at: &str,
+) -> HorizonEffect {
+    HorizonEffect {
+        id: format!("{}-{}", balance_id, effect_type),
+        paging_token: String::new(),
+        effect_type: effect_type.to_string(),
+        account: Some(account.to_string()),
+        amount: amount.map(str::to_string),
+        asset_type: None,
+        asset: Some(format!("USDC:{}", ISSUER)),
+        balance_id: Some(balance_id.to_string()),
+        created_at: Some(created_at.to_string()),
+        ..Default::default()
+    }
+}
+
+fn bundle(sequence: u64, effects: Vec<HorizonEffect>) -> LedgerBundle {
+    LedgerBundle {
+        effects,
+        ..LedgerBundle::new(sequence)
+    }
+}
+
+async fn insert_anchor(pool: &SqlitePool) {
+    sqlx::query(
+        "INSERT INTO anchors (id, name, stellar_account) VALUES ('anchor-1', 'Circle', $1)",
+    )
+    .bind(ISSUER)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_claimable_balance_lifecycle(pool: SqlitePool) {
+    insert_anchor(&pool).await;
+    let tracker = ClaimableBalanceTracker::new(pool.clone());
+
+    let created = bundle(
+        100,
+        vec![
+            effect(
+                "claimable_balance_created",
+                "b1",
+                "GSPONSOR",
+                Some("50.0"),
+                "2024-01-01T00:00:00Z",
+            ),
+            effect(
+                "claimable_balance_claimant_created",
+                "b1",
+                "GCLAIMANT",
+                Some("50.0"),
+                "2024-01-01T00:00:00Z",
+            ),
+            HorizonEffect {
+                sponsor: Some("GSPONSOR".to_string()),
+                ..effect(
+                    "claimable_balance_sponsorship_created",
+                    "b1",
+                    "GCREATOR",
+                    None,
+                    "2024-01-01T00:00:00Z",
+                )
+            },
+            effect(
+                "claimable_balance_created",
+                "b2",
+                "GSPONSOR",
+                Some("25.0"),
+                "2024-01-01T00:00:00Z",
+            ),
+            effect(
+                "claimable_balance_created",
+                "b3",
+                "GSPONSOR",
+                Some("10.0"),
+                "2024-01-01T00:00:00Z",
+            ),
+        ],
+    );
+    assert_eq!(tracker.process(&created).await.unwrap(), 5);
+
+    let resolved = bundle(
+        200,
+        vec![
+            effect(
+                "claimable_balance_claimed",
+                "b1",
+                "GCLAIMANT",
+                Some("50.0"),
+                "2024-01-01T02:00:00Z",
+            ),
+            effect(
+                "claimable_balance_clawed_back",
+                "b3",
+                ISSUER,
+                Some("10.0"),
+                "2024-01-02T00:00:00Z",
+            ),
+        ],
+    );
+    assert_eq!(tracker.process(&resolved).await.unwrap(), 2);
+    // A replayed claim is a no-op once the balance is resolved
+    assert_eq!(tracker.process(&resolved).await.unwrap(), 0);
+
+    let b1 = tracker.get_balance("b1").await.unwrap().unwrap();
+    assert_eq!(b1.status, "claimed");
+    assert_eq!(b1.claimed_by.as_deref(), Some("GCLAIMANT"));
+    assert_eq!(b1.claimants, vec!["GCLAIMANT".to_string()]);
+    assert_eq!(b1.created_ledger, Some(100));
+    assert_eq!(b1.resolved_ledger, Some(200));
+    assert_eq!(b1.sponsor.as_deref(), Some("GSPONSOR"));
+
+    // The creating account is not taken for the sponsor
+    let b2 = tracker.get_balance("b2").await.unwrap().unwrap();
+    assert_eq!(b2.sponsor, None);
+
+    let filter = ClaimableBalanceFilter {
+        claimant: Some("GCLAIMANT".to_string()),
+        ..Default::default()
+    };
+    let by_claimant = tracker.list_balances(&filter, 50, 0).await.unwrap();
+    assert_eq!(by_claimant.len(), 1);
+
+    let stats = tracker.get_stats().await.unwrap();
+    assert_eq!(
+        (
+            stats.total,
+            stats.unclaimed,
+            stats.claimed,
+            stats.clawed_back
+        ),
+        (3, 1, 1, 1)
+    );
+    assert_eq!(stats.unclaimed_by_asset.len(), 1);
+    assert!((stats.unclaimed_by_asset[0].unclaimed_amount - 25.0).abs() < f64::EPSILON);
+    assert_eq!(stats.time_to_claim.sample_size, 1);
+    assert_eq!(stats.time_to_claim.median_seconds, Some(7_200.0));
+
+    let anchors = tracker.get_unclaimed_by_anchor().await.unwrap();
+    assert_eq!(anchors.len(), 1);
+    assert_eq!(anchors[0].anchor_id, "anchor-1");
+    assert_eq!(anchors[0].unclaimed_count, 1);
+}
+
+#[sqlx::test]
+async fn test_claim_of_balance_created_before_ingestion(pool: SqlitePool) {
+    let tracker = ClaimableBalanceTracker::new(pool.clone());
+
+    let claimed = bundle(
+        300,
+        vec![effect(
+            "claimable_balance_claimed",
+            "old",
+            "GCLAIMANT",
+            Some("5.0"),
+            "2024-02-01T00:00:00Z",
+        )],
+    );
+    assert_eq!(tracker.process(&claimed).await.unwrap(), 1);
+
+    let balance = tracker.get_balance("old").await.unwrap().unwrap();
+    assert_eq!(balance.status, "claimed");
+    assert_eq!(balance.created_ledger, None);
+
+    // Unknown creation time keeps it out of the time-to-claim distribution
+    let stats = tracker.get_stats().await.unwrap();
+    assert_eq!(stats.time_to_claim.sample_size, 0);
+}
@@ -0,0 +1,65 @@
+//! Fixtures shared by the integration tests; each test crate uses a subset
+#![allow(dead_code)]
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use chrono::{DateTime, Duration, DurationRound, Timelike, Utc};
+use sqlx::SqlitePool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+/// Hourly volume in USD: busy afternoons, quiet nights
+pub fn daily_volume(hour: DateTime<Utc>) -> f64 {
+    match hour.hour() {
+        0..=5 => 1_000.0,
+        6..=11 => 20_000.0,
+        12..=17 => 60_000.0,
+        _ => 10_000.0,
+    }
+}
+
+/// Ten days of hourly metrics for `CODE:ISSUER->XLM:native` corridors, given
+/// as `(code, issuer, base, spread)`: each hour 100 payments are made, of
+/// which `base + h % spread` succeed, `h` hours before the current hour
+pub async fn seed_hourly_metrics(pool: &SqlitePool, corridors: &[(&str, &str, i64, i64)]) {
+    let now = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+    for h in 1..=240 {
+        let hour = now - Duration::hours(h);
+        for &(code, issuer, base, spread) in corridors {
+            let successes = base + h % spread;
+            sqlx::query(
+                r#"
+                INSERT INTO corridor_metrics_hourly (
+                    id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code,
+                    asset_b_issuer, hour_bucket, total_transactions, successful_transactions,
+                    failed_transactions, success_rate, volume_usd, liquidity_depth_usd
+                )
+                VALUES ($1, $2, $3, $4, 'XLM', 'native', $5, 100, $6, $7, $8, 25000.0, 50000.0)
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(format!("{}:{}->XLM:native", code, issuer))
+            .bind(code)
+            .bind(issuer)
+            .bind(hour.to_rfc3339())
+            .bind(successes)
+            .bind(100 - successes)
+            .bind(successes as f64)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+    }
+}
+
+/// Send a GET through the router; returns the status and the JSON body
+pub async fn get(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
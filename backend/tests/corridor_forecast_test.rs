@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use chrono::{DateTime, Duration, DurationRound, Timelike, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use stellar_insights_backend::api::corridor_forecast;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::corridor_forecast::{CorridorForecaster, ForecastConfig};
+
+mod common;
+use common::{daily_volume, get};
+
+const CORRIDOR: &str = "USDC:GAISSUER->XLM:native";
+const CORRIDOR_PATH: &str = "USDC:GAISSUER-%3EXLM:native";
+
+/// Fourteen days of hourly history up to the hour before the current one
+async fn seed(pool: &SqlitePool) {
+    seed_until(pool, 1).await;
+}
+
+/// Hourly history from fourteen days back up to `latest` hours before the
+/// current one
+async fn seed_until(pool: &SqlitePool, latest: i64) {
+    let current_hour = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+    for h in latest..=24 * 14 {
+        let hour = current_hour - Duration::hours(h);
+        let transactions = (daily_volume(hour) / 500.0) as i64;
+        let success_rate = 95.0 + (h % 3) as f64;
+        sqlx::query(
+            r#"
+            INSERT INTO corridor_metrics_hourly (
+                id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                hour_bucket, total_transactions, successful_transactions, failed_transactions,
+                success_rate, volume_usd
+            )
+            VALUES ($1, $2, 'USDC', 'GAISSUER', 'XLM', 'native', $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(CORRIDOR)
+        .bind(hour.to_rfc3339())
+        .bind(transactions)
+        .bind(transactions)
+        .bind(0)
+        .bind(success_rate)
+        .bind(daily_volume(hour))
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+}
+
+fn new_forecaster(pool: SqlitePool) -> Arc<CorridorForecaster> {
+    Arc::new(CorridorForecaster::new(
+        Arc::new(Database::new(pool)),
+        ForecastConfig::default(),
+    ))
+}
+
+#[sqlx::test]
+async fn test_forecast_follows_daily_cycle(pool: SqlitePool) {
+    seed(&pool).await;
+    let forecaster = new_forecaster(pool.clone());
+    assert_eq!(forecaster.refresh_all().await.unwrap(), 1);
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM corridor_forecasts")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 3);
+
+    let app = axum::Router::new().nest("/api/corridors", corridor_forecast::routes(forecaster));
+    let (status, body) = get(
+        &app,
+        &format!("/api/corridors/{}/forecast?horizon=48h", CORRIDOR_PATH),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["corridor_key"], CORRIDOR);
+    assert_eq!(body["horizon_hours"], 48);
+
+    let metrics = body["metrics"].as_array().unwrap();
+    let names: Vec<&str> = metrics
+        .iter()
+        .map(|m| m["metric"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["volume_usd", "transaction_count", "success_rate"]);
+
+    for metric in metrics {
+        let points = metric["points"].as_array().unwrap();
+        assert_eq!(points.len(), 48);
+        for point in points {
+            let value = point["value"].as_f64().unwrap();
+            assert!(point["lower"].as_f64().unwrap() <= value);
+            assert!(point["upper"].as_f64().unwrap() >= value);
+        }
+    }
+
+    // Afternoon volume is forecast well above the night's
+    let volume_at = |hour: u32| {
+        metrics[0]["points"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| {
+                DateTime::parse_from_rfc3339(p["timestamp"].as_str().unwrap())
+                    .unwrap()
+                    .hour()
+                    == hour
+            })
+            .unwrap()["value"]
+            .as_f64()
+            .unwrap()
+    };
+    assert!(volume_at(15) > 40_000.0);
+    assert!(volume_at(3) < 10_000.0);
+
+    let success = metrics[2]["points"][0]["value"].as_f64().unwrap();
+    assert!((90.0..=100.0).contains(&success));
+}
+
+#[sqlx::test]
+async fn test_forecast_starts_at_current_hour_after_quiet_hours(pool: SqlitePool) {
+    // Nothing recorded in the last three hours
+    seed_until(&pool, 4).await;
+    let current_hour = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+    let forecaster = new_forecaster(pool);
+    let forecast = forecaster
+        .get_forecast(CORRIDOR, 24)
+        .await
+        .unwrap()
+        .unwrap();
+
+    for metric in &forecast.metrics {
+        assert_eq!(metric.training_hours, 24 * 14);
+        assert_eq!(
+            DateTime::parse_from_rfc3339(&metric.points[0].timestamp).unwrap(),
+            current_hour
+        );
+    }
+}
+
+#[sqlx::test]
+async fn test_stale_forecast_is_refitted_on_read(pool: SqlitePool) {
+    seed(&pool).await;
+    let forecaster = new_forecaster(pool.clone());
+    forecaster.refresh_all().await.unwrap();
+
+    let stale_at = (Utc::now() - Duration::hours(2)).to_rfc3339();
+    sqlx::query("UPDATE corridor_forecasts SET generated_at = $1")
+        .bind(&stale_at)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let forecast = forecaster
+        .get_forecast(CORRIDOR, 24)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(forecast.generated_at > stale_at);
+    let stored: Vec<String> = sqlx::query_scalar("SELECT generated_at FROM corridor_forecasts")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert!(stored.iter().all(|at| *at == forecast.generated_at));
+}
+
+#[sqlx::test]
+async fn test_forecast_fitted_on_demand_and_validated(pool: SqlitePool) {
+    seed(&pool).await;
+    let app = axum::Router::new().nest(
+        "/api/corridors",
+        corridor_forecast::routes(new_forecaster(pool.clone())),
+    );
+
+    // Not refreshed by the job yet: fitted on the first request
+    let (status, body) = get(
+        &app,
+        &format!("/api/corridors/{}/forecast?horizon=2d", CORRIDOR_PATH),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["horizon_hours"], 48);
+
+    let (status, _) = get(
+        &app,
+        &format!("/api/corridors/{}/forecast?horizon=500h", CORRIDOR_PATH),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = get(&app, "/api/corridors/USDC/forecast").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, body) = get(
+        &app,
+        "/api/corridors/EURC:GCISSUER-%3EXLM:native/forecast?horizon=24h",
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error"]["code"], "FORECAST_NOT_FOUND");
+}
@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{Duration, DurationRound, Utc};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::corridor_health::{
+    CorridorHealthScorer, HealthScoreConfig,
+};
+
+const CORRIDOR: &str = "USDC:GAISSUER->XLM:native";
+
+/// A week of hourly history with liquidity draining over the last day
+async fn seed(pool: &SqlitePool) {
+    let current_hour = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+    for h in 1..=24 * 7 {
+        let hour = current_hour - Duration::hours(h);
+        let liquidity = if h <= 24 { 500_000.0 } else { 1_000_000.0 };
+        sqlx::query(
+            r#"
+            INSERT INTO corridor_metrics_hourly (
+                id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                hour_bucket, total_transactions, successful_transactions, failed_transactions,
+                success_rate, volume_usd, avg_settlement_latency_ms, liquidity_depth_usd
+            )
+            VALUES ($1, $2, 'USDC', 'GAISSUER', 'XLM', 'native', $3, 10, 10, 0, 100.0, 5000.0, $4, $5)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(CORRIDOR)
+        .bind(hour.to_rfc3339())
+        .bind(if h % 10 == 0 { 20_000 } else { 2_000 })
+        .bind(liquidity)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO anomalies (
+            id, subject_type, subject_id, metric, kind, baseline, observed_value,
+            expected_value, expected_low, expected_high, score, severity, observed_at, detected_at
+        )
+        VALUES ($1, 'corridor', $2, 'volume_usd', 'spike', 'ewma', 1, 1, 0, 2, 4.5, 'critical', $3, $3)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(CORRIDOR)
+    .bind((current_hour - Duration::hours(2)).to_rfc3339())
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_history_feeds_breakdown(pool: SqlitePool) {
+    seed(&pool).await;
+    let scorer =
+        CorridorHealthScorer::new(Arc::new(Database::new(pool)), HealthScoreConfig::default());
+    let history = scorer.load_history().await.unwrap();
+
+    let corridor = &history[CORRIDOR];
+    assert_eq!(corridor.latency_p50_ms, Some(2_000.0));
+    assert_eq!(corridor.latency_p95_ms, Some(20_000.0));
+    assert_eq!(corridor.liquidity_depth_usd, Some(500_000.0));
+    assert_eq!(corridor.liquidity_trend.as_deref(), Some("decreasing"));
+    assert_eq!(corridor.daily_volumes_usd.len(), 7);
+    assert_eq!(corridor.critical_anomalies, 1);
+
+    let health = scorer.assess(CORRIDOR, 100.0, &history);
+    assert_eq!(health.liquidity_trend, "decreasing");
+    assert_eq!(health.breakdown.scoring_version, scorer.version());
+
+    let components: HashMap<&str, f64> = health
+        .breakdown
+        .components
+        .iter()
+        .map(|c| (c.component.as_str(), c.contribution))
+        .collect();
+    assert_eq!(components["success_rate"], 40.0);
+    assert_eq!(components["volume_stability"], 20.0);
+    assert_eq!(components["anomaly_penalty"], -15.0);
+    let total: f64 = components.values().sum();
+    assert!((total - health.score).abs() < 0.1);
+
+    // Corridors without history are scored on success rate alone
+    let unknown = scorer.assess("EURC:GCISSUER->XLM:native", 90.0, &history);
+    assert_eq!(unknown.score, 50.0);
+    assert_eq!(unknown.liquidity_trend, "stable");
+}
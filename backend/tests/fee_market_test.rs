@@ -0,0 +1,163 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+
+use stellar_insights_backend::ingestion::processor::{LedgerBundle, LedgerProcessor};
+use stellar_insights_backend::rpc::{FeeBumpTransactionInfo, HorizonTransaction};
+use stellar_insights_backend::services::fee_market::{FeeMarketConfig, FeeMarketService};
+
+fn transaction(ledger: u64, index: usize, fee_charged: i64, operations: u32) -> HorizonTransaction {
+    HorizonTransaction {
+        id: format!("tx_{ledger}_{index}"),
+        hash: format!("hash_{ledger}_{index}"),
+        ledger,
+        created_at: (Utc::now() - Duration::minutes(30) + Duration::seconds(ledger as i64 * 5))
+            .to_rfc3339(),
+        source_account: "GSOURCE".to_string(),
+        fee_account: Some("GSOURCE".to_string()),
+        fee_charged: Some(fee_charged.to_string()),
+        max_fee: Some((fee_charged * 4).to_string()),
+        operation_count: operations,
+        successful: true,
+        paging_token: format!("pt_{ledger}_{index}"),
+        fee_bump_transaction: None,
+        inner_transaction: None,
+    }
+}
+
+/// A ledger whose transactions all paid `fee` per operation
+fn bundle(sequence: u64, fee: i64, transactions: usize) -> LedgerBundle {
+    let mut bundle = LedgerBundle::new(sequence);
+    bundle.transactions = (0..transactions)
+        .map(|i| transaction(sequence, i, fee, 1))
+        .collect();
+    bundle
+}
+
+fn service(pool: SqlitePool) -> FeeMarketService {
+    FeeMarketService::new(
+        pool,
+        FeeMarketConfig {
+            ledger_capacity_ops: 10,
+            recommendation_window: 5,
+            ..Default::default()
+        },
+    )
+}
+
+#[sqlx::test]
+async fn test_processor_records_ledger_percentiles(pool: SqlitePool) {
+    let service = service(pool);
+
+    let mut ledger = LedgerBundle::new(100);
+    ledger.transactions = vec![
+        transaction(100, 0, 100, 1),
+        transaction(100, 1, 400, 2),
+        transaction(100, 2, 1000, 1),
+    ];
+    let mut fee_bump = transaction(100, 3, 300, 1);
+    fee_bump.fee_bump_transaction = Some(FeeBumpTransactionInfo {
+        hash: "fb".to_string(),
+        signatures: vec![],
+    });
+    ledger.transactions.push(fee_bump);
+
+    assert_eq!(service.process(&ledger).await.unwrap(), 1);
+    assert_eq!(service.process(&LedgerBundle::new(101)).await.unwrap(), 0);
+
+    let ledgers = service.get_recent_ledgers(10).await.unwrap();
+    assert_eq!(ledgers.len(), 1);
+    let stats = &ledgers[0];
+    assert_eq!(stats.transaction_count, 4);
+    assert_eq!(stats.operation_count, 5);
+    assert!((stats.capacity_usage - 0.5).abs() < 1e-9);
+    // Per-op fees: 100, 200, 1000 and 150 for the fee bump
+    assert_eq!(stats.min_fee, 100);
+    assert_eq!(stats.p50_fee, 150);
+    assert_eq!(stats.max_fee, 1000);
+    assert_eq!(stats.total_fee_charged, 1800);
+    assert!(!stats.surge_pricing);
+
+    // Reprocessing the same ledger replaces its row
+    assert_eq!(service.process(&ledger).await.unwrap(), 1);
+    assert_eq!(service.get_recent_ledgers(10).await.unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_surge_periods_and_stats(pool: SqlitePool) {
+    let service = service(pool);
+
+    // 200 quiet, 201-202 surge on fees, 203 quiet, 204 surge on a full ledger
+    for ledger in [
+        bundle(200, 100, 2),
+        bundle(201, 500, 2),
+        bundle(202, 800, 2),
+        bundle(203, 100, 2),
+        bundle(204, 100, 10),
+    ] {
+        service.process(&ledger).await.unwrap();
+    }
+
+    let since = Utc::now() - Duration::hours(1);
+    let periods = service.get_surge_periods(since).await.unwrap();
+    assert_eq!(periods.len(), 2);
+    assert_eq!((periods[0].start_ledger, periods[0].end_ledger), (204, 204));
+    assert_eq!((periods[1].start_ledger, periods[1].end_ledger), (201, 202));
+    assert_eq!(periods[1].ledger_count, 2);
+    assert_eq!(periods[1].peak_p50_fee, 800);
+
+    let stats = service.get_stats(since).await.unwrap();
+    assert_eq!(stats.ledgers, 5);
+    assert_eq!(stats.transactions, 18);
+    assert_eq!(stats.surge_ledgers, 3);
+    assert!((stats.surge_ratio - 0.6).abs() < 1e-9);
+    assert_eq!(stats.max_p99_fee, 800);
+}
+
+#[sqlx::test]
+async fn test_recommended_fee(pool: SqlitePool) {
+    let service = service(pool);
+
+    let empty = service.recommended_fee(None, 1).await.unwrap();
+    assert_eq!(empty.based_on_ledgers, 0);
+    assert_eq!((empty.low, empty.medium, empty.high), (100, 100, 100));
+
+    for (sequence, fee) in [(300, 100), (301, 100), (302, 200), (303, 400), (304, 900)] {
+        service.process(&bundle(sequence, fee, 3)).await.unwrap();
+    }
+
+    let recommended = service.recommended_fee(None, 3).await.unwrap();
+    assert_eq!(recommended.based_on_ledgers, 5);
+    assert_eq!(recommended.latest_ledger, Some(304));
+    assert!(recommended.surge_pricing_active);
+    assert_eq!(recommended.medium, 200);
+    assert_eq!(recommended.high, 900);
+    assert!(recommended.low <= recommended.medium);
+    assert!((recommended.estimated_fee_xlm - 0.00006).abs() < 1e-12);
+
+    // A shorter window only sees the two latest ledgers
+    let recent = service.recommended_fee(Some(2), 1).await.unwrap();
+    assert_eq!(recent.based_on_ledgers, 2);
+    assert_eq!(recent.medium, 400);
+}
+
+#[sqlx::test]
+async fn test_stats_use_ledger_header_fees(pool: SqlitePool) {
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time, base_fee, max_tx_set_size) VALUES (400, 'h400', '2026-01-22T10:30:00Z', 200, 4)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let service = service(pool);
+
+    // 200 stroops per operation is the header's base fee, not a surge
+    service.process(&bundle(400, 200, 2)).await.unwrap();
+    let stats = &service.get_recent_ledgers(1).await.unwrap()[0];
+    assert_eq!(stats.base_fee, 200);
+    assert!((stats.capacity_usage - 0.5).abs() < 1e-9);
+    assert!(!stats.surge_pricing);
+
+    let recommended = service.recommended_fee(None, 1).await.unwrap();
+    assert_eq!(recommended.base_fee, 200);
+    assert_eq!(recommended.low, 200);
+}
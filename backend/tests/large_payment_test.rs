@@ -0,0 +1,155 @@
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use stellar_insights_backend::ingestion::processor::{LedgerBundle, LedgerProcessor};
+use stellar_insights_backend::rpc::Payment;
+use stellar_insights_backend::services::large_payment_detector::{
+    LargePaymentConfig, LargePaymentDetector, LargePaymentFilter,
+};
+use stellar_insights_backend::services::price_feed::{PriceFeedClient, PriceFeedConfig};
+use stellar_insights_backend::websocket::WsState;
+
+const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+fn detector(pool: SqlitePool) -> LargePaymentDetector {
+    LargePaymentDetector::new(
+        pool,
+        // No asset mapping, so nothing is priced and no request leaves the test
+        Arc::new(PriceFeedClient::new(
+            PriceFeedConfig::default(),
+            HashMap::new(),
+        )),
+        Arc::new(WsState::new()),
+        LargePaymentConfig {
+            asset_thresholds: HashMap::from([("XLM:native".to_string(), 50_000.0)]),
+            stddev_multiplier: 3.0,
+            window_hours: 24,
+            min_samples: 5,
+        },
+    )
+}
+
+fn usdc_payment(id: &str, amount: f64, created_at: &str) -> Payment {
+    Payment {
+        id: id.to_string(),
+        paging_token: id.to_string(),
+        transaction_hash: format!("tx_{id}"),
+        source_account: "GSENDER".to_string(),
+        destination: "GRECEIVER".to_string(),
+        asset_type: "credit_alphanum4".to_string(),
+        asset_code: Some("USDC".to_string()),
+        asset_issuer: Some(ISSUER.to_string()),
+        amount: format!("{amount:.7}"),
+        created_at: created_at.to_string(),
+        operation_type: Some("payment".to_string()),
+        source_asset_type: None,
+        source_asset_code: None,
+        source_asset_issuer: None,
+        source_amount: None,
+        path: vec![],
+        from: Some("GSENDER".to_string()),
+        to: Some("GRECEIVER".to_string()),
+        transaction_successful: None,
+        asset_balance_changes: None,
+    }
+}
+
+fn ledger(sequence: u64, payments: Vec<Payment>) -> LedgerBundle {
+    let mut bundle = LedgerBundle::new(sequence);
+    bundle.payments = payments;
+    bundle
+}
+
+#[sqlx::test]
+async fn test_flags_payments_far_above_corridor_norm(pool: SqlitePool) {
+    let detector = detector(pool.clone());
+    let corridor = format!("USDC:{ISSUER}->USDC:{ISSUER}");
+
+    let history = [90.0, 110.0, 100.0, 95.0, 105.0, 100.0]
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| usdc_payment(&format!("h{i}"), *amount, "2026-05-01T09:10:00Z"))
+        .collect();
+    assert_eq!(detector.process(&ledger(100, history)).await.unwrap(), 0);
+
+    let bundle = ledger(
+        101,
+        vec![
+            usdc_payment("big", 5_000.0, "2026-05-01T10:05:00Z"),
+            usdc_payment("normal", 104.0, "2026-05-01T10:06:00Z"),
+        ],
+    );
+    assert_eq!(detector.process(&bundle).await.unwrap(), 1);
+    // Replaying the ledger neither re-flags nor re-counts it
+    assert_eq!(detector.process(&bundle).await.unwrap(), 0);
+
+    let flagged = detector
+        .get_large_payments(&LargePaymentFilter {
+            corridor_key: Some(corridor.clone()),
+            limit: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(flagged.len(), 1);
+    let big = &flagged[0];
+    assert_eq!(big.payment_id, "big");
+    assert_eq!(big.reasons, "deviation");
+    assert_eq!(big.amount, 5_000.0);
+    assert_eq!(big.amount_usd, None);
+    assert_eq!(big.corridor_mean, Some(100.0));
+    assert!(big.z_score.unwrap() > 3.0);
+    assert_eq!(big.source_account, "GSENDER");
+
+    let stats: (i64,) = sqlx::query_as(
+        "SELECT SUM(payment_count) FROM corridor_payment_hourly_stats WHERE corridor_key = $1",
+    )
+    .bind(&corridor)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(stats.0, 8);
+}
+
+#[sqlx::test]
+async fn test_flags_payments_above_asset_threshold(pool: SqlitePool) {
+    let detector = detector(pool);
+
+    let mut whale = usdc_payment("whale", 75_000.0, "2026-05-01T10:00:00Z");
+    whale.asset_type = "native".to_string();
+    whale.asset_code = None;
+    whale.asset_issuer = None;
+    let mut path = usdc_payment("path", 60_000.0, "2026-05-01T10:01:00Z");
+    path.asset_type = "native".to_string();
+    path.asset_code = None;
+    path.asset_issuer = None;
+    path.operation_type = Some("path_payment_strict_receive".to_string());
+    path.source_asset_type = Some("credit_alphanum4".to_string());
+    path.source_asset_code = Some("USDC".to_string());
+    path.source_asset_issuer = Some(ISSUER.to_string());
+    path.source_amount = Some("6000.0000000".to_string());
+    let usdc = usdc_payment("usdc", 75_000.0, "2026-05-01T10:02:00Z");
+
+    assert_eq!(
+        detector
+            .process(&ledger(200, vec![whale, path, usdc]))
+            .await
+            .unwrap(),
+        2
+    );
+
+    let native = detector
+        .get_large_payments(&LargePaymentFilter {
+            asset: Some("XLM:native".to_string()),
+            limit: 10,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(native.len(), 2);
+    assert!(native.iter().all(|p| p.reasons == "absolute_threshold"));
+    assert_eq!(native[0].corridor_key, format!("USDC:{ISSUER}->XLM:native"));
+    assert_eq!(native[1].corridor_key, "XLM:native->XLM:native");
+    assert_eq!(native[1].z_score, None);
+}
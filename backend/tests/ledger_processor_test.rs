@@ -0,0 +1,257 @@
+use async_trait::async_trait;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::{routing::get, Json, Router};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use stellar_insights_backend::ingestion::ledger::{LedgerIngestionService, LedgerProcessors};
+use stellar_insights_backend::ingestion::processor::{BundlePart, LedgerBundle, LedgerProcessor};
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use stellar_insights_backend::services::alert_service::AlertService;
+use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+use stellar_insights_backend::services::price_feed::{PriceFeedClient, PriceFeedConfig};
+use stellar_insights_backend::websocket::WsState;
+
+/// Records what it was handed, like a custom extractor would
+#[derive(Default)]
+struct RecordingProcessor {
+    seen: Mutex<Vec<(u64, usize, usize)>>,
+}
+
+#[async_trait]
+impl LedgerProcessor for RecordingProcessor {
+    fn name(&self) -> &'static str {
+        "recording"
+    }
+
+    fn requires(&self) -> &'static [BundlePart] {
+        &[BundlePart::Operations]
+    }
+
+    async fn process(&self, bundle: &LedgerBundle) -> anyhow::Result<u64> {
+        self.seen.lock().unwrap().push((
+            bundle.sequence,
+            bundle.operations.len(),
+            bundle.payments.len(),
+        ));
+        Ok(bundle.operations.len() as u64)
+    }
+}
+
+fn ingestion_service(pool: &SqlitePool) -> LedgerIngestionService {
+    ingestion_service_with(pool, Arc::new(StellarRpcClient::new_with_defaults(true)))
+}
+
+fn ingestion_service_with(
+    pool: &SqlitePool,
+    rpc_client: Arc<StellarRpcClient>,
+) -> LedgerIngestionService {
+    LedgerIngestionService::new(
+        Arc::clone(&rpc_client),
+        Arc::new(FeeBumpTrackerService::new(pool.clone())),
+        Arc::new(AccountMergeDetector::new(
+            pool.clone(),
+            Arc::clone(&rpc_client),
+        )),
+        pool.clone(),
+    )
+}
+
+#[sqlx::test]
+async fn test_custom_processor_receives_each_ledger_bundle(pool: SqlitePool) {
+    let recording = Arc::new(RecordingProcessor::default());
+    let service = ingestion_service(&pool).with_processor(recording.clone());
+
+    let names: Vec<&str> = service
+        .processors()
+        .statuses()
+        .iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(names, vec!["fee_bumps", "account_merges", "recording"]);
+
+    let ledgers = StellarRpcClient::new_with_defaults(true)
+        .fetch_ledgers(Some(51_565_760), 2, None)
+        .await
+        .unwrap()
+        .ledgers;
+    assert_eq!(service.process_ledgers(&ledgers).await.unwrap(), (2, None));
+
+    assert_eq!(
+        *recording.seen.lock().unwrap(),
+        vec![(51_565_760, 3, 5), (51_565_761, 3, 5)]
+    );
+
+    // Merged balances come from the bundle's effects: 125.5 + 10.5 per ledger
+    let (merges, merged_balance): (i64, f64) =
+        sqlx::query_as("SELECT COUNT(*), SUM(merged_balance) FROM account_merges")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(merges, 4);
+    assert!((merged_balance - 272.0).abs() < f64::EPSILON);
+}
+
+#[sqlx::test]
+async fn test_shared_constructor_registers_every_builtin_processor(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let processors = LedgerProcessors::from_env(
+        pool.clone(),
+        Arc::clone(&rpc_client),
+        Arc::new(AlertService::new()),
+        Arc::new(PriceFeedClient::new(
+            PriceFeedConfig::default(),
+            HashMap::new(),
+        )),
+        Arc::new(WsState::new()),
+    );
+    let service = LedgerIngestionService::with_processors(rpc_client, &processors, pool);
+
+    let names: Vec<&str> = service
+        .processors()
+        .statuses()
+        .iter()
+        .map(|s| s.name)
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "fee_bumps",
+            "account_merges",
+            "claimable_balances",
+            "sponsorships",
+            "fee_market",
+            "asset_supply",
+            "asset_authorizations",
+            "large_payments",
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn test_disabled_processor_is_skipped(pool: SqlitePool) {
+    let service = ingestion_service(&pool);
+    assert!(service.processors().set_enabled("account_merges", false));
+
+    let ledgers = StellarRpcClient::new_with_defaults(true)
+        .fetch_ledgers(Some(51_565_760), 1, None)
+        .await
+        .unwrap()
+        .ledgers;
+    service.process_ledgers(&ledgers).await.unwrap();
+
+    let merges: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM account_merges")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(merges, 0);
+}
+
+/// Horizon stand-in: ledger payments span two pages, operations are missing
+async fn spawn_horizon() -> (Arc<StellarRpcClient>, tokio::task::JoinHandle<()>) {
+    async fn payments(
+        State(base): State<String>,
+        Path(sequence): Path<u64>,
+        Query(params): Query<HashMap<String, String>>,
+    ) -> Json<Value> {
+        let (from, count) = match params.get("cursor") {
+            None => (0, 200),
+            Some(_) => (200, 3),
+        };
+        let records: Vec<Value> = (from..from + count)
+            .map(|i| {
+                json!({
+                    "id": format!("{}{:04}", sequence, i),
+                    "paging_token": format!("{}{:04}", sequence, i),
+                    "transaction_hash": format!("tx_{}", i),
+                    "source_account": "GSOURCE",
+                    "type": "payment",
+                    "from": "GSOURCE",
+                    "to": "GDEST",
+                    "asset_type": "native",
+                    "amount": "1.0000000",
+                    "created_at": "2026-01-22T10:30:00Z"
+                })
+            })
+            .collect();
+        Json(json!({
+            "_links": { "next": { "href": format!(
+                "{}/ledgers/{}/payments?cursor={}&limit=200",
+                base,
+                sequence,
+                from + count
+            ) } },
+            "_embedded": { "records": records }
+        }))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let app = Router::new()
+        .route("/ledgers/:sequence/payments", get(payments))
+        .route(
+            "/ledgers/:sequence/operations",
+            get(|| async { StatusCode::NOT_FOUND }),
+        )
+        .with_state(url.clone());
+    let handle = tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (
+        Arc::new(StellarRpcClient::new(url.clone(), url, false)),
+        handle,
+    )
+}
+
+async fn count(pool: &SqlitePool, table: &str) -> i64 {
+    sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_ledger_payments_follow_every_page(pool: SqlitePool) {
+    let (rpc_client, server) = spawn_horizon().await;
+    let service = ingestion_service_with(&pool, rpc_client);
+    assert!(service.processors().set_enabled("fee_bumps", false));
+    assert!(service.processors().set_enabled("account_merges", false));
+
+    let ledgers = StellarRpcClient::new_with_defaults(true)
+        .fetch_ledgers(Some(51_565_760), 1, None)
+        .await
+        .unwrap()
+        .ledgers;
+    assert_eq!(service.process_ledgers(&ledgers).await.unwrap(), (1, None));
+    assert_eq!(count(&pool, "ledger_payments").await, 203);
+    server.abort();
+}
+
+#[sqlx::test]
+async fn test_ledger_with_missing_part_is_not_stored(pool: SqlitePool) {
+    let (rpc_client, server) = spawn_horizon().await;
+    let recording = Arc::new(RecordingProcessor::default());
+    let service = ingestion_service_with(&pool, rpc_client).with_processor(recording.clone());
+    assert!(service.processors().set_enabled("fee_bumps", false));
+    assert!(service.processors().set_enabled("account_merges", false));
+
+    let ledgers = StellarRpcClient::new_with_defaults(true)
+        .fetch_ledgers(Some(51_565_760), 2, None)
+        .await
+        .unwrap()
+        .ledgers;
+    assert_eq!(
+        service.process_ledgers(&ledgers).await.unwrap(),
+        (0, Some(51_565_760))
+    );
+
+    // Neither the ledger nor its payments are committed, so a rerun starts over
+    assert_eq!(count(&pool, "ledgers").await, 0);
+    assert_eq!(count(&pool, "ledger_payments").await, 0);
+    assert!(recording.seen.lock().unwrap().is_empty());
+    server.abort();
+}
@@ -0,0 +1,226 @@
+use std::sync::Arc;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use sqlx::SqlitePool;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+use stellar_insights_backend::api::metrics_cached;
+use stellar_insights_backend::cache::{CacheConfig, CacheManager};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::network::StellarNetwork;
+use stellar_insights_backend::services::metrics_overview::{
+    MetricsOverviewService, OverviewWindow,
+};
+
+async fn seed_hour(pool: &SqlitePool, corridor: &str, hour: DateTime<Utc>, total: i64, ok: i64) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridor_metrics_hourly (
+            id, corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+            hour_bucket, total_transactions, successful_transactions, failed_transactions,
+            success_rate, volume_usd
+        )
+        VALUES ($1, $2, 'USDC', 'GAISSUER', 'XLM', 'native', $3, $4, $5, $6, $7, $8)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(corridor)
+    .bind(hour.to_rfc3339())
+    .bind(total)
+    .bind(ok)
+    .bind(total - ok)
+    .bind(ok as f64 / total as f64 * 100.0)
+    .bind(total as f64 * 100.0)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn seed_payment(
+    pool: &SqlitePool,
+    source: &str,
+    destination: &str,
+    network: Option<&str>,
+    at: DateTime<Utc>,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO payments (
+            id, transaction_hash, source_account, destination_account,
+            asset_type, amount, network, created_at
+        )
+        VALUES ($1, 'tx', $2, $3, 'native', 10.0, $4, $5)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(source)
+    .bind(destination)
+    .bind(network)
+    .bind(at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Activity in the last day and in the day before it
+async fn seed(pool: &SqlitePool, now: DateTime<Utc>) {
+    let current_hour = now.duration_trunc(Duration::hours(1)).unwrap();
+    seed_hour(
+        pool,
+        "USDC:GAISSUER->XLM:native",
+        current_hour - Duration::hours(2),
+        100,
+        90,
+    )
+    .await;
+    seed_hour(
+        pool,
+        "EURC:GBISSUER->XLM:native",
+        current_hour - Duration::hours(3),
+        100,
+        100,
+    )
+    .await;
+    seed_hour(
+        pool,
+        "USDC:GAISSUER->XLM:native",
+        current_hour - Duration::hours(30),
+        50,
+        50,
+    )
+    .await;
+
+    let recent = now - Duration::hours(1);
+    let earlier = now - Duration::hours(30);
+    seed_payment(pool, "GA", "GB", Some("mainnet"), recent).await;
+    seed_payment(pool, "GB", "GC", None, recent).await;
+    seed_payment(pool, "GT1", "GT2", Some("testnet"), recent).await;
+    seed_payment(pool, "GA", "GB", Some("mainnet"), earlier).await;
+
+    sqlx::query(
+        "INSERT INTO anchors (id, name, stellar_account) VALUES ('anchor-1', 'Anchor', 'GANCHOR')",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO anchor_metrics_history (
+            id, anchor_id, timestamp, success_rate, failure_rate, reliability_score,
+            total_transactions, successful_transactions, failed_transactions
+        )
+        VALUES ($1, 'anchor-1', $2, 100, 0, 100, 10, 10, 0)
+        "#,
+    )
+    .bind(Uuid::new_v4().to_string())
+    .bind(recent)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO liquidity_pools (pool_id, reserve_a_asset_code, reserve_b_asset_code) VALUES ('pool-1', 'USDC', 'XLM')",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    for (at, tvl) in [
+        (recent, 2_000.0),
+        (now - Duration::hours(5), 1_500.0),
+        (earlier, 1_000.0),
+    ] {
+        sqlx::query(
+            r#"
+            INSERT INTO liquidity_pool_snapshots (
+                pool_id, reserve_a_amount, reserve_b_amount, total_value_usd, snapshot_at
+            )
+            VALUES ('pool-1', 1, 1, $1, $2)
+            "#,
+        )
+        .bind(tvl)
+        .bind(at)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+}
+
+fn new_service(pool: SqlitePool) -> Arc<MetricsOverviewService> {
+    Arc::new(MetricsOverviewService::new(
+        Arc::new(Database::new(pool)),
+        StellarNetwork::Mainnet,
+    ))
+}
+
+#[sqlx::test]
+async fn test_overview_compares_with_previous_window(pool: SqlitePool) {
+    let now = Utc::now();
+    seed(&pool, now).await;
+    let overview = new_service(pool)
+        .overview_at(OverviewWindow::Day, now)
+        .await
+        .unwrap();
+
+    assert_eq!(overview.network, StellarNetwork::Mainnet);
+    assert_eq!(overview.total_volume.current, 20_000.0);
+    assert_eq!(overview.total_volume.previous, 5_000.0);
+    assert_eq!(overview.total_volume.change_pct, Some(300.0));
+    assert_eq!(overview.total_transactions.current, 200.0);
+    assert_eq!(overview.success_rate.current, 95.0);
+    assert_eq!(overview.success_rate.change, -5.0);
+    assert_eq!(overview.average_transaction_value.current, 100.0);
+    assert_eq!(overview.corridor_count.current, 2.0);
+    assert_eq!(overview.corridor_count.previous, 1.0);
+    assert_eq!(overview.active_anchors.current, 1.0);
+    assert_eq!(overview.active_anchors.change_pct, None);
+    assert_eq!(overview.liquidity_pool_tvl.current, 2_000.0);
+    assert_eq!(overview.liquidity_pool_tvl.previous, 1_000.0);
+
+    // Untagged payments count under the configured network
+    assert_eq!(overview.active_users.current, 3.0);
+    assert_eq!(overview.active_users.previous, 2.0);
+    let networks: Vec<(&str, f64)> = overview
+        .networks
+        .iter()
+        .map(|n| (n.network.as_str(), n.payments.current))
+        .collect();
+    assert_eq!(networks, [("mainnet", 2.0), ("testnet", 1.0)]);
+}
+
+#[sqlx::test]
+async fn test_overview_endpoint(pool: SqlitePool) {
+    seed(&pool, Utc::now()).await;
+    let cache = Arc::new(CacheManager::new(CacheConfig::default()).await.unwrap());
+    let app = metrics_cached::routes(new_service(pool), cache);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/api/metrics/overview?window=7d")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["window"], "7d");
+    assert_eq!(body["total_volume"]["current"], 25_000.0);
+    assert!(body["total_volume"]["change_pct"].is_null());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/api/metrics/overview?window=1y")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
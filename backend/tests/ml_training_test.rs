@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use sqlx::SqlitePool;
+use tokio::sync::RwLock;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::ml::{retrain, MLService, MLTrainingConfig, BASELINE_MODEL_VERSION};
+use stellar_insights_backend::models::PaymentRecord;
+use stellar_insights_backend::services::aggregation::{AggregationConfig, AggregationService};
+
+mod common;
+use common::seed_hourly_metrics;
+
+const GOOD_CORRIDOR: &str = "USDC:GAISSUER->XLM:native";
+const BAD_CORRIDOR: &str = "EURT:GBISSUER->XLM:native";
+
+/// One corridor settles ~95% of payments, the other ~60%
+async fn seed(pool: &SqlitePool) {
+    seed_hourly_metrics(
+        pool,
+        &[("USDC", "GAISSUER", 90, 10), ("EURT", "GBISSUER", 50, 20)],
+    )
+    .await;
+    let now = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+
+    sqlx::query(
+        r#"
+        INSERT INTO payments (
+            id, transaction_hash, source_account, destination_account, asset_type,
+            asset_code, asset_issuer, amount, created_at
+        )
+        VALUES ('p1', 'tx1', 'GSRC', 'GDST', 'credit_alphanum4', 'USDC', 'GAISSUER', 250.0, $1)
+        "#,
+    )
+    .bind(now - Duration::hours(3))
+    .execute(pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        r#"
+        INSERT INTO liquidity_pools (
+            pool_id, reserve_a_asset_code, reserve_a_asset_issuer, reserve_b_asset_code
+        )
+        VALUES ('pool1', 'USDC', 'GAISSUER', 'XLM')
+        "#,
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO liquidity_pool_snapshots (
+            pool_id, reserve_a_amount, reserve_b_amount, total_value_usd, snapshot_at
+        )
+        VALUES ('pool1', 1000.0, 5000.0, 80000.0, $1)
+        "#,
+    )
+    .bind(now - Duration::days(5))
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn test_config() -> MLTrainingConfig {
+    MLTrainingConfig {
+        min_samples: 100,
+        ..Default::default()
+    }
+}
+
+#[sqlx::test]
+async fn test_trains_persists_and_activates_models(pool: SqlitePool) {
+    seed(&pool).await;
+    let mut service = MLService::with_config(Database::new(pool.clone()), test_config());
+
+    let first = service.train_model().await.unwrap();
+    assert!(first.is_active);
+    assert_eq!(first.training_samples + first.validation_samples, 480);
+    assert!(first.auc.unwrap() > 0.6);
+    assert!(first.log_loss.unwrap() > 0.0);
+    assert!(first.window_start < first.window_end);
+    assert_eq!(service.model_version(), first.version);
+
+    let now = Utc::now();
+    let good = service
+        .predict_payment_success(GOOD_CORRIDOR, 100.0, now)
+        .await
+        .unwrap();
+    let bad = service
+        .predict_payment_success("XLM->EURT:GBISSUER", 100.0, now)
+        .await
+        .unwrap();
+    assert_eq!(good.model_version, first.version);
+    assert!(good.success_probability > bad.success_probability);
+    assert!(service
+        .predict_payment_success("USDC-XLM", 100.0, now)
+        .await
+        .is_err());
+    assert_eq!(service.total_predictions(), 2);
+
+    // Same data, same AUC: the newer model takes over
+    let second = service.train_model().await.unwrap();
+    assert_ne!(second.version, first.version);
+    assert!(second.is_active);
+    let models = service.list_models().await.unwrap();
+    assert_eq!(models.len(), 2);
+    assert_eq!(models.iter().filter(|m| m.is_active).count(), 1);
+
+    // Roll back to the first model; a restarted service picks it up
+    let activated = service
+        .activate_model(&first.version)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(activated.is_active);
+    assert_eq!(service.model_version(), first.version);
+    assert!(service.activate_model("missing").await.unwrap().is_none());
+
+    let mut restarted = MLService::with_config(Database::new(pool), test_config());
+    assert_eq!(restarted.model_version(), BASELINE_MODEL_VERSION);
+    let loaded = restarted.load_active_model().await.unwrap().unwrap();
+    assert_eq!(loaded.version, first.version);
+    assert_eq!(restarted.model_version(), first.version);
+}
+
+#[sqlx::test]
+async fn test_retrain_swaps_model_into_shared_service(pool: SqlitePool) {
+    seed(&pool).await;
+    let shared = RwLock::new(MLService::with_config(Database::new(pool), test_config()));
+
+    // A fitted model is stored but not used until it is promoted
+    let version = shared
+        .read()
+        .await
+        .training_copy()
+        .fit_model()
+        .await
+        .unwrap();
+    {
+        let service = shared.read().await;
+        assert_eq!(service.model_version(), BASELINE_MODEL_VERSION);
+        assert!(service.active_model().is_none());
+        assert!(!service.list_models().await.unwrap()[0].is_active);
+    }
+
+    let model = retrain(&shared).await.unwrap();
+    assert_ne!(model.version, version);
+    assert!(model.is_active);
+    assert_eq!(shared.read().await.model_version(), model.version);
+}
+
+/// A payment as payment ingestion stores it, keyed on its Horizon operation id
+fn ingested_payment(
+    id: String,
+    source: (&str, &str),
+    destination: (&str, &str),
+    successful: bool,
+    at: DateTime<Utc>,
+) -> PaymentRecord {
+    PaymentRecord {
+        id,
+        transaction_hash: "tx".to_string(),
+        source_account: "GSRC".to_string(),
+        destination_account: "GDST".to_string(),
+        asset_type: "credit_alphanum4".to_string(),
+        asset_code: Some(destination.0.to_string()),
+        asset_issuer: Some(destination.1.to_string()),
+        source_asset_code: source.0.to_string(),
+        source_asset_issuer: source.1.to_string(),
+        destination_asset_code: destination.0.to_string(),
+        destination_asset_issuer: destination.1.to_string(),
+        amount: 250.0,
+        successful,
+        timestamp: Some(at),
+        submission_time: None,
+        confirmation_time: None,
+        network: Some("testnet".to_string()),
+        created_at: at,
+    }
+}
+
+#[sqlx::test]
+async fn test_trains_on_rolled_up_payments(pool: SqlitePool) {
+    // Three days of path payments from lumens: USDC settles ~90%, EURT ~60%
+    let db = Arc::new(Database::new(pool.clone()));
+    let now = Utc::now().duration_trunc(Duration::hours(1)).unwrap();
+    let mut payments = Vec::new();
+    for h in 1..=72 {
+        let hour = now - Duration::hours(h);
+        for (code, issuer, successes) in [
+            ("USDC", "GAISSUER", 17 + h % 3),
+            ("EURT", "GBISSUER", 10 + h % 5),
+        ] {
+            for i in 0..20 {
+                payments.push(ingested_payment(
+                    format!("{}{:03}{:02}-1", code, h, i),
+                    ("XLM", "native"),
+                    (code, issuer),
+                    i < successes,
+                    hour + Duration::minutes(i * 2),
+                ));
+            }
+        }
+    }
+    db.save_payments(payments).await.unwrap();
+
+    let aggregation = AggregationService::new(
+        Arc::clone(&db),
+        AggregationConfig {
+            lookback_hours: 96,
+            ..Default::default()
+        },
+    );
+    aggregation.run_hourly_aggregation().await.unwrap();
+    // Hours already rolled up are not counted again
+    aggregation.run_hourly_aggregation().await.unwrap();
+
+    let (rows, total, successful): (i64, i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), SUM(total_transactions), SUM(successful_transactions)
+        FROM corridor_metrics_hourly
+        WHERE corridor_key = $1
+        "#,
+    )
+    .bind(GOOD_CORRIDOR)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!((rows, total), (72, 72 * 20));
+    assert!(successful < total);
+
+    let mut service = MLService::with_config(Database::new(pool), test_config());
+    let model = service.train_model().await.unwrap();
+    assert_eq!(model.training_samples + model.validation_samples, 144);
+
+    let good = service
+        .predict_payment_success(GOOD_CORRIDOR, 100.0, Utc::now())
+        .await
+        .unwrap();
+    let bad = service
+        .predict_payment_success(BAD_CORRIDOR, 100.0, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(good.model_version, model.version);
+    assert!(good.success_probability > bad.success_probability);
+}
+
+#[sqlx::test]
+async fn test_refuses_to_train_without_history(pool: SqlitePool) {
+    let mut service = MLService::with_config(Database::new(pool), test_config());
+
+    assert!(service.train_model().await.is_err());
+    assert!(service.list_models().await.unwrap().is_empty());
+    assert!(service.load_active_model().await.unwrap().is_none());
+
+    let prediction = service
+        .predict_payment_success(GOOD_CORRIDOR, 100.0, Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(prediction.model_version, BASELINE_MODEL_VERSION);
+}
@@ -0,0 +1,127 @@
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use stellar_insights_backend::rpc::{Asset, OrderBook, OrderBookEntry, Price, StellarRpcClient};
+use stellar_insights_backend::services::order_book_snapshot::{
+    OrderBookSnapshotConfig, OrderBookSnapshotService,
+};
+
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+const PAIR_KEY: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN->XLM:native";
+
+fn service(pool: SqlitePool) -> OrderBookSnapshotService {
+    let config = OrderBookSnapshotConfig {
+        slippage_bands_pct: vec![1.0, 2.0],
+        ..Default::default()
+    };
+    OrderBookSnapshotService::new(
+        pool,
+        Arc::new(StellarRpcClient::new_with_defaults(true)),
+        config,
+    )
+}
+
+fn entry(price: f64, amount: f64) -> OrderBookEntry {
+    OrderBookEntry {
+        price: price.to_string(),
+        amount: amount.to_string(),
+        price_r: Price { n: 1, d: 1 },
+    }
+}
+
+/// Symmetric book around 1.0 whose size shrinks with `scale`
+fn order_book(scale: f64) -> OrderBook {
+    let native = Asset {
+        asset_type: "native".to_string(),
+        asset_code: None,
+        asset_issuer: None,
+    };
+    OrderBook {
+        bids: vec![entry(0.995, 1000.0 * scale)],
+        asks: vec![entry(1.005, 1000.0 * scale)],
+        base: native.clone(),
+        counter: native,
+    }
+}
+
+#[sqlx::test]
+async fn test_snapshot_all_tracked_corridors(pool: SqlitePool) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridors (
+            id, source_asset_code, source_asset_issuer,
+            destination_asset_code, destination_asset_issuer
+        )
+        VALUES ('c1', 'USDC', $1, 'XLM', 'native'),
+               ('c2', 'USDC', $1, 'USDC', $1)
+        "#,
+    )
+    .bind(USDC_ISSUER)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let service = service(pool);
+    // Same-asset corridor is skipped
+    assert_eq!(service.snapshot_all().await.unwrap(), 1);
+
+    let pairs = service.get_tracked_pairs().await.unwrap();
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs[0].pair_key, PAIR_KEY);
+
+    let latest = service
+        .get_latest_snapshot(PAIR_KEY)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.bids.len(), 3);
+    assert_eq!(latest.asks.len(), 3);
+    assert_eq!(latest.depth.len(), 2);
+    assert!(latest.spread_bps.unwrap() > 0.0);
+
+    assert!(service
+        .get_latest_snapshot("XLM:native->EURC:native")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[sqlx::test]
+async fn test_depth_series_trend(pool: SqlitePool) {
+    let service = service(pool);
+    let start = Utc::now() - Duration::hours(4);
+
+    for (i, scale) in [1.0, 1.0, 0.6, 0.5].into_iter().enumerate() {
+        service
+            .record_snapshot(
+                PAIR_KEY,
+                &order_book(scale),
+                start + Duration::hours(i as i64),
+            )
+            .await
+            .unwrap();
+    }
+
+    let series = service
+        .get_depth_series(PAIR_KEY, start - Duration::minutes(1), None)
+        .await
+        .unwrap();
+    assert_eq!(series.points.len(), 4);
+    assert_eq!(series.points[0].depth.len(), 2);
+    assert_eq!(series.trend_slippage_pct, Some(1.0));
+    assert_eq!(series.depth_trend.as_deref(), Some("decreasing"));
+
+    let first = &series.points[0].depth[0];
+    assert!((first.ask_depth - 1000.0).abs() < 1e-9);
+    // Bid amounts are quoted in the counter asset and converted to base units
+    assert!((first.bid_depth - 1000.0 / 0.995).abs() < 1e-6);
+
+    // Window excludes older snapshots
+    let recent = service
+        .get_depth_series(PAIR_KEY, start + Duration::minutes(150), Some(2.0))
+        .await
+        .unwrap();
+    assert_eq!(recent.points.len(), 1);
+    assert_eq!(recent.depth_trend, None);
+}
@@ -0,0 +1,74 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+
+use stellar_insights_backend::ingestion::ledger::LedgerIngestionService;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+
+#[sqlx::test]
+async fn test_path_payments_persist_both_legs_and_path(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let service = LedgerIngestionService::new(
+        Arc::clone(&rpc_client),
+        Arc::new(FeeBumpTrackerService::new(pool.clone())),
+        Arc::new(AccountMergeDetector::new(
+            pool.clone(),
+            Arc::clone(&rpc_client),
+        )),
+        pool.clone(),
+    );
+
+    let ledgers = rpc_client
+        .fetch_ledgers(Some(51_565_760), 1, None)
+        .await
+        .unwrap()
+        .ledgers;
+    assert_eq!(service.process_ledgers(&ledgers).await.unwrap(), (1, None));
+
+    let operation_types: Vec<String> =
+        sqlx::query_scalar("SELECT operation_type FROM ledger_payments ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(operation_types.len(), 5);
+    assert_eq!(operation_types[0], "path_payment_strict_send");
+    assert!(operation_types[1..].iter().all(|t| t == "payment"));
+
+    let (source_asset_type, source_amount, amount, exchange_rate, path, path_hops): (
+        Option<String>,
+        Option<String>,
+        String,
+        Option<f64>,
+        Option<String>,
+        i64,
+    ) = sqlx::query_as(
+        "SELECT source_asset_type, source_amount, amount, exchange_rate, path, path_hops FROM ledger_payments WHERE operation_type = 'path_payment_strict_send'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(source_asset_type.as_deref(), Some("native"));
+    assert_eq!(source_amount.as_deref(), Some("90.0000000"));
+    assert_eq!(amount, "100.0000000");
+    assert!((exchange_rate.unwrap() - 100.0 / 90.0).abs() < 1e-9);
+    assert_eq!(path_hops, 1);
+    assert!(path.unwrap().contains(r#""asset_type":"native""#));
+
+    // Plain payments carry no source leg
+    let with_source_leg: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM ledger_payments WHERE operation_type = 'payment' AND (source_amount IS NOT NULL OR exchange_rate IS NOT NULL)",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(with_source_leg, 0);
+
+    // Reprocessing the ledger keeps one row per operation
+    service.process_ledgers(&ledgers).await.unwrap();
+    let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM ledger_payments")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(rows, 5);
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use sqlx::SqlitePool;
+use tokio::sync::RwLock;
+
+use stellar_insights_backend::api::prediction;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::ml::{MLService, MLTrainingConfig};
+use stellar_insights_backend::services::price_feed::{PriceFeedClient, PriceFeedConfig};
+
+mod common;
+use common::{get, seed_hourly_metrics};
+
+/// USDC from GAISSUER settles reliably against XLM, the same code from
+/// GBISSUER does not, and EURC is reliable
+async fn seed(pool: &SqlitePool) {
+    seed_hourly_metrics(
+        pool,
+        &[
+            ("USDC", "GAISSUER", 90, 10),
+            ("USDC", "GBISSUER", 50, 20),
+            ("EURC", "GCISSUER", 92, 8),
+        ],
+    )
+    .await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO liquidity_pools (
+            pool_id, fee_bp, reserve_a_asset_code, reserve_a_asset_issuer,
+            reserve_b_asset_code, total_value_usd
+        )
+        VALUES ('pool1', 30, 'EURC', 'GCISSUER', 'XLM', 108000.0)
+        "#,
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn test_app(pool: SqlitePool) -> (axum::Router, String) {
+    seed(&pool).await;
+    let mut ml = MLService::with_config(
+        Database::new(pool),
+        MLTrainingConfig {
+            min_samples: 100,
+            ..Default::default()
+        },
+    );
+    let model = ml.train_model().await.unwrap();
+    let price_feed = Arc::new(PriceFeedClient::new(
+        PriceFeedConfig::default(),
+        HashMap::new(),
+    ));
+    let app = prediction::routes(Arc::new(RwLock::new(ml)), price_feed);
+    (app, model.version)
+}
+
+#[sqlx::test]
+async fn test_predicts_with_trained_model_and_ranks_routes(pool: SqlitePool) {
+    let (app, version) = test_app(pool).await;
+
+    let (status, body) = get(
+        &app,
+        "/api/predict/success?source_asset=XLM&destination_asset=USDC:GBISSUER&amount=1000&time_of_day=12:00",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["model_version"], version);
+    assert!((body["amount_usd"].as_f64().unwrap() - 120.0).abs() < 1e-6);
+
+    let probability = body["success_probability"].as_f64().unwrap();
+    let interval = &body["confidence_interval"];
+    assert!(interval[0].as_f64().unwrap() <= probability);
+    assert!(interval[1].as_f64().unwrap() >= probability);
+
+    // The reliable issuer of the same code beats the requested one
+    let routes = body["alternative_routes"].as_array().unwrap();
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes[0]["route"], "alternative_anchor");
+    assert_eq!(routes[0]["path"][1], "USDC:GAISSUER");
+    assert_eq!(routes[1]["route"], "direct");
+    assert!(
+        routes[0]["success_probability"].as_f64().unwrap()
+            > routes[1]["success_probability"].as_f64().unwrap()
+    );
+    let delivered: Vec<f64> = routes
+        .iter()
+        .map(|r| r["expected_delivered_usd"].as_f64().unwrap())
+        .collect();
+    assert!(delivered.windows(2).all(|w| w[0] >= w[1]));
+}
+
+#[sqlx::test]
+async fn test_offers_xlm_hop_and_pool_routes(pool: SqlitePool) {
+    let (app, _) = test_app(pool).await;
+
+    let (status, body) = get(
+        &app,
+        "/api/predict/success?source_asset=EURC:GCISSUER&destination_asset=XLM&amount=100&time_of_day=08:30",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    // The pool swap is scored and ranked with the other routes
+    let routes = body["alternative_routes"].as_array().unwrap();
+    let pool = routes
+        .iter()
+        .find(|r| r["route"] == "liquidity_pool")
+        .unwrap();
+    assert_eq!(pool["pool_id"], "pool1");
+    assert_eq!(pool["path"][0], "EURC:GCISSUER");
+    assert_eq!(pool["path"][1], "XLM:native");
+    assert!(pool["estimated_cost_usd"].as_f64().unwrap() > 0.0);
+    let probability = pool["success_probability"].as_f64().unwrap();
+    assert!(probability > 0.0 && probability < 1.0);
+    assert!(routes
+        .iter()
+        .filter(|r| r["route"] != "liquidity_pool")
+        .all(|r| r.get("pool_id").is_none()));
+    let delivered: Vec<f64> = routes
+        .iter()
+        .map(|r| r["expected_delivered_usd"].as_f64().unwrap())
+        .collect();
+    assert!(delivered.windows(2).all(|w| w[0] >= w[1]));
+
+    let (status, body) = get(
+        &app,
+        "/api/predict/success?source_asset=EURC:GCISSUER&destination_asset=USDC:GAISSUER&amount=100&time_of_day=08:30",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let routes = body["alternative_routes"].as_array().unwrap();
+    let via_xlm = routes.iter().find(|r| r["route"] == "via_xlm").unwrap();
+    assert_eq!(via_xlm["path"][1], "XLM:native");
+    // Leg intervals multiply like the leg probabilities
+    let probability = via_xlm["success_probability"].as_f64().unwrap();
+    assert!(via_xlm["confidence_interval"][0].as_f64().unwrap() <= probability);
+    assert!(via_xlm["confidence_interval"][1].as_f64().unwrap() >= probability);
+    assert!(routes
+        .iter()
+        .all(|r| r["route"] != "alternative_anchor" && r["route"] != "liquidity_pool"));
+}
+
+#[sqlx::test]
+async fn test_rejects_invalid_requests(pool: SqlitePool) {
+    let (app, _) = test_app(pool).await;
+
+    for (query, code) in [
+        (
+            "source_asset=USDC&destination_asset=XLM&amount=100&time_of_day=12:00",
+            "INVALID_ASSET",
+        ),
+        (
+            "source_asset=XLM&destination_asset=native&amount=100&time_of_day=12:00",
+            "SAME_ASSET",
+        ),
+        (
+            "source_asset=USDC:GAISSUER&destination_asset=XLM&amount=-5&time_of_day=12:00",
+            "INVALID_AMOUNT",
+        ),
+        (
+            "source_asset=USDC:GAISSUER&destination_asset=XLM&amount=100&time_of_day=25:00",
+            "INVALID_TIME",
+        ),
+    ] {
+        let (status, body) = get(&app, &format!("/api/predict/success?{}", query)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+        assert_eq!(body["error"]["code"], code, "{}", query);
+    }
+}
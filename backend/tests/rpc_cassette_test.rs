@@ -0,0 +1,97 @@
+use axum::{routing::get, Json, Router};
+use serde_json::json;
+use std::sync::Arc;
+
+use stellar_insights_backend::rpc::error::RpcError;
+use stellar_insights_backend::rpc::{Cassette, CassetteMode, StellarRpcClient};
+
+/// Minimal Horizon stand-in serving one ledger and one payment page
+async fn spawn_horizon() -> (String, tokio::task::JoinHandle<()>) {
+    let app = Router::new()
+        .route(
+            "/ledgers",
+            get(|| async {
+                Json(json!({
+                    "_embedded": { "records": [{
+                        "sequence": 51565820,
+                        "hash": "abc123",
+                        "previous_hash": "abc122",
+                        "transaction_count": 12,
+                        "operation_count": 30,
+                        "closed_at": "2026-01-22T10:30:00Z",
+                        "total_coins": "105443902087.3472865",
+                        "fee_pool": "3145678.9012345",
+                        "base_fee": 100,
+                        "base_reserve": "0.5000000"
+                    }]}
+                }))
+            }),
+        )
+        .route(
+            "/payments",
+            get(|| async {
+                Json(json!({
+                    "_embedded": { "records": [{
+                        "id": "221486977826817",
+                        "paging_token": "221486977826817",
+                        "transaction_hash": "tx_001",
+                        "source_account": "GSOURCE",
+                        "type": "payment",
+                        "from": "GSOURCE",
+                        "to": "GDEST",
+                        "asset_type": "credit_alphanum4",
+                        "asset_code": "USDC",
+                        "asset_issuer": "GISSUER",
+                        "amount": "100.0000000",
+                        "created_at": "2026-01-22T10:30:00Z"
+                    }]}
+                }))
+            }),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (url, handle)
+}
+
+#[tokio::test]
+async fn test_recorded_cassette_replays_without_network() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("horizon.jsonl");
+
+    let (horizon_url, server) = spawn_horizon().await;
+    let recorder = Arc::new(Cassette::record(&path));
+    let client = StellarRpcClient::new(horizon_url.clone(), horizon_url, false)
+        .with_cassette(Arc::clone(&recorder));
+
+    let recorded_ledger = client.fetch_latest_ledger().await.unwrap();
+    let recorded_payments = client.fetch_payments(5, None).await.unwrap();
+    assert_eq!(recorded_ledger.sequence, 51_565_820);
+    assert_eq!(recorded_payments.len(), 1);
+    assert_eq!(recorder.interactions().len(), 2);
+    server.abort();
+
+    // Nothing listens on the discard port; every answer must come from the cassette
+    let replayer = Arc::new(Cassette::replay(&path).unwrap());
+    assert_eq!(replayer.mode(), CassetteMode::Replay);
+    let client = StellarRpcClient::new(
+        "http://127.0.0.1:9".to_string(),
+        "http://127.0.0.1:9".to_string(),
+        false,
+    )
+    .with_cassette(replayer);
+
+    let ledger = client.fetch_latest_ledger().await.unwrap();
+    assert_eq!(ledger.hash, recorded_ledger.hash);
+    assert_eq!(ledger.previous_hash, "abc122");
+
+    let payments = client.fetch_payments(5, None).await.unwrap();
+    assert_eq!(payments[0].id, recorded_payments[0].id);
+    assert_eq!(payments[0].get_destination().as_deref(), Some("GDEST"));
+
+    let err = client.fetch_trades(5, None).await.unwrap_err();
+    assert!(matches!(err, RpcError::ServerError { status: 404, .. }));
+}
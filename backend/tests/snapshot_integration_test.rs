@@ -11,6 +11,9 @@
 use sqlx::Row;
 use std::sync::Arc;
 use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::corridor_health::{
+    CorridorHealthScorer, HealthScoreConfig,
+};
 use stellar_insights_backend::services::snapshot::SnapshotService;
 use stellar_insights_backend::snapshot::schema::AnalyticsSnapshot;
 
@@ -99,12 +102,20 @@ async fn setup_test_database() -> Arc<Database> {
     Arc::new(db)
 }
 
+fn new_service(db: Arc<Database>) -> SnapshotService {
+    let health_scorer = Arc::new(CorridorHealthScorer::new(
+        Arc::clone(&db),
+        HealthScoreConfig::default(),
+    ));
+    SnapshotService::new(db, None, None, health_scorer)
+}
+
 #[tokio::test]
 async fn test_acceptance_criteria_1_aggregate_all_metrics() {
     println!("🧪 Testing Acceptance Criteria 1: Aggregate all metrics");
 
     let db = setup_test_database().await;
-    let service = SnapshotService::new(db, None);
+    let service = new_service(db);
 
     let snapshot = service.aggregate_all_metrics(1).await.unwrap();
 
@@ -132,7 +143,7 @@ async fn test_acceptance_criteria_2_serialize_deterministic_json() {
     println!("🧪 Testing Acceptance Criteria 2: Serialize to deterministic JSON");
 
     let db = setup_test_database().await;
-    let service = SnapshotService::new(db, None);
+    let service = new_service(db);
 
     let mut snapshot1 = service.aggregate_all_metrics(2).await.unwrap();
     let mut snapshot2 = service.aggregate_all_metrics(2).await.unwrap();
@@ -164,7 +175,7 @@ async fn test_acceptance_criteria_3_compute_sha256_hash() {
     println!("🧪 Testing Acceptance Criteria 3: Compute SHA-256 hash");
 
     let db = setup_test_database().await;
-    let service = SnapshotService::new(db, None);
+    let service = new_service(db);
 
     let snapshot = service.aggregate_all_metrics(3).await.unwrap();
 
@@ -191,7 +202,7 @@ async fn test_acceptance_criteria_4_store_hash_in_database() {
     println!("🧪 Testing Acceptance Criteria 4: Store hash in database");
 
     let db = setup_test_database().await;
-    let service = SnapshotService::new(db.clone(), None);
+    let service = new_service(db.clone());
 
     let result = service.generate_and_submit_snapshot(4).await.unwrap();
 
@@ -219,7 +230,7 @@ async fn test_acceptance_criteria_5_and_6_contract_submission_and_verification()
     println!("🧪 Testing Acceptance Criteria 5 & 6: Submit to contract & verify (simulated)");
 
     let db = setup_test_database().await;
-    let service = SnapshotService::new(db, None);
+    let service = new_service(db);
 
     // Without contract service, submission should be skipped but other steps should work
     let result = service.generate_and_submit_snapshot(5).await.unwrap();
@@ -248,7 +259,7 @@ async fn test_complete_workflow() {
     println!("🧪 Testing Complete Workflow - All Acceptance Criteria");
 
     let db = setup_test_database().await;
-    let service = SnapshotService::new(db.clone(), None);
+    let service = new_service(db.clone());
 
     let epoch = 12345;
     let result = service.generate_and_submit_snapshot(epoch).await.unwrap();
@@ -44,6 +44,7 @@ mod tests {
             volume_usd: 50000.0,
             avg_settlement_latency_ms: Some(250),
             liquidity_depth_usd: 100000.0,
+            health_score: Some(92.5),
         }
     }
 
@@ -294,7 +295,7 @@ mod tests {
 
     #[test]
     fn test_snapshot_schema_version_constant() {
-        assert_eq!(SCHEMA_VERSION, 1, "Schema version should be 1");
+        assert_eq!(SCHEMA_VERSION, 2, "Schema version should be 2");
     }
 
     #[test]
@@ -0,0 +1,137 @@
+use sqlx::SqlitePool;
+
+use stellar_insights_backend::ingestion::processor::{LedgerBundle, LedgerProcessor};
+use stellar_insights_backend::rpc::{HorizonEffect, HorizonOperation};
+use stellar_insights_backend::services::sponsorship_tracker::SponsorshipTrackerService;
+
+const SPONSOR: &str = "GSPONSORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const ACCOUNT: &str = "GSPONSOREDAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+fn operation(id: &str, operation_type: &str, source_account: &str) -> HorizonOperation {
+    HorizonOperation {
+        id: id.to_string(),
+        paging_token: id.to_string(),
+        transaction_hash: format!("tx_{}", id),
+        source_account: source_account.to_string(),
+        operation_type: operation_type.to_string(),
+        created_at: "2026-01-22T10:30:00Z".to_string(),
+        ..Default::default()
+    }
+}
+
+fn sponsorship_effect(id: &str, effect_type: &str) -> HorizonEffect {
+    HorizonEffect {
+        id: id.to_string(),
+        effect_type: effect_type.to_string(),
+        account: Some(ACCOUNT.to_string()),
+        ..Default::default()
+    }
+}
+
+/// Sponsor creates an account and a trustline for it inside a begin/end sandwich
+fn sandwich_ledger(sequence: u64) -> LedgerBundle {
+    let begin = HorizonOperation {
+        sponsored_id: Some(ACCOUNT.to_string()),
+        ..operation("op_1", "begin_sponsoring_future_reserves", SPONSOR)
+    };
+    let end = HorizonOperation {
+        begin_sponsor: Some(SPONSOR.to_string()),
+        ..operation("op_3", "end_sponsoring_future_reserves", ACCOUNT)
+    };
+    let account_created = HorizonEffect {
+        sponsor: Some(SPONSOR.to_string()),
+        ..sponsorship_effect("effect_2_1", "account_sponsorship_created")
+    };
+    let trustline_created = HorizonEffect {
+        sponsor: Some(SPONSOR.to_string()),
+        ..sponsorship_effect("effect_2_2", "trustline_sponsorship_created")
+    };
+
+    LedgerBundle {
+        operations: vec![begin, end],
+        effects: vec![account_created, trustline_created],
+        ..LedgerBundle::new(sequence)
+    }
+}
+
+#[sqlx::test]
+async fn test_sponsorship_sandwich_and_revocation(pool: SqlitePool) {
+    let tracker = SponsorshipTrackerService::new(pool.clone());
+
+    assert_eq!(tracker.process(&sandwich_ledger(100)).await.unwrap(), 4);
+    // Re-ingesting the same ledger does not double count reserves
+    assert_eq!(tracker.process(&sandwich_ledger(100)).await.unwrap(), 0);
+
+    let sponsorships = tracker.get_sponsorships_by_sponsor(SPONSOR).await.unwrap();
+    assert_eq!(sponsorships.len(), 1);
+    assert_eq!(sponsorships[0].sponsored_account, ACCOUNT);
+    assert_eq!(sponsorships[0].sponsored_reserves, 3);
+    assert_eq!(sponsorships[0].status, "active");
+    assert!((sponsorships[0].reserve_exposure_xlm - 1.5).abs() < f64::EPSILON);
+
+    let revoke = HorizonOperation {
+        trustline_account_id: Some(ACCOUNT.to_string()),
+        ..operation("op_9", "revoke_sponsorship", SPONSOR)
+    };
+    let removed = HorizonEffect {
+        former_sponsor: Some(SPONSOR.to_string()),
+        ..sponsorship_effect("effect_9_1", "trustline_sponsorship_removed")
+    };
+    let revocation = LedgerBundle {
+        operations: vec![revoke],
+        effects: vec![removed],
+        ..LedgerBundle::new(101)
+    };
+    assert_eq!(tracker.process(&revocation).await.unwrap(), 2);
+
+    let sponsorship = tracker
+        .get_sponsorship(&sponsorships[0].id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sponsorship.sponsored_reserves, 2);
+    assert_eq!(sponsorship.last_ledger, 101);
+
+    let history = tracker
+        .get_sponsorship_history(&sponsorship.id)
+        .await
+        .unwrap();
+    let changes: Vec<&str> = history.iter().map(|h| h.change_type.as_str()).collect();
+    assert_eq!(changes.len(), 6);
+    assert!(changes.contains(&"REVOKED"));
+
+    let leaderboard = tracker.get_sponsor_leaderboard(10).await.unwrap();
+    assert_eq!(leaderboard.len(), 1);
+    assert_eq!(leaderboard[0].rank, 1);
+    assert_eq!(leaderboard[0].total_sponsored_reserves, 2);
+
+    let analytics = tracker.get_analytics().await.unwrap();
+    assert_eq!(analytics.unique_sponsors, 1);
+    assert!((analytics.total_reserve_exposure_xlm - 1.0).abs() < f64::EPSILON);
+}
+
+#[sqlx::test]
+async fn test_sponsorship_transfer_between_sponsors(pool: SqlitePool) {
+    let tracker = SponsorshipTrackerService::new(pool.clone());
+    let new_sponsor = "GNEWSPONSORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+    tracker.process(&sandwich_ledger(100)).await.unwrap();
+
+    let updated = HorizonEffect {
+        former_sponsor: Some(SPONSOR.to_string()),
+        new_sponsor: Some(new_sponsor.to_string()),
+        ..sponsorship_effect("effect_5_1", "account_sponsorship_updated")
+    };
+    let transfer = LedgerBundle {
+        effects: vec![updated],
+        ..LedgerBundle::new(102)
+    };
+    assert_eq!(tracker.process(&transfer).await.unwrap(), 2);
+
+    let for_account = tracker.get_sponsorships_for_account(ACCOUNT).await.unwrap();
+    let reserves: Vec<(&str, i64)> = for_account
+        .iter()
+        .map(|s| (s.sponsor.as_str(), s.sponsored_reserves))
+        .collect();
+    assert_eq!(reserves, vec![(new_sponsor, 2), (SPONSOR, 1)]);
+}
@@ -0,0 +1,151 @@
+use sqlx::SqlitePool;
+
+use stellar_insights_backend::rpc::{Price, Trade};
+use stellar_insights_backend::services::trade_aggregator::{
+    CandleQuery, MarketType, Resolution, TradeAggregator,
+};
+
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+fn trade(id: &str, time: &str, base_amount: f64, price: (i64, i64)) -> Trade {
+    Trade {
+        id: id.to_string(),
+        paging_token: id.to_string(),
+        ledger_close_time: time.to_string(),
+        base_account: "GBASEACCOUNT".to_string(),
+        base_liquidity_pool_id: None,
+        base_amount: base_amount.to_string(),
+        base_asset_type: "native".to_string(),
+        base_asset_code: None,
+        base_asset_issuer: None,
+        counter_account: "GCOUNTERACCOUNT".to_string(),
+        counter_liquidity_pool_id: None,
+        counter_amount: (base_amount * price.0 as f64 / price.1 as f64).to_string(),
+        counter_asset_type: "credit_alphanum4".to_string(),
+        counter_asset_code: Some("USDC".to_string()),
+        counter_asset_issuer: Some(USDC_ISSUER.to_string()),
+        price: Price {
+            n: price.0,
+            d: price.1,
+        },
+        trade_type: "orderbook".to_string(),
+    }
+}
+
+fn pair_key() -> String {
+    format!("XLM:native->USDC:{}", USDC_ISSUER)
+}
+
+fn query() -> CandleQuery {
+    CandleQuery {
+        limit: 100,
+        ..Default::default()
+    }
+}
+
+#[sqlx::test]
+async fn test_candles_from_out_of_order_trades(pool: SqlitePool) {
+    let aggregator = TradeAggregator::new(pool);
+
+    // Close arrives before open; the duplicate is ignored
+    let trades = vec![
+        trade("t3", "2026-01-22T10:00:50Z", 30.0, (11, 100)),
+        trade("t1", "2026-01-22T10:00:05Z", 10.0, (10, 100)),
+        trade("t2", "2026-01-22T10:00:20Z", 20.0, (13, 100)),
+        trade("t1", "2026-01-22T10:00:05Z", 10.0, (10, 100)),
+        trade("t4", "2026-01-22T10:01:10Z", 5.0, (9, 100)),
+    ];
+    assert_eq!(aggregator.ingest_trades(&trades).await.unwrap(), 4);
+
+    let minutes = aggregator
+        .get_pair_candles(&pair_key(), Resolution::OneMinute, &query())
+        .await
+        .unwrap();
+    assert_eq!(minutes.len(), 2);
+    let first = &minutes[0];
+    assert_eq!(first.bucket_start, "2026-01-22T10:00:00Z");
+    assert!((first.open - 0.10).abs() < 1e-9);
+    assert!((first.high - 0.13).abs() < 1e-9);
+    assert!((first.low - 0.10).abs() < 1e-9);
+    assert!((first.close - 0.11).abs() < 1e-9);
+    assert!((first.base_volume - 60.0).abs() < 1e-9);
+    assert_eq!(first.trade_count, 3);
+
+    let hours = aggregator
+        .get_pair_candles(&pair_key(), Resolution::OneHour, &query())
+        .await
+        .unwrap();
+    assert_eq!(hours.len(), 1);
+    assert!((hours[0].close - 0.09).abs() < 1e-9);
+    assert!((hours[0].low - 0.09).abs() < 1e-9);
+    assert_eq!(hours[0].trade_count, 4);
+
+    // Querying the reverse direction inverts prices and volumes
+    let reversed = format!("USDC:{}->XLM:native", USDC_ISSUER);
+    let inverted = aggregator
+        .get_pair_candles(&reversed, Resolution::OneHour, &query())
+        .await
+        .unwrap();
+    assert_eq!(inverted[0].market_key, reversed);
+    assert!((inverted[0].open - 10.0).abs() < 1e-9);
+    assert!((inverted[0].high - 1.0 / 0.09).abs() < 1e-9);
+    assert!((inverted[0].counter_volume - 65.0).abs() < 1e-9);
+}
+
+#[sqlx::test]
+async fn test_pool_candles_and_markets(pool: SqlitePool) {
+    let aggregator = TradeAggregator::new(pool);
+
+    let pool_trade = Trade {
+        counter_account: String::new(),
+        counter_liquidity_pool_id: Some("pool_abc".to_string()),
+        trade_type: "liquidity_pool".to_string(),
+        ..trade("t1", "2026-01-22T10:00:05Z", 10.0, (12, 100))
+    };
+    let orderbook_trade = trade("t2", "2026-01-22T11:30:00Z", 10.0, (14, 100));
+    aggregator
+        .ingest_trades(&[pool_trade, orderbook_trade])
+        .await
+        .unwrap();
+
+    let pool_candles = aggregator
+        .get_candles(
+            MarketType::LiquidityPool,
+            "pool_abc",
+            Resolution::OneDay,
+            &query(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(pool_candles.len(), 1);
+    assert_eq!(pool_candles[0].trade_count, 1);
+
+    let markets = aggregator.get_markets(None).await.unwrap();
+    assert_eq!(markets.len(), 2);
+    assert_eq!(markets[0].market_key, pair_key());
+    assert_eq!(markets[0].trade_count, 2);
+    assert!((markets[0].last_price - 0.14).abs() < 1e-9);
+
+    let pools = aggregator
+        .get_markets(Some(MarketType::LiquidityPool))
+        .await
+        .unwrap();
+    assert_eq!(pools.len(), 1);
+    assert_eq!(pools[0].market_key, "pool_abc");
+
+    // Time window filters buckets
+    let windowed = aggregator
+        .get_pair_candles(
+            &pair_key(),
+            Resolution::OneHour,
+            &CandleQuery {
+                start: Some("2026-01-22T11:00:00Z".parse().unwrap()),
+                end: None,
+                limit: 100,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(windowed.len(), 1);
+    assert_eq!(windowed[0].bucket_start, "2026-01-22T11:00:00Z");
+}